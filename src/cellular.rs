@@ -6,10 +6,219 @@ use std::f32;
 const X_PRIME: i32 = 1619;
 const Y_PRIME: i32 = 31337;
 const Z_PRIME: i32 = 6971;
+const W_PRIME: i32 = 1013;
 //const BIT_5_MASK : i32 = 31;
+const BIT_8_MASK: i32 = 255;
 const BIT_10_MASK: i32 = 1023;
 const HASH_2_FLOAT: f32 = 1.0 / 2147483648.0;
 
+/// Raises the magnitude of each lane to the power p, there is no SIMD pow
+/// so this falls back to the scalar one lane at a time
+#[inline(always)]
+unsafe fn abs_pow<S: Simd>(a: S::Vf32, p: f32) -> S::Vf32 {
+    let mut r = S::abs_ps(a);
+    for i in 0..S::VF32_WIDTH {
+        r[i] = r[i].powf(p);
+    }
+    r
+}
+
+/// How many of the sorted distances, less one, a cellular2 return type reads
+#[inline(always)]
+pub(crate) fn cell2_depth(return_type: Cell2ReturnType, index1: usize) -> usize {
+    match return_type {
+        Cell2ReturnType::Weighted(weights) => weights.iter().rposition(|&c| c != 0.0).unwrap_or(0),
+        _ => index1,
+    }
+}
+
+/// Combines the sorted distances as the cellular2 return type asks. The
+/// distances are only searched for, to the depth needed, when the return type
+/// uses them, edge distance measures to the nearest border instead
+#[inline(always)]
+unsafe fn cell2_combine<S, D, E>(
+    return_type: Cell2ReturnType,
+    index0: usize,
+    index1: usize,
+    sorted_distances: D,
+    edge_distance: E,
+) -> S::Vf32
+where
+    S: Simd,
+    D: FnOnce(usize) -> [S::Vf32; 4],
+    E: FnOnce() -> S::Vf32,
+{
+    match return_type {
+        Cell2ReturnType::EdgeDistance => edge_distance(),
+        Cell2ReturnType::Weighted(weights) => {
+            let depth = cell2_depth(return_type, index1);
+            let distance = sorted_distances(depth);
+            let mut result = S::setzero_ps();
+            for i in 0..=depth {
+                result = S::fmadd_ps(S::set1_ps(weights[i]), distance[i], result);
+            }
+            result
+        }
+        _ => {
+            let distance = sorted_distances(cell2_depth(return_type, index1));
+            let (d0, d1) = (distance[index0], distance[index1]);
+            match return_type {
+                Cell2ReturnType::Distance2Add => S::add_ps(d0, d1),
+                Cell2ReturnType::Distance2Sub => S::sub_ps(d0, d1),
+                Cell2ReturnType::Distance2Mul => S::mul_ps(d0, d1),
+                Cell2ReturnType::Distance2Div => S::div_ps(d0, d1),
+                _ => d1,
+            }
+        }
+    }
+}
+
+#[inline(always)]
+unsafe fn hash_1d<S: Simd>(seed: i32, x: S::Vi32) -> S::Vi32 {
+    let hash = S::xor_epi32(x, S::set1_epi32(seed));
+    S::mullo_epi32(
+        S::mullo_epi32(S::mullo_epi32(hash, hash), S::set1_epi32(60493)),
+        hash,
+    )
+}
+
+/// Offsets the feature point of each cell by up to `jitter` in either direction.
+/// Unlike the higher dimensions the offset is not normalized, since a unit
+/// vector in 1d could only point left or right.
+#[inline(always)]
+unsafe fn feature_offset_1d<S: Simd>(hash: S::Vi32, jitter: S::Vf32) -> S::Vf32 {
+    let xd = S::sub_ps(
+        S::cvtepi32_ps(S::and_epi32(hash, S::set1_epi32(BIT_10_MASK))),
+        S::set1_ps(511.5),
+    );
+    S::mul_ps(xd, S::mul_ps(jitter, S::set1_ps(1.0 / 511.5)))
+}
+
+/// 1d cellular noise, the distance to the nearest feature point or its
+/// value, depending on `return_type`
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn cellular_1d<S: Simd>(
+    x: S::Vf32,
+    distance_function: CellDistanceFunction,
+    return_type: CellReturnType,
+    jitter: S::Vf32,
+    seed: i32,
+) -> S::Vf32 {
+    let mut distance = S::set1_ps(999999.0);
+    let mut cell_value = S::setzero_ps();
+
+    let mut xc = S::sub_epi32(S::cvtps_epi32(x), S::set1_epi32(1));
+    let mut xcf = S::sub_ps(S::cvtepi32_ps(xc), x);
+    xc = S::mullo_epi32(xc, S::set1_epi32(X_PRIME));
+
+    for _x in 0..3 {
+        let hash = hash_1d::<S>(seed, xc);
+        let xd = S::add_ps(feature_offset_1d::<S>(hash, jitter), xcf);
+
+        let new_cell_value = S::mul_ps(S::set1_ps(HASH_2_FLOAT), S::cvtepi32_ps(hash));
+        let new_distance = match distance_function {
+            CellDistanceFunction::Euclidean => S::mul_ps(xd, xd),
+            CellDistanceFunction::Manhattan => S::abs_ps(xd),
+            CellDistanceFunction::Natural => S::add_ps(S::mul_ps(xd, xd), S::abs_ps(xd)),
+            CellDistanceFunction::Chebyshev => S::abs_ps(xd),
+            CellDistanceFunction::Minkowski(p) => abs_pow::<S>(xd, p),
+        };
+        let closer = S::cmplt_ps(new_distance, distance);
+        distance = S::min_ps(new_distance, distance);
+        cell_value = S::blendv_ps(cell_value, new_cell_value, closer);
+        xcf = S::add_ps(xcf, S::set1_ps(1.0));
+        xc = S::add_epi32(xc, S::set1_epi32(X_PRIME));
+    }
+
+    match return_type {
+        CellReturnType::Distance => distance,
+        CellReturnType::CellValue => cell_value,
+    }
+}
+
+#[inline(always)]
+unsafe fn sorted_distances_1d<S: Simd>(
+    x: S::Vf32,
+    distance_function: CellDistanceFunction,
+    jitter: S::Vf32,
+    depth: usize,
+    seed: i32,
+) -> [S::Vf32; 4] {
+    let mut distance: [S::Vf32; 4] = [S::set1_ps(999999.0); 4];
+
+    // A 1d neighbourhood of 3 cells can't hold four distances, so search 5
+    let mut xc = S::sub_epi32(S::cvtps_epi32(x), S::set1_epi32(2));
+    let mut xcf = S::sub_ps(S::cvtepi32_ps(xc), x);
+    xc = S::mullo_epi32(xc, S::set1_epi32(X_PRIME));
+
+    for _x in 0..5 {
+        let hash = hash_1d::<S>(seed, xc);
+        let xd = S::add_ps(feature_offset_1d::<S>(hash, jitter), xcf);
+
+        let new_distance = match distance_function {
+            CellDistanceFunction::Euclidean => S::mul_ps(xd, xd),
+            CellDistanceFunction::Manhattan => S::abs_ps(xd),
+            CellDistanceFunction::Natural => S::add_ps(S::mul_ps(xd, xd), S::abs_ps(xd)),
+            CellDistanceFunction::Chebyshev => S::abs_ps(xd),
+            CellDistanceFunction::Minkowski(p) => abs_pow::<S>(xd, p),
+        };
+        let mut i = depth;
+        while i > 0 {
+            distance[i] = S::max_ps(S::min_ps(distance[i], new_distance), distance[i - 1]);
+            i -= 1;
+        }
+        distance[0] = S::min_ps(distance[0], new_distance);
+        xcf = S::add_ps(xcf, S::set1_ps(1.0));
+        xc = S::add_epi32(xc, S::set1_epi32(X_PRIME));
+    }
+
+    distance
+}
+
+/// The distances to the four nearest feature points, F1 to F4 in ascending order
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn cellular2_distances_1d<S: Simd>(
+    x: S::Vf32,
+    distance_function: CellDistanceFunction,
+    jitter: S::Vf32,
+    seed: i32,
+) -> [S::Vf32; 4] {
+    sorted_distances_1d::<S>(x, distance_function, jitter, 3, seed)
+}
+
+/// 1d cellular noise combining the distances to two of the nearest
+/// feature points, picked by `index0` and `index1`
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn cellular2_1d<S: Simd>(
+    x: S::Vf32,
+    distance_function: CellDistanceFunction,
+    return_type: Cell2ReturnType,
+    jitter: S::Vf32,
+    index0: usize,
+    index1: usize,
+    seed: i32,
+) -> S::Vf32 {
+    cell2_combine::<S, _, _>(
+        return_type,
+        index0,
+        index1,
+        |depth| sorted_distances_1d::<S>(x, distance_function, jitter, depth, seed),
+        || cellular_edge_1d::<S>(x, jitter, seed),
+    )
+}
+
 #[inline(always)]
 unsafe fn hash_2d<S: Simd>(seed: i32, x: S::Vi32, y: S::Vi32) -> S::Vi32 {
     let mut hash = S::xor_epi32(x, S::set1_epi32(seed));
@@ -20,6 +229,25 @@ unsafe fn hash_2d<S: Simd>(seed: i32, x: S::Vi32, y: S::Vi32) -> S::Vi32 {
     )
 }
 
+#[inline(always)]
+unsafe fn distance_2d<S: Simd>(
+    distance_function: CellDistanceFunction,
+    xd: S::Vf32,
+    yd: S::Vf32,
+) -> S::Vf32 {
+    match distance_function {
+        CellDistanceFunction::Euclidean => S::add_ps(S::mul_ps(xd, xd), S::mul_ps(yd, yd)),
+        CellDistanceFunction::Manhattan => S::add_ps(S::abs_ps(xd), S::abs_ps(yd)),
+        CellDistanceFunction::Natural => {
+            let euc = S::add_ps(S::mul_ps(xd, xd), S::mul_ps(yd, yd));
+            let man = S::add_ps(S::abs_ps(xd), S::abs_ps(yd));
+            S::add_ps(euc, man)
+        }
+        CellDistanceFunction::Chebyshev => S::max_ps(S::abs_ps(xd), S::abs_ps(yd)),
+        CellDistanceFunction::Minkowski(p) => S::add_ps(abs_pow::<S>(xd, p), abs_pow::<S>(yd, p)),
+    }
+}
+
 #[inline(always)]
 pub unsafe fn cellular_2d<S: Simd>(
     x: S::Vf32,
@@ -30,6 +258,8 @@ pub unsafe fn cellular_2d<S: Simd>(
     seed: i32,
 ) -> S::Vf32 {
     let mut distance = S::set1_ps(999999.0);
+    let mut cell_value = S::setzero_ps();
+
     let mut xc = S::sub_epi32(S::cvtps_epi32(x), S::set1_epi32(1));
     let mut yc_base = S::sub_epi32(S::cvtps_epi32(y), S::set1_epi32(1));
 
@@ -38,255 +268,58 @@ pub unsafe fn cellular_2d<S: Simd>(
 
     xc = S::mullo_epi32(xc, S::set1_epi32(X_PRIME));
     yc_base = S::mullo_epi32(yc_base, S::set1_epi32(Y_PRIME));
-    match return_type {
-        CellReturnType::Distance => {
-            match distance_function {
-                CellDistanceFunction::Euclidean => {
-                    for _x in 0..3 {
-                        let mut ycf = ycf_base;
-                        let mut yc = yc_base;
-                        for _y in 0..3 {
-                            let hash = hash_2d::<S>(seed, xc, yc);
-                            let mut xd = S::sub_ps(
-                                S::cvtepi32_ps(S::and_epi32(hash, S::set1_epi32(BIT_10_MASK))),
-                                S::set1_ps(511.5),
-                            );
-                            let mut yd = S::sub_ps(
-                                S::cvtepi32_ps(S::and_epi32(
-                                    S::srai_epi32(hash, 10),
-                                    S::set1_epi32(BIT_10_MASK),
-                                )),
-                                S::set1_ps(511.5),
-                            );
-                            let mut xd2 = S::mul_ps(xd, xd);
-                            let inv_mag =
-                                S::mul_ps(jitter, S::rsqrt_ps(S::add_ps(xd2, S::mul_ps(yd, yd))));
-                            xd = S::add_ps(S::mul_ps(xd, inv_mag), xcf);
-                            yd = S::add_ps(S::mul_ps(yd, inv_mag), ycf);
-                            xd2 = S::mul_ps(xd, xd);
-                            let new_distance = S::add_ps(xd2, S::mul_ps(yd, yd));
-                            distance = S::min_ps(new_distance, distance);
-
-                            ycf = S::add_ps(ycf, S::set1_ps(1.0));
-                            yc = S::add_epi32(yc, S::set1_epi32(Y_PRIME));
-                        }
-                        xcf = S::add_ps(xcf, S::set1_ps(1.0));
-                        xc = S::add_epi32(xc, S::set1_epi32(X_PRIME));
-                    }
-                }
-                CellDistanceFunction::Manhattan => {
-                    for _x in 0..3 {
-                        let mut ycf = ycf_base;
-                        let mut yc = yc_base;
-                        for _y in 0..3 {
-                            let hash = hash_2d::<S>(seed, xc, yc);
-                            let mut xd = S::sub_ps(
-                                S::cvtepi32_ps(S::and_epi32(hash, S::set1_epi32(BIT_10_MASK))),
-                                S::set1_ps(511.5),
-                            );
-                            let mut yd = S::sub_ps(
-                                S::cvtepi32_ps(S::and_epi32(
-                                    S::srai_epi32(hash, 10),
-                                    S::set1_epi32(BIT_10_MASK),
-                                )),
-                                S::set1_ps(511.5),
-                            );
-                            let inv_mag = S::mul_ps(
-                                jitter,
-                                S::rsqrt_ps(S::add_ps(S::mul_ps(xd, xd), S::mul_ps(yd, yd))),
-                            );
-                            xd = S::add_ps(S::mul_ps(xd, inv_mag), xcf);
-                            yd = S::add_ps(S::mul_ps(yd, inv_mag), ycf);
-
-                            let new_distance = S::add_ps(S::abs_ps(xd), S::abs_ps(yd));
-                            distance = S::min_ps(new_distance, distance);
-
-                            ycf = S::add_ps(ycf, S::set1_ps(1.0));
-                            yc = S::add_epi32(yc, S::set1_epi32(Y_PRIME));
-                        }
-                        xcf = S::add_ps(xcf, S::set1_ps(1.0));
-                        xc = S::add_epi32(xc, S::set1_epi32(X_PRIME));
-                    }
-                }
-                CellDistanceFunction::Natural => {
-                    for _x in 0..3 {
-                        let mut ycf = ycf_base;
-                        let mut yc = yc_base;
-                        for _y in 0..3 {
-                            let hash = hash_2d::<S>(seed, xc, yc);
-                            let mut xd = S::sub_ps(
-                                S::cvtepi32_ps(S::and_epi32(hash, S::set1_epi32(BIT_10_MASK))),
-                                S::set1_ps(511.5),
-                            );
-                            let mut yd = S::sub_ps(
-                                S::cvtepi32_ps(S::and_epi32(
-                                    S::srai_epi32(hash, 10),
-                                    S::set1_epi32(BIT_10_MASK),
-                                )),
-                                S::set1_ps(511.5),
-                            );
-                            let inv_mag = S::mul_ps(
-                                jitter,
-                                S::rsqrt_ps(S::add_ps(S::mul_ps(xd, xd), S::mul_ps(yd, yd))),
-                            );
-                            xd = S::add_ps(S::mul_ps(xd, inv_mag), xcf);
-                            yd = S::add_ps(S::mul_ps(yd, inv_mag), ycf);
-
-                            let new_distance = {
-                                let euc = S::add_ps(S::mul_ps(xd, xd), S::mul_ps(yd, yd));
-                                let man = S::add_ps(S::abs_ps(xd), S::abs_ps(yd));
-                                S::add_ps(euc, man)
-                            };
-                            distance = S::min_ps(new_distance, distance);
-
-                            ycf = S::add_ps(ycf, S::set1_ps(1.0));
-                            yc = S::add_epi32(yc, S::set1_epi32(Y_PRIME));
-                        }
-                        xcf = S::add_ps(xcf, S::set1_ps(1.0));
-                        xc = S::add_epi32(xc, S::set1_epi32(X_PRIME));
-                    }
-                }
-            }
-            distance
-        }
-        CellReturnType::CellValue => {
-            let mut cell_value = S::setzero_ps();
-            match distance_function {
-                CellDistanceFunction::Euclidean => {
-                    for _x in 0..3 {
-                        let mut ycf = ycf_base;
-                        let mut yc = yc_base;
-                        for _y in 0..3 {
-                            let hash = hash_2d::<S>(seed, xc, yc);
-                            let mut xd = S::sub_ps(
-                                S::cvtepi32_ps(S::and_epi32(hash, S::set1_epi32(BIT_10_MASK))),
-                                S::set1_ps(511.5),
-                            );
-                            let mut yd = S::sub_ps(
-                                S::cvtepi32_ps(S::and_epi32(
-                                    S::srai_epi32(hash, 10),
-                                    S::set1_epi32(BIT_10_MASK),
-                                )),
-                                S::set1_ps(511.5),
-                            );
-                            let inv_mag = S::mul_ps(
-                                jitter,
-                                S::rsqrt_ps(S::add_ps(S::mul_ps(xd, xd), S::mul_ps(yd, yd))),
-                            );
-                            xd = S::add_ps(S::mul_ps(xd, inv_mag), xcf);
-                            yd = S::add_ps(S::mul_ps(yd, inv_mag), ycf);
-
-                            let new_cell_value =
-                                S::mul_ps(S::set1_ps(HASH_2_FLOAT), S::cvtepi32_ps(hash));
-                            let new_distance = S::add_ps(S::mul_ps(xd, xd), S::mul_ps(yd, yd));
-                            let closer = S::cmplt_ps(new_distance, distance);
-                            distance = S::min_ps(new_distance, distance);
-                            cell_value = S::blendv_ps(cell_value, new_cell_value, closer);
-
-                            ycf = S::add_ps(ycf, S::set1_ps(1.0));
-                            yc = S::add_epi32(yc, S::set1_epi32(Y_PRIME));
-                        }
-                        xcf = S::add_ps(xcf, S::set1_ps(1.0));
-                        xc = S::add_epi32(xc, S::set1_epi32(X_PRIME));
-                    }
-                }
-                CellDistanceFunction::Manhattan => {
-                    for _x in 0..3 {
-                        let mut ycf = ycf_base;
-                        let mut yc = yc_base;
-                        for _y in 0..3 {
-                            let hash = hash_2d::<S>(seed, xc, yc);
-                            let mut xd = S::sub_ps(
-                                S::cvtepi32_ps(S::and_epi32(hash, S::set1_epi32(BIT_10_MASK))),
-                                S::set1_ps(511.5),
-                            );
-                            let mut yd = S::sub_ps(
-                                S::cvtepi32_ps(S::and_epi32(
-                                    S::srai_epi32(hash, 10),
-                                    S::set1_epi32(BIT_10_MASK),
-                                )),
-                                S::set1_ps(511.5),
-                            );
-                            let inv_mag = S::mul_ps(
-                                jitter,
-                                S::rsqrt_ps(S::add_ps(S::mul_ps(xd, xd), S::mul_ps(yd, yd))),
-                            );
-                            xd = S::add_ps(S::mul_ps(xd, inv_mag), xcf);
-                            yd = S::add_ps(S::mul_ps(yd, inv_mag), ycf);
-
-                            let new_cell_value =
-                                S::mul_ps(S::set1_ps(HASH_2_FLOAT), S::cvtepi32_ps(hash));
-                            let new_distance = S::add_ps(S::abs_ps(xd), S::abs_ps(yd));
-                            let closer = S::cmplt_ps(new_distance, distance);
-                            distance = S::min_ps(new_distance, distance);
-                            cell_value = S::blendv_ps(cell_value, new_cell_value, closer);
-
-                            ycf = S::add_ps(ycf, S::set1_ps(1.0));
-                            yc = S::add_epi32(yc, S::set1_epi32(Y_PRIME));
-                        }
-                        xcf = S::add_ps(xcf, S::set1_ps(1.0));
-                        xc = S::add_epi32(xc, S::set1_epi32(X_PRIME));
-                    }
-                }
-                CellDistanceFunction::Natural => {
-                    for _x in 0..3 {
-                        let mut ycf = ycf_base;
-                        let mut yc = yc_base;
-                        for _y in 0..3 {
-                            let hash = hash_2d::<S>(seed, xc, yc);
-                            let mut xd = S::sub_ps(
-                                S::cvtepi32_ps(S::and_epi32(hash, S::set1_epi32(BIT_10_MASK))),
-                                S::set1_ps(511.5),
-                            );
-                            let mut yd = S::sub_ps(
-                                S::cvtepi32_ps(S::and_epi32(
-                                    S::srai_epi32(hash, 10),
-                                    S::set1_epi32(BIT_10_MASK),
-                                )),
-                                S::set1_ps(511.5),
-                            );
-                            let inv_mag = S::mul_ps(
-                                jitter,
-                                S::rsqrt_ps(S::add_ps(S::mul_ps(xd, xd), S::mul_ps(yd, yd))),
-                            );
-                            xd = S::add_ps(S::mul_ps(xd, inv_mag), xcf);
-                            yd = S::add_ps(S::mul_ps(yd, inv_mag), ycf);
-
-                            let new_cell_value =
-                                S::mul_ps(S::set1_ps(HASH_2_FLOAT), S::cvtepi32_ps(hash));
-                            let new_distance = {
-                                let euc = S::add_ps(S::mul_ps(xd, xd), S::mul_ps(yd, yd));
-                                let man = S::add_ps(S::abs_ps(xd), S::abs_ps(yd));
-                                S::add_ps(euc, man)
-                            };
-                            let closer = S::cmplt_ps(new_distance, distance);
-                            distance = S::min_ps(new_distance, distance);
-                            cell_value = S::blendv_ps(cell_value, new_cell_value, closer);
-
-                            ycf = S::add_ps(ycf, S::set1_ps(1.0));
-                            yc = S::add_epi32(yc, S::set1_epi32(Y_PRIME));
-                        }
-                        xcf = S::add_ps(xcf, S::set1_ps(1.0));
-                        xc = S::add_epi32(xc, S::set1_epi32(X_PRIME));
-                    }
-                }
-            }
-            cell_value
+
+    for _x in 0..3 {
+        let mut ycf = ycf_base;
+        let mut yc = yc_base;
+        for _y in 0..3 {
+            let hash = hash_2d::<S>(seed, xc, yc);
+            let mut xd = S::sub_ps(
+                S::cvtepi32_ps(S::and_epi32(hash, S::set1_epi32(BIT_10_MASK))),
+                S::set1_ps(511.5),
+            );
+            let mut yd = S::sub_ps(
+                S::cvtepi32_ps(S::and_epi32(
+                    S::srai_epi32(hash, 10),
+                    S::set1_epi32(BIT_10_MASK),
+                )),
+                S::set1_ps(511.5),
+            );
+            let inv_mag = S::mul_ps(
+                jitter,
+                S::rsqrt_ps(S::add_ps(S::mul_ps(xd, xd), S::mul_ps(yd, yd))),
+            );
+            xd = S::add_ps(S::mul_ps(xd, inv_mag), xcf);
+            yd = S::add_ps(S::mul_ps(yd, inv_mag), ycf);
+
+            let new_cell_value = S::mul_ps(S::set1_ps(HASH_2_FLOAT), S::cvtepi32_ps(hash));
+            let new_distance = distance_2d::<S>(distance_function, xd, yd);
+            let closer = S::cmplt_ps(new_distance, distance);
+            distance = S::min_ps(new_distance, distance);
+            cell_value = S::blendv_ps(cell_value, new_cell_value, closer);
+
+            ycf = S::add_ps(ycf, S::set1_ps(1.0));
+            yc = S::add_epi32(yc, S::set1_epi32(Y_PRIME));
         }
+        xcf = S::add_ps(xcf, S::set1_ps(1.0));
+        xc = S::add_epi32(xc, S::set1_epi32(X_PRIME));
+    }
+
+    match return_type {
+        CellReturnType::Distance => distance,
+        CellReturnType::CellValue => cell_value,
     }
 }
 
 #[inline(always)]
-pub unsafe fn cellular2_2d<S: Simd>(
+unsafe fn sorted_distances_2d<S: Simd>(
     x: S::Vf32,
     y: S::Vf32,
     distance_function: CellDistanceFunction,
-    return_type: Cell2ReturnType,
     jitter: S::Vf32,
-    index0: usize,
-    index1: usize,
+    depth: usize,
     seed: i32,
-) -> S::Vf32 {
+) -> [S::Vf32; 4] {
     let mut distance: [S::Vf32; 4] = [S::set1_ps(999999.0); 4];
 
     let mut xc = S::sub_epi32(S::cvtps_epi32(x), S::set1_epi32(1));
@@ -321,21 +354,13 @@ pub unsafe fn cellular2_2d<S: Simd>(
             xd = S::add_ps(S::mul_ps(xd, inv_mag), xcf);
             yd = S::add_ps(S::mul_ps(yd, inv_mag), ycf);
 
-            let new_distance = match distance_function {
-                CellDistanceFunction::Euclidean => S::add_ps(S::mul_ps(xd, xd), S::mul_ps(yd, yd)),
-                CellDistanceFunction::Manhattan => S::add_ps(S::abs_ps(xd), S::abs_ps(yd)),
-                CellDistanceFunction::Natural => {
-                    let euc = S::add_ps(S::mul_ps(xd, xd), S::mul_ps(yd, yd));
-                    let man = S::add_ps(S::abs_ps(xd), S::abs_ps(yd));
-                    S::add_ps(euc, man)
-                }
-            };
-            let mut i = index1;
+            let new_distance = distance_2d::<S>(distance_function, xd, yd);
+            let mut i = depth;
             while i > 0 {
                 distance[i] = S::max_ps(S::min_ps(distance[i], new_distance), distance[i - 1]);
-                distance[0] = S::min_ps(distance[0], new_distance);
                 i -= 1;
             }
+            distance[0] = S::min_ps(distance[0], new_distance);
             ycf = S::add_ps(ycf, S::set1_ps(1.0));
             yc = S::add_epi32(yc, S::set1_epi32(Y_PRIME));
         }
@@ -343,13 +368,43 @@ pub unsafe fn cellular2_2d<S: Simd>(
         xc = S::add_epi32(xc, S::set1_epi32(X_PRIME));
     }
 
-    match return_type {
-        Cell2ReturnType::Distance2 => distance[index1],
-        Cell2ReturnType::Distance2Add => S::add_ps(distance[index0], distance[index1]),
-        Cell2ReturnType::Distance2Sub => S::sub_ps(distance[index0], distance[index1]),
-        Cell2ReturnType::Distance2Mul => S::mul_ps(distance[index0], distance[index1]),
-        Cell2ReturnType::Distance2Div => S::div_ps(distance[index0], distance[index1]),
-    }
+    distance
+}
+
+/// The distances to the four nearest feature points, F1 to F4 in ascending order
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn cellular2_distances_2d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    distance_function: CellDistanceFunction,
+    jitter: S::Vf32,
+    seed: i32,
+) -> [S::Vf32; 4] {
+    sorted_distances_2d::<S>(x, y, distance_function, jitter, 3, seed)
+}
+
+#[inline(always)]
+pub unsafe fn cellular2_2d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    distance_function: CellDistanceFunction,
+    return_type: Cell2ReturnType,
+    jitter: S::Vf32,
+    index0: usize,
+    index1: usize,
+    seed: i32,
+) -> S::Vf32 {
+    cell2_combine::<S, _, _>(
+        return_type,
+        index0,
+        index1,
+        |depth| sorted_distances_2d::<S>(x, y, distance_function, jitter, depth, seed),
+        || cellular_edge_2d::<S>(x, y, jitter, seed),
+    )
 }
 
 #[inline(always)]
@@ -442,6 +497,13 @@ pub unsafe fn cellular_3d<S: Simd>(
                         let man = S::add_ps(S::add_ps(S::abs_ps(xd), S::abs_ps(yd)), S::abs_ps(zd));
                         S::add_ps(euc, man)
                     }
+                    CellDistanceFunction::Chebyshev => {
+                        S::max_ps(S::max_ps(S::abs_ps(xd), S::abs_ps(yd)), S::abs_ps(zd))
+                    }
+                    CellDistanceFunction::Minkowski(p) => S::add_ps(
+                        S::add_ps(abs_pow::<S>(xd, p), abs_pow::<S>(yd, p)),
+                        abs_pow::<S>(zd, p),
+                    ),
                 };
                 let closer = S::cmplt_ps(new_distance, distance);
                 distance = S::min_ps(new_distance, distance);
@@ -462,40 +524,56 @@ pub unsafe fn cellular_3d<S: Simd>(
     }
 }
 
+/// Wraps the lattice cell `c` into a period of `period` cells
 #[inline(always)]
-pub unsafe fn cellular2_3d<S: Simd>(
+unsafe fn wrap_cell<S: Simd>(c: S::Vf32, period: f32) -> S::Vi32 {
+    let p = S::set1_ps(period);
+    let mut wrapped = S::sub_ps(c, S::mul_ps(p, S::fast_floor_ps(S::div_ps(c, p))));
+    // Rounding in the division can land a hair outside of the period
+    wrapped = S::blendv_ps(wrapped, S::sub_ps(wrapped, p), S::cmpge_ps(wrapped, p));
+    wrapped = S::blendv_ps(
+        wrapped,
+        S::add_ps(wrapped, p),
+        S::cmplt_ps(wrapped, S::setzero_ps()),
+    );
+    S::cvtps_epi32(wrapped)
+}
+
+/// 3d cellular noise whose cells repeat every `period` cells along each axis,
+/// see `cellular_3d`. Periods should be whole numbers.
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn cellular_3d_periodic<S: Simd>(
     x: S::Vf32,
     y: S::Vf32,
     z: S::Vf32,
+    period: [f32; 3],
     distance_function: CellDistanceFunction,
-    return_type: Cell2ReturnType,
+    return_type: CellReturnType,
     jitter: S::Vf32,
-    index0: usize,
-    index1: usize,
     seed: i32,
 ) -> S::Vf32 {
-    let mut distance: [S::Vf32; 4] = [S::set1_ps(999999.0); 4];
-
-    let mut xc = S::sub_epi32(S::cvtps_epi32(x), S::set1_epi32(1));
-    let mut yc_base = S::sub_epi32(S::cvtps_epi32(y), S::set1_epi32(1));
-    let mut zc_base = S::sub_epi32(S::cvtps_epi32(z), S::set1_epi32(1));
+    let mut distance = S::set1_ps(999999.0);
+    let mut cell_value = S::setzero_ps();
 
-    let mut xcf = S::sub_ps(S::cvtepi32_ps(xc), x);
-    let ycf_base = S::sub_ps(S::cvtepi32_ps(yc_base), y);
-    let zcf_base = S::sub_ps(S::cvtepi32_ps(zc_base), z);
+    let xc_base = S::sub_ps(S::cvtepi32_ps(S::cvtps_epi32(x)), S::set1_ps(1.0));
+    let yc_base = S::sub_ps(S::cvtepi32_ps(S::cvtps_epi32(y)), S::set1_ps(1.0));
+    let zc_base = S::sub_ps(S::cvtepi32_ps(S::cvtps_epi32(z)), S::set1_ps(1.0));
 
-    xc = S::mullo_epi32(xc, S::set1_epi32(X_PRIME));
-    yc_base = S::mullo_epi32(yc_base, S::set1_epi32(Y_PRIME));
-    zc_base = S::mullo_epi32(zc_base, S::set1_epi32(Z_PRIME));
+    for dx in 0..3 {
+        let xc = S::add_ps(xc_base, S::set1_ps(dx as f32));
+        let xh = S::mullo_epi32(wrap_cell::<S>(xc, period[0]), S::set1_epi32(X_PRIME));
+        for dy in 0..3 {
+            let yc = S::add_ps(yc_base, S::set1_ps(dy as f32));
+            let yh = S::mullo_epi32(wrap_cell::<S>(yc, period[1]), S::set1_epi32(Y_PRIME));
+            for dz in 0..3 {
+                let zc = S::add_ps(zc_base, S::set1_ps(dz as f32));
+                let zh = S::mullo_epi32(wrap_cell::<S>(zc, period[2]), S::set1_epi32(Z_PRIME));
 
-    for _x in 0..3 {
-        let mut ycf = ycf_base;
-        let mut yc = yc_base;
-        for _y in 0..3 {
-            let mut zcf = zcf_base;
-            let mut zc = zc_base;
-            for _z in 0..3 {
-                let hash = hash_3d::<S>(seed, xc, yc, zc);
+                let hash = hash_3d::<S>(seed, xh, yh, zh);
                 let mut xd = S::sub_ps(
                     S::cvtepi32_ps(S::and_epi32(hash, S::set1_epi32(BIT_10_MASK))),
                     S::set1_ps(511.5),
@@ -521,48 +599,1033 @@ pub unsafe fn cellular2_3d<S: Simd>(
                         S::add_ps(S::mul_ps(yd, yd), S::mul_ps(zd, zd)),
                     )),
                 );
-                xd = S::add_ps(S::mul_ps(xd, inv_mag), xcf);
-                yd = S::add_ps(S::mul_ps(yd, inv_mag), ycf);
-                zd = S::add_ps(S::mul_ps(zd, inv_mag), zcf);
+                xd = S::add_ps(S::mul_ps(xd, inv_mag), S::sub_ps(xc, x));
+                yd = S::add_ps(S::mul_ps(yd, inv_mag), S::sub_ps(yc, y));
+                zd = S::add_ps(S::mul_ps(zd, inv_mag), S::sub_ps(zc, z));
 
-                let new_distance = match distance_function {
-                    CellDistanceFunction::Euclidean => S::add_ps(
-                        S::mul_ps(xd, xd),
-                        S::add_ps(S::mul_ps(yd, yd), S::mul_ps(zd, zd)),
-                    ),
-                    CellDistanceFunction::Manhattan => {
-                        S::add_ps(S::add_ps(S::abs_ps(xd), S::abs_ps(yd)), S::abs_ps(zd))
-                    }
-                    CellDistanceFunction::Natural => {
-                        let euc = S::add_ps(
-                            S::mul_ps(xd, xd),
-                            S::add_ps(S::mul_ps(yd, yd), S::mul_ps(zd, zd)),
-                        );
-                        let man = S::add_ps(S::add_ps(S::abs_ps(xd), S::abs_ps(yd)), S::abs_ps(zd));
-                        S::add_ps(euc, man)
-                    }
-                };
-                let mut i = index1;
-                while i > 0 {
-                    distance[i] = S::max_ps(S::min_ps(distance[i], new_distance), distance[i - 1]);
-                    distance[0] = S::min_ps(distance[0], new_distance);
-                    i -= 1;
-                }
-                zcf = S::add_ps(ycf, S::set1_ps(1.0));
-                zc = S::add_epi32(yc, S::set1_epi32(Z_PRIME));
+                let new_cell_value = S::mul_ps(S::set1_ps(HASH_2_FLOAT), S::cvtepi32_ps(hash));
+                let new_distance = distance_3d::<S>(distance_function, xd, yd, zd);
+                let closer = S::cmplt_ps(new_distance, distance);
+                distance = S::min_ps(new_distance, distance);
+                cell_value = S::blendv_ps(cell_value, new_cell_value, closer);
             }
-            ycf = S::add_ps(ycf, S::set1_ps(1.0));
-            yc = S::add_epi32(yc, S::set1_epi32(Y_PRIME));
         }
-        xcf = S::add_ps(xcf, S::set1_ps(1.0));
-        xc = S::add_epi32(xc, S::set1_epi32(X_PRIME));
     }
 
     match return_type {
-        Cell2ReturnType::Distance2 => distance[index1],
-        Cell2ReturnType::Distance2Add => S::add_ps(distance[index0], distance[index1]),
-        Cell2ReturnType::Distance2Sub => S::sub_ps(distance[index0], distance[index1]),
-        Cell2ReturnType::Distance2Mul => S::mul_ps(distance[index0], distance[index1]),
-        Cell2ReturnType::Distance2Div => S::div_ps(distance[index0], distance[index1]),
+        CellReturnType::Distance => distance,
+        CellReturnType::CellValue => cell_value,
     }
 }
+
+#[inline(always)]
+unsafe fn distance_3d<S: Simd>(
+    distance_function: CellDistanceFunction,
+    xd: S::Vf32,
+    yd: S::Vf32,
+    zd: S::Vf32,
+) -> S::Vf32 {
+    match distance_function {
+        CellDistanceFunction::Euclidean => S::add_ps(
+            S::mul_ps(xd, xd),
+            S::add_ps(S::mul_ps(yd, yd), S::mul_ps(zd, zd)),
+        ),
+        CellDistanceFunction::Manhattan => {
+            S::add_ps(S::add_ps(S::abs_ps(xd), S::abs_ps(yd)), S::abs_ps(zd))
+        }
+        CellDistanceFunction::Natural => {
+            let euc = S::add_ps(
+                S::mul_ps(xd, xd),
+                S::add_ps(S::mul_ps(yd, yd), S::mul_ps(zd, zd)),
+            );
+            let man = S::add_ps(S::add_ps(S::abs_ps(xd), S::abs_ps(yd)), S::abs_ps(zd));
+            S::add_ps(euc, man)
+        }
+        CellDistanceFunction::Chebyshev => {
+            S::max_ps(S::max_ps(S::abs_ps(xd), S::abs_ps(yd)), S::abs_ps(zd))
+        }
+        CellDistanceFunction::Minkowski(p) => S::add_ps(
+            S::add_ps(abs_pow::<S>(xd, p), abs_pow::<S>(yd, p)),
+            abs_pow::<S>(zd, p),
+        ),
+    }
+}
+
+#[inline(always)]
+unsafe fn sorted_distances_3d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    distance_function: CellDistanceFunction,
+    jitter: S::Vf32,
+    depth: usize,
+    seed: i32,
+) -> [S::Vf32; 4] {
+    let mut distance: [S::Vf32; 4] = [S::set1_ps(999999.0); 4];
+
+    let mut xc = S::sub_epi32(S::cvtps_epi32(x), S::set1_epi32(1));
+    let mut yc_base = S::sub_epi32(S::cvtps_epi32(y), S::set1_epi32(1));
+    let mut zc_base = S::sub_epi32(S::cvtps_epi32(z), S::set1_epi32(1));
+
+    let mut xcf = S::sub_ps(S::cvtepi32_ps(xc), x);
+    let ycf_base = S::sub_ps(S::cvtepi32_ps(yc_base), y);
+    let zcf_base = S::sub_ps(S::cvtepi32_ps(zc_base), z);
+
+    xc = S::mullo_epi32(xc, S::set1_epi32(X_PRIME));
+    yc_base = S::mullo_epi32(yc_base, S::set1_epi32(Y_PRIME));
+    zc_base = S::mullo_epi32(zc_base, S::set1_epi32(Z_PRIME));
+
+    for _x in 0..3 {
+        let mut ycf = ycf_base;
+        let mut yc = yc_base;
+        for _y in 0..3 {
+            let mut zcf = zcf_base;
+            let mut zc = zc_base;
+            for _z in 0..3 {
+                let hash = hash_3d::<S>(seed, xc, yc, zc);
+                let mut xd = S::sub_ps(
+                    S::cvtepi32_ps(S::and_epi32(hash, S::set1_epi32(BIT_10_MASK))),
+                    S::set1_ps(511.5),
+                );
+                let mut yd = S::sub_ps(
+                    S::cvtepi32_ps(S::and_epi32(
+                        S::srai_epi32(hash, 10),
+                        S::set1_epi32(BIT_10_MASK),
+                    )),
+                    S::set1_ps(511.5),
+                );
+                let mut zd = S::sub_ps(
+                    S::cvtepi32_ps(S::and_epi32(
+                        S::srai_epi32(hash, 20),
+                        S::set1_epi32(BIT_10_MASK),
+                    )),
+                    S::set1_ps(511.5),
+                );
+                let inv_mag = S::mul_ps(
+                    jitter,
+                    S::rsqrt_ps(S::add_ps(
+                        S::mul_ps(xd, xd),
+                        S::add_ps(S::mul_ps(yd, yd), S::mul_ps(zd, zd)),
+                    )),
+                );
+                xd = S::add_ps(S::mul_ps(xd, inv_mag), xcf);
+                yd = S::add_ps(S::mul_ps(yd, inv_mag), ycf);
+                zd = S::add_ps(S::mul_ps(zd, inv_mag), zcf);
+
+                let new_distance = match distance_function {
+                    CellDistanceFunction::Euclidean => S::add_ps(
+                        S::mul_ps(xd, xd),
+                        S::add_ps(S::mul_ps(yd, yd), S::mul_ps(zd, zd)),
+                    ),
+                    CellDistanceFunction::Manhattan => {
+                        S::add_ps(S::add_ps(S::abs_ps(xd), S::abs_ps(yd)), S::abs_ps(zd))
+                    }
+                    CellDistanceFunction::Natural => {
+                        let euc = S::add_ps(
+                            S::mul_ps(xd, xd),
+                            S::add_ps(S::mul_ps(yd, yd), S::mul_ps(zd, zd)),
+                        );
+                        let man = S::add_ps(S::add_ps(S::abs_ps(xd), S::abs_ps(yd)), S::abs_ps(zd));
+                        S::add_ps(euc, man)
+                    }
+                    CellDistanceFunction::Chebyshev => {
+                        S::max_ps(S::max_ps(S::abs_ps(xd), S::abs_ps(yd)), S::abs_ps(zd))
+                    }
+                    CellDistanceFunction::Minkowski(p) => S::add_ps(
+                        S::add_ps(abs_pow::<S>(xd, p), abs_pow::<S>(yd, p)),
+                        abs_pow::<S>(zd, p),
+                    ),
+                };
+                let mut i = depth;
+                while i > 0 {
+                    distance[i] = S::max_ps(S::min_ps(distance[i], new_distance), distance[i - 1]);
+                    i -= 1;
+                }
+                distance[0] = S::min_ps(distance[0], new_distance);
+                zcf = S::add_ps(ycf, S::set1_ps(1.0));
+                zc = S::add_epi32(yc, S::set1_epi32(Z_PRIME));
+            }
+            ycf = S::add_ps(ycf, S::set1_ps(1.0));
+            yc = S::add_epi32(yc, S::set1_epi32(Y_PRIME));
+        }
+        xcf = S::add_ps(xcf, S::set1_ps(1.0));
+        xc = S::add_epi32(xc, S::set1_epi32(X_PRIME));
+    }
+
+    distance
+}
+
+/// The distances to the four nearest feature points, F1 to F4 in ascending order
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn cellular2_distances_3d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    distance_function: CellDistanceFunction,
+    jitter: S::Vf32,
+    seed: i32,
+) -> [S::Vf32; 4] {
+    sorted_distances_3d::<S>(x, y, z, distance_function, jitter, 3, seed)
+}
+
+#[inline(always)]
+pub unsafe fn cellular2_3d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    distance_function: CellDistanceFunction,
+    return_type: Cell2ReturnType,
+    jitter: S::Vf32,
+    index0: usize,
+    index1: usize,
+    seed: i32,
+) -> S::Vf32 {
+    cell2_combine::<S, _, _>(
+        return_type,
+        index0,
+        index1,
+        |depth| sorted_distances_3d::<S>(x, y, z, distance_function, jitter, depth, seed),
+        || cellular_edge_3d::<S>(x, y, z, jitter, seed),
+    )
+}
+
+#[inline(always)]
+unsafe fn hash_4d<S: Simd>(seed: i32, x: S::Vi32, y: S::Vi32, z: S::Vi32, w: S::Vi32) -> S::Vi32 {
+    let mut hash = S::xor_epi32(x, S::set1_epi32(seed));
+    hash = S::xor_epi32(y, hash);
+    hash = S::xor_epi32(z, hash);
+    hash = S::xor_epi32(w, hash);
+    S::mullo_epi32(
+        S::mullo_epi32(S::mullo_epi32(hash, hash), S::set1_epi32(60493)),
+        hash,
+    )
+}
+
+/// Extracts one 8 bit component of the feature point direction from the hash,
+/// four of them fit where the lower dimensions use 10 bits per axis
+#[inline(always)]
+unsafe fn hash_component_4d<S: Simd>(hash: S::Vi32, shift: i32) -> S::Vf32 {
+    S::sub_ps(
+        S::cvtepi32_ps(S::and_epi32(
+            S::srai_epi32(hash, shift),
+            S::set1_epi32(BIT_8_MASK),
+        )),
+        S::set1_ps(127.5),
+    )
+}
+
+/// Returns the offset from the sample point to the feature point of a cell
+#[inline(always)]
+unsafe fn feature_offset_4d<S: Simd>(
+    hash: S::Vi32,
+    jitter: S::Vf32,
+    xcf: S::Vf32,
+    ycf: S::Vf32,
+    zcf: S::Vf32,
+    wcf: S::Vf32,
+) -> (S::Vf32, S::Vf32, S::Vf32, S::Vf32) {
+    let xd = hash_component_4d::<S>(hash, 0);
+    let yd = hash_component_4d::<S>(hash, 8);
+    let zd = hash_component_4d::<S>(hash, 16);
+    let wd = hash_component_4d::<S>(hash, 24);
+    let inv_mag = S::mul_ps(
+        jitter,
+        S::rsqrt_ps(S::add_ps(
+            S::add_ps(S::mul_ps(xd, xd), S::mul_ps(yd, yd)),
+            S::add_ps(S::mul_ps(zd, zd), S::mul_ps(wd, wd)),
+        )),
+    );
+    (
+        S::add_ps(S::mul_ps(xd, inv_mag), xcf),
+        S::add_ps(S::mul_ps(yd, inv_mag), ycf),
+        S::add_ps(S::mul_ps(zd, inv_mag), zcf),
+        S::add_ps(S::mul_ps(wd, inv_mag), wcf),
+    )
+}
+
+/// Returns the position of the feature point of the cell at `xi, yi, zi, wi`
+#[inline(always)]
+unsafe fn feature_point_4d<S: Simd>(
+    xi: S::Vi32,
+    yi: S::Vi32,
+    zi: S::Vi32,
+    wi: S::Vi32,
+    jitter: S::Vf32,
+    seed: i32,
+) -> (S::Vf32, S::Vf32, S::Vf32, S::Vf32) {
+    let hash = hash_4d::<S>(
+        seed,
+        S::mullo_epi32(xi, S::set1_epi32(X_PRIME)),
+        S::mullo_epi32(yi, S::set1_epi32(Y_PRIME)),
+        S::mullo_epi32(zi, S::set1_epi32(Z_PRIME)),
+        S::mullo_epi32(wi, S::set1_epi32(W_PRIME)),
+    );
+    feature_offset_4d::<S>(
+        hash,
+        jitter,
+        S::cvtepi32_ps(xi),
+        S::cvtepi32_ps(yi),
+        S::cvtepi32_ps(zi),
+        S::cvtepi32_ps(wi),
+    )
+}
+
+#[inline(always)]
+unsafe fn distance_4d<S: Simd>(
+    distance_function: CellDistanceFunction,
+    xd: S::Vf32,
+    yd: S::Vf32,
+    zd: S::Vf32,
+    wd: S::Vf32,
+) -> S::Vf32 {
+    let euc = || {
+        S::add_ps(
+            S::add_ps(S::mul_ps(xd, xd), S::mul_ps(yd, yd)),
+            S::add_ps(S::mul_ps(zd, zd), S::mul_ps(wd, wd)),
+        )
+    };
+    let man = || {
+        S::add_ps(
+            S::add_ps(S::abs_ps(xd), S::abs_ps(yd)),
+            S::add_ps(S::abs_ps(zd), S::abs_ps(wd)),
+        )
+    };
+    match distance_function {
+        CellDistanceFunction::Euclidean => euc(),
+        CellDistanceFunction::Manhattan => man(),
+        CellDistanceFunction::Natural => S::add_ps(euc(), man()),
+        CellDistanceFunction::Chebyshev => S::max_ps(
+            S::max_ps(S::abs_ps(xd), S::abs_ps(yd)),
+            S::max_ps(S::abs_ps(zd), S::abs_ps(wd)),
+        ),
+        CellDistanceFunction::Minkowski(p) => S::add_ps(
+            S::add_ps(abs_pow::<S>(xd, p), abs_pow::<S>(yd, p)),
+            S::add_ps(abs_pow::<S>(zd, p), abs_pow::<S>(wd, p)),
+        ),
+    }
+}
+
+/// 4d cellular noise, the distance to the nearest feature point or its
+/// value, depending on `return_type`
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn cellular_4d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    w: S::Vf32,
+    distance_function: CellDistanceFunction,
+    return_type: CellReturnType,
+    jitter: S::Vf32,
+    seed: i32,
+) -> S::Vf32 {
+    let mut distance = S::set1_ps(999999.0);
+    let mut cell_value = S::setzero_ps();
+
+    let mut xc = S::sub_epi32(S::cvtps_epi32(x), S::set1_epi32(1));
+    let mut yc_base = S::sub_epi32(S::cvtps_epi32(y), S::set1_epi32(1));
+    let mut zc_base = S::sub_epi32(S::cvtps_epi32(z), S::set1_epi32(1));
+    let mut wc_base = S::sub_epi32(S::cvtps_epi32(w), S::set1_epi32(1));
+
+    let mut xcf = S::sub_ps(S::cvtepi32_ps(xc), x);
+    let ycf_base = S::sub_ps(S::cvtepi32_ps(yc_base), y);
+    let zcf_base = S::sub_ps(S::cvtepi32_ps(zc_base), z);
+    let wcf_base = S::sub_ps(S::cvtepi32_ps(wc_base), w);
+
+    xc = S::mullo_epi32(xc, S::set1_epi32(X_PRIME));
+    yc_base = S::mullo_epi32(yc_base, S::set1_epi32(Y_PRIME));
+    zc_base = S::mullo_epi32(zc_base, S::set1_epi32(Z_PRIME));
+    wc_base = S::mullo_epi32(wc_base, S::set1_epi32(W_PRIME));
+
+    for _x in 0..3 {
+        let mut ycf = ycf_base;
+        let mut yc = yc_base;
+        for _y in 0..3 {
+            let mut zcf = zcf_base;
+            let mut zc = zc_base;
+            for _z in 0..3 {
+                let mut wcf = wcf_base;
+                let mut wc = wc_base;
+                for _w in 0..3 {
+                    let hash = hash_4d::<S>(seed, xc, yc, zc, wc);
+                    let (xd, yd, zd, wd) = feature_offset_4d::<S>(hash, jitter, xcf, ycf, zcf, wcf);
+                    let new_distance = distance_4d::<S>(distance_function, xd, yd, zd, wd);
+
+                    let new_cell_value = S::mul_ps(S::set1_ps(HASH_2_FLOAT), S::cvtepi32_ps(hash));
+                    let closer = S::cmplt_ps(new_distance, distance);
+                    distance = S::min_ps(new_distance, distance);
+                    cell_value = S::blendv_ps(cell_value, new_cell_value, closer);
+                    wcf = S::add_ps(wcf, S::set1_ps(1.0));
+                    wc = S::add_epi32(wc, S::set1_epi32(W_PRIME));
+                }
+                zcf = S::add_ps(zcf, S::set1_ps(1.0));
+                zc = S::add_epi32(zc, S::set1_epi32(Z_PRIME));
+            }
+            ycf = S::add_ps(ycf, S::set1_ps(1.0));
+            yc = S::add_epi32(yc, S::set1_epi32(Y_PRIME));
+        }
+        xcf = S::add_ps(xcf, S::set1_ps(1.0));
+        xc = S::add_epi32(xc, S::set1_epi32(X_PRIME));
+    }
+
+    match return_type {
+        CellReturnType::Distance => distance,
+        CellReturnType::CellValue => cell_value,
+    }
+}
+
+#[inline(always)]
+unsafe fn sorted_distances_4d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    w: S::Vf32,
+    distance_function: CellDistanceFunction,
+    jitter: S::Vf32,
+    depth: usize,
+    seed: i32,
+) -> [S::Vf32; 4] {
+    let mut distance: [S::Vf32; 4] = [S::set1_ps(999999.0); 4];
+
+    let mut xc = S::sub_epi32(S::cvtps_epi32(x), S::set1_epi32(1));
+    let mut yc_base = S::sub_epi32(S::cvtps_epi32(y), S::set1_epi32(1));
+    let mut zc_base = S::sub_epi32(S::cvtps_epi32(z), S::set1_epi32(1));
+    let mut wc_base = S::sub_epi32(S::cvtps_epi32(w), S::set1_epi32(1));
+
+    let mut xcf = S::sub_ps(S::cvtepi32_ps(xc), x);
+    let ycf_base = S::sub_ps(S::cvtepi32_ps(yc_base), y);
+    let zcf_base = S::sub_ps(S::cvtepi32_ps(zc_base), z);
+    let wcf_base = S::sub_ps(S::cvtepi32_ps(wc_base), w);
+
+    xc = S::mullo_epi32(xc, S::set1_epi32(X_PRIME));
+    yc_base = S::mullo_epi32(yc_base, S::set1_epi32(Y_PRIME));
+    zc_base = S::mullo_epi32(zc_base, S::set1_epi32(Z_PRIME));
+    wc_base = S::mullo_epi32(wc_base, S::set1_epi32(W_PRIME));
+
+    for _x in 0..3 {
+        let mut ycf = ycf_base;
+        let mut yc = yc_base;
+        for _y in 0..3 {
+            let mut zcf = zcf_base;
+            let mut zc = zc_base;
+            for _z in 0..3 {
+                let mut wcf = wcf_base;
+                let mut wc = wc_base;
+                for _w in 0..3 {
+                    let hash = hash_4d::<S>(seed, xc, yc, zc, wc);
+                    let (xd, yd, zd, wd) = feature_offset_4d::<S>(hash, jitter, xcf, ycf, zcf, wcf);
+                    let new_distance = distance_4d::<S>(distance_function, xd, yd, zd, wd);
+
+                    let mut i = depth;
+
+                    while i > 0 {
+                        distance[i] =
+                            S::max_ps(S::min_ps(distance[i], new_distance), distance[i - 1]);
+                        i -= 1;
+                    }
+                    distance[0] = S::min_ps(distance[0], new_distance);
+                    wcf = S::add_ps(wcf, S::set1_ps(1.0));
+                    wc = S::add_epi32(wc, S::set1_epi32(W_PRIME));
+                }
+                zcf = S::add_ps(zcf, S::set1_ps(1.0));
+                zc = S::add_epi32(zc, S::set1_epi32(Z_PRIME));
+            }
+            ycf = S::add_ps(ycf, S::set1_ps(1.0));
+            yc = S::add_epi32(yc, S::set1_epi32(Y_PRIME));
+        }
+        xcf = S::add_ps(xcf, S::set1_ps(1.0));
+        xc = S::add_epi32(xc, S::set1_epi32(X_PRIME));
+    }
+
+    distance
+}
+
+/// The distances to the four nearest feature points, F1 to F4 in ascending order
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn cellular2_distances_4d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    w: S::Vf32,
+    distance_function: CellDistanceFunction,
+    jitter: S::Vf32,
+    seed: i32,
+) -> [S::Vf32; 4] {
+    sorted_distances_4d::<S>(x, y, z, w, distance_function, jitter, 3, seed)
+}
+
+/// 4d cellular noise combining the distances to two of the nearest
+/// feature points, picked by `index0` and `index1`
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn cellular2_4d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    w: S::Vf32,
+    distance_function: CellDistanceFunction,
+    return_type: Cell2ReturnType,
+    jitter: S::Vf32,
+    index0: usize,
+    index1: usize,
+    seed: i32,
+) -> S::Vf32 {
+    cell2_combine::<S, _, _>(
+        return_type,
+        index0,
+        index1,
+        |depth| sorted_distances_4d::<S>(x, y, z, w, distance_function, jitter, depth, seed),
+        || cellular_edge_4d::<S>(x, y, z, w, jitter, seed),
+    )
+}
+
+/// The nearest cell to each 2d sample point, as found by `cellular_feature_2d`
+pub struct CellularFeature2d<S: Simd> {
+    /// Distance to the nearest feature point, as returned by `CellReturnType::Distance`
+    pub distance: S::Vf32,
+    /// Integer lattice coordinates of the nearest cell
+    pub cell_x: S::Vi32,
+    pub cell_y: S::Vi32,
+    /// Hash of the nearest cell, `CellReturnType::CellValue` is this scaled into [-1, 1]
+    pub hash: S::Vi32,
+    /// Position of the nearest feature point, in the same space as the sample point
+    pub feature_x: S::Vf32,
+    pub feature_y: S::Vf32,
+}
+
+/// The nearest cell to each 3d sample point, as found by `cellular_feature_3d`
+pub struct CellularFeature3d<S: Simd> {
+    /// Distance to the nearest feature point, as returned by `CellReturnType::Distance`
+    pub distance: S::Vf32,
+    /// Integer lattice coordinates of the nearest cell
+    pub cell_x: S::Vi32,
+    pub cell_y: S::Vi32,
+    pub cell_z: S::Vi32,
+    /// Hash of the nearest cell, `CellReturnType::CellValue` is this scaled into [-1, 1]
+    pub hash: S::Vi32,
+    /// Position of the nearest feature point, in the same space as the sample point
+    pub feature_x: S::Vf32,
+    pub feature_y: S::Vf32,
+    pub feature_z: S::Vf32,
+}
+
+/// Finds the nearest cell of 2d cellular noise, returning its lattice coordinates,
+/// hash and feature point along with the distance so per cell data can be looked up
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn cellular_feature_2d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    distance_function: CellDistanceFunction,
+    jitter: S::Vf32,
+    seed: i32,
+) -> CellularFeature2d<S> {
+    let mut nearest = CellularFeature2d::<S> {
+        distance: S::set1_ps(999999.0),
+        cell_x: S::setzero_epi32(),
+        cell_y: S::setzero_epi32(),
+        hash: S::setzero_epi32(),
+        feature_x: S::setzero_ps(),
+        feature_y: S::setzero_ps(),
+    };
+
+    let mut xi = S::sub_epi32(S::cvtps_epi32(x), S::set1_epi32(1));
+    let yi_base = S::sub_epi32(S::cvtps_epi32(y), S::set1_epi32(1));
+
+    let mut xcf = S::sub_ps(S::cvtepi32_ps(xi), x);
+    let ycf_base = S::sub_ps(S::cvtepi32_ps(yi_base), y);
+
+    let mut xc = S::mullo_epi32(xi, S::set1_epi32(X_PRIME));
+    let yc_base = S::mullo_epi32(yi_base, S::set1_epi32(Y_PRIME));
+
+    for _x in 0..3 {
+        let mut yi = yi_base;
+        let mut ycf = ycf_base;
+        let mut yc = yc_base;
+        for _y in 0..3 {
+            let hash = hash_2d::<S>(seed, xc, yc);
+            let mut xd = S::sub_ps(
+                S::cvtepi32_ps(S::and_epi32(hash, S::set1_epi32(BIT_10_MASK))),
+                S::set1_ps(511.5),
+            );
+            let mut yd = S::sub_ps(
+                S::cvtepi32_ps(S::and_epi32(
+                    S::srai_epi32(hash, 10),
+                    S::set1_epi32(BIT_10_MASK),
+                )),
+                S::set1_ps(511.5),
+            );
+            let inv_mag = S::mul_ps(
+                jitter,
+                S::rsqrt_ps(S::add_ps(S::mul_ps(xd, xd), S::mul_ps(yd, yd))),
+            );
+            xd = S::add_ps(S::mul_ps(xd, inv_mag), xcf);
+            yd = S::add_ps(S::mul_ps(yd, inv_mag), ycf);
+
+            let new_distance = match distance_function {
+                CellDistanceFunction::Euclidean => S::add_ps(S::mul_ps(xd, xd), S::mul_ps(yd, yd)),
+                CellDistanceFunction::Manhattan => S::add_ps(S::abs_ps(xd), S::abs_ps(yd)),
+                CellDistanceFunction::Natural => S::add_ps(
+                    S::add_ps(S::mul_ps(xd, xd), S::mul_ps(yd, yd)),
+                    S::add_ps(S::abs_ps(xd), S::abs_ps(yd)),
+                ),
+                CellDistanceFunction::Chebyshev => S::max_ps(S::abs_ps(xd), S::abs_ps(yd)),
+                CellDistanceFunction::Minkowski(p) => {
+                    S::add_ps(abs_pow::<S>(xd, p), abs_pow::<S>(yd, p))
+                }
+            };
+            let closer = S::cmplt_ps(new_distance, nearest.distance);
+            let closer_i = S::castps_epi32(closer);
+            nearest.distance = S::min_ps(new_distance, nearest.distance);
+            nearest.cell_x = S::blendv_epi32(nearest.cell_x, xi, closer_i);
+            nearest.cell_y = S::blendv_epi32(nearest.cell_y, yi, closer_i);
+            nearest.hash = S::blendv_epi32(nearest.hash, hash, closer_i);
+            nearest.feature_x = S::blendv_ps(nearest.feature_x, S::add_ps(x, xd), closer);
+            nearest.feature_y = S::blendv_ps(nearest.feature_y, S::add_ps(y, yd), closer);
+
+            yi = S::add_epi32(yi, S::set1_epi32(1));
+            ycf = S::add_ps(ycf, S::set1_ps(1.0));
+            yc = S::add_epi32(yc, S::set1_epi32(Y_PRIME));
+        }
+        xi = S::add_epi32(xi, S::set1_epi32(1));
+        xcf = S::add_ps(xcf, S::set1_ps(1.0));
+        xc = S::add_epi32(xc, S::set1_epi32(X_PRIME));
+    }
+
+    nearest
+}
+
+/// Finds the nearest cell of 3d cellular noise, returning its lattice coordinates,
+/// hash and feature point along with the distance so per cell data can be looked up
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn cellular_feature_3d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    distance_function: CellDistanceFunction,
+    jitter: S::Vf32,
+    seed: i32,
+) -> CellularFeature3d<S> {
+    let mut nearest = CellularFeature3d::<S> {
+        distance: S::set1_ps(999999.0),
+        cell_x: S::setzero_epi32(),
+        cell_y: S::setzero_epi32(),
+        cell_z: S::setzero_epi32(),
+        hash: S::setzero_epi32(),
+        feature_x: S::setzero_ps(),
+        feature_y: S::setzero_ps(),
+        feature_z: S::setzero_ps(),
+    };
+
+    let mut xi = S::sub_epi32(S::cvtps_epi32(x), S::set1_epi32(1));
+    let yi_base = S::sub_epi32(S::cvtps_epi32(y), S::set1_epi32(1));
+    let zi_base = S::sub_epi32(S::cvtps_epi32(z), S::set1_epi32(1));
+
+    let mut xcf = S::sub_ps(S::cvtepi32_ps(xi), x);
+    let ycf_base = S::sub_ps(S::cvtepi32_ps(yi_base), y);
+    let zcf_base = S::sub_ps(S::cvtepi32_ps(zi_base), z);
+
+    let mut xc = S::mullo_epi32(xi, S::set1_epi32(X_PRIME));
+    let yc_base = S::mullo_epi32(yi_base, S::set1_epi32(Y_PRIME));
+    let zc_base = S::mullo_epi32(zi_base, S::set1_epi32(Z_PRIME));
+
+    for _x in 0..3 {
+        let mut yi = yi_base;
+        let mut ycf = ycf_base;
+        let mut yc = yc_base;
+        for _y in 0..3 {
+            let mut zi = zi_base;
+            let mut zcf = zcf_base;
+            let mut zc = zc_base;
+            for _z in 0..3 {
+                let hash = hash_3d::<S>(seed, xc, yc, zc);
+                let mut xd = S::sub_ps(
+                    S::cvtepi32_ps(S::and_epi32(hash, S::set1_epi32(BIT_10_MASK))),
+                    S::set1_ps(511.5),
+                );
+                let mut yd = S::sub_ps(
+                    S::cvtepi32_ps(S::and_epi32(
+                        S::srai_epi32(hash, 10),
+                        S::set1_epi32(BIT_10_MASK),
+                    )),
+                    S::set1_ps(511.5),
+                );
+                let mut zd = S::sub_ps(
+                    S::cvtepi32_ps(S::and_epi32(
+                        S::srai_epi32(hash, 20),
+                        S::set1_epi32(BIT_10_MASK),
+                    )),
+                    S::set1_ps(511.5),
+                );
+                let inv_mag = S::mul_ps(
+                    jitter,
+                    S::rsqrt_ps(S::add_ps(
+                        S::mul_ps(xd, xd),
+                        S::add_ps(S::mul_ps(yd, yd), S::mul_ps(zd, zd)),
+                    )),
+                );
+                xd = S::add_ps(S::mul_ps(xd, inv_mag), xcf);
+                yd = S::add_ps(S::mul_ps(yd, inv_mag), ycf);
+                zd = S::add_ps(S::mul_ps(zd, inv_mag), zcf);
+
+                let new_distance = match distance_function {
+                    CellDistanceFunction::Euclidean => S::add_ps(
+                        S::mul_ps(xd, xd),
+                        S::add_ps(S::mul_ps(yd, yd), S::mul_ps(zd, zd)),
+                    ),
+                    CellDistanceFunction::Manhattan => {
+                        S::add_ps(S::add_ps(S::abs_ps(xd), S::abs_ps(yd)), S::abs_ps(zd))
+                    }
+                    CellDistanceFunction::Natural => {
+                        let euc = S::add_ps(
+                            S::mul_ps(xd, xd),
+                            S::add_ps(S::mul_ps(yd, yd), S::mul_ps(zd, zd)),
+                        );
+                        let man = S::add_ps(S::add_ps(S::abs_ps(xd), S::abs_ps(yd)), S::abs_ps(zd));
+                        S::add_ps(euc, man)
+                    }
+                    CellDistanceFunction::Chebyshev => {
+                        S::max_ps(S::max_ps(S::abs_ps(xd), S::abs_ps(yd)), S::abs_ps(zd))
+                    }
+                    CellDistanceFunction::Minkowski(p) => S::add_ps(
+                        S::add_ps(abs_pow::<S>(xd, p), abs_pow::<S>(yd, p)),
+                        abs_pow::<S>(zd, p),
+                    ),
+                };
+                let closer = S::cmplt_ps(new_distance, nearest.distance);
+                let closer_i = S::castps_epi32(closer);
+                nearest.distance = S::min_ps(new_distance, nearest.distance);
+                nearest.cell_x = S::blendv_epi32(nearest.cell_x, xi, closer_i);
+                nearest.cell_y = S::blendv_epi32(nearest.cell_y, yi, closer_i);
+                nearest.cell_z = S::blendv_epi32(nearest.cell_z, zi, closer_i);
+                nearest.hash = S::blendv_epi32(nearest.hash, hash, closer_i);
+                nearest.feature_x = S::blendv_ps(nearest.feature_x, S::add_ps(x, xd), closer);
+                nearest.feature_y = S::blendv_ps(nearest.feature_y, S::add_ps(y, yd), closer);
+                nearest.feature_z = S::blendv_ps(nearest.feature_z, S::add_ps(z, zd), closer);
+
+                zi = S::add_epi32(zi, S::set1_epi32(1));
+                zcf = S::add_ps(zcf, S::set1_ps(1.0));
+                zc = S::add_epi32(zc, S::set1_epi32(Z_PRIME));
+            }
+            yi = S::add_epi32(yi, S::set1_epi32(1));
+            ycf = S::add_ps(ycf, S::set1_ps(1.0));
+            yc = S::add_epi32(yc, S::set1_epi32(Y_PRIME));
+        }
+        xi = S::add_epi32(xi, S::set1_epi32(1));
+        xcf = S::add_ps(xcf, S::set1_ps(1.0));
+        xc = S::add_epi32(xc, S::set1_epi32(X_PRIME));
+    }
+
+    nearest
+}
+
+/// Exact distance from each sample point to the nearest cell border, the
+/// midpoint between the nearest feature point and its neighbour on that side.
+/// The neighbours searched are the 2 cells either side of the nearest cell.
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn cellular_edge_1d<S: Simd>(x: S::Vf32, jitter: S::Vf32, seed: i32) -> S::Vf32 {
+    let mut distance = S::set1_ps(999999.0);
+    let mut nearest_cell = S::setzero_epi32();
+    let mut nearest_x = S::setzero_ps();
+
+    let mut xi = S::sub_epi32(S::cvtps_epi32(x), S::set1_epi32(1));
+    for _x in 0..3 {
+        let hash = hash_1d::<S>(seed, S::mullo_epi32(xi, S::set1_epi32(X_PRIME)));
+        let fx = S::add_ps(feature_offset_1d::<S>(hash, jitter), S::cvtepi32_ps(xi));
+        let new_distance = S::abs_ps(S::sub_ps(fx, x));
+        let closer = S::cmplt_ps(new_distance, distance);
+        distance = S::min_ps(new_distance, distance);
+        nearest_cell = S::blendv_epi32(nearest_cell, xi, S::castps_epi32(closer));
+        nearest_x = S::blendv_ps(nearest_x, fx, closer);
+        xi = S::add_epi32(xi, S::set1_epi32(1));
+    }
+
+    let mut edge = S::set1_ps(999999.0);
+    for dx in -2..=2 {
+        if dx == 0 {
+            continue;
+        }
+        let xi = S::add_epi32(nearest_cell, S::set1_epi32(dx));
+        let hash = hash_1d::<S>(seed, S::mullo_epi32(xi, S::set1_epi32(X_PRIME)));
+        let fx = S::add_ps(feature_offset_1d::<S>(hash, jitter), S::cvtepi32_ps(xi));
+
+        // Distance to the midpoint, measured towards the neighbour
+        let ex = S::sub_ps(fx, nearest_x);
+        let mx = S::sub_ps(S::mul_ps(S::add_ps(fx, nearest_x), S::set1_ps(0.5)), x);
+        edge = S::min_ps(edge, S::div_ps(S::mul_ps(mx, ex), S::abs_ps(ex)));
+    }
+
+    edge
+}
+
+/// Exact Euclidean distance from each sample point to the nearest Voronoi edge,
+/// the bisector between the nearest feature point and its closest neighbour.
+/// The neighbours searched are the 5x5 cells around the nearest cell.
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn cellular_edge_2d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    jitter: S::Vf32,
+    seed: i32,
+) -> S::Vf32 {
+    let nearest = cellular_feature_2d::<S>(x, y, CellDistanceFunction::Euclidean, jitter, seed);
+    let mut edge = S::set1_ps(999999.0);
+
+    for dx in -2..=2 {
+        let xi = S::add_epi32(nearest.cell_x, S::set1_epi32(dx));
+        let xc = S::mullo_epi32(xi, S::set1_epi32(X_PRIME));
+        for dy in -2..=2 {
+            if dx == 0 && dy == 0 {
+                continue;
+            }
+            let yi = S::add_epi32(nearest.cell_y, S::set1_epi32(dy));
+            let yc = S::mullo_epi32(yi, S::set1_epi32(Y_PRIME));
+
+            let hash = hash_2d::<S>(seed, xc, yc);
+            let xd = S::sub_ps(
+                S::cvtepi32_ps(S::and_epi32(hash, S::set1_epi32(BIT_10_MASK))),
+                S::set1_ps(511.5),
+            );
+            let yd = S::sub_ps(
+                S::cvtepi32_ps(S::and_epi32(
+                    S::srai_epi32(hash, 10),
+                    S::set1_epi32(BIT_10_MASK),
+                )),
+                S::set1_ps(511.5),
+            );
+            let inv_mag = S::mul_ps(
+                jitter,
+                S::rsqrt_ps(S::add_ps(S::mul_ps(xd, xd), S::mul_ps(yd, yd))),
+            );
+            let fx = S::add_ps(S::mul_ps(xd, inv_mag), S::cvtepi32_ps(xi));
+            let fy = S::add_ps(S::mul_ps(yd, inv_mag), S::cvtepi32_ps(yi));
+
+            // Project the midpoint of the two feature points onto the line joining them
+            let ex = S::sub_ps(fx, nearest.feature_x);
+            let ey = S::sub_ps(fy, nearest.feature_y);
+            let mx = S::sub_ps(
+                S::mul_ps(S::add_ps(fx, nearest.feature_x), S::set1_ps(0.5)),
+                x,
+            );
+            let my = S::sub_ps(
+                S::mul_ps(S::add_ps(fy, nearest.feature_y), S::set1_ps(0.5)),
+                y,
+            );
+            let dot = S::add_ps(S::mul_ps(mx, ex), S::mul_ps(my, ey));
+            let len = S::sqrt_ps(S::add_ps(S::mul_ps(ex, ex), S::mul_ps(ey, ey)));
+            edge = S::min_ps(edge, S::div_ps(dot, len));
+        }
+    }
+
+    edge
+}
+
+/// Exact Euclidean distance from each sample point to the nearest Voronoi face,
+/// the bisecting plane between the nearest feature point and its closest neighbour.
+/// The neighbours searched are the 5x5x5 cells around the nearest cell.
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn cellular_edge_3d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    jitter: S::Vf32,
+    seed: i32,
+) -> S::Vf32 {
+    let nearest = cellular_feature_3d::<S>(x, y, z, CellDistanceFunction::Euclidean, jitter, seed);
+    let mut edge = S::set1_ps(999999.0);
+
+    for dx in -2..=2 {
+        let xi = S::add_epi32(nearest.cell_x, S::set1_epi32(dx));
+        let xc = S::mullo_epi32(xi, S::set1_epi32(X_PRIME));
+        for dy in -2..=2 {
+            let yi = S::add_epi32(nearest.cell_y, S::set1_epi32(dy));
+            let yc = S::mullo_epi32(yi, S::set1_epi32(Y_PRIME));
+            for dz in -2..=2 {
+                if dx == 0 && dy == 0 && dz == 0 {
+                    continue;
+                }
+                let zi = S::add_epi32(nearest.cell_z, S::set1_epi32(dz));
+                let zc = S::mullo_epi32(zi, S::set1_epi32(Z_PRIME));
+
+                let hash = hash_3d::<S>(seed, xc, yc, zc);
+                let xd = S::sub_ps(
+                    S::cvtepi32_ps(S::and_epi32(hash, S::set1_epi32(BIT_10_MASK))),
+                    S::set1_ps(511.5),
+                );
+                let yd = S::sub_ps(
+                    S::cvtepi32_ps(S::and_epi32(
+                        S::srai_epi32(hash, 10),
+                        S::set1_epi32(BIT_10_MASK),
+                    )),
+                    S::set1_ps(511.5),
+                );
+                let zd = S::sub_ps(
+                    S::cvtepi32_ps(S::and_epi32(
+                        S::srai_epi32(hash, 20),
+                        S::set1_epi32(BIT_10_MASK),
+                    )),
+                    S::set1_ps(511.5),
+                );
+                let inv_mag = S::mul_ps(
+                    jitter,
+                    S::rsqrt_ps(S::add_ps(
+                        S::mul_ps(xd, xd),
+                        S::add_ps(S::mul_ps(yd, yd), S::mul_ps(zd, zd)),
+                    )),
+                );
+                let fx = S::add_ps(S::mul_ps(xd, inv_mag), S::cvtepi32_ps(xi));
+                let fy = S::add_ps(S::mul_ps(yd, inv_mag), S::cvtepi32_ps(yi));
+                let fz = S::add_ps(S::mul_ps(zd, inv_mag), S::cvtepi32_ps(zi));
+
+                // Project the midpoint of the two feature points onto the line joining them
+                let ex = S::sub_ps(fx, nearest.feature_x);
+                let ey = S::sub_ps(fy, nearest.feature_y);
+                let ez = S::sub_ps(fz, nearest.feature_z);
+                let mx = S::sub_ps(
+                    S::mul_ps(S::add_ps(fx, nearest.feature_x), S::set1_ps(0.5)),
+                    x,
+                );
+                let my = S::sub_ps(
+                    S::mul_ps(S::add_ps(fy, nearest.feature_y), S::set1_ps(0.5)),
+                    y,
+                );
+                let mz = S::sub_ps(
+                    S::mul_ps(S::add_ps(fz, nearest.feature_z), S::set1_ps(0.5)),
+                    z,
+                );
+                let dot = S::add_ps(
+                    S::mul_ps(mx, ex),
+                    S::add_ps(S::mul_ps(my, ey), S::mul_ps(mz, ez)),
+                );
+                let len = S::sqrt_ps(S::add_ps(
+                    S::mul_ps(ex, ex),
+                    S::add_ps(S::mul_ps(ey, ey), S::mul_ps(ez, ez)),
+                ));
+                edge = S::min_ps(edge, S::div_ps(dot, len));
+            }
+        }
+    }
+
+    edge
+}
+
+/// Exact Euclidean distance from each sample point to the nearest Voronoi cell
+/// boundary, the bisecting hyperplane between the nearest feature point and its
+/// closest neighbour. The neighbours searched are the 5x5x5x5 cells around the
+/// nearest cell, so this is far slower than the other return types.
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn cellular_edge_4d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    w: S::Vf32,
+    jitter: S::Vf32,
+    seed: i32,
+) -> S::Vf32 {
+    let mut distance = S::set1_ps(999999.0);
+    let mut cell = [S::setzero_epi32(); 4];
+    let mut feature = [S::setzero_ps(); 4];
+
+    let xi_base = S::sub_epi32(S::cvtps_epi32(x), S::set1_epi32(1));
+    let yi_base = S::sub_epi32(S::cvtps_epi32(y), S::set1_epi32(1));
+    let zi_base = S::sub_epi32(S::cvtps_epi32(z), S::set1_epi32(1));
+    let wi_base = S::sub_epi32(S::cvtps_epi32(w), S::set1_epi32(1));
+
+    for dx in 0..3 {
+        let xi = S::add_epi32(xi_base, S::set1_epi32(dx));
+        for dy in 0..3 {
+            let yi = S::add_epi32(yi_base, S::set1_epi32(dy));
+            for dz in 0..3 {
+                let zi = S::add_epi32(zi_base, S::set1_epi32(dz));
+                for dw in 0..3 {
+                    let wi = S::add_epi32(wi_base, S::set1_epi32(dw));
+                    let (fx, fy, fz, fw) = feature_point_4d::<S>(xi, yi, zi, wi, jitter, seed);
+                    let new_distance = distance_4d::<S>(
+                        CellDistanceFunction::Euclidean,
+                        S::sub_ps(fx, x),
+                        S::sub_ps(fy, y),
+                        S::sub_ps(fz, z),
+                        S::sub_ps(fw, w),
+                    );
+                    let closer = S::cmplt_ps(new_distance, distance);
+                    let closer_i = S::castps_epi32(closer);
+                    distance = S::min_ps(new_distance, distance);
+                    for (c, i) in cell.iter_mut().zip([xi, yi, zi, wi]) {
+                        *c = S::blendv_epi32(*c, i, closer_i);
+                    }
+                    for (f, p) in feature.iter_mut().zip([fx, fy, fz, fw]) {
+                        *f = S::blendv_ps(*f, p, closer);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut edge = S::set1_ps(999999.0);
+    for dx in -2..=2 {
+        let xi = S::add_epi32(cell[0], S::set1_epi32(dx));
+        for dy in -2..=2 {
+            let yi = S::add_epi32(cell[1], S::set1_epi32(dy));
+            for dz in -2..=2 {
+                let zi = S::add_epi32(cell[2], S::set1_epi32(dz));
+                for dw in -2..=2 {
+                    if dx == 0 && dy == 0 && dz == 0 && dw == 0 {
+                        continue;
+                    }
+                    let wi = S::add_epi32(cell[3], S::set1_epi32(dw));
+                    let (fx, fy, fz, fw) = feature_point_4d::<S>(xi, yi, zi, wi, jitter, seed);
+
+                    // Project the midpoint of the two feature points onto the line joining them
+                    let mut dot = S::setzero_ps();
+                    let mut len = S::setzero_ps();
+                    for ((f, n), p) in [fx, fy, fz, fw].iter().zip(feature).zip([x, y, z, w]) {
+                        let e = S::sub_ps(*f, n);
+                        let m = S::sub_ps(S::mul_ps(S::add_ps(*f, n), S::set1_ps(0.5)), p);
+                        dot = S::fmadd_ps(m, e, dot);
+                        len = S::fmadd_ps(e, e, len);
+                    }
+                    edge = S::min_ps(edge, S::div_ps(dot, S::sqrt_ps(len)));
+                }
+            }
+        }
+    }
+
+    edge
+}
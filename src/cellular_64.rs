@@ -1,6 +1,7 @@
 extern crate simdeez;
 use self::simdeez::*;
 use super::*;
+use crate::cellular::cell2_depth;
 use std::f64;
 
 const X_PRIME: i64 = 1619;
@@ -10,6 +11,58 @@ const Z_PRIME: i64 = 6971;
 const BIT_10_MASK: i64 = 1023;
 const HASH_2_FLOAT: f64 = 1.0 / 2147483648.0;
 
+/// Raises the magnitude of each lane to the power p, there is no SIMD pow
+/// so this falls back to the scalar one lane at a time
+#[inline(always)]
+unsafe fn abs_pow<S: Simd>(a: S::Vf64, p: f64) -> S::Vf64 {
+    let mut r = S::abs_pd(a);
+    for i in 0..S::VF64_WIDTH {
+        r[i] = r[i].powf(p);
+    }
+    r
+}
+
+/// Combines the sorted distances as the cellular2 return type asks. The
+/// distances are only searched for, to the depth needed, when the return type
+/// uses them, edge distance measures to the nearest border instead
+#[inline(always)]
+unsafe fn cell2_combine<S, D, E>(
+    return_type: Cell2ReturnType,
+    index0: usize,
+    index1: usize,
+    sorted_distances: D,
+    edge_distance: E,
+) -> S::Vf64
+where
+    S: Simd,
+    D: FnOnce(usize) -> [S::Vf64; 4],
+    E: FnOnce() -> S::Vf64,
+{
+    match return_type {
+        Cell2ReturnType::EdgeDistance => edge_distance(),
+        Cell2ReturnType::Weighted(weights) => {
+            let depth = cell2_depth(return_type, index1);
+            let distance = sorted_distances(depth);
+            let mut result = S::setzero_pd();
+            for i in 0..=depth {
+                result = S::fmadd_pd(S::set1_pd(weights[i] as f64), distance[i], result);
+            }
+            result
+        }
+        _ => {
+            let distance = sorted_distances(cell2_depth(return_type, index1));
+            let (d0, d1) = (distance[index0], distance[index1]);
+            match return_type {
+                Cell2ReturnType::Distance2Add => S::add_pd(d0, d1),
+                Cell2ReturnType::Distance2Sub => S::sub_pd(d0, d1),
+                Cell2ReturnType::Distance2Mul => S::mul_pd(d0, d1),
+                Cell2ReturnType::Distance2Div => S::div_pd(d0, d1),
+                _ => d1,
+            }
+        }
+    }
+}
+
 #[inline(always)]
 unsafe fn hash_2d<S: Simd>(seed: i64, x: S::Vi64, y: S::Vi64) -> S::Vi64 {
     let mut hash = S::xor_epi64(x, S::set1_epi64(seed));
@@ -146,6 +199,75 @@ pub unsafe fn cellular_2d<S: Simd>(
                         xc = S::add_epi64(xc, S::set1_epi64(X_PRIME));
                     }
                 }
+                CellDistanceFunction::Chebyshev => {
+                    for _x in 0..3 {
+                        let mut ycf = ycf_base;
+                        let mut yc = yc_base;
+                        for _y in 0..3 {
+                            let hash = hash_2d::<S>(seed, xc, yc);
+                            let mut xd = S::sub_pd(
+                                S::cvtepi64_pd(S::and_epi64(hash, S::set1_epi64(BIT_10_MASK))),
+                                S::set1_pd(511.5),
+                            );
+                            let mut yd = S::sub_pd(
+                                S::cvtepi64_pd(S::and_epi64(
+                                    S::srai_epi64(hash, 10),
+                                    S::set1_epi64(BIT_10_MASK),
+                                )),
+                                S::set1_pd(511.5),
+                            );
+                            let inv_mag = S::mul_pd(
+                                jitter,
+                                S::rsqrt_pd(S::add_pd(S::mul_pd(xd, xd), S::mul_pd(yd, yd))),
+                            );
+                            xd = S::add_pd(S::mul_pd(xd, inv_mag), xcf);
+                            yd = S::add_pd(S::mul_pd(yd, inv_mag), ycf);
+
+                            let new_distance = S::max_pd(S::abs_pd(xd), S::abs_pd(yd));
+                            distance = S::min_pd(new_distance, distance);
+
+                            ycf = S::add_pd(ycf, S::set1_pd(1.0));
+                            yc = S::add_epi64(yc, S::set1_epi64(Y_PRIME));
+                        }
+                        xcf = S::add_pd(xcf, S::set1_pd(1.0));
+                        xc = S::add_epi64(xc, S::set1_epi64(X_PRIME));
+                    }
+                }
+                CellDistanceFunction::Minkowski(p) => {
+                    for _x in 0..3 {
+                        let mut ycf = ycf_base;
+                        let mut yc = yc_base;
+                        for _y in 0..3 {
+                            let hash = hash_2d::<S>(seed, xc, yc);
+                            let mut xd = S::sub_pd(
+                                S::cvtepi64_pd(S::and_epi64(hash, S::set1_epi64(BIT_10_MASK))),
+                                S::set1_pd(511.5),
+                            );
+                            let mut yd = S::sub_pd(
+                                S::cvtepi64_pd(S::and_epi64(
+                                    S::srai_epi64(hash, 10),
+                                    S::set1_epi64(BIT_10_MASK),
+                                )),
+                                S::set1_pd(511.5),
+                            );
+                            let inv_mag = S::mul_pd(
+                                jitter,
+                                S::rsqrt_pd(S::add_pd(S::mul_pd(xd, xd), S::mul_pd(yd, yd))),
+                            );
+                            xd = S::add_pd(S::mul_pd(xd, inv_mag), xcf);
+                            yd = S::add_pd(S::mul_pd(yd, inv_mag), ycf);
+
+                            let new_distance =
+                                S::add_pd(abs_pow::<S>(xd, p as f64), abs_pow::<S>(yd, p as f64));
+                            distance = S::min_pd(new_distance, distance);
+
+                            ycf = S::add_pd(ycf, S::set1_pd(1.0));
+                            yc = S::add_epi64(yc, S::set1_epi64(Y_PRIME));
+                        }
+                        xcf = S::add_pd(xcf, S::set1_pd(1.0));
+                        xc = S::add_epi64(xc, S::set1_epi64(X_PRIME));
+                    }
+                }
             }
             distance
         }
@@ -270,6 +392,83 @@ pub unsafe fn cellular_2d<S: Simd>(
                         xc = S::add_epi64(xc, S::set1_epi64(X_PRIME));
                     }
                 }
+                CellDistanceFunction::Chebyshev => {
+                    for _x in 0..3 {
+                        let mut ycf = ycf_base;
+                        let mut yc = yc_base;
+                        for _y in 0..3 {
+                            let hash = hash_2d::<S>(seed, xc, yc);
+                            let mut xd = S::sub_pd(
+                                S::cvtepi64_pd(S::and_epi64(hash, S::set1_epi64(BIT_10_MASK))),
+                                S::set1_pd(511.5),
+                            );
+                            let mut yd = S::sub_pd(
+                                S::cvtepi64_pd(S::and_epi64(
+                                    S::srai_epi64(hash, 10),
+                                    S::set1_epi64(BIT_10_MASK),
+                                )),
+                                S::set1_pd(511.5),
+                            );
+                            let inv_mag = S::mul_pd(
+                                jitter,
+                                S::rsqrt_pd(S::add_pd(S::mul_pd(xd, xd), S::mul_pd(yd, yd))),
+                            );
+                            xd = S::add_pd(S::mul_pd(xd, inv_mag), xcf);
+                            yd = S::add_pd(S::mul_pd(yd, inv_mag), ycf);
+
+                            let new_cell_value =
+                                S::mul_pd(S::set1_pd(HASH_2_FLOAT), S::cvtepi64_pd(hash));
+                            let new_distance = S::max_pd(S::abs_pd(xd), S::abs_pd(yd));
+                            let closer = S::cmplt_pd(new_distance, distance);
+                            distance = S::min_pd(new_distance, distance);
+                            cell_value = S::blendv_pd(cell_value, new_cell_value, closer);
+
+                            ycf = S::add_pd(ycf, S::set1_pd(1.0));
+                            yc = S::add_epi64(yc, S::set1_epi64(Y_PRIME));
+                        }
+                        xcf = S::add_pd(xcf, S::set1_pd(1.0));
+                        xc = S::add_epi64(xc, S::set1_epi64(X_PRIME));
+                    }
+                }
+                CellDistanceFunction::Minkowski(p) => {
+                    for _x in 0..3 {
+                        let mut ycf = ycf_base;
+                        let mut yc = yc_base;
+                        for _y in 0..3 {
+                            let hash = hash_2d::<S>(seed, xc, yc);
+                            let mut xd = S::sub_pd(
+                                S::cvtepi64_pd(S::and_epi64(hash, S::set1_epi64(BIT_10_MASK))),
+                                S::set1_pd(511.5),
+                            );
+                            let mut yd = S::sub_pd(
+                                S::cvtepi64_pd(S::and_epi64(
+                                    S::srai_epi64(hash, 10),
+                                    S::set1_epi64(BIT_10_MASK),
+                                )),
+                                S::set1_pd(511.5),
+                            );
+                            let inv_mag = S::mul_pd(
+                                jitter,
+                                S::rsqrt_pd(S::add_pd(S::mul_pd(xd, xd), S::mul_pd(yd, yd))),
+                            );
+                            xd = S::add_pd(S::mul_pd(xd, inv_mag), xcf);
+                            yd = S::add_pd(S::mul_pd(yd, inv_mag), ycf);
+
+                            let new_cell_value =
+                                S::mul_pd(S::set1_pd(HASH_2_FLOAT), S::cvtepi64_pd(hash));
+                            let new_distance =
+                                S::add_pd(abs_pow::<S>(xd, p as f64), abs_pow::<S>(yd, p as f64));
+                            let closer = S::cmplt_pd(new_distance, distance);
+                            distance = S::min_pd(new_distance, distance);
+                            cell_value = S::blendv_pd(cell_value, new_cell_value, closer);
+
+                            ycf = S::add_pd(ycf, S::set1_pd(1.0));
+                            yc = S::add_epi64(yc, S::set1_epi64(Y_PRIME));
+                        }
+                        xcf = S::add_pd(xcf, S::set1_pd(1.0));
+                        xc = S::add_epi64(xc, S::set1_epi64(X_PRIME));
+                    }
+                }
             }
             cell_value
         }
@@ -277,16 +476,14 @@ pub unsafe fn cellular_2d<S: Simd>(
 }
 
 #[inline(always)]
-pub unsafe fn cellular2_2d<S: Simd>(
+unsafe fn sorted_distances_2d<S: Simd>(
     x: S::Vf64,
     y: S::Vf64,
     distance_function: CellDistanceFunction,
-    return_type: Cell2ReturnType,
     jitter: S::Vf64,
-    index0: usize,
-    index1: usize,
+    depth: usize,
     seed: i64,
-) -> S::Vf64 {
+) -> [S::Vf64; 4] {
     let mut distance: [S::Vf64; 4] = [S::set1_pd(999999.0); 4];
 
     let mut xc = S::sub_epi64(S::cvtpd_epi64(x), S::set1_epi64(1));
@@ -329,13 +526,17 @@ pub unsafe fn cellular2_2d<S: Simd>(
                     let man = S::add_pd(S::abs_pd(xd), S::abs_pd(yd));
                     S::add_pd(euc, man)
                 }
+                CellDistanceFunction::Chebyshev => S::max_pd(S::abs_pd(xd), S::abs_pd(yd)),
+                CellDistanceFunction::Minkowski(p) => {
+                    S::add_pd(abs_pow::<S>(xd, p as f64), abs_pow::<S>(yd, p as f64))
+                }
             };
-            let mut i = index1;
+            let mut i = depth;
             while i > 0 {
                 distance[i] = S::max_pd(S::min_pd(distance[i], new_distance), distance[i - 1]);
-                distance[0] = S::min_pd(distance[0], new_distance);
                 i -= 1;
             }
+            distance[0] = S::min_pd(distance[0], new_distance);
             ycf = S::add_pd(ycf, S::set1_pd(1.0));
             yc = S::add_epi64(yc, S::set1_epi64(Y_PRIME));
         }
@@ -343,13 +544,27 @@ pub unsafe fn cellular2_2d<S: Simd>(
         xc = S::add_epi64(xc, S::set1_epi64(X_PRIME));
     }
 
-    match return_type {
-        Cell2ReturnType::Distance2 => distance[index1],
-        Cell2ReturnType::Distance2Add => S::add_pd(distance[index0], distance[index1]),
-        Cell2ReturnType::Distance2Sub => S::sub_pd(distance[index0], distance[index1]),
-        Cell2ReturnType::Distance2Mul => S::mul_pd(distance[index0], distance[index1]),
-        Cell2ReturnType::Distance2Div => S::div_pd(distance[index0], distance[index1]),
-    }
+    distance
+}
+
+#[inline(always)]
+pub unsafe fn cellular2_2d<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    distance_function: CellDistanceFunction,
+    return_type: Cell2ReturnType,
+    jitter: S::Vf64,
+    index0: usize,
+    index1: usize,
+    seed: i64,
+) -> S::Vf64 {
+    cell2_combine::<S, _, _>(
+        return_type,
+        index0,
+        index1,
+        |depth| sorted_distances_2d::<S>(x, y, distance_function, jitter, depth, seed),
+        || cellular_edge_2d::<S>(x, y, jitter, seed),
+    )
 }
 
 #[inline(always)]
@@ -442,6 +657,13 @@ pub unsafe fn cellular_3d<S: Simd>(
                         let man = S::add_pd(S::add_pd(S::abs_pd(xd), S::abs_pd(yd)), S::abs_pd(zd));
                         S::add_pd(euc, man)
                     }
+                    CellDistanceFunction::Chebyshev => {
+                        S::max_pd(S::max_pd(S::abs_pd(xd), S::abs_pd(yd)), S::abs_pd(zd))
+                    }
+                    CellDistanceFunction::Minkowski(p) => S::add_pd(
+                        S::add_pd(abs_pow::<S>(xd, p as f64), abs_pow::<S>(yd, p as f64)),
+                        abs_pow::<S>(zd, p as f64),
+                    ),
                 };
                 let closer = S::cmplt_pd(new_distance, distance);
                 distance = S::min_pd(new_distance, distance);
@@ -463,17 +685,15 @@ pub unsafe fn cellular_3d<S: Simd>(
 }
 
 #[inline(always)]
-pub unsafe fn cellular2_3d<S: Simd>(
+unsafe fn sorted_distances_3d<S: Simd>(
     x: S::Vf64,
     y: S::Vf64,
     z: S::Vf64,
     distance_function: CellDistanceFunction,
-    return_type: Cell2ReturnType,
     jitter: S::Vf64,
-    index0: usize,
-    index1: usize,
+    depth: usize,
     seed: i64,
-) -> S::Vf64 {
+) -> [S::Vf64; 4] {
     let mut distance: [S::Vf64; 4] = [S::set1_pd(999999.0); 4];
 
     let mut xc = S::sub_epi64(S::cvtpd_epi64(x), S::set1_epi64(1));
@@ -541,13 +761,20 @@ pub unsafe fn cellular2_3d<S: Simd>(
                         let man = S::add_pd(S::add_pd(S::abs_pd(xd), S::abs_pd(yd)), S::abs_pd(zd));
                         S::add_pd(euc, man)
                     }
+                    CellDistanceFunction::Chebyshev => {
+                        S::max_pd(S::max_pd(S::abs_pd(xd), S::abs_pd(yd)), S::abs_pd(zd))
+                    }
+                    CellDistanceFunction::Minkowski(p) => S::add_pd(
+                        S::add_pd(abs_pow::<S>(xd, p as f64), abs_pow::<S>(yd, p as f64)),
+                        abs_pow::<S>(zd, p as f64),
+                    ),
                 };
-                let mut i = index1;
+                let mut i = depth;
                 while i > 0 {
                     distance[i] = S::max_pd(S::min_pd(distance[i], new_distance), distance[i - 1]);
-                    distance[0] = S::min_pd(distance[0], new_distance);
                     i -= 1;
                 }
+                distance[0] = S::min_pd(distance[0], new_distance);
                 zcf = S::add_pd(ycf, S::set1_pd(1.0));
                 zc = S::add_epi64(yc, S::set1_epi64(Z_PRIME));
             }
@@ -558,11 +785,261 @@ pub unsafe fn cellular2_3d<S: Simd>(
         xc = S::add_epi64(xc, S::set1_epi64(X_PRIME));
     }
 
-    match return_type {
-        Cell2ReturnType::Distance2 => distance[index1],
-        Cell2ReturnType::Distance2Add => S::add_pd(distance[index0], distance[index1]),
-        Cell2ReturnType::Distance2Sub => S::sub_pd(distance[index0], distance[index1]),
-        Cell2ReturnType::Distance2Mul => S::mul_pd(distance[index0], distance[index1]),
-        Cell2ReturnType::Distance2Div => S::div_pd(distance[index0], distance[index1]),
+    distance
+}
+
+#[inline(always)]
+pub unsafe fn cellular2_3d<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    z: S::Vf64,
+    distance_function: CellDistanceFunction,
+    return_type: Cell2ReturnType,
+    jitter: S::Vf64,
+    index0: usize,
+    index1: usize,
+    seed: i64,
+) -> S::Vf64 {
+    cell2_combine::<S, _, _>(
+        return_type,
+        index0,
+        index1,
+        |depth| sorted_distances_3d::<S>(x, y, z, distance_function, jitter, depth, seed),
+        || cellular_edge_3d::<S>(x, y, z, jitter, seed),
+    )
+}
+
+/// Returns the position of the feature point of the cell at `xi, yi`
+#[inline(always)]
+unsafe fn feature_point_2d<S: Simd>(
+    xi: S::Vi64,
+    yi: S::Vi64,
+    jitter: S::Vf64,
+    seed: i64,
+) -> (S::Vf64, S::Vf64) {
+    let hash = hash_2d::<S>(
+        seed,
+        S::mullo_epi64(xi, S::set1_epi64(X_PRIME)),
+        S::mullo_epi64(yi, S::set1_epi64(Y_PRIME)),
+    );
+    let xd = S::sub_pd(
+        S::cvtepi64_pd(S::and_epi64(hash, S::set1_epi64(BIT_10_MASK))),
+        S::set1_pd(511.5),
+    );
+    let yd = S::sub_pd(
+        S::cvtepi64_pd(S::and_epi64(
+            S::srai_epi64(hash, 10),
+            S::set1_epi64(BIT_10_MASK),
+        )),
+        S::set1_pd(511.5),
+    );
+    let inv_mag = S::mul_pd(
+        jitter,
+        S::rsqrt_pd(S::add_pd(S::mul_pd(xd, xd), S::mul_pd(yd, yd))),
+    );
+    (
+        S::add_pd(S::mul_pd(xd, inv_mag), S::cvtepi64_pd(xi)),
+        S::add_pd(S::mul_pd(yd, inv_mag), S::cvtepi64_pd(yi)),
+    )
+}
+
+/// Returns the position of the feature point of the cell at `xi, yi, zi`
+#[inline(always)]
+unsafe fn feature_point_3d<S: Simd>(
+    xi: S::Vi64,
+    yi: S::Vi64,
+    zi: S::Vi64,
+    jitter: S::Vf64,
+    seed: i64,
+) -> (S::Vf64, S::Vf64, S::Vf64) {
+    let hash = hash_3d::<S>(
+        seed,
+        S::mullo_epi64(xi, S::set1_epi64(X_PRIME)),
+        S::mullo_epi64(yi, S::set1_epi64(Y_PRIME)),
+        S::mullo_epi64(zi, S::set1_epi64(Z_PRIME)),
+    );
+    let xd = S::sub_pd(
+        S::cvtepi64_pd(S::and_epi64(hash, S::set1_epi64(BIT_10_MASK))),
+        S::set1_pd(511.5),
+    );
+    let yd = S::sub_pd(
+        S::cvtepi64_pd(S::and_epi64(
+            S::srai_epi64(hash, 10),
+            S::set1_epi64(BIT_10_MASK),
+        )),
+        S::set1_pd(511.5),
+    );
+    let zd = S::sub_pd(
+        S::cvtepi64_pd(S::and_epi64(
+            S::srai_epi64(hash, 20),
+            S::set1_epi64(BIT_10_MASK),
+        )),
+        S::set1_pd(511.5),
+    );
+    let inv_mag = S::mul_pd(
+        jitter,
+        S::rsqrt_pd(S::add_pd(
+            S::mul_pd(xd, xd),
+            S::add_pd(S::mul_pd(yd, yd), S::mul_pd(zd, zd)),
+        )),
+    );
+    (
+        S::add_pd(S::mul_pd(xd, inv_mag), S::cvtepi64_pd(xi)),
+        S::add_pd(S::mul_pd(yd, inv_mag), S::cvtepi64_pd(yi)),
+        S::add_pd(S::mul_pd(zd, inv_mag), S::cvtepi64_pd(zi)),
+    )
+}
+
+/// Exact Euclidean distance from each sample point to the nearest Voronoi edge,
+/// the bisector between the nearest feature point and its closest neighbour.
+/// The neighbours searched are the 5x5 cells around the nearest cell.
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn cellular_edge_2d<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    jitter: S::Vf64,
+    seed: i64,
+) -> S::Vf64 {
+    let mut distance = S::set1_pd(999999.0);
+    let mut cell = [S::setzero_epi64(); 2];
+    let mut feature = [S::setzero_pd(); 2];
+
+    let xi_base = S::sub_epi64(S::cvtpd_epi64(x), S::set1_epi64(1));
+    let yi_base = S::sub_epi64(S::cvtpd_epi64(y), S::set1_epi64(1));
+    for dx in 0..3 {
+        let xi = S::add_epi64(xi_base, S::set1_epi64(dx));
+        for dy in 0..3 {
+            let yi = S::add_epi64(yi_base, S::set1_epi64(dy));
+            let (fx, fy) = feature_point_2d::<S>(xi, yi, jitter, seed);
+            let xd = S::sub_pd(fx, x);
+            let yd = S::sub_pd(fy, y);
+            let new_distance = S::add_pd(S::mul_pd(xd, xd), S::mul_pd(yd, yd));
+            let closer = S::cmplt_pd(new_distance, distance);
+            let closer_i = S::castpd_epi64(closer);
+            distance = S::min_pd(new_distance, distance);
+            cell = [
+                S::blendv_epi64(cell[0], xi, closer_i),
+                S::blendv_epi64(cell[1], yi, closer_i),
+            ];
+            feature = [
+                S::blendv_pd(feature[0], fx, closer),
+                S::blendv_pd(feature[1], fy, closer),
+            ];
+        }
     }
+
+    let mut edge = S::set1_pd(999999.0);
+    for dx in -2..=2 {
+        let xi = S::add_epi64(cell[0], S::set1_epi64(dx));
+        for dy in -2..=2 {
+            if dx == 0 && dy == 0 {
+                continue;
+            }
+            let yi = S::add_epi64(cell[1], S::set1_epi64(dy));
+            let (fx, fy) = feature_point_2d::<S>(xi, yi, jitter, seed);
+
+            // Project the midpoint of the two feature points onto the line joining them
+            let ex = S::sub_pd(fx, feature[0]);
+            let ey = S::sub_pd(fy, feature[1]);
+            let mx = S::sub_pd(S::mul_pd(S::add_pd(fx, feature[0]), S::set1_pd(0.5)), x);
+            let my = S::sub_pd(S::mul_pd(S::add_pd(fy, feature[1]), S::set1_pd(0.5)), y);
+            let dot = S::add_pd(S::mul_pd(mx, ex), S::mul_pd(my, ey));
+            let len = S::sqrt_pd(S::add_pd(S::mul_pd(ex, ex), S::mul_pd(ey, ey)));
+            edge = S::min_pd(edge, S::div_pd(dot, len));
+        }
+    }
+
+    edge
+}
+
+/// Exact Euclidean distance from each sample point to the nearest Voronoi face,
+/// the bisecting plane between the nearest feature point and its closest neighbour.
+/// The neighbours searched are the 5x5x5 cells around the nearest cell.
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn cellular_edge_3d<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    z: S::Vf64,
+    jitter: S::Vf64,
+    seed: i64,
+) -> S::Vf64 {
+    let mut distance = S::set1_pd(999999.0);
+    let mut cell = [S::setzero_epi64(); 3];
+    let mut feature = [S::setzero_pd(); 3];
+
+    let xi_base = S::sub_epi64(S::cvtpd_epi64(x), S::set1_epi64(1));
+    let yi_base = S::sub_epi64(S::cvtpd_epi64(y), S::set1_epi64(1));
+    let zi_base = S::sub_epi64(S::cvtpd_epi64(z), S::set1_epi64(1));
+    for dx in 0..3 {
+        let xi = S::add_epi64(xi_base, S::set1_epi64(dx));
+        for dy in 0..3 {
+            let yi = S::add_epi64(yi_base, S::set1_epi64(dy));
+            for dz in 0..3 {
+                let zi = S::add_epi64(zi_base, S::set1_epi64(dz));
+                let (fx, fy, fz) = feature_point_3d::<S>(xi, yi, zi, jitter, seed);
+                let xd = S::sub_pd(fx, x);
+                let yd = S::sub_pd(fy, y);
+                let zd = S::sub_pd(fz, z);
+                let new_distance = S::add_pd(
+                    S::mul_pd(xd, xd),
+                    S::add_pd(S::mul_pd(yd, yd), S::mul_pd(zd, zd)),
+                );
+                let closer = S::cmplt_pd(new_distance, distance);
+                let closer_i = S::castpd_epi64(closer);
+                distance = S::min_pd(new_distance, distance);
+                cell = [
+                    S::blendv_epi64(cell[0], xi, closer_i),
+                    S::blendv_epi64(cell[1], yi, closer_i),
+                    S::blendv_epi64(cell[2], zi, closer_i),
+                ];
+                feature = [
+                    S::blendv_pd(feature[0], fx, closer),
+                    S::blendv_pd(feature[1], fy, closer),
+                    S::blendv_pd(feature[2], fz, closer),
+                ];
+            }
+        }
+    }
+
+    let mut edge = S::set1_pd(999999.0);
+    for dx in -2..=2 {
+        let xi = S::add_epi64(cell[0], S::set1_epi64(dx));
+        for dy in -2..=2 {
+            let yi = S::add_epi64(cell[1], S::set1_epi64(dy));
+            for dz in -2..=2 {
+                if dx == 0 && dy == 0 && dz == 0 {
+                    continue;
+                }
+                let zi = S::add_epi64(cell[2], S::set1_epi64(dz));
+                let (fx, fy, fz) = feature_point_3d::<S>(xi, yi, zi, jitter, seed);
+
+                // Project the midpoint of the two feature points onto the line joining them
+                let ex = S::sub_pd(fx, feature[0]);
+                let ey = S::sub_pd(fy, feature[1]);
+                let ez = S::sub_pd(fz, feature[2]);
+                let mx = S::sub_pd(S::mul_pd(S::add_pd(fx, feature[0]), S::set1_pd(0.5)), x);
+                let my = S::sub_pd(S::mul_pd(S::add_pd(fy, feature[1]), S::set1_pd(0.5)), y);
+                let mz = S::sub_pd(S::mul_pd(S::add_pd(fz, feature[2]), S::set1_pd(0.5)), z);
+                let dot = S::add_pd(
+                    S::mul_pd(mx, ex),
+                    S::add_pd(S::mul_pd(my, ey), S::mul_pd(mz, ez)),
+                );
+                let len = S::sqrt_pd(S::add_pd(
+                    S::mul_pd(ex, ex),
+                    S::add_pd(S::mul_pd(ey, ey), S::mul_pd(ez, ez)),
+                ));
+                edge = S::min_pd(edge, S::div_pd(dot, len));
+            }
+        }
+    }
+
+    edge
 }
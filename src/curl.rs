@@ -0,0 +1,93 @@
+//! Curl noise, divergence free vector fields for particle flow.
+//!
+//! The velocity is the curl of a simplex fbm potential, taken with central
+//! differences. In 2d the potential is a single scalar field, in 3d it is a
+//! vector of three independently seeded fields. Results are unscaled, and are
+//! derivatives with respect to the frequency scaled coordinates.
+use self::simdeez::*;
+use super::*;
+use crate::simplex::{fbm_2d, fbm_3d};
+
+/// Step used for the central differences, in frequency scaled coordinates
+const CURL_EPSILON: f32 = 0.01;
+/// Offsets the seeds of the second and third 3d potentials
+const CURL_SEED_STEP: i32 = 1013;
+
+/// The 2d curl of a simplex fbm potential, its gradient rotated a quarter turn
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn curl_2d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+) -> (S::Vf32, S::Vf32) {
+    let eps = S::set1_ps(CURL_EPSILON);
+    let inv = S::set1_ps(0.5 / CURL_EPSILON);
+    let potential = |x, y| fbm_2d::<S>(x, y, lac, gain, octaves, seed);
+
+    let dx = S::mul_ps(
+        S::sub_ps(
+            potential(S::add_ps(x, eps), y),
+            potential(S::sub_ps(x, eps), y),
+        ),
+        inv,
+    );
+    let dy = S::mul_ps(
+        S::sub_ps(
+            potential(x, S::add_ps(y, eps)),
+            potential(x, S::sub_ps(y, eps)),
+        ),
+        inv,
+    );
+
+    (dy, S::sub_ps(S::setzero_ps(), dx))
+}
+
+/// The curl of three independently seeded simplex fbm potentials
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn curl_3d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+) -> (S::Vf32, S::Vf32, S::Vf32) {
+    let eps = S::set1_ps(CURL_EPSILON);
+    let inv = S::set1_ps(0.5 / CURL_EPSILON);
+    let potential = |x, y, z, component: i32| {
+        let seed = seed.wrapping_add(CURL_SEED_STEP.wrapping_mul(component));
+        fbm_3d::<S>(x, y, z, lac, gain, octaves, seed)
+    };
+    // Central difference of one potential component along one axis
+    let partial = |component: i32, axis: usize| {
+        let mut lo = [x, y, z];
+        let mut hi = [x, y, z];
+        lo[axis] = S::sub_ps(lo[axis], eps);
+        hi[axis] = S::add_ps(hi[axis], eps);
+        S::mul_ps(
+            S::sub_ps(
+                potential(hi[0], hi[1], hi[2], component),
+                potential(lo[0], lo[1], lo[2], component),
+            ),
+            inv,
+        )
+    };
+
+    (
+        S::sub_ps(partial(2, 1), partial(1, 2)),
+        S::sub_ps(partial(0, 2), partial(2, 0)),
+        S::sub_ps(partial(1, 0), partial(0, 1)),
+    )
+}
@@ -0,0 +1,218 @@
+//! Domain warping, sampling a noise at coordinates pushed around by other noises.
+//!
+//! Each warp layer samples fbm over its base noise once per axis, at the
+//! coordinates left by the previous layer, and offsets the original coordinates
+//! by the result. With several layers this is the iterated warp described by
+//! Inigo Quilez, `f(p + a * w(p + a * w(p)))`. The warped noise is then sampled
+//! at the final coordinates.
+use self::simdeez::*;
+use super::*;
+
+/// Keeps the per axis and per layer warp noises independent of each other
+/// and of the warped noise
+const WARP_SEED_STEP: i32 = 1013;
+
+#[inline(always)]
+fn warp_seed(seed: i32, layer: usize, axis: usize) -> i32 {
+    seed.wrapping_add(WARP_SEED_STEP.wrapping_mul((layer * 4 + axis + 1) as i32))
+}
+
+/// 1d fbm over `base`, sampled at coordinates warped by each of `warps`
+/// in turn
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn domain_warp_1d<S: Simd>(
+    x: S::Vf32,
+    base: BaseNoise,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    warps: &[Warp],
+    seed: i32,
+) -> S::Vf32 {
+    let mut wx = x;
+    for (layer, warp) in warps.iter().enumerate() {
+        let freq = S::set1_ps(warp.freq);
+        let amp = S::set1_ps(warp.amplitude);
+        let px = S::mul_ps(wx, freq);
+        let qx = fractal::fbm_1d::<S, _>(
+            px,
+            lac,
+            gain,
+            warp.octaves,
+            warp_seed(seed, layer, 0),
+            |x, seed| fractal::base_1d::<S>(warp.base, x, seed),
+        );
+        wx = S::fmadd_ps(qx, amp, x);
+    }
+    fractal::fbm_1d::<S, _>(wx, lac, gain, octaves, seed, |x, seed| {
+        fractal::base_1d::<S>(base, x, seed)
+    })
+}
+
+/// 2d fbm over `base`, sampled at coordinates warped by each of `warps`
+/// in turn
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn domain_warp_2d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    base: BaseNoise,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    warps: &[Warp],
+    seed: i32,
+) -> S::Vf32 {
+    let mut wx = x;
+    let mut wy = y;
+    for (layer, warp) in warps.iter().enumerate() {
+        let freq = S::set1_ps(warp.freq);
+        let amp = S::set1_ps(warp.amplitude);
+        let px = S::mul_ps(wx, freq);
+        let py = S::mul_ps(wy, freq);
+        let warp_noise = |x, y, seed| fractal::base_2d::<S>(warp.base, x, y, seed);
+        let qx = fractal::fbm_2d::<S, _>(
+            px,
+            py,
+            lac,
+            gain,
+            warp.octaves,
+            warp_seed(seed, layer, 0),
+            warp_noise,
+        );
+        let qy = fractal::fbm_2d::<S, _>(
+            px,
+            py,
+            lac,
+            gain,
+            warp.octaves,
+            warp_seed(seed, layer, 1),
+            warp_noise,
+        );
+        wx = S::fmadd_ps(qx, amp, x);
+        wy = S::fmadd_ps(qy, amp, y);
+    }
+    fractal::fbm_2d::<S, _>(wx, wy, lac, gain, octaves, seed, |x, y, seed| {
+        fractal::base_2d::<S>(base, x, y, seed)
+    })
+}
+
+/// 3d fbm over `base`, sampled at coordinates warped by each of `warps`
+/// in turn
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn domain_warp_3d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    base: BaseNoise,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    warps: &[Warp],
+    seed: i32,
+) -> S::Vf32 {
+    let mut wx = x;
+    let mut wy = y;
+    let mut wz = z;
+    for (layer, warp) in warps.iter().enumerate() {
+        let freq = S::set1_ps(warp.freq);
+        let amp = S::set1_ps(warp.amplitude);
+        let px = S::mul_ps(wx, freq);
+        let py = S::mul_ps(wy, freq);
+        let pz = S::mul_ps(wz, freq);
+        let warp_noise = |x, y, z, seed| fractal::base_3d::<S>(warp.base, x, y, z, seed);
+        let mut q = [S::setzero_ps(); 3];
+        for (axis, q) in q.iter_mut().enumerate() {
+            *q = fractal::fbm_3d::<S, _>(
+                px,
+                py,
+                pz,
+                lac,
+                gain,
+                warp.octaves,
+                warp_seed(seed, layer, axis),
+                warp_noise,
+            );
+        }
+        wx = S::fmadd_ps(q[0], amp, x);
+        wy = S::fmadd_ps(q[1], amp, y);
+        wz = S::fmadd_ps(q[2], amp, z);
+    }
+    fractal::fbm_3d::<S, _>(wx, wy, wz, lac, gain, octaves, seed, |x, y, z, seed| {
+        fractal::base_3d::<S>(base, x, y, z, seed)
+    })
+}
+
+/// 4d fbm over `base`, sampled at coordinates warped by each of `warps`
+/// in turn
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn domain_warp_4d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    w: S::Vf32,
+    base: BaseNoise,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    warps: &[Warp],
+    seed: i32,
+) -> S::Vf32 {
+    let mut wx = x;
+    let mut wy = y;
+    let mut wz = z;
+    let mut ww = w;
+    for (layer, warp) in warps.iter().enumerate() {
+        let freq = S::set1_ps(warp.freq);
+        let amp = S::set1_ps(warp.amplitude);
+        let px = S::mul_ps(wx, freq);
+        let py = S::mul_ps(wy, freq);
+        let pz = S::mul_ps(wz, freq);
+        let pw = S::mul_ps(ww, freq);
+        let warp_noise = |x, y, z, w, seed| fractal::base_4d::<S>(warp.base, x, y, z, w, seed);
+        let mut q = [S::setzero_ps(); 4];
+        for (axis, q) in q.iter_mut().enumerate() {
+            *q = fractal::fbm_4d::<S, _>(
+                px,
+                py,
+                pz,
+                pw,
+                lac,
+                gain,
+                warp.octaves,
+                warp_seed(seed, layer, axis),
+                warp_noise,
+            );
+        }
+        wx = S::fmadd_ps(q[0], amp, x);
+        wy = S::fmadd_ps(q[1], amp, y);
+        wz = S::fmadd_ps(q[2], amp, z);
+        ww = S::fmadd_ps(q[3], amp, w);
+    }
+    fractal::fbm_4d::<S, _>(
+        wx,
+        wy,
+        wz,
+        ww,
+        lac,
+        gain,
+        octaves,
+        seed,
+        |x, y, z, w, seed| fractal::base_4d::<S>(base, x, y, z, w, seed),
+    )
+}
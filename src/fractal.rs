@@ -0,0 +1,980 @@
+//! Fractal kernels that are generic over the base noise function.
+//!
+//! The fractals in `simplex` are hardwired to simplex noise, these take the
+//! noise to sum as a closure, so they can be built on top of any of the noise
+//! kernels in this crate, or your own.
+use self::simdeez::*;
+use super::*;
+
+/// 1d fbm, `octaves` layers of `noise` at rising frequency and falling
+/// amplitude
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn fbm_1d<S, F>(
+    mut x: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+    noise: F,
+) -> S::Vf32
+where
+    S: Simd,
+    F: Fn(S::Vf32, i32) -> S::Vf32,
+{
+    let mut result = noise(x, seed);
+    let mut amp = S::set1_ps(1.0);
+
+    for _ in 1..octaves {
+        x = S::mul_ps(x, lac);
+        amp = S::mul_ps(amp, gain);
+        result = S::add_ps(S::mul_ps(noise(x, seed), amp), result);
+    }
+
+    result
+}
+
+/// 1d ridged fractal, `1 - |noise|` summed over `octaves` layers like
+/// `fbm_1d`
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn ridge_1d<S, F>(
+    mut x: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+    noise: F,
+) -> S::Vf32
+where
+    S: Simd,
+    F: Fn(S::Vf32, i32) -> S::Vf32,
+{
+    let mut result = S::sub_ps(S::set1_ps(1.0), S::abs_ps(noise(x, seed)));
+    let mut amp = S::set1_ps(1.0);
+
+    for _ in 1..octaves {
+        x = S::mul_ps(x, lac);
+        amp = S::mul_ps(amp, gain);
+        result = S::add_ps(
+            result,
+            S::fnmadd_ps(S::abs_ps(noise(x, seed)), amp, S::set1_ps(1.0)),
+        );
+    }
+
+    result
+}
+
+/// 1d turbulence, `|noise|` summed over `octaves` layers like `fbm_1d`
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn turbulence_1d<S, F>(
+    mut x: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+    noise: F,
+) -> S::Vf32
+where
+    S: Simd,
+    F: Fn(S::Vf32, i32) -> S::Vf32,
+{
+    let mut result = S::abs_ps(noise(x, seed));
+    let mut amp = S::set1_ps(1.0);
+
+    for _ in 1..octaves {
+        x = S::mul_ps(x, lac);
+        amp = S::mul_ps(amp, gain);
+        result = S::add_ps(result, S::abs_ps(S::mul_ps(noise(x, seed), amp)));
+    }
+
+    result
+}
+
+/// 2d fbm, `octaves` layers of `noise` at rising frequency and falling
+/// amplitude
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn fbm_2d<S, F>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+    noise: F,
+) -> S::Vf32
+where
+    S: Simd,
+    F: Fn(S::Vf32, S::Vf32, i32) -> S::Vf32,
+{
+    let mut result = noise(x, y, seed);
+    let mut amp = S::set1_ps(1.0);
+
+    for _ in 1..octaves {
+        x = S::mul_ps(x, lac);
+        y = S::mul_ps(y, lac);
+        amp = S::mul_ps(amp, gain);
+        result = S::add_ps(S::mul_ps(noise(x, y, seed), amp), result);
+    }
+
+    result
+}
+
+/// 2d ridged fractal, `1 - |noise|` summed over `octaves` layers like
+/// `fbm_2d`
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn ridge_2d<S, F>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+    noise: F,
+) -> S::Vf32
+where
+    S: Simd,
+    F: Fn(S::Vf32, S::Vf32, i32) -> S::Vf32,
+{
+    let mut result = S::sub_ps(S::set1_ps(1.0), S::abs_ps(noise(x, y, seed)));
+    let mut amp = S::set1_ps(1.0);
+
+    for _ in 1..octaves {
+        x = S::mul_ps(x, lac);
+        y = S::mul_ps(y, lac);
+        amp = S::mul_ps(amp, gain);
+        result = S::add_ps(
+            result,
+            S::fnmadd_ps(S::abs_ps(noise(x, y, seed)), amp, S::set1_ps(1.0)),
+        );
+    }
+
+    result
+}
+
+/// 2d turbulence, `|noise|` summed over `octaves` layers like `fbm_2d`
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn turbulence_2d<S, F>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+    noise: F,
+) -> S::Vf32
+where
+    S: Simd,
+    F: Fn(S::Vf32, S::Vf32, i32) -> S::Vf32,
+{
+    let mut result = S::abs_ps(noise(x, y, seed));
+    let mut amp = S::set1_ps(1.0);
+
+    for _ in 1..octaves {
+        x = S::mul_ps(x, lac);
+        y = S::mul_ps(y, lac);
+        amp = S::mul_ps(amp, gain);
+        result = S::add_ps(result, S::abs_ps(S::mul_ps(noise(x, y, seed), amp)));
+    }
+
+    result
+}
+
+/// 3d fbm, `octaves` layers of `noise` at rising frequency and falling
+/// amplitude
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn fbm_3d<S, F>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    mut z: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+    noise: F,
+) -> S::Vf32
+where
+    S: Simd,
+    F: Fn(S::Vf32, S::Vf32, S::Vf32, i32) -> S::Vf32,
+{
+    let mut result = noise(x, y, z, seed);
+    let mut amp = S::set1_ps(1.0);
+
+    for _ in 1..octaves {
+        x = S::mul_ps(x, lac);
+        y = S::mul_ps(y, lac);
+        z = S::mul_ps(z, lac);
+        amp = S::mul_ps(amp, gain);
+        result = S::add_ps(S::mul_ps(noise(x, y, z, seed), amp), result);
+    }
+
+    result
+}
+
+/// 3d ridged fractal, `1 - |noise|` summed over `octaves` layers like
+/// `fbm_3d`
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn ridge_3d<S, F>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    mut z: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+    noise: F,
+) -> S::Vf32
+where
+    S: Simd,
+    F: Fn(S::Vf32, S::Vf32, S::Vf32, i32) -> S::Vf32,
+{
+    let mut result = S::sub_ps(S::set1_ps(1.0), S::abs_ps(noise(x, y, z, seed)));
+    let mut amp = S::set1_ps(1.0);
+
+    for _ in 1..octaves {
+        x = S::mul_ps(x, lac);
+        y = S::mul_ps(y, lac);
+        z = S::mul_ps(z, lac);
+        amp = S::mul_ps(amp, gain);
+        result = S::add_ps(
+            result,
+            S::fnmadd_ps(S::abs_ps(noise(x, y, z, seed)), amp, S::set1_ps(1.0)),
+        );
+    }
+
+    result
+}
+
+/// 3d turbulence, `|noise|` summed over `octaves` layers like `fbm_3d`
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn turbulence_3d<S, F>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    mut z: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+    noise: F,
+) -> S::Vf32
+where
+    S: Simd,
+    F: Fn(S::Vf32, S::Vf32, S::Vf32, i32) -> S::Vf32,
+{
+    let mut result = S::abs_ps(noise(x, y, z, seed));
+    let mut amp = S::set1_ps(1.0);
+
+    for _ in 1..octaves {
+        x = S::mul_ps(x, lac);
+        y = S::mul_ps(y, lac);
+        z = S::mul_ps(z, lac);
+        amp = S::mul_ps(amp, gain);
+        result = S::add_ps(result, S::abs_ps(S::mul_ps(noise(x, y, z, seed), amp)));
+    }
+
+    result
+}
+
+/// 4d fbm, `octaves` layers of `noise` at rising frequency and falling
+/// amplitude
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn fbm_4d<S, F>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    mut z: S::Vf32,
+    mut w: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+    noise: F,
+) -> S::Vf32
+where
+    S: Simd,
+    F: Fn(S::Vf32, S::Vf32, S::Vf32, S::Vf32, i32) -> S::Vf32,
+{
+    let mut result = noise(x, y, z, w, seed);
+    let mut amp = S::set1_ps(1.0);
+
+    for _ in 1..octaves {
+        x = S::mul_ps(x, lac);
+        y = S::mul_ps(y, lac);
+        z = S::mul_ps(z, lac);
+        w = S::mul_ps(w, lac);
+        amp = S::mul_ps(amp, gain);
+        result = S::add_ps(result, S::mul_ps(noise(x, y, z, w, seed), amp));
+    }
+
+    result
+}
+
+/// 4d ridged fractal, `1 - |noise|` summed over `octaves` layers like
+/// `fbm_4d`
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn ridge_4d<S, F>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    mut z: S::Vf32,
+    mut w: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+    noise: F,
+) -> S::Vf32
+where
+    S: Simd,
+    F: Fn(S::Vf32, S::Vf32, S::Vf32, S::Vf32, i32) -> S::Vf32,
+{
+    let mut result = S::sub_ps(S::set1_ps(1.0), S::abs_ps(noise(x, y, z, w, seed)));
+    let mut amp = S::set1_ps(1.0);
+
+    for _ in 1..octaves {
+        x = S::mul_ps(x, lac);
+        y = S::mul_ps(y, lac);
+        z = S::mul_ps(z, lac);
+        w = S::mul_ps(w, lac);
+        amp = S::mul_ps(amp, gain);
+        result = S::add_ps(
+            result,
+            S::fnmadd_ps(S::abs_ps(noise(x, y, z, w, seed)), amp, S::set1_ps(1.0)),
+        );
+    }
+
+    result
+}
+
+/// 4d turbulence, `|noise|` summed over `octaves` layers like `fbm_4d`
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn turbulence_4d<S, F>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    mut z: S::Vf32,
+    mut w: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+    noise: F,
+) -> S::Vf32
+where
+    S: Simd,
+    F: Fn(S::Vf32, S::Vf32, S::Vf32, S::Vf32, i32) -> S::Vf32,
+{
+    let mut result = S::abs_ps(noise(x, y, z, w, seed));
+    let mut amp = S::set1_ps(1.0);
+
+    for _ in 1..octaves {
+        x = S::mul_ps(x, lac);
+        y = S::mul_ps(y, lac);
+        z = S::mul_ps(z, lac);
+        w = S::mul_ps(w, lac);
+        amp = S::mul_ps(amp, gain);
+        result = S::add_ps(result, S::abs_ps(S::mul_ps(noise(x, y, z, w, seed), amp)));
+    }
+
+    result
+}
+
+/// Salt mixed into the hash for per octave seeds
+const SEED_SALT: u32 = 0x68e3_1da4;
+/// Salt mixed into the hash for per octave rotations and shifts
+const ROTATION_SALT: u32 = 0xb529_7a4d;
+/// Largest distance an octave is shifted by when it is rotated
+const MAX_OCTAVE_SHIFT: f32 = 64.0;
+
+/// Hashes the seed, octave and salt together, the same on every backend
+#[inline(always)]
+fn octave_hash(seed: i32, octave: usize, salt: u32) -> u32 {
+    let mut h = (seed as u32) ^ (octave as u32).wrapping_mul(0x9e37_79b9) ^ salt;
+    h ^= h >> 16;
+    h = h.wrapping_mul(0x7feb_352d);
+    h ^= h >> 15;
+    h = h.wrapping_mul(0x846c_a68b);
+    h ^= h >> 16;
+    h
+}
+
+/// Seed to use for the given octave, the first octave always keeps `seed`
+#[inline(always)]
+fn octave_seed(variation: OctaveVariation, seed: i32, octave: usize) -> i32 {
+    if variation.seeds && octave > 0 {
+        octave_hash(seed, octave, SEED_SALT) as i32
+    } else {
+        seed
+    }
+}
+
+/// N numbers in [0, 1) that drive the rotation and shift of an octave.
+/// Fixed rotations hash a seed of 0, so they are the same for every seed.
+#[inline(always)]
+fn octave_randoms<const N: usize>(
+    variation: OctaveVariation,
+    seed: i32,
+    octave: usize,
+) -> [f32; N] {
+    let seed = match variation.rotation {
+        OctaveRotation::Seeded => seed,
+        _ => 0,
+    };
+    let mut randoms = [0.0; N];
+    for (i, r) in randoms.iter_mut().enumerate() {
+        let h = octave_hash(seed, octave, ROTATION_SALT.wrapping_add(i as u32));
+        *r = (h >> 8) as f32 / (1 << 24) as f32;
+    }
+    randoms
+}
+
+/// Uniformly distributed unit quaternion, w first
+#[inline(always)]
+fn random_quaternion(u: [f32; 3]) -> [f32; 4] {
+    let tau = std::f32::consts::PI * 2.0;
+    let a = (1.0 - u[0]).sqrt();
+    let b = u[0].sqrt();
+    [
+        a * (tau * u[1]).sin(),
+        a * (tau * u[1]).cos(),
+        b * (tau * u[2]).sin(),
+        b * (tau * u[2]).cos(),
+    ]
+}
+
+/// Rotation and shift applied to the coordinates of an octave. The first
+/// octave, and every octave when rotation is off, is left as it is.
+#[inline(always)]
+fn octave_transform_2d(
+    variation: OctaveVariation,
+    seed: i32,
+    octave: usize,
+) -> ([[f32; 2]; 2], [f32; 2]) {
+    if variation.rotation == OctaveRotation::None || octave == 0 {
+        return ([[1.0, 0.0], [0.0, 1.0]], [0.0; 2]);
+    }
+    let r = octave_randoms::<3>(variation, seed, octave);
+    let (sin, cos) = (r[0] * std::f32::consts::PI * 2.0).sin_cos();
+    (
+        [[cos, -sin], [sin, cos]],
+        [r[1] * MAX_OCTAVE_SHIFT, r[2] * MAX_OCTAVE_SHIFT],
+    )
+}
+
+/// See `octave_transform_2d`
+#[inline(always)]
+fn octave_transform_3d(
+    variation: OctaveVariation,
+    seed: i32,
+    octave: usize,
+) -> ([[f32; 3]; 3], [f32; 3]) {
+    if variation.rotation == OctaveRotation::None || octave == 0 {
+        return (
+            [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+            [0.0; 3],
+        );
+    }
+    let r = octave_randoms::<6>(variation, seed, octave);
+    let [w, x, y, z] = random_quaternion([r[0], r[1], r[2]]);
+    (
+        [
+            [
+                1.0 - 2.0 * (y * y + z * z),
+                2.0 * (x * y - w * z),
+                2.0 * (x * z + w * y),
+            ],
+            [
+                2.0 * (x * y + w * z),
+                1.0 - 2.0 * (x * x + z * z),
+                2.0 * (y * z - w * x),
+            ],
+            [
+                2.0 * (x * z - w * y),
+                2.0 * (y * z + w * x),
+                1.0 - 2.0 * (x * x + y * y),
+            ],
+        ],
+        [
+            r[3] * MAX_OCTAVE_SHIFT,
+            r[4] * MAX_OCTAVE_SHIFT,
+            r[5] * MAX_OCTAVE_SHIFT,
+        ],
+    )
+}
+
+/// See `octave_transform_2d`. 4d rotates by left multiplying with a unit
+/// quaternion, which turns every pair of axes.
+#[inline(always)]
+fn octave_transform_4d(
+    variation: OctaveVariation,
+    seed: i32,
+    octave: usize,
+) -> ([[f32; 4]; 4], [f32; 4]) {
+    if variation.rotation == OctaveRotation::None || octave == 0 {
+        return (
+            [
+                [1.0, 0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+            [0.0; 4],
+        );
+    }
+    let r = octave_randoms::<7>(variation, seed, octave);
+    let [a, b, c, d] = random_quaternion([r[0], r[1], r[2]]);
+    (
+        [[a, -b, -c, -d], [b, a, -d, c], [c, d, a, -b], [d, -c, b, a]],
+        [
+            r[3] * MAX_OCTAVE_SHIFT,
+            r[4] * MAX_OCTAVE_SHIFT,
+            r[5] * MAX_OCTAVE_SHIFT,
+            r[6] * MAX_OCTAVE_SHIFT,
+        ],
+    )
+}
+
+/// Sums octaves of `noise` like the fractals above, but each octave can get
+/// its own seed, rotation and shift, see `OctaveVariation`. `combine` adds an
+/// octave to the result, it is passed the result so far, the noise and the
+/// amplitude of the octave, which falls by `gain` every octave like in the
+/// plain 1d fractals. 1d has nothing to rotate, so octaves are only shifted.
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn varied_1d<S, F, G>(
+    mut x: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+    variation: OctaveVariation,
+    noise: F,
+    combine: G,
+) -> S::Vf32
+where
+    S: Simd,
+    F: Fn(S::Vf32, i32) -> S::Vf32,
+    G: Fn(S::Vf32, S::Vf32, S::Vf32) -> S::Vf32,
+{
+    let mut result = S::setzero_ps();
+    let mut amp = S::set1_ps(1.0);
+
+    for octave in 0..octaves.max(1) as usize {
+        let (_, shift) = octave_transform_2d(variation, seed, octave);
+        let n = noise(
+            S::add_ps(x, S::set1_ps(shift[0])),
+            octave_seed(variation, seed, octave),
+        );
+        result = combine(result, n, amp);
+        x = S::mul_ps(x, lac);
+        amp = S::mul_ps(amp, gain);
+    }
+
+    result
+}
+
+/// See `varied_1d`
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn varied_2d<S, F, G>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+    variation: OctaveVariation,
+    noise: F,
+    combine: G,
+) -> S::Vf32
+where
+    S: Simd,
+    F: Fn(S::Vf32, S::Vf32, i32) -> S::Vf32,
+    G: Fn(S::Vf32, S::Vf32, S::Vf32) -> S::Vf32,
+{
+    let mut result = S::setzero_ps();
+    let mut amp = S::set1_ps(1.0);
+
+    for octave in 0..octaves.max(1) as usize {
+        let (m, shift) = octave_transform_2d(variation, seed, octave);
+        let mut p = [S::setzero_ps(); 2];
+        for (i, p) in p.iter_mut().enumerate() {
+            *p = S::fmadd_ps(
+                S::set1_ps(m[i][0]),
+                x,
+                S::fmadd_ps(S::set1_ps(m[i][1]), y, S::set1_ps(shift[i])),
+            );
+        }
+        let n = noise(p[0], p[1], octave_seed(variation, seed, octave));
+        result = combine(result, n, amp);
+        x = S::mul_ps(x, lac);
+        y = S::mul_ps(y, lac);
+        amp = S::mul_ps(amp, gain);
+    }
+
+    result
+}
+
+/// See `varied_1d`
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn varied_3d<S, F, G>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    mut z: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+    variation: OctaveVariation,
+    noise: F,
+    combine: G,
+) -> S::Vf32
+where
+    S: Simd,
+    F: Fn(S::Vf32, S::Vf32, S::Vf32, i32) -> S::Vf32,
+    G: Fn(S::Vf32, S::Vf32, S::Vf32) -> S::Vf32,
+{
+    let mut result = S::setzero_ps();
+    let mut amp = S::set1_ps(1.0);
+
+    for octave in 0..octaves.max(1) as usize {
+        let (m, shift) = octave_transform_3d(variation, seed, octave);
+        let mut p = [S::setzero_ps(); 3];
+        for (i, p) in p.iter_mut().enumerate() {
+            *p = S::fmadd_ps(
+                S::set1_ps(m[i][0]),
+                x,
+                S::fmadd_ps(
+                    S::set1_ps(m[i][1]),
+                    y,
+                    S::fmadd_ps(S::set1_ps(m[i][2]), z, S::set1_ps(shift[i])),
+                ),
+            );
+        }
+        let n = noise(p[0], p[1], p[2], octave_seed(variation, seed, octave));
+        result = combine(result, n, amp);
+        x = S::mul_ps(x, lac);
+        y = S::mul_ps(y, lac);
+        z = S::mul_ps(z, lac);
+        amp = S::mul_ps(amp, gain);
+    }
+
+    result
+}
+
+/// See `varied_1d`
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn varied_4d<S, F, G>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    mut z: S::Vf32,
+    mut w: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+    variation: OctaveVariation,
+    noise: F,
+    combine: G,
+) -> S::Vf32
+where
+    S: Simd,
+    F: Fn(S::Vf32, S::Vf32, S::Vf32, S::Vf32, i32) -> S::Vf32,
+    G: Fn(S::Vf32, S::Vf32, S::Vf32) -> S::Vf32,
+{
+    let mut result = S::setzero_ps();
+    let mut amp = S::set1_ps(1.0);
+
+    for octave in 0..octaves.max(1) as usize {
+        let (m, shift) = octave_transform_4d(variation, seed, octave);
+        let mut p = [S::setzero_ps(); 4];
+        for (i, p) in p.iter_mut().enumerate() {
+            *p = S::fmadd_ps(
+                S::set1_ps(m[i][0]),
+                x,
+                S::fmadd_ps(
+                    S::set1_ps(m[i][1]),
+                    y,
+                    S::fmadd_ps(
+                        S::set1_ps(m[i][2]),
+                        z,
+                        S::fmadd_ps(S::set1_ps(m[i][3]), w, S::set1_ps(shift[i])),
+                    ),
+                ),
+            );
+        }
+        let n = noise(p[0], p[1], p[2], p[3], octave_seed(variation, seed, octave));
+        result = combine(result, n, amp);
+        x = S::mul_ps(x, lac);
+        y = S::mul_ps(y, lac);
+        z = S::mul_ps(z, lac);
+        w = S::mul_ps(w, lac);
+        amp = S::mul_ps(amp, gain);
+    }
+
+    result
+}
+
+/// Evaluates the selected base noise at a 1d point
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn base_1d<S: Simd>(base: BaseNoise, x: S::Vf32, seed: i32) -> S::Vf32 {
+    match base {
+        BaseNoise::Simplex => simplex::simplex_1d::<S>(x, seed),
+        BaseNoise::Perlin => perlin::perlin_1d::<S>(x, seed),
+        BaseNoise::OpenSimplex2 => opensimplex::opensimplex2_2d::<S>(x, S::setzero_ps(), seed),
+        BaseNoise::OpenSimplex2S => opensimplex::opensimplex2s_2d::<S>(x, S::setzero_ps(), seed),
+        BaseNoise::Value(interpolation) => value::value_1d::<S>(x, interpolation, seed),
+        BaseNoise::Cellular {
+            distance_function,
+            return_type,
+            jitter,
+        } => {
+            cellular::cellular_1d::<S>(x, distance_function, return_type, S::set1_ps(jitter), seed)
+        }
+    }
+}
+
+/// Evaluates the selected base noise at a 2d point
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn base_2d<S: Simd>(base: BaseNoise, x: S::Vf32, y: S::Vf32, seed: i32) -> S::Vf32 {
+    match base {
+        BaseNoise::Simplex => simplex::simplex_2d::<S>(x, y, seed),
+        BaseNoise::Perlin => perlin::perlin_2d::<S>(x, y, seed),
+        BaseNoise::OpenSimplex2 => opensimplex::opensimplex2_2d::<S>(x, y, seed),
+        BaseNoise::OpenSimplex2S => opensimplex::opensimplex2s_2d::<S>(x, y, seed),
+        BaseNoise::Value(interpolation) => value::value_2d::<S>(x, y, interpolation, seed),
+        BaseNoise::Cellular {
+            distance_function,
+            return_type,
+            jitter,
+        } => cellular::cellular_2d::<S>(
+            x,
+            y,
+            distance_function,
+            return_type,
+            S::set1_ps(jitter),
+            seed,
+        ),
+    }
+}
+
+/// Evaluates the selected base noise at a 3d point
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn base_3d<S: Simd>(
+    base: BaseNoise,
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    seed: i32,
+) -> S::Vf32 {
+    match base {
+        BaseNoise::Simplex => simplex::simplex_3d::<S>(x, y, z, seed),
+        BaseNoise::Perlin => perlin::perlin_3d::<S>(x, y, z, seed),
+        BaseNoise::OpenSimplex2 => opensimplex::opensimplex2_3d::<S>(x, y, z, seed),
+        BaseNoise::OpenSimplex2S => opensimplex::opensimplex2s_3d::<S>(x, y, z, seed),
+        BaseNoise::Value(interpolation) => value::value_3d::<S>(x, y, z, interpolation, seed),
+        BaseNoise::Cellular {
+            distance_function,
+            return_type,
+            jitter,
+        } => cellular::cellular_3d::<S>(
+            x,
+            y,
+            z,
+            distance_function,
+            return_type,
+            S::set1_ps(jitter),
+            seed,
+        ),
+    }
+}
+
+/// Periods of `base_3d_periodic` must be whole multiples of this many cells
+pub fn periodic_step_3d(base: BaseNoise) -> f32 {
+    match base {
+        BaseNoise::Perlin => 1.0,
+        _ => simplex::SIMPLEX_3D_PERIOD_STEP,
+    }
+}
+
+/// Evaluates the selected base noise at a 3d point, on a lattice that repeats
+/// every `period` cells. Only Perlin and simplex tile in 3d, any other base is
+/// taken as simplex, the settings don't allow them.
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn base_3d_periodic<S: Simd>(
+    base: BaseNoise,
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    period: [f32; 3],
+    seed: i32,
+) -> S::Vf32 {
+    match base {
+        BaseNoise::Perlin => perlin::perlin_3d_periodic::<S>(x, y, z, period, seed),
+        _ => simplex::simplex_3d_periodic::<S>(x, y, z, period, seed),
+    }
+}
+
+/// Sums octaves of a periodic 3d `noise` that all repeat every `period`
+/// lattice cells of the first octave. Each octave scales by the lacunarity,
+/// rounded so a whole multiple of `step` of its cells fit in the period.
+/// Octave seeds are applied, octave rotation would break the period and is
+/// left out, the settings don't allow it. `combine` adds an octave to the
+/// result, as in `varied_3d`.
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn fractal_3d_periodic<S, F, G>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    period: [f32; 3],
+    step: f32,
+    lac: f32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+    variation: OctaveVariation,
+    noise: F,
+    combine: G,
+) -> S::Vf32
+where
+    S: Simd,
+    F: Fn(S::Vf32, S::Vf32, S::Vf32, [f32; 3], i32) -> S::Vf32,
+    G: Fn(S::Vf32, S::Vf32, S::Vf32) -> S::Vf32,
+{
+    let mut result = S::setzero_ps();
+    let mut amp = S::set1_ps(1.0);
+    let mut octave_lac = 1.0f32;
+
+    for octave in 0..octaves.max(1) as usize {
+        let mut cells = [0.0; 3];
+        for (c, p) in cells.iter_mut().zip(period.iter()) {
+            *c = (p * octave_lac / step).round().max(1.0) * step;
+        }
+        let n = noise(
+            S::mul_ps(x, S::set1_ps(cells[0] / period[0])),
+            S::mul_ps(y, S::set1_ps(cells[1] / period[1])),
+            S::mul_ps(z, S::set1_ps(cells[2] / period[2])),
+            cells,
+            octave_seed(variation, seed, octave),
+        );
+        result = combine(result, n, amp);
+        amp = S::mul_ps(amp, gain);
+        octave_lac *= lac;
+    }
+
+    result
+}
+
+/// Evaluates the selected base noise at a 4d point
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn base_4d<S: Simd>(
+    base: BaseNoise,
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    w: S::Vf32,
+    seed: i32,
+) -> S::Vf32 {
+    match base {
+        BaseNoise::Simplex => simplex::simplex_4d::<S>(x, y, z, w, seed),
+        BaseNoise::Perlin => perlin::perlin_4d::<S>(x, y, z, w, seed),
+        BaseNoise::OpenSimplex2 => opensimplex::opensimplex2_4d::<S>(x, y, z, w, seed),
+        BaseNoise::OpenSimplex2S => opensimplex::opensimplex2s_4d::<S>(x, y, z, w, seed),
+        BaseNoise::Value(interpolation) => value::value_4d::<S>(x, y, z, w, interpolation, seed),
+        BaseNoise::Cellular {
+            distance_function,
+            return_type,
+            jitter,
+        } => cellular::cellular_4d::<S>(
+            x,
+            y,
+            z,
+            w,
+            distance_function,
+            return_type,
+            S::set1_ps(jitter),
+            seed,
+        ),
+    }
+}
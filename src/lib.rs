@@ -7,9 +7,28 @@
 //!
 //!* SSE2, SSE41, and AVX2 instruction sets, along with non SIMD fallback
 //!* Runtime detection picks the best available instruction set
-//!* Simplex noise, fractal brownian motion, turbulence, and ridge
+//!* Simplex noise, fractal brownian motion, turbulence, ridge, and billow
+//!* Classic improved Perlin noise, usable as the base of any fractal
+//!* OpenSimplex2 and OpenSimplex2S fractal bases, free of simplex axis artifacts
+//!* Fractals over any gradient, value, or cellular base noise
+//!* Value noise with linear, cubic or quintic interpolation
+//!* Domain warping, including iterated warps, over any base noise
+//!* Curl noise, divergence free 2D and 3D vector fields
+//!* Simplex noise and fbm with exact analytic derivatives
+//!* Derivative damped fbm, Swiss and Jordan turbulence for terrain
+//!* Musgrave hybrid multifractal, heterogeneous terrain and ridged multifractal
+//!* Per octave seeds and rotations, off by default for reproducible output
+//!* Normalized fractals with fixed range scaling, so chunks line up
+//!* `ChunkedGenerator` for tiles that share one scale and match along edges
+//!* Seamlessly repeating noise with `with_period` and `with_period_3d`
+//!* Seamlessly looping 2D fbm animations with `NoiseBuilder::fbm_2d_looping`
+//!* Equirectangular and cubemap noise on a sphere with `SphereGenerator`
+//!* Noise at arbitrary points with `get_3d_noise_at` and friends
+//!* `fill` and `fill_pitched` to generate into your own buffers without allocating
+//!* Rotated, sheared and tilted blocks with `with_transform`
+//!* Blocks split across threads with the optional `rayon` feature
 //!* 1D, 2D, 3D, and 4D
-//!* Cellular / Voroni Noise  2D and 3D
+//!* Cellular / Voroni Noise 1D, 2D, 3D, and 4D
 //!
 //!## Benchmarks
 //! See [Github](https://github.com/jackmott/rust-simd-noise)
@@ -42,8 +61,31 @@
 //!
 //! ```
 //!
+//!## Noise on a sphere
+//!
+//! ```rust
+//! use simdnoise::*;
+//!
+//! // A 256 x 128 equirectangular map of a planet, and the same planet as six 64 x 64 cubemap faces
+//! let planet = NoiseBuilder::fbm_3d(1, 1, 1).with_freq(2.0).with_octaves(5).wrap();
+//! let (map, min, max) = SphereGenerator::new(planet).equirectangular(256, 128);
+//! let (faces, min, max) = SphereGenerator::new(planet).with_radius(2.0).cubemap(64);
+//! ```
+//!
+//!## Noise at a list of points
+//!
+//! ```rust
+//! use simdnoise::*;
+//!
+//! // Mesh vertices or particles, either one list per axis or one array per point
+//! let terrain = NoiseBuilder::fbm_3d(1, 1, 1).with_freq(0.05).with_octaves(5).wrap();
+//! let heights = get_3d_noise_at(&terrain, &[0.0, 10.5, 3.2], &[1.0, 2.0, 3.0], &[0.0, 0.0, 7.5]);
+//! let vertices = [[0.0, 1.0, 0.0], [10.5, 2.0, 0.0], [3.2, 3.0, 7.5]];
+//! assert_eq!(get_3d_noise_at_points(&terrain, &vertices), heights);
+//! ```
+//!
 //! ## Call noise functions directly
-//! Sometimes you need something other than a block, like the points on the surface of a sphere.
+//! Sometimes you need something other than a block, like your own set of points.
 //! Sometimes you may want to use SSE41 even with AVX2 is available
 //!
 //!
@@ -84,14 +126,21 @@ extern crate simdeez;
 pub mod avx2;
 pub mod cellular;
 pub mod cellular_64;
+pub mod curl;
+pub mod domain_warp;
+pub mod fractal;
 mod noise_helpers;
 mod noise_helpers_64;
+pub mod opensimplex;
+pub mod perlin;
 pub mod scalar;
 mod shared;
 pub mod simplex;
 pub mod simplex_64;
 pub mod sse2;
 pub mod sse41;
+pub mod terrain;
+pub mod value;
 
 macro_rules! get_1d_noise {
     ($setting:expr) => {
@@ -205,6 +254,34 @@ macro_rules! get_4d_scaled_noise {
     };
 }
 
+macro_rules! get_noise_at {
+    ($f:ident, $setting:expr $(, $coords:expr)*) => {
+        if is_x86_feature_detected!("avx2") {
+            unsafe { avx2::$f($setting $(, $coords)*) }
+        } else if is_x86_feature_detected!("sse4.1") {
+            unsafe { sse41::$f($setting $(, $coords)*) }
+        } else if is_x86_feature_detected!("sse2") {
+            unsafe { sse2::$f($setting $(, $coords)*) }
+        } else {
+            unsafe { scalar::$f($setting $(, $coords)*) }
+        }
+    };
+}
+
+macro_rules! fill_noise {
+    ($f:ident, $setting:expr, $out:expr $(, $pitch:expr)?) => {
+        if is_x86_feature_detected!("avx2") {
+            unsafe { avx2::$f($setting, $out $(, $pitch)?) }
+        } else if is_x86_feature_detected!("sse4.1") {
+            unsafe { sse41::$f($setting, $out $(, $pitch)?) }
+        } else if is_x86_feature_detected!("sse2") {
+            unsafe { sse2::$f($setting, $out $(, $pitch)?) }
+        } else {
+            unsafe { scalar::$f($setting, $out $(, $pitch)?) }
+        }
+    };
+}
+
 #[derive(Copy, Clone)]
 /// The function to use to compute distance between cells
 pub enum CellDistanceFunction {
@@ -214,10 +291,18 @@ pub enum CellDistanceFunction {
     Manhattan,
     /// Combines Manhattan and Euclidean
     Natural,
+    /// The largest of the X and Y distances, gives square cells
+    Chebyshev,
+    /// Sum of |d|^p over every axis of the noise, without taking the 1/p
+    /// root, the same way Euclidean gives the squared distance. 1 is
+    /// Manhattan, 2 is Euclidean, and cells approach Chebyshev as p grows
+    Minkowski(f32),
 }
 
 #[derive(Copy, Clone)]
-/// Determines what final value is returned for the cell noise
+/// Determines what final value is returned for the cell noise. To get the
+/// coordinates, hash, or feature point of the nearest cell instead see
+/// `cellular::cellular_feature_2d` and `cellular::cellular_feature_3d`
 pub enum CellReturnType {
     /// Will return solid colors in each cell
     CellValue,
@@ -233,6 +318,196 @@ pub enum Cell2ReturnType {
     Distance2Sub,
     Distance2Mul,
     Distance2Div,
+    /// Exact distance to the border between the two nearest cells, giving
+    /// lines of even width where `Distance2Sub` only approximates them.
+    /// Always Euclidean, and slow in 4d where 625 cells are searched.
+    EdgeDistance,
+    /// Sum of the sorted distances F1 to F4, each multiplied by its weight,
+    /// so `[-1.0, 1.0, 0.0, 0.0]` gives F2 - F1. Ignores index0 and index1
+    Weighted([f32; 4]),
+}
+
+#[derive(Copy, Clone)]
+/// The noise that the fractal types sum over each octave
+pub enum BaseNoise {
+    /// Simplex gradient noise
+    Simplex,
+    /// Classic improved Perlin gradient noise
+    Perlin,
+    /// OpenSimplex2, free of the axis aligned artifacts of simplex in 3d and 4d.
+    /// 1d samples the 2d noise along the x axis.
+    OpenSimplex2,
+    /// OpenSimplex2S, a smoother but slower OpenSimplex2.
+    /// 1d samples the 2d noise along the x axis.
+    OpenSimplex2S,
+    /// Value noise blended with the given interpolation
+    Value(Interpolation),
+    /// Cellular noise with the same parameters as `CellularSettings`, see
+    /// `CellularSettings::as_base`.
+    Cellular {
+        distance_function: CellDistanceFunction,
+        return_type: CellReturnType,
+        jitter: f32,
+    },
+}
+
+impl BaseNoise {
+    /// Brings the base noise to roughly -1 to 1. Only simplex needs scaling,
+    /// cellular returns distances and is left as it is.
+    fn scale(&self, dim: usize) -> f32 {
+        match (self, dim) {
+            (BaseNoise::Simplex, 1) => simplex::SIMPLEX_1D_SCALE,
+            (BaseNoise::Simplex, 2) => simplex::SIMPLEX_2D_SCALE,
+            (BaseNoise::Simplex, 3) => simplex::SIMPLEX_3D_SCALE,
+            (BaseNoise::Simplex, _) => simplex::SIMPLEX_4D_SCALE,
+            _ => 1.0,
+        }
+    }
+}
+
+/// Dimension of the kernel behind noise of dimension `dim`, 2d tileable and
+/// looping noise sample the 4d kernels
+fn kernel_dim(dim: usize, on_torus: bool) -> usize {
+    if on_torus && dim == 2 {
+        4
+    } else {
+        dim
+    }
+}
+
+/// Tileable 3d fractals sum octaves of a periodic lattice, which only simplex
+/// and Perlin noise have, and rotating the octaves would break the period
+fn check_periodic_fractal(
+    dim: &NoiseDimensions,
+    period: Option<[f32; 3]>,
+    base: BaseNoise,
+    variation: OctaveVariation,
+) {
+    if dim.dim != 3 || period.is_none() {
+        return;
+    }
+    if !matches!(base, BaseNoise::Simplex | BaseNoise::Perlin) {
+        panic!("tileable 3d fractals need a simplex or Perlin base");
+    }
+    if variation.rotation != OctaveRotation::None {
+        panic!("tileable 3d fractals can't rotate their octaves");
+    }
+}
+
+/// The derivative driven terrain fractals only have 2d and 3d kernels
+fn check_terrain_dim(name: &str, dim: &NoiseDimensions) {
+    if dim.dim != 2 && dim.dim != 3 {
+        panic!("{} noise is only 2d or 3d, not {}d", name, dim.dim);
+    }
+}
+
+/// Sum of the amplitudes of the octaves of a fractal over `base`. The 1d
+/// simplex fractals ignore the gain and add every octave at full amplitude.
+fn fractal_amplitude(
+    base: BaseNoise,
+    dim: usize,
+    variation: OctaveVariation,
+    gain: f32,
+    octaves: u8,
+) -> f32 {
+    match base {
+        BaseNoise::Simplex if dim == 1 && !variation.is_varied() => octaves.max(1) as f32,
+        _ => total_amplitude(gain, octaves),
+    }
+}
+
+/// Sum of the amplitudes of the octaves of a fractal
+fn total_amplitude(gain: f32, octaves: u8) -> f32 {
+    let mut amp = 1.0;
+    let mut total = 0.0;
+    for _ in 0..octaves.max(1) {
+        total += amp;
+        amp *= gain.abs();
+    }
+    total
+}
+
+/// Maps noise from `from_min` to `from_max` onto `to_min` to `to_max`
+fn scale_fixed(noise: &mut [f32], from_min: f32, from_max: f32, to_min: f32, to_max: f32) {
+    let multiplier = (to_max - to_min) / (from_max - from_min);
+    let offset = to_min - from_min * multiplier;
+    for n in noise.iter_mut() {
+        *n = *n * multiplier + offset;
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+/// How each octave of a fractal is rotated, on top of being scaled by the
+/// lacunarity
+pub enum OctaveRotation {
+    /// Octaves are only scaled
+    None,
+    /// Each octave gets its own rotation, the same for every seed
+    Fixed,
+    /// Each octave gets its own rotation, derived from the seed
+    Seeded,
+}
+
+#[derive(Copy, Clone, PartialEq)]
+/// Options that decorrelate the octaves of a fractal, so they stop lining up
+/// along the axes and at the origin. Everything is off by default, which
+/// reproduces the noise of earlier versions. The first octave is never changed.
+pub struct OctaveVariation {
+    /// Derive a distinct seed for every octave after the first
+    pub seeds: bool,
+    /// Rotate and shift every octave after the first
+    pub rotation: OctaveRotation,
+}
+
+impl OctaveVariation {
+    /// Every octave uses the same seed and is only scaled
+    pub const NONE: OctaveVariation = OctaveVariation {
+        seeds: false,
+        rotation: OctaveRotation::None,
+    };
+
+    /// True if the octaves differ by more than frequency and amplitude
+    pub fn is_varied(&self) -> bool {
+        *self != OctaveVariation::NONE
+    }
+}
+
+#[derive(Copy, Clone)]
+/// The curve used to blend between lattice points in value noise
+pub enum Interpolation {
+    /// Straight linear blend, shows visible creases along cell edges
+    Linear,
+    /// Hermite smoothstep, 3t^2 - 2t^3
+    Cubic,
+    /// Quintic fade curve, 6t^5 - 15t^4 + 10t^3
+    Quintic,
+}
+
+#[derive(Copy, Clone)]
+/// One layer of domain warp, see `DomainWarpSettings::with_warp`
+pub struct Warp {
+    /// The noise the coordinate offsets are drawn from
+    pub base: BaseNoise,
+    /// Octaves of fbm over the base, 1 for the plain base noise
+    pub octaves: u8,
+    /// Scales the unscaled warp noise into an offset, in the frequency scaled
+    /// coordinates of the warped noise. Mind that simplex output is much
+    /// smaller than the other bases, so it needs a larger amplitude
+    pub amplitude: f32,
+    /// Frequency of the warp noise relative to the warped noise
+    pub freq: f32,
+}
+
+/// The most warp layers a `DomainWarpSettings` can hold
+pub const MAX_WARPS: usize = 4;
+
+#[derive(Copy, Clone)]
+/// How the components of vector valued noise are laid out in a block
+pub enum VectorLayout {
+    /// All components of a sample together, `[x0, y0, x1, y1, ...]`
+    Interleaved,
+    /// One whole block per component, `[x0, x1, ..., y0, y1, ...]`
+    Planar,
 }
 
 trait DimensionalBeing {
@@ -253,8 +528,17 @@ pub struct NoiseDimensions {
     min: f32,
     max: f32,
     seed: i32,
+    transform: Option<[[f32; 4]; 4]>,
 }
 
+/// The transform of a block without `with_transform`
+const IDENTITY_TRANSFORM: [[f32; 4]; 4] = [
+    [1.0, 0.0, 0.0, 0.0],
+    [0.0, 1.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0, 0.0],
+    [0.0, 0.0, 0.0, 1.0],
+];
+
 impl NoiseDimensions {
     pub fn default(d: usize) -> NoiseDimensions {
         if d < 1 || d > 4 {
@@ -273,7 +557,38 @@ impl NoiseDimensions {
             min: 0.0,
             max: 1.0,
             seed: 1,
+            transform: None,
+        }
+    }
+
+    /// Checks a period given to `with_period` or `with_period_3d`, tileable
+    /// noise needs a positive period on each axis and can't be transformed
+    fn check_period(&self, period: &[f32]) {
+        if self.dim != period.len() {
+            panic!("{}d noise can't take a {}d period", self.dim, period.len());
+        }
+        if period.iter().any(|&p| p.is_nan() || p <= 0.0) {
+            panic!("periods must be greater than 0");
+        }
+        if self.transform.is_some() {
+            panic!("tileable noise can't be transformed");
+        }
+    }
+
+    /// Sets the transform of the sample positions, a 1x1 up to 4x4 matrix
+    /// that becomes the top left of an otherwise identity 4x4 matrix
+    fn set_transform<const N: usize>(&mut self, matrix: [[f32; N]; N]) {
+        if N < 1 || N > 4 {
+            panic!("transform must be 1x1 up to 4x4");
+        }
+        if N > self.dim {
+            panic!("{}d noise can't take a {}x{} transform", self.dim, N, N);
+        }
+        let mut transform = IDENTITY_TRANSFORM;
+        for (row, matrix_row) in transform.iter_mut().zip(matrix.iter()) {
+            row[..N].copy_from_slice(matrix_row);
         }
+        self.transform = Some(transform);
     }
 }
 
@@ -283,9 +598,11 @@ pub struct CellularSettings {
     freq_x: f32,
     freq_y: f32,
     freq_z: f32,
+    freq_w: f32,
     distance_function: CellDistanceFunction,
     return_type: CellReturnType,
     jitter: f32,
+    period: Option<[f32; 3]>,
 }
 
 impl DimensionalBeing for CellularSettings {
@@ -301,9 +618,11 @@ impl CellularSettings {
             freq_x: 0.02,
             freq_y: 0.02,
             freq_z: 0.02,
+            freq_w: 0.02,
             distance_function: CellDistanceFunction::Euclidean,
             return_type: CellReturnType::Distance,
             jitter: 0.25,
+            period: None,
         }
     }
 
@@ -312,10 +631,46 @@ impl CellularSettings {
         self
     }
 
+    /// Maps the sample positions of the block through `matrix`, see
+    /// `FbmSettings::with_transform`
+    pub fn with_transform<const N: usize>(
+        &mut self,
+        matrix: [[f32; N]; N],
+    ) -> &mut CellularSettings {
+        if self.period.is_some() {
+            panic!("tileable noise can't be transformed");
+        }
+        self.dim.set_transform(matrix);
+        self
+    }
+
+    /// Makes 2d noise tile, repeating every `period_x` and `period_y` samples,
+    /// by sampling 4d noise on a torus. A block that size wraps exactly.
+    pub fn with_period(&mut self, period_x: f32, period_y: f32) -> &mut CellularSettings {
+        self.dim.check_period(&[period_x, period_y]);
+        self.period = Some([period_x, period_y, 0.0]);
+        self
+    }
+
+    /// Makes 3d noise tile, repeating every `period_x`, `period_y` and `period_z`
+    /// samples. The frequency is adjusted so a whole number of cells fit in
+    /// each period.
+    pub fn with_period_3d(
+        &mut self,
+        period_x: f32,
+        period_y: f32,
+        period_z: f32,
+    ) -> &mut CellularSettings {
+        self.dim.check_period(&[period_x, period_y, period_z]);
+        self.period = Some([period_x, period_y, period_z]);
+        self
+    }
+
     pub fn with_freq(&mut self, freq: f32) -> &mut CellularSettings {
         self.freq_x = freq;
         self.freq_y = freq;
         self.freq_z = freq;
+        self.freq_w = freq;
         self
     }
 
@@ -332,6 +687,20 @@ impl CellularSettings {
         self
     }
 
+    pub fn with_freq_4d(
+        &mut self,
+        freq_x: f32,
+        freq_y: f32,
+        freq_z: f32,
+        freq_w: f32,
+    ) -> &mut CellularSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self.freq_z = freq_z;
+        self.freq_w = freq_w;
+        self
+    }
+
     pub fn with_distance_function(&mut self, dist: CellDistanceFunction) -> &mut CellularSettings {
         self.distance_function = dist;
         self
@@ -347,6 +716,16 @@ impl CellularSettings {
         self
     }
 
+    /// Use the distance function, return type and jitter of these settings
+    /// as the base noise of a fractal, see `FbmSettings::with_base`
+    pub fn as_base(&self) -> BaseNoise {
+        BaseNoise::Cellular {
+            distance_function: self.distance_function,
+            return_type: self.return_type,
+            jitter: self.jitter,
+        }
+    }
+
     /// If you want to call noise functions by hand, call wrap on the settings
     /// to get back a NoiseType to call the noise functions with
     pub fn wrap(self) -> NoiseType {
@@ -358,8 +737,55 @@ impl CellularSettings {
     pub fn generate(self) -> (Vec<f32>, f32, f32) {
         let d = self.dim.dim;
         match d {
+            1 => get_1d_noise!(&NoiseType::Cellular(self)),
             2 => get_2d_noise!(&NoiseType::Cellular(self)),
             3 => get_3d_noise!(&NoiseType::Cellular(self)),
+            4 => get_4d_noise!(&NoiseType::Cellular(self)),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Generate a chunk of noise into `out`, which must hold exactly one
+    /// value per sample, instead of allocating. Returns the min and max value
+    pub fn fill(self, out: &mut [f32]) -> (f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            1 => fill_noise!(fill_1d_noise, &NoiseType::Cellular(self), out),
+            2 => fill_noise!(fill_2d_noise, &NoiseType::Cellular(self), out),
+            3 => fill_noise!(fill_3d_noise, &NoiseType::Cellular(self), out),
+            4 => fill_noise!(fill_4d_noise, &NoiseType::Cellular(self), out),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Like `fill`, but rows start `pitch` values apart in `out`, to write
+    /// into part of a larger image
+    pub fn fill_pitched(self, out: &mut [f32], pitch: usize) -> (f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            1 => {
+                let width = self.dim.width;
+                noise_helpers::check_1d_pitched_len(out.len(), width, pitch);
+                fill_noise!(fill_1d_noise, &NoiseType::Cellular(self), &mut out[..width])
+            }
+            2 => fill_noise!(
+                fill_2d_noise_pitched,
+                &NoiseType::Cellular(self),
+                out,
+                pitch
+            ),
+            3 => fill_noise!(
+                fill_3d_noise_pitched,
+                &NoiseType::Cellular(self),
+                out,
+                pitch
+            ),
+            4 => fill_noise!(
+                fill_4d_noise_pitched,
+                &NoiseType::Cellular(self),
+                out,
+                pitch
+            ),
             _ => panic!("not implemented"),
         }
     }
@@ -371,8 +797,10 @@ impl CellularSettings {
         new_self.dim.min = min;
         new_self.dim.max = max;
         match d {
+            1 => get_1d_scaled_noise!(&NoiseType::Cellular(new_self)),
             2 => get_2d_scaled_noise!(&NoiseType::Cellular(new_self)),
             3 => get_3d_scaled_noise!(&NoiseType::Cellular(new_self)),
+            4 => get_4d_scaled_noise!(&NoiseType::Cellular(new_self)),
             _ => panic!("not implemented"),
         }
     }
@@ -384,6 +812,7 @@ pub struct Cellular2Settings {
     freq_x: f32,
     freq_y: f32,
     freq_z: f32,
+    freq_w: f32,
     distance_function: CellDistanceFunction,
     return_type: Cell2ReturnType,
     jitter: f32,
@@ -404,6 +833,7 @@ impl Cellular2Settings {
             freq_x: 0.02,
             freq_y: 0.02,
             freq_z: 0.02,
+            freq_w: 0.02,
             distance_function: CellDistanceFunction::Euclidean,
             return_type: Cell2ReturnType::Distance2,
             jitter: 0.25,
@@ -417,10 +847,21 @@ impl Cellular2Settings {
         self
     }
 
+    /// Maps the sample positions of the block through `matrix`, see
+    /// `FbmSettings::with_transform`
+    pub fn with_transform<const N: usize>(
+        &mut self,
+        matrix: [[f32; N]; N],
+    ) -> &mut Cellular2Settings {
+        self.dim.set_transform(matrix);
+        self
+    }
+
     pub fn with_freq(&mut self, freq: f32) -> &mut Cellular2Settings {
         self.freq_x = freq;
         self.freq_y = freq;
         self.freq_z = freq;
+        self.freq_w = freq;
         self
     }
 
@@ -442,6 +883,20 @@ impl Cellular2Settings {
         self
     }
 
+    pub fn with_freq_4d(
+        &mut self,
+        freq_x: f32,
+        freq_y: f32,
+        freq_z: f32,
+        freq_w: f32,
+    ) -> &mut Cellular2Settings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self.freq_z = freq_z;
+        self.freq_w = freq_w;
+        self
+    }
+
     pub fn with_distance_function(&mut self, dist: CellDistanceFunction) -> &mut Cellular2Settings {
         self.distance_function = dist;
         self
@@ -457,50 +912,138 @@ impl Cellular2Settings {
         self
     }
 
+    /// Combine the sorted distances with these weights, see `Cell2ReturnType::Weighted`
+    pub fn with_weights(&mut self, weights: [f32; 4]) -> &mut Cellular2Settings {
+        self.return_type = Cell2ReturnType::Weighted(weights);
+        self
+    }
+
+    /// Index0 must end up less than index1, which is checked when the noise
+    /// is generated
     pub fn with_index0(&mut self, i: usize) -> &mut Cellular2Settings {
         self.index0 = i;
         self
     }
 
+    /// Index1 must end up greater than index0 and at most 3, see `with_index0`
     pub fn with_index1(&mut self, i: usize) -> &mut Cellular2Settings {
         self.index1 = i;
         self
     }
 
+    /// Which of the sorted distances, 0 for F1 up to 3 for F4, the `Distance2`
+    /// return types combine. Panics unless index0 < index1 <= 3
+    pub fn with_indices(&mut self, index0: usize, index1: usize) -> &mut Cellular2Settings {
+        Self::validate(index0, index1);
+        self.index0 = index0;
+        self.index1 = index1;
+        self
+    }
+
     /// If you want to call noise functions by hand, call wrap on the settings
     /// to get back a NoiseType to call the noise functions with
     pub fn wrap(self) -> NoiseType {
-        self.validate();
+        Self::validate(self.index0, self.index1);
         NoiseType::Cellular2(self)
     }
 
     /// Generate a chunk of noise based on your settings, and the min and max value
     /// generated, so you can scale it as you wish
     pub fn generate(self) -> (Vec<f32>, f32, f32) {
+        Self::validate(self.index0, self.index1);
         let d = self.dim.dim;
         match d {
+            1 => get_1d_noise!(&NoiseType::Cellular2(self)),
             2 => get_2d_noise!(&NoiseType::Cellular2(self)),
             3 => get_3d_noise!(&NoiseType::Cellular2(self)),
+            4 => get_4d_noise!(&NoiseType::Cellular2(self)),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Generate a chunk of noise into `out`, which must hold exactly one
+    /// value per sample, instead of allocating. Returns the min and max value
+    pub fn fill(self, out: &mut [f32]) -> (f32, f32) {
+        Self::validate(self.index0, self.index1);
+        let d = self.dim.dim;
+        match d {
+            1 => fill_noise!(fill_1d_noise, &NoiseType::Cellular2(self), out),
+            2 => fill_noise!(fill_2d_noise, &NoiseType::Cellular2(self), out),
+            3 => fill_noise!(fill_3d_noise, &NoiseType::Cellular2(self), out),
+            4 => fill_noise!(fill_4d_noise, &NoiseType::Cellular2(self), out),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Like `fill`, but rows start `pitch` values apart in `out`, to write
+    /// into part of a larger image
+    pub fn fill_pitched(self, out: &mut [f32], pitch: usize) -> (f32, f32) {
+        Self::validate(self.index0, self.index1);
+        let d = self.dim.dim;
+        match d {
+            1 => {
+                let width = self.dim.width;
+                noise_helpers::check_1d_pitched_len(out.len(), width, pitch);
+                fill_noise!(
+                    fill_1d_noise,
+                    &NoiseType::Cellular2(self),
+                    &mut out[..width]
+                )
+            }
+            2 => fill_noise!(
+                fill_2d_noise_pitched,
+                &NoiseType::Cellular2(self),
+                out,
+                pitch
+            ),
+            3 => fill_noise!(
+                fill_3d_noise_pitched,
+                &NoiseType::Cellular2(self),
+                out,
+                pitch
+            ),
+            4 => fill_noise!(
+                fill_4d_noise_pitched,
+                &NoiseType::Cellular2(self),
+                out,
+                pitch
+            ),
             _ => panic!("not implemented"),
         }
     }
 
-    fn validate(self) {
-        if self.index0 > 2 || self.index1 > 3 || self.index0 >= self.index1 {
+    fn validate(index0: usize, index1: usize) {
+        if index1 > 3 || index0 >= index1 {
             panic!("invalid index settings in cellular2 noise");
         }
     }
 
+    /// Generate all four sorted distances, F1 to F4, in one pass. Each
+    /// channel is unscaled and laid out like the block from `generate`
+    pub fn generate_distances(self) -> [Vec<f32>; 4] {
+        if is_x86_feature_detected!("avx2") {
+            unsafe { avx2::get_cellular2_distances(&self) }
+        } else if is_x86_feature_detected!("sse4.1") {
+            unsafe { sse41::get_cellular2_distances(&self) }
+        } else if is_x86_feature_detected!("sse2") {
+            unsafe { sse2::get_cellular2_distances(&self) }
+        } else {
+            unsafe { scalar::get_cellular2_distances(&self) }
+        }
+    }
+
     /// Generate a chunk of noise with values scaled from min to max
     pub fn generate_scaled(self, min: f32, max: f32) -> Vec<f32> {
-        self.validate();
+        Self::validate(self.index0, self.index1);
         let d = self.dim.dim;
         let mut new_self = self;
         new_self.dim.min = min;
         new_self.dim.max = max;
         match d {
+            1 => get_1d_scaled_noise!(&NoiseType::Cellular2(new_self)),
             2 => get_2d_scaled_noise!(&NoiseType::Cellular2(new_self)),
             3 => get_3d_scaled_noise!(&NoiseType::Cellular2(new_self)),
+            4 => get_4d_scaled_noise!(&NoiseType::Cellular2(new_self)),
             _ => panic!("not implemented"),
         }
     }
@@ -516,6 +1059,11 @@ pub struct FbmSettings {
     lacunarity: f32,
     gain: f32,
     octaves: u8,
+    base: BaseNoise,
+    variation: OctaveVariation,
+    normalize: bool,
+    period: Option<[f32; 3]>,
+    looping: Option<f32>,
 }
 
 impl DimensionalBeing for FbmSettings {
@@ -535,6 +1083,11 @@ impl FbmSettings {
             lacunarity: 0.5,
             gain: 2.0,
             octaves: 3,
+            base: BaseNoise::Simplex,
+            variation: OctaveVariation::NONE,
+            normalize: false,
+            period: None,
+            looping: None,
         }
     }
 
@@ -543,6 +1096,54 @@ impl FbmSettings {
         self
     }
 
+    /// Maps the sample positions of the block, after its offset, through
+    /// `matrix` before the frequency is applied, for rotated, sheared or
+    /// tilted blocks. Its columns are the steps between neighbouring samples
+    /// along each axis, so sample spacing is set here and feature size by the
+    /// frequency. Takes a 1x1 matrix up to one as large as the dimensions of
+    /// the noise, the rest stays identity. A 3d block one sample deep with a
+    /// 3x3 rotation samples a tilted plane. Points given to `get_2d_noise_at`
+    /// and friends go through the same transform. Tileable noise can't be
+    /// transformed.
+    pub fn with_transform<const N: usize>(&mut self, matrix: [[f32; N]; N]) -> &mut FbmSettings {
+        if self.period.is_some() {
+            panic!("tileable noise can't be transformed");
+        }
+        self.dim.set_transform(matrix);
+        self
+    }
+
+    /// Makes 2d noise tile, repeating every `period_x` and `period_y` samples,
+    /// by sampling 4d noise on a torus. A block that size wraps exactly.
+    /// Looping noise already uses the 4d noise for time and can't tile.
+    pub fn with_period(&mut self, period_x: f32, period_y: f32) -> &mut FbmSettings {
+        if self.looping.is_some() {
+            panic!("looping noise can't be tileable");
+        }
+        self.dim.check_period(&[period_x, period_y]);
+        self.period = Some([period_x, period_y, 0.0]);
+        self
+    }
+
+    /// Makes 3d noise tile, repeating every `period_x`, `period_y` and `period_z`
+    /// samples. The frequency is adjusted so a whole number of lattice cells
+    /// fit in each period, a multiple of 3 for a simplex base. Needs a simplex
+    /// or Perlin base, and octaves can't be rotated.
+    pub fn with_period_3d(
+        &mut self,
+        period_x: f32,
+        period_y: f32,
+        period_z: f32,
+    ) -> &mut FbmSettings {
+        if self.looping.is_some() {
+            panic!("looping noise can't be tileable");
+        }
+        self.dim.check_period(&[period_x, period_y, period_z]);
+        self.period = Some([period_x, period_y, period_z]);
+        check_periodic_fractal(&self.dim, self.period, self.base, self.variation);
+        self
+    }
+
     pub fn with_freq(&mut self, freq: f32) -> &mut FbmSettings {
         self.freq_x = freq;
         self.freq_y = freq;
@@ -593,6 +1194,33 @@ impl FbmSettings {
         self
     }
 
+    /// Gives every octave after the first its own seed, off by default
+    pub fn with_octave_seeds(&mut self, seeds: bool) -> &mut FbmSettings {
+        self.variation.seeds = seeds;
+        self
+    }
+
+    /// Rotates and shifts every octave after the first, off by default
+    pub fn with_octave_rotation(&mut self, rotation: OctaveRotation) -> &mut FbmSettings {
+        self.variation.rotation = rotation;
+        check_periodic_fractal(&self.dim, self.period, self.base, self.variation);
+        self
+    }
+
+    /// Divides the output by the total amplitude of the octaves, so it stays
+    /// within -1 to 1 whatever the octaves and gain. Off by default.
+    pub fn with_normalize(&mut self, normalize: bool) -> &mut FbmSettings {
+        self.normalize = normalize;
+        self
+    }
+
+    /// Sets the noise summed over each octave, simplex by default
+    pub fn with_base(&mut self, base: BaseNoise) -> &mut FbmSettings {
+        self.base = base;
+        check_periodic_fractal(&self.dim, self.period, self.base, self.variation);
+        self
+    }
+
     /// If you want to call noise functions by hand, call wrap on the settings
     /// to get back a NoiseType to call the noise functions with
     pub fn wrap(self) -> NoiseType {
@@ -612,6 +1240,36 @@ impl FbmSettings {
         }
     }
 
+    /// Generate a chunk of noise into `out`, which must hold exactly one
+    /// value per sample, instead of allocating. Returns the min and max value
+    pub fn fill(self, out: &mut [f32]) -> (f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            1 => fill_noise!(fill_1d_noise, &NoiseType::Fbm(self), out),
+            2 => fill_noise!(fill_2d_noise, &NoiseType::Fbm(self), out),
+            3 => fill_noise!(fill_3d_noise, &NoiseType::Fbm(self), out),
+            4 => fill_noise!(fill_4d_noise, &NoiseType::Fbm(self), out),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Like `fill`, but rows start `pitch` values apart in `out`, to write
+    /// into part of a larger image
+    pub fn fill_pitched(self, out: &mut [f32], pitch: usize) -> (f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            1 => {
+                let width = self.dim.width;
+                noise_helpers::check_1d_pitched_len(out.len(), width, pitch);
+                fill_noise!(fill_1d_noise, &NoiseType::Fbm(self), &mut out[..width])
+            }
+            2 => fill_noise!(fill_2d_noise_pitched, &NoiseType::Fbm(self), out, pitch),
+            3 => fill_noise!(fill_3d_noise_pitched, &NoiseType::Fbm(self), out, pitch),
+            4 => fill_noise!(fill_4d_noise_pitched, &NoiseType::Fbm(self), out, pitch),
+            _ => panic!("not implemented"),
+        }
+    }
+
     /// Generate a chunk of noise with values scaled from min to max
     pub fn generate_scaled(self, min: f32, max: f32) -> Vec<f32> {
         let d = self.dim.dim;
@@ -626,10 +1284,43 @@ impl FbmSettings {
             _ => panic!("not implemented"),
         }
     }
+
+    /// Generate a chunk of normalized noise, mapped from -1 to 1 to min to max.
+    /// Unlike `generate_scaled` the mapping doesn't depend on the values in
+    /// the chunk, so neighbouring chunks line up. The base noise range is
+    /// approximate, so values can stray slightly outside min to max.
+    pub fn generate_scaled_fixed(self, min: f32, max: f32) -> Vec<f32> {
+        let mut new_self = self;
+        new_self.normalize = true;
+        let (mut noise, _, _) = new_self.generate();
+        scale_fixed(&mut noise, -1.0, 1.0, min, max);
+        noise
+    }
+
+    /// Linear map that brings the raw output to -1 to 1, if normalizing
+    fn normalization(&self) -> Option<(f32, f32)> {
+        if !self.normalize {
+            return None;
+        }
+        Some((
+            self.base.scale(kernel_dim(
+                self.dim.dim,
+                self.period.is_some() || self.looping.is_some(),
+            )) / fractal_amplitude(
+                self.base,
+                self.dim.dim,
+                self.variation,
+                self.gain,
+                self.octaves,
+            ),
+            0.0,
+        ))
+    }
 }
 
+/// Billow noise, the sum of `2|n| - 1` over simplex octaves, puffy like clouds
 #[derive(Copy, Clone)]
-pub struct RidgeSettings {
+pub struct BillowSettings {
     dim: NoiseDimensions,
     freq_x: f32,
     freq_y: f32,
@@ -638,17 +1329,20 @@ pub struct RidgeSettings {
     lacunarity: f32,
     gain: f32,
     octaves: u8,
+    variation: OctaveVariation,
+    normalize: bool,
+    period: Option<[f32; 3]>,
 }
 
-impl DimensionalBeing for RidgeSettings {
+impl DimensionalBeing for BillowSettings {
     fn get_dimensions(&self) -> NoiseDimensions {
-        return self.dim;
+        self.dim
     }
 }
 
-impl RidgeSettings {
-    pub fn default(dim: NoiseDimensions) -> RidgeSettings {
-        RidgeSettings {
+impl BillowSettings {
+    pub fn default(dim: NoiseDimensions) -> BillowSettings {
+        BillowSettings {
             dim,
             freq_x: 0.02,
             freq_y: 0.02,
@@ -657,15 +1351,52 @@ impl RidgeSettings {
             lacunarity: 0.5,
             gain: 2.0,
             octaves: 3,
+            variation: OctaveVariation::NONE,
+            normalize: false,
+            period: None,
         }
     }
 
-    pub fn with_seed(&mut self, seed: i32) -> &mut RidgeSettings {
+    pub fn with_seed(&mut self, seed: i32) -> &mut BillowSettings {
         self.dim.seed = seed;
         self
     }
 
-    pub fn with_freq(&mut self, freq: f32) -> &mut RidgeSettings {
+    /// Maps the sample positions of the block through `matrix`, see
+    /// `FbmSettings::with_transform`
+    pub fn with_transform<const N: usize>(&mut self, matrix: [[f32; N]; N]) -> &mut BillowSettings {
+        if self.period.is_some() {
+            panic!("tileable noise can't be transformed");
+        }
+        self.dim.set_transform(matrix);
+        self
+    }
+
+    /// Makes 2d noise tile, repeating every `period_x` and `period_y` samples,
+    /// by sampling 4d noise on a torus. A block that size wraps exactly.
+    pub fn with_period(&mut self, period_x: f32, period_y: f32) -> &mut BillowSettings {
+        self.dim.check_period(&[period_x, period_y]);
+        self.period = Some([period_x, period_y, 0.0]);
+        self
+    }
+
+    /// Makes 3d noise tile, repeating every `period_x`, `period_y` and `period_z`
+    /// samples. The frequency is adjusted so a whole multiple of 3 lattice
+    /// cells fit in each period, the smallest period of the simplex lattice.
+    /// Octaves can't be rotated.
+    pub fn with_period_3d(
+        &mut self,
+        period_x: f32,
+        period_y: f32,
+        period_z: f32,
+    ) -> &mut BillowSettings {
+        self.dim.check_period(&[period_x, period_y, period_z]);
+        self.period = Some([period_x, period_y, period_z]);
+        check_periodic_fractal(&self.dim, self.period, BaseNoise::Simplex, self.variation);
+        self
+    }
+
+    pub fn with_freq(&mut self, freq: f32) -> &mut BillowSettings {
         self.freq_x = freq;
         self.freq_y = freq;
         self.freq_z = freq;
@@ -673,13 +1404,13 @@ impl RidgeSettings {
         self
     }
 
-    pub fn with_freq_2d(&mut self, freq_x: f32, freq_y: f32) -> &mut RidgeSettings {
+    pub fn with_freq_2d(&mut self, freq_x: f32, freq_y: f32) -> &mut BillowSettings {
         self.freq_x = freq_x;
         self.freq_y = freq_y;
         self
     }
 
-    pub fn with_freq_3d(&mut self, freq_x: f32, freq_y: f32, freq_z: f32) -> &mut RidgeSettings {
+    pub fn with_freq_3d(&mut self, freq_x: f32, freq_y: f32, freq_z: f32) -> &mut BillowSettings {
         self.freq_x = freq_x;
         self.freq_y = freq_y;
         self.freq_z = freq_z;
@@ -692,7 +1423,7 @@ impl RidgeSettings {
         freq_y: f32,
         freq_z: f32,
         freq_w: f32,
-    ) -> &mut RidgeSettings {
+    ) -> &mut BillowSettings {
         self.freq_x = freq_x;
         self.freq_y = freq_y;
         self.freq_z = freq_z;
@@ -700,35 +1431,86 @@ impl RidgeSettings {
         self
     }
 
-    pub fn with_lacunarity(&mut self, lacunarity: f32) -> &mut RidgeSettings {
+    pub fn with_lacunarity(&mut self, lacunarity: f32) -> &mut BillowSettings {
         self.lacunarity = lacunarity;
         self
     }
 
-    pub fn with_gain(&mut self, gain: f32) -> &mut RidgeSettings {
+    pub fn with_gain(&mut self, gain: f32) -> &mut BillowSettings {
         self.gain = gain;
         self
     }
 
-    pub fn with_octaves(&mut self, octaves: u8) -> &mut RidgeSettings {
+    pub fn with_octaves(&mut self, octaves: u8) -> &mut BillowSettings {
         self.octaves = octaves;
         self
     }
 
+    /// Gives every octave after the first its own seed, off by default
+    pub fn with_octave_seeds(&mut self, seeds: bool) -> &mut BillowSettings {
+        self.variation.seeds = seeds;
+        self
+    }
+
+    /// Rotates and shifts every octave after the first, off by default
+    pub fn with_octave_rotation(&mut self, rotation: OctaveRotation) -> &mut BillowSettings {
+        self.variation.rotation = rotation;
+        check_periodic_fractal(&self.dim, self.period, BaseNoise::Simplex, self.variation);
+        self
+    }
+
+    /// Divides the output by the total amplitude of the octaves, so it stays
+    /// within -1 to 1 whatever the octaves and gain. Off by default.
+    pub fn with_normalize(&mut self, normalize: bool) -> &mut BillowSettings {
+        self.normalize = normalize;
+        self
+    }
+
     /// If you want to call noise functions by hand, call wrap on the settings
     /// to get back a NoiseType to call the noise functions with
     pub fn wrap(self) -> NoiseType {
-        NoiseType::Ridge(self)
+        NoiseType::Billow(self)
     }
+
     /// Generate a chunk of noise based on your settings, and the min and max value
     /// generated, so you can scale it as you wish
     pub fn generate(self) -> (Vec<f32>, f32, f32) {
         let d = self.dim.dim;
         match d {
-            1 => get_1d_noise!(&NoiseType::Ridge(self)),
-            2 => get_2d_noise!(&NoiseType::Ridge(self)),
-            3 => get_3d_noise!(&NoiseType::Ridge(self)),
-            4 => get_4d_noise!(&NoiseType::Ridge(self)),
+            1 => get_1d_noise!(&NoiseType::Billow(self)),
+            2 => get_2d_noise!(&NoiseType::Billow(self)),
+            3 => get_3d_noise!(&NoiseType::Billow(self)),
+            4 => get_4d_noise!(&NoiseType::Billow(self)),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Generate a chunk of noise into `out`, which must hold exactly one
+    /// value per sample, instead of allocating. Returns the min and max value
+    pub fn fill(self, out: &mut [f32]) -> (f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            1 => fill_noise!(fill_1d_noise, &NoiseType::Billow(self), out),
+            2 => fill_noise!(fill_2d_noise, &NoiseType::Billow(self), out),
+            3 => fill_noise!(fill_3d_noise, &NoiseType::Billow(self), out),
+            4 => fill_noise!(fill_4d_noise, &NoiseType::Billow(self), out),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Like `fill`, but rows start `pitch` values apart in `out`, to write
+    /// into part of a larger image
+    pub fn fill_pitched(self, out: &mut [f32], pitch: usize) -> (f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            1 => {
+                let width = self.dim.width;
+                noise_helpers::check_1d_pitched_len(out.len(), width, pitch);
+                fill_noise!(fill_1d_noise, &NoiseType::Billow(self), &mut out[..width])
+            }
+            2 => fill_noise!(fill_2d_noise_pitched, &NoiseType::Billow(self), out, pitch),
+            3 => fill_noise!(fill_3d_noise_pitched, &NoiseType::Billow(self), out, pitch),
+            4 => fill_noise!(fill_4d_noise_pitched, &NoiseType::Billow(self), out, pitch),
             _ => panic!("not implemented"),
         }
     }
@@ -740,17 +1522,38 @@ impl RidgeSettings {
         new_self.dim.min = min;
         new_self.dim.max = max;
         match d {
-            1 => get_1d_scaled_noise!(&NoiseType::Ridge(new_self)),
-            2 => get_2d_scaled_noise!(&NoiseType::Ridge(new_self)),
-            3 => get_3d_scaled_noise!(&NoiseType::Ridge(new_self)),
-            4 => get_4d_scaled_noise!(&NoiseType::Ridge(new_self)),
+            1 => get_1d_scaled_noise!(&NoiseType::Billow(new_self)),
+            2 => get_2d_scaled_noise!(&NoiseType::Billow(new_self)),
+            3 => get_3d_scaled_noise!(&NoiseType::Billow(new_self)),
+            4 => get_4d_scaled_noise!(&NoiseType::Billow(new_self)),
             _ => panic!("not implemented"),
         }
     }
+
+    /// Generate a chunk of normalized noise, mapped from -1 to 1 to min to max.
+    /// Unlike `generate_scaled` the mapping doesn't depend on the values in
+    /// the chunk, so neighbouring chunks line up. The base noise range is
+    /// approximate, so values can stray slightly outside min to max.
+    pub fn generate_scaled_fixed(self, min: f32, max: f32) -> Vec<f32> {
+        let mut new_self = self;
+        new_self.normalize = true;
+        let (mut noise, _, _) = new_self.generate();
+        scale_fixed(&mut noise, -1.0, 1.0, min, max);
+        noise
+    }
+
+    /// Linear map that brings the raw output to -1 to 1, if normalizing
+    fn normalization(&self) -> Option<(f32, f32)> {
+        if !self.normalize {
+            return None;
+        }
+        // The octaves are already scaled to -1 to 1
+        Some((1.0 / total_amplitude(self.gain, self.octaves), 0.0))
+    }
 }
 
 #[derive(Copy, Clone)]
-pub struct TurbulenceSettings {
+pub struct RidgeSettings {
     dim: NoiseDimensions,
     freq_x: f32,
     freq_y: f32,
@@ -759,17 +1562,21 @@ pub struct TurbulenceSettings {
     lacunarity: f32,
     gain: f32,
     octaves: u8,
+    base: BaseNoise,
+    variation: OctaveVariation,
+    normalize: bool,
+    period: Option<[f32; 3]>,
 }
 
-impl DimensionalBeing for TurbulenceSettings {
+impl DimensionalBeing for RidgeSettings {
     fn get_dimensions(&self) -> NoiseDimensions {
         return self.dim;
     }
 }
 
-impl TurbulenceSettings {
-    pub fn default(dim: NoiseDimensions) -> TurbulenceSettings {
-        TurbulenceSettings {
+impl RidgeSettings {
+    pub fn default(dim: NoiseDimensions) -> RidgeSettings {
+        RidgeSettings {
             dim,
             freq_x: 0.02,
             freq_y: 0.02,
@@ -778,15 +1585,53 @@ impl TurbulenceSettings {
             lacunarity: 0.5,
             gain: 2.0,
             octaves: 3,
+            base: BaseNoise::Simplex,
+            variation: OctaveVariation::NONE,
+            normalize: false,
+            period: None,
         }
     }
 
-    pub fn with_seed(&mut self, seed: i32) -> &mut TurbulenceSettings {
+    pub fn with_seed(&mut self, seed: i32) -> &mut RidgeSettings {
         self.dim.seed = seed;
         self
     }
 
-    pub fn with_freq(&mut self, freq: f32) -> &mut TurbulenceSettings {
+    /// Maps the sample positions of the block through `matrix`, see
+    /// `FbmSettings::with_transform`
+    pub fn with_transform<const N: usize>(&mut self, matrix: [[f32; N]; N]) -> &mut RidgeSettings {
+        if self.period.is_some() {
+            panic!("tileable noise can't be transformed");
+        }
+        self.dim.set_transform(matrix);
+        self
+    }
+
+    /// Makes 2d noise tile, repeating every `period_x` and `period_y` samples,
+    /// by sampling 4d noise on a torus. A block that size wraps exactly.
+    pub fn with_period(&mut self, period_x: f32, period_y: f32) -> &mut RidgeSettings {
+        self.dim.check_period(&[period_x, period_y]);
+        self.period = Some([period_x, period_y, 0.0]);
+        self
+    }
+
+    /// Makes 3d noise tile, repeating every `period_x`, `period_y` and `period_z`
+    /// samples. The frequency is adjusted so a whole number of lattice cells
+    /// fit in each period, a multiple of 3 for a simplex base. Needs a simplex
+    /// or Perlin base, and octaves can't be rotated.
+    pub fn with_period_3d(
+        &mut self,
+        period_x: f32,
+        period_y: f32,
+        period_z: f32,
+    ) -> &mut RidgeSettings {
+        self.dim.check_period(&[period_x, period_y, period_z]);
+        self.period = Some([period_x, period_y, period_z]);
+        check_periodic_fractal(&self.dim, self.period, self.base, self.variation);
+        self
+    }
+
+    pub fn with_freq(&mut self, freq: f32) -> &mut RidgeSettings {
         self.freq_x = freq;
         self.freq_y = freq;
         self.freq_z = freq;
@@ -794,18 +1639,13 @@ impl TurbulenceSettings {
         self
     }
 
-    pub fn with_freq_2d(&mut self, freq_x: f32, freq_y: f32) -> &mut TurbulenceSettings {
+    pub fn with_freq_2d(&mut self, freq_x: f32, freq_y: f32) -> &mut RidgeSettings {
         self.freq_x = freq_x;
         self.freq_y = freq_y;
         self
     }
 
-    pub fn with_freq_3d(
-        &mut self,
-        freq_x: f32,
-        freq_y: f32,
-        freq_z: f32,
-    ) -> &mut TurbulenceSettings {
+    pub fn with_freq_3d(&mut self, freq_x: f32, freq_y: f32, freq_z: f32) -> &mut RidgeSettings {
         self.freq_x = freq_x;
         self.freq_y = freq_y;
         self.freq_z = freq_z;
@@ -818,7 +1658,7 @@ impl TurbulenceSettings {
         freq_y: f32,
         freq_z: f32,
         freq_w: f32,
-    ) -> &mut TurbulenceSettings {
+    ) -> &mut RidgeSettings {
         self.freq_x = freq_x;
         self.freq_y = freq_y;
         self.freq_z = freq_z;
@@ -826,36 +1666,92 @@ impl TurbulenceSettings {
         self
     }
 
-    pub fn with_lacunarity(&mut self, lacunarity: f32) -> &mut TurbulenceSettings {
+    pub fn with_lacunarity(&mut self, lacunarity: f32) -> &mut RidgeSettings {
         self.lacunarity = lacunarity;
         self
     }
 
-    pub fn with_gain(&mut self, gain: f32) -> &mut TurbulenceSettings {
+    pub fn with_gain(&mut self, gain: f32) -> &mut RidgeSettings {
         self.gain = gain;
         self
     }
 
-    pub fn with_octaves(&mut self, octaves: u8) -> &mut TurbulenceSettings {
+    pub fn with_octaves(&mut self, octaves: u8) -> &mut RidgeSettings {
         self.octaves = octaves;
         self
     }
 
+    /// Gives every octave after the first its own seed, off by default
+    pub fn with_octave_seeds(&mut self, seeds: bool) -> &mut RidgeSettings {
+        self.variation.seeds = seeds;
+        self
+    }
+
+    /// Rotates and shifts every octave after the first, off by default
+    pub fn with_octave_rotation(&mut self, rotation: OctaveRotation) -> &mut RidgeSettings {
+        self.variation.rotation = rotation;
+        check_periodic_fractal(&self.dim, self.period, self.base, self.variation);
+        self
+    }
+
+    /// Divides the output by the total amplitude of the octaves, so it stays
+    /// within 0 to 1 whatever the octaves and gain. Off by default.
+    pub fn with_normalize(&mut self, normalize: bool) -> &mut RidgeSettings {
+        self.normalize = normalize;
+        self
+    }
+
+    /// Sets the noise summed over each octave, simplex by default
+    pub fn with_base(&mut self, base: BaseNoise) -> &mut RidgeSettings {
+        self.base = base;
+        check_periodic_fractal(&self.dim, self.period, self.base, self.variation);
+        self
+    }
+
     /// If you want to call noise functions by hand, call wrap on the settings
     /// to get back a NoiseType to call the noise functions with
     pub fn wrap(self) -> NoiseType {
-        NoiseType::Turbulence(self)
+        NoiseType::Ridge(self)
     }
-
     /// Generate a chunk of noise based on your settings, and the min and max value
     /// generated, so you can scale it as you wish
     pub fn generate(self) -> (Vec<f32>, f32, f32) {
         let d = self.dim.dim;
         match d {
-            1 => get_1d_noise!(&NoiseType::Turbulence(self)),
-            2 => get_2d_noise!(&NoiseType::Turbulence(self)),
-            3 => get_3d_noise!(&NoiseType::Turbulence(self)),
-            4 => get_4d_noise!(&NoiseType::Turbulence(self)),
+            1 => get_1d_noise!(&NoiseType::Ridge(self)),
+            2 => get_2d_noise!(&NoiseType::Ridge(self)),
+            3 => get_3d_noise!(&NoiseType::Ridge(self)),
+            4 => get_4d_noise!(&NoiseType::Ridge(self)),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Generate a chunk of noise into `out`, which must hold exactly one
+    /// value per sample, instead of allocating. Returns the min and max value
+    pub fn fill(self, out: &mut [f32]) -> (f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            1 => fill_noise!(fill_1d_noise, &NoiseType::Ridge(self), out),
+            2 => fill_noise!(fill_2d_noise, &NoiseType::Ridge(self), out),
+            3 => fill_noise!(fill_3d_noise, &NoiseType::Ridge(self), out),
+            4 => fill_noise!(fill_4d_noise, &NoiseType::Ridge(self), out),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Like `fill`, but rows start `pitch` values apart in `out`, to write
+    /// into part of a larger image
+    pub fn fill_pitched(self, out: &mut [f32], pitch: usize) -> (f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            1 => {
+                let width = self.dim.width;
+                noise_helpers::check_1d_pitched_len(out.len(), width, pitch);
+                fill_noise!(fill_1d_noise, &NoiseType::Ridge(self), &mut out[..width])
+            }
+            2 => fill_noise!(fill_2d_noise_pitched, &NoiseType::Ridge(self), out, pitch),
+            3 => fill_noise!(fill_3d_noise_pitched, &NoiseType::Ridge(self), out, pitch),
+            4 => fill_noise!(fill_4d_noise_pitched, &NoiseType::Ridge(self), out, pitch),
             _ => panic!("not implemented"),
         }
     }
@@ -867,47 +1763,129 @@ impl TurbulenceSettings {
         new_self.dim.min = min;
         new_self.dim.max = max;
         match d {
-            1 => get_1d_scaled_noise!(&NoiseType::Turbulence(new_self)),
-            2 => get_2d_scaled_noise!(&NoiseType::Turbulence(new_self)),
-            3 => get_3d_scaled_noise!(&NoiseType::Turbulence(new_self)),
-            4 => get_4d_scaled_noise!(&NoiseType::Turbulence(new_self)),
+            1 => get_1d_scaled_noise!(&NoiseType::Ridge(new_self)),
+            2 => get_2d_scaled_noise!(&NoiseType::Ridge(new_self)),
+            3 => get_3d_scaled_noise!(&NoiseType::Ridge(new_self)),
+            4 => get_4d_scaled_noise!(&NoiseType::Ridge(new_self)),
             _ => panic!("not implemented"),
         }
     }
+
+    /// Generate a chunk of normalized noise, mapped from 0 to 1 to min to max.
+    /// Unlike `generate_scaled` the mapping doesn't depend on the values in
+    /// the chunk, so neighbouring chunks line up. The base noise range is
+    /// approximate, so values can stray slightly outside min to max.
+    pub fn generate_scaled_fixed(self, min: f32, max: f32) -> Vec<f32> {
+        let mut new_self = self;
+        new_self.normalize = true;
+        let (mut noise, _, _) = new_self.generate();
+        scale_fixed(&mut noise, 0.0, 1.0, min, max);
+        noise
+    }
+
+    /// Linear map that brings the raw output to 0 to 1, if normalizing
+    fn normalization(&self) -> Option<(f32, f32)> {
+        if !self.normalize {
+            return None;
+        }
+        // Each octave is 1 - |n| * amp, so shift by the sum of the ones
+        let multiplier = self
+            .base
+            .scale(kernel_dim(self.dim.dim, self.period.is_some()))
+            / fractal_amplitude(
+                self.base,
+                self.dim.dim,
+                self.variation,
+                self.gain,
+                self.octaves,
+            );
+        Some((multiplier, 1.0 - multiplier * self.octaves.max(1) as f32))
+    }
 }
 
 #[derive(Copy, Clone)]
-pub struct GradientSettings {
+pub struct TurbulenceSettings {
     dim: NoiseDimensions,
     freq_x: f32,
     freq_y: f32,
     freq_z: f32,
     freq_w: f32,
+    lacunarity: f32,
+    gain: f32,
+    octaves: u8,
+    base: BaseNoise,
+    variation: OctaveVariation,
+    normalize: bool,
+    period: Option<[f32; 3]>,
 }
 
-impl DimensionalBeing for GradientSettings {
+impl DimensionalBeing for TurbulenceSettings {
     fn get_dimensions(&self) -> NoiseDimensions {
         return self.dim;
     }
 }
 
-impl GradientSettings {
-    pub fn default(dim: NoiseDimensions) -> GradientSettings {
-        GradientSettings {
+impl TurbulenceSettings {
+    pub fn default(dim: NoiseDimensions) -> TurbulenceSettings {
+        TurbulenceSettings {
             dim,
             freq_x: 0.02,
             freq_y: 0.02,
             freq_z: 0.02,
             freq_w: 0.02,
+            lacunarity: 0.5,
+            gain: 2.0,
+            octaves: 3,
+            base: BaseNoise::Simplex,
+            variation: OctaveVariation::NONE,
+            normalize: false,
+            period: None,
         }
     }
 
-    pub fn with_seed(&mut self, seed: i32) -> &mut GradientSettings {
+    pub fn with_seed(&mut self, seed: i32) -> &mut TurbulenceSettings {
         self.dim.seed = seed;
         self
     }
 
-    pub fn with_freq(&mut self, freq: f32) -> &mut GradientSettings {
+    /// Maps the sample positions of the block through `matrix`, see
+    /// `FbmSettings::with_transform`
+    pub fn with_transform<const N: usize>(
+        &mut self,
+        matrix: [[f32; N]; N],
+    ) -> &mut TurbulenceSettings {
+        if self.period.is_some() {
+            panic!("tileable noise can't be transformed");
+        }
+        self.dim.set_transform(matrix);
+        self
+    }
+
+    /// Makes 2d noise tile, repeating every `period_x` and `period_y` samples,
+    /// by sampling 4d noise on a torus. A block that size wraps exactly.
+    pub fn with_period(&mut self, period_x: f32, period_y: f32) -> &mut TurbulenceSettings {
+        self.dim.check_period(&[period_x, period_y]);
+        self.period = Some([period_x, period_y, 0.0]);
+        self
+    }
+
+    /// Makes 3d noise tile, repeating every `period_x`, `period_y` and `period_z`
+    /// samples. The frequency is adjusted so a whole number of lattice cells
+    /// fit in each period, a multiple of 3 for a simplex base. Needs a simplex
+    /// or Perlin base, and octaves can't be rotated.
+    pub fn with_period_3d(
+        &mut self,
+        period_x: f32,
+        period_y: f32,
+        period_z: f32,
+    ) -> &mut TurbulenceSettings {
+        self.dim.check_period(&[period_x, period_y, period_z]);
+        self.period = Some([period_x, period_y, period_z]);
+        check_periodic_fractal(&self.dim, self.period, self.base, self.variation);
+        self
+    }
+
+    pub fn with_freq(&mut self, freq: f32) -> &mut TurbulenceSettings {
         self.freq_x = freq;
         self.freq_y = freq;
         self.freq_z = freq;
@@ -915,13 +1893,18 @@ impl GradientSettings {
         self
     }
 
-    pub fn with_freq_2d(&mut self, freq_x: f32, freq_y: f32) -> &mut GradientSettings {
+    pub fn with_freq_2d(&mut self, freq_x: f32, freq_y: f32) -> &mut TurbulenceSettings {
         self.freq_x = freq_x;
         self.freq_y = freq_y;
         self
     }
 
-    pub fn with_freq_3d(&mut self, freq_x: f32, freq_y: f32, freq_z: f32) -> &mut GradientSettings {
+    pub fn with_freq_3d(
+        &mut self,
+        freq_x: f32,
+        freq_y: f32,
+        freq_z: f32,
+    ) -> &mut TurbulenceSettings {
         self.freq_x = freq_x;
         self.freq_y = freq_y;
         self.freq_z = freq_z;
@@ -934,7 +1917,7 @@ impl GradientSettings {
         freq_y: f32,
         freq_z: f32,
         freq_w: f32,
-    ) -> &mut GradientSettings {
+    ) -> &mut TurbulenceSettings {
         self.freq_x = freq_x;
         self.freq_y = freq_y;
         self.freq_z = freq_z;
@@ -942,10 +1925,52 @@ impl GradientSettings {
         self
     }
 
+    pub fn with_lacunarity(&mut self, lacunarity: f32) -> &mut TurbulenceSettings {
+        self.lacunarity = lacunarity;
+        self
+    }
+
+    pub fn with_gain(&mut self, gain: f32) -> &mut TurbulenceSettings {
+        self.gain = gain;
+        self
+    }
+
+    pub fn with_octaves(&mut self, octaves: u8) -> &mut TurbulenceSettings {
+        self.octaves = octaves;
+        self
+    }
+
+    /// Gives every octave after the first its own seed, off by default
+    pub fn with_octave_seeds(&mut self, seeds: bool) -> &mut TurbulenceSettings {
+        self.variation.seeds = seeds;
+        self
+    }
+
+    /// Rotates and shifts every octave after the first, off by default
+    pub fn with_octave_rotation(&mut self, rotation: OctaveRotation) -> &mut TurbulenceSettings {
+        self.variation.rotation = rotation;
+        check_periodic_fractal(&self.dim, self.period, self.base, self.variation);
+        self
+    }
+
+    /// Divides the output by the total amplitude of the octaves, so it stays
+    /// within 0 to 1 whatever the octaves and gain. Off by default.
+    pub fn with_normalize(&mut self, normalize: bool) -> &mut TurbulenceSettings {
+        self.normalize = normalize;
+        self
+    }
+
+    /// Sets the noise summed over each octave, simplex by default
+    pub fn with_base(&mut self, base: BaseNoise) -> &mut TurbulenceSettings {
+        self.base = base;
+        check_periodic_fractal(&self.dim, self.period, self.base, self.variation);
+        self
+    }
+
     /// If you want to call noise functions by hand, call wrap on the settings
     /// to get back a NoiseType to call the noise functions with
     pub fn wrap(self) -> NoiseType {
-        NoiseType::Gradient(self)
+        NoiseType::Turbulence(self)
     }
 
     /// Generate a chunk of noise based on your settings, and the min and max value
@@ -953,10 +1978,59 @@ impl GradientSettings {
     pub fn generate(self) -> (Vec<f32>, f32, f32) {
         let d = self.dim.dim;
         match d {
-            1 => get_1d_noise!(&NoiseType::Gradient(self)),
-            2 => get_2d_noise!(&NoiseType::Gradient(self)),
-            3 => get_3d_noise!(&NoiseType::Gradient(self)),
-            4 => get_4d_noise!(&NoiseType::Gradient(self)),
+            1 => get_1d_noise!(&NoiseType::Turbulence(self)),
+            2 => get_2d_noise!(&NoiseType::Turbulence(self)),
+            3 => get_3d_noise!(&NoiseType::Turbulence(self)),
+            4 => get_4d_noise!(&NoiseType::Turbulence(self)),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Generate a chunk of noise into `out`, which must hold exactly one
+    /// value per sample, instead of allocating. Returns the min and max value
+    pub fn fill(self, out: &mut [f32]) -> (f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            1 => fill_noise!(fill_1d_noise, &NoiseType::Turbulence(self), out),
+            2 => fill_noise!(fill_2d_noise, &NoiseType::Turbulence(self), out),
+            3 => fill_noise!(fill_3d_noise, &NoiseType::Turbulence(self), out),
+            4 => fill_noise!(fill_4d_noise, &NoiseType::Turbulence(self), out),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Like `fill`, but rows start `pitch` values apart in `out`, to write
+    /// into part of a larger image
+    pub fn fill_pitched(self, out: &mut [f32], pitch: usize) -> (f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            1 => {
+                let width = self.dim.width;
+                noise_helpers::check_1d_pitched_len(out.len(), width, pitch);
+                fill_noise!(
+                    fill_1d_noise,
+                    &NoiseType::Turbulence(self),
+                    &mut out[..width]
+                )
+            }
+            2 => fill_noise!(
+                fill_2d_noise_pitched,
+                &NoiseType::Turbulence(self),
+                out,
+                pitch
+            ),
+            3 => fill_noise!(
+                fill_3d_noise_pitched,
+                &NoiseType::Turbulence(self),
+                out,
+                pitch
+            ),
+            4 => fill_noise!(
+                fill_4d_noise_pitched,
+                &NoiseType::Turbulence(self),
+                out,
+                pitch
+            ),
             _ => panic!("not implemented"),
         }
     }
@@ -968,78 +2042,3211 @@ impl GradientSettings {
         new_self.dim.min = min;
         new_self.dim.max = max;
         match d {
-            1 => get_1d_scaled_noise!(&NoiseType::Gradient(new_self)),
-            2 => get_2d_scaled_noise!(&NoiseType::Gradient(new_self)),
-            3 => get_3d_scaled_noise!(&NoiseType::Gradient(new_self)),
-            4 => get_4d_scaled_noise!(&NoiseType::Gradient(new_self)),
+            1 => get_1d_scaled_noise!(&NoiseType::Turbulence(new_self)),
+            2 => get_2d_scaled_noise!(&NoiseType::Turbulence(new_self)),
+            3 => get_3d_scaled_noise!(&NoiseType::Turbulence(new_self)),
+            4 => get_4d_scaled_noise!(&NoiseType::Turbulence(new_self)),
             _ => panic!("not implemented"),
         }
     }
+
+    /// Generate a chunk of normalized noise, mapped from 0 to 1 to min to max.
+    /// Unlike `generate_scaled` the mapping doesn't depend on the values in
+    /// the chunk, so neighbouring chunks line up. The base noise range is
+    /// approximate, so values can stray slightly outside min to max.
+    pub fn generate_scaled_fixed(self, min: f32, max: f32) -> Vec<f32> {
+        let mut new_self = self;
+        new_self.normalize = true;
+        let (mut noise, _, _) = new_self.generate();
+        scale_fixed(&mut noise, 0.0, 1.0, min, max);
+        noise
+    }
+
+    /// Linear map that brings the raw output to 0 to 1, if normalizing
+    fn normalization(&self) -> Option<(f32, f32)> {
+        if !self.normalize {
+            return None;
+        }
+        Some((
+            self.base
+                .scale(kernel_dim(self.dim.dim, self.period.is_some()))
+                / fractal_amplitude(
+                    self.base,
+                    self.dim.dim,
+                    self.variation,
+                    self.gain,
+                    self.octaves,
+                ),
+            0.0,
+        ))
+    }
 }
 
-/// Specifies what type of noise to generate and contains any relevant settings.
+/// Derivative damped fbm, after Inigo Quilez. Each octave is divided by
+/// `1 + damping * |d|^2`, with `d` the noise derivatives summed so far, which
+/// keeps detail off of steep slopes. 2D and 3D only
 #[derive(Copy, Clone)]
-pub enum NoiseType {
-    Fbm(FbmSettings),
-    Ridge(RidgeSettings),
-    Turbulence(TurbulenceSettings),
-    Gradient(GradientSettings),
-    Cellular(CellularSettings),
-    Cellular2(Cellular2Settings),
+pub struct IqFbmSettings {
+    dim: NoiseDimensions,
+    freq_x: f32,
+    freq_y: f32,
+    freq_z: f32,
+    lacunarity: f32,
+    gain: f32,
+    octaves: u8,
+    damping: f32,
 }
 
-impl DimensionalBeing for NoiseType {
+impl DimensionalBeing for IqFbmSettings {
     fn get_dimensions(&self) -> NoiseDimensions {
-        match self {
-            NoiseType::Fbm(s) => s.get_dimensions(),
-            NoiseType::Ridge(s) => s.get_dimensions(),
-            NoiseType::Turbulence(s) => s.get_dimensions(),
-            NoiseType::Gradient(s) => s.get_dimensions(),
-            NoiseType::Cellular(s) => s.get_dimensions(),
-            NoiseType::Cellular2(s) => s.get_dimensions(),
-        }
+        self.dim
     }
 }
 
-pub struct NoiseBuilder {}
-impl NoiseBuilder {
-    pub fn cellular_2d(width: usize, height: usize) -> CellularSettings {
-        let mut dim = NoiseDimensions::default(2);
+impl IqFbmSettings {
+    /// Unlike the other fractals, lacunarity defaults to 2.0 and gain to 0.5.
+    /// Panics unless `dim` is 2d or 3d
+    pub fn default(dim: NoiseDimensions) -> IqFbmSettings {
+        check_terrain_dim("iq fbm", &dim);
+        IqFbmSettings {
+            dim,
+            freq_x: 0.02,
+            freq_y: 0.02,
+            freq_z: 0.02,
+            lacunarity: 2.0,
+            gain: 0.5,
+            octaves: 3,
+            damping: 1.0,
+        }
+    }
+
+    pub fn with_seed(&mut self, seed: i32) -> &mut IqFbmSettings {
+        self.dim.seed = seed;
+        self
+    }
+
+    /// Maps the sample positions of the block through `matrix`, see
+    /// `FbmSettings::with_transform`
+    pub fn with_transform<const N: usize>(&mut self, matrix: [[f32; N]; N]) -> &mut IqFbmSettings {
+        self.dim.set_transform(matrix);
+        self
+    }
+
+    pub fn with_freq(&mut self, freq: f32) -> &mut IqFbmSettings {
+        self.freq_x = freq;
+        self.freq_y = freq;
+        self.freq_z = freq;
+        self
+    }
+
+    pub fn with_freq_2d(&mut self, freq_x: f32, freq_y: f32) -> &mut IqFbmSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self
+    }
+
+    pub fn with_freq_3d(&mut self, freq_x: f32, freq_y: f32, freq_z: f32) -> &mut IqFbmSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self.freq_z = freq_z;
+        self
+    }
+
+    pub fn with_lacunarity(&mut self, lacunarity: f32) -> &mut IqFbmSettings {
+        self.lacunarity = lacunarity;
+        self
+    }
+
+    pub fn with_gain(&mut self, gain: f32) -> &mut IqFbmSettings {
+        self.gain = gain;
+        self
+    }
+
+    pub fn with_octaves(&mut self, octaves: u8) -> &mut IqFbmSettings {
+        self.octaves = octaves;
+        self
+    }
+
+    /// How strongly steep slopes suppress the following octaves, 1.0 by default.
+    /// 0.0 gives plain fbm
+    pub fn with_damping(&mut self, damping: f32) -> &mut IqFbmSettings {
+        self.damping = damping;
+        self
+    }
+
+    /// If you want to call noise functions by hand, call wrap on the settings
+    /// to get back a NoiseType to call the noise functions with
+    pub fn wrap(self) -> NoiseType {
+        NoiseType::IqFbm(self)
+    }
+
+    /// Generate a chunk of noise based on your settings, and the min and max value
+    /// generated, so you can scale it as you wish
+    pub fn generate(self) -> (Vec<f32>, f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            2 => get_2d_noise!(&NoiseType::IqFbm(self)),
+            3 => get_3d_noise!(&NoiseType::IqFbm(self)),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Generate a chunk of noise into `out`, which must hold exactly one
+    /// value per sample, instead of allocating. Returns the min and max value
+    pub fn fill(self, out: &mut [f32]) -> (f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            2 => fill_noise!(fill_2d_noise, &NoiseType::IqFbm(self), out),
+            3 => fill_noise!(fill_3d_noise, &NoiseType::IqFbm(self), out),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Like `fill`, but rows start `pitch` values apart in `out`, to write
+    /// into part of a larger image
+    pub fn fill_pitched(self, out: &mut [f32], pitch: usize) -> (f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            2 => fill_noise!(fill_2d_noise_pitched, &NoiseType::IqFbm(self), out, pitch),
+            3 => fill_noise!(fill_3d_noise_pitched, &NoiseType::IqFbm(self), out, pitch),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Generate a chunk of noise with values scaled from min to max
+    pub fn generate_scaled(self, min: f32, max: f32) -> Vec<f32> {
+        let d = self.dim.dim;
+        let mut new_self = self;
+        new_self.dim.min = min;
+        new_self.dim.max = max;
+        match d {
+            2 => get_2d_scaled_noise!(&NoiseType::IqFbm(new_self)),
+            3 => get_3d_scaled_noise!(&NoiseType::IqFbm(new_self)),
+            _ => panic!("not implemented"),
+        }
+    }
+}
+
+/// Swiss turbulence, after Giliam de Carpentier. Ridged octaves, warped by
+/// the derivatives of the octaves before them and faded out in low areas.
+/// 2D and 3D only
+#[derive(Copy, Clone)]
+pub struct SwissSettings {
+    dim: NoiseDimensions,
+    freq_x: f32,
+    freq_y: f32,
+    freq_z: f32,
+    lacunarity: f32,
+    gain: f32,
+    octaves: u8,
+    warp: f32,
+}
+
+impl DimensionalBeing for SwissSettings {
+    fn get_dimensions(&self) -> NoiseDimensions {
+        self.dim
+    }
+}
+
+impl SwissSettings {
+    /// Unlike the other fractals, lacunarity defaults to 2.0 and gain to 0.5.
+    /// Panics unless `dim` is 2d or 3d
+    pub fn default(dim: NoiseDimensions) -> SwissSettings {
+        check_terrain_dim("swiss", &dim);
+        SwissSettings {
+            dim,
+            freq_x: 0.02,
+            freq_y: 0.02,
+            freq_z: 0.02,
+            lacunarity: 2.0,
+            gain: 0.5,
+            octaves: 3,
+            warp: 0.15,
+        }
+    }
+
+    pub fn with_seed(&mut self, seed: i32) -> &mut SwissSettings {
+        self.dim.seed = seed;
+        self
+    }
+
+    /// Maps the sample positions of the block through `matrix`, see
+    /// `FbmSettings::with_transform`
+    pub fn with_transform<const N: usize>(&mut self, matrix: [[f32; N]; N]) -> &mut SwissSettings {
+        self.dim.set_transform(matrix);
+        self
+    }
+
+    pub fn with_freq(&mut self, freq: f32) -> &mut SwissSettings {
+        self.freq_x = freq;
+        self.freq_y = freq;
+        self.freq_z = freq;
+        self
+    }
+
+    pub fn with_freq_2d(&mut self, freq_x: f32, freq_y: f32) -> &mut SwissSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self
+    }
+
+    pub fn with_freq_3d(&mut self, freq_x: f32, freq_y: f32, freq_z: f32) -> &mut SwissSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self.freq_z = freq_z;
+        self
+    }
+
+    pub fn with_lacunarity(&mut self, lacunarity: f32) -> &mut SwissSettings {
+        self.lacunarity = lacunarity;
+        self
+    }
+
+    pub fn with_gain(&mut self, gain: f32) -> &mut SwissSettings {
+        self.gain = gain;
+        self
+    }
+
+    pub fn with_octaves(&mut self, octaves: u8) -> &mut SwissSettings {
+        self.octaves = octaves;
+        self
+    }
+
+    /// How far the derivatives of earlier octaves push the coordinates of
+    /// later ones, 0.15 by default
+    pub fn with_warp(&mut self, warp: f32) -> &mut SwissSettings {
+        self.warp = warp;
+        self
+    }
+
+    /// If you want to call noise functions by hand, call wrap on the settings
+    /// to get back a NoiseType to call the noise functions with
+    pub fn wrap(self) -> NoiseType {
+        NoiseType::Swiss(self)
+    }
+
+    /// Generate a chunk of noise based on your settings, and the min and max value
+    /// generated, so you can scale it as you wish
+    pub fn generate(self) -> (Vec<f32>, f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            2 => get_2d_noise!(&NoiseType::Swiss(self)),
+            3 => get_3d_noise!(&NoiseType::Swiss(self)),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Generate a chunk of noise into `out`, which must hold exactly one
+    /// value per sample, instead of allocating. Returns the min and max value
+    pub fn fill(self, out: &mut [f32]) -> (f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            2 => fill_noise!(fill_2d_noise, &NoiseType::Swiss(self), out),
+            3 => fill_noise!(fill_3d_noise, &NoiseType::Swiss(self), out),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Like `fill`, but rows start `pitch` values apart in `out`, to write
+    /// into part of a larger image
+    pub fn fill_pitched(self, out: &mut [f32], pitch: usize) -> (f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            2 => fill_noise!(fill_2d_noise_pitched, &NoiseType::Swiss(self), out, pitch),
+            3 => fill_noise!(fill_3d_noise_pitched, &NoiseType::Swiss(self), out, pitch),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Generate a chunk of noise with values scaled from min to max
+    pub fn generate_scaled(self, min: f32, max: f32) -> Vec<f32> {
+        let d = self.dim.dim;
+        let mut new_self = self;
+        new_self.dim.min = min;
+        new_self.dim.max = max;
+        match d {
+            2 => get_2d_scaled_noise!(&NoiseType::Swiss(new_self)),
+            3 => get_3d_scaled_noise!(&NoiseType::Swiss(new_self)),
+            _ => panic!("not implemented"),
+        }
+    }
+}
+
+/// Jordan turbulence, after Giliam de Carpentier. Squared octaves, warped and
+/// damped by the derivatives of the octaves before them, with separate
+/// strengths for the first octave. 2D and 3D only
+#[derive(Copy, Clone)]
+pub struct JordanSettings {
+    dim: NoiseDimensions,
+    freq_x: f32,
+    freq_y: f32,
+    freq_z: f32,
+    lacunarity: f32,
+    gain: f32,
+    octaves: u8,
+    first_gain: f32,
+    first_warp: f32,
+    warp: f32,
+    first_damping: f32,
+    damping: f32,
+    damping_scale: f32,
+}
+
+impl DimensionalBeing for JordanSettings {
+    fn get_dimensions(&self) -> NoiseDimensions {
+        self.dim
+    }
+}
+
+impl JordanSettings {
+    /// Unlike the other fractals, lacunarity defaults to 2.0 and gain to 0.5.
+    /// Panics unless `dim` is 2d or 3d
+    pub fn default(dim: NoiseDimensions) -> JordanSettings {
+        check_terrain_dim("jordan", &dim);
+        JordanSettings {
+            dim,
+            freq_x: 0.02,
+            freq_y: 0.02,
+            freq_z: 0.02,
+            lacunarity: 2.0,
+            gain: 0.5,
+            octaves: 3,
+            first_gain: 0.8,
+            first_warp: 0.4,
+            warp: 0.35,
+            first_damping: 1.0,
+            damping: 0.8,
+            damping_scale: 1.0,
+        }
+    }
+
+    pub fn with_seed(&mut self, seed: i32) -> &mut JordanSettings {
+        self.dim.seed = seed;
+        self
+    }
+
+    /// Maps the sample positions of the block through `matrix`, see
+    /// `FbmSettings::with_transform`
+    pub fn with_transform<const N: usize>(&mut self, matrix: [[f32; N]; N]) -> &mut JordanSettings {
+        self.dim.set_transform(matrix);
+        self
+    }
+
+    pub fn with_freq(&mut self, freq: f32) -> &mut JordanSettings {
+        self.freq_x = freq;
+        self.freq_y = freq;
+        self.freq_z = freq;
+        self
+    }
+
+    pub fn with_freq_2d(&mut self, freq_x: f32, freq_y: f32) -> &mut JordanSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self
+    }
+
+    pub fn with_freq_3d(&mut self, freq_x: f32, freq_y: f32, freq_z: f32) -> &mut JordanSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self.freq_z = freq_z;
+        self
+    }
+
+    pub fn with_lacunarity(&mut self, lacunarity: f32) -> &mut JordanSettings {
+        self.lacunarity = lacunarity;
+        self
+    }
+
+    pub fn with_gain(&mut self, gain: f32) -> &mut JordanSettings {
+        self.gain = gain;
+        self
+    }
+
+    pub fn with_octaves(&mut self, octaves: u8) -> &mut JordanSettings {
+        self.octaves = octaves;
+        self
+    }
+
+    /// Amplitude of the second octave relative to the first, 0.8 by default.
+    /// Later octaves follow `with_gain`
+    pub fn with_first_gain(&mut self, first_gain: f32) -> &mut JordanSettings {
+        self.first_gain = first_gain;
+        self
+    }
+
+    /// How far the derivatives of the first and of the later octaves push the
+    /// coordinates of the octaves after them, 0.4 and 0.35 by default
+    pub fn with_warp(&mut self, first_warp: f32, warp: f32) -> &mut JordanSettings {
+        self.first_warp = first_warp;
+        self.warp = warp;
+        self
+    }
+
+    /// How much the derivatives of the first and of the later octaves count
+    /// towards damping, 1.0 and 0.8 by default, and how strong that damping
+    /// is, 1.0 by default
+    pub fn with_damping(
+        &mut self,
+        first_damping: f32,
+        damping: f32,
+        damping_scale: f32,
+    ) -> &mut JordanSettings {
+        self.first_damping = first_damping;
+        self.damping = damping;
+        self.damping_scale = damping_scale;
+        self
+    }
+
+    /// If you want to call noise functions by hand, call wrap on the settings
+    /// to get back a NoiseType to call the noise functions with
+    pub fn wrap(self) -> NoiseType {
+        NoiseType::Jordan(self)
+    }
+
+    /// Generate a chunk of noise based on your settings, and the min and max value
+    /// generated, so you can scale it as you wish
+    pub fn generate(self) -> (Vec<f32>, f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            2 => get_2d_noise!(&NoiseType::Jordan(self)),
+            3 => get_3d_noise!(&NoiseType::Jordan(self)),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Generate a chunk of noise into `out`, which must hold exactly one
+    /// value per sample, instead of allocating. Returns the min and max value
+    pub fn fill(self, out: &mut [f32]) -> (f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            2 => fill_noise!(fill_2d_noise, &NoiseType::Jordan(self), out),
+            3 => fill_noise!(fill_3d_noise, &NoiseType::Jordan(self), out),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Like `fill`, but rows start `pitch` values apart in `out`, to write
+    /// into part of a larger image
+    pub fn fill_pitched(self, out: &mut [f32], pitch: usize) -> (f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            2 => fill_noise!(fill_2d_noise_pitched, &NoiseType::Jordan(self), out, pitch),
+            3 => fill_noise!(fill_3d_noise_pitched, &NoiseType::Jordan(self), out, pitch),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Generate a chunk of noise with values scaled from min to max
+    pub fn generate_scaled(self, min: f32, max: f32) -> Vec<f32> {
+        let d = self.dim.dim;
+        let mut new_self = self;
+        new_self.dim.min = min;
+        new_self.dim.max = max;
+        match d {
+            2 => get_2d_scaled_noise!(&NoiseType::Jordan(new_self)),
+            3 => get_3d_scaled_noise!(&NoiseType::Jordan(new_self)),
+            _ => panic!("not implemented"),
+        }
+    }
+}
+
+/// Musgrave's hybrid multifractal over simplex noise. Each octave is weighted
+/// by the octaves before it, so valleys are smooth and peaks are rough
+#[derive(Copy, Clone)]
+pub struct HybridMultifractalSettings {
+    dim: NoiseDimensions,
+    freq_x: f32,
+    freq_y: f32,
+    freq_z: f32,
+    freq_w: f32,
+    lacunarity: f32,
+    h: f32,
+    offset: f32,
+    gain: f32,
+    octaves: u8,
+}
+
+impl DimensionalBeing for HybridMultifractalSettings {
+    fn get_dimensions(&self) -> NoiseDimensions {
+        self.dim
+    }
+}
+
+impl HybridMultifractalSettings {
+    /// Lacunarity defaults to 2.0
+    pub fn default(dim: NoiseDimensions) -> HybridMultifractalSettings {
+        HybridMultifractalSettings {
+            dim,
+            freq_x: 0.02,
+            freq_y: 0.02,
+            freq_z: 0.02,
+            freq_w: 0.02,
+            lacunarity: 2.0,
+            h: 0.25,
+            offset: 0.7,
+            gain: 1.0,
+            octaves: 3,
+        }
+    }
+
+    pub fn with_seed(&mut self, seed: i32) -> &mut HybridMultifractalSettings {
+        self.dim.seed = seed;
+        self
+    }
+
+    /// Maps the sample positions of the block through `matrix`, see
+    /// `FbmSettings::with_transform`
+    pub fn with_transform<const N: usize>(
+        &mut self,
+        matrix: [[f32; N]; N],
+    ) -> &mut HybridMultifractalSettings {
+        self.dim.set_transform(matrix);
+        self
+    }
+
+    pub fn with_freq(&mut self, freq: f32) -> &mut HybridMultifractalSettings {
+        self.freq_x = freq;
+        self.freq_y = freq;
+        self.freq_z = freq;
+        self.freq_w = freq;
+        self
+    }
+
+    pub fn with_freq_2d(&mut self, freq_x: f32, freq_y: f32) -> &mut HybridMultifractalSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self
+    }
+
+    pub fn with_freq_3d(
+        &mut self,
+        freq_x: f32,
+        freq_y: f32,
+        freq_z: f32,
+    ) -> &mut HybridMultifractalSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self.freq_z = freq_z;
+        self
+    }
+
+    pub fn with_freq_4d(
+        &mut self,
+        freq_x: f32,
+        freq_y: f32,
+        freq_z: f32,
+        freq_w: f32,
+    ) -> &mut HybridMultifractalSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self.freq_z = freq_z;
+        self.freq_w = freq_w;
+        self
+    }
+
+    pub fn with_lacunarity(&mut self, lacunarity: f32) -> &mut HybridMultifractalSettings {
+        self.lacunarity = lacunarity;
+        self
+    }
+
+    /// The fractal increment H, octave `i` has amplitude `lacunarity^(-i * H)`.
+    /// Higher values give smoother noise, 0.25 by default
+    pub fn with_h(&mut self, h: f32) -> &mut HybridMultifractalSettings {
+        self.h = h;
+        self
+    }
+
+    /// Added to the noise of each octave, 0.7 by default
+    pub fn with_offset(&mut self, offset: f32) -> &mut HybridMultifractalSettings {
+        self.offset = offset;
+        self
+    }
+
+    /// How strongly each octave weights the next, 1.0 by default
+    pub fn with_gain(&mut self, gain: f32) -> &mut HybridMultifractalSettings {
+        self.gain = gain;
+        self
+    }
+
+    pub fn with_octaves(&mut self, octaves: u8) -> &mut HybridMultifractalSettings {
+        self.octaves = octaves;
+        self
+    }
+
+    /// `lacunarity^-H`, the amplitude step between octaves
+    fn lac_h(&self) -> f32 {
+        self.lacunarity.powf(-self.h)
+    }
+
+    /// If you want to call noise functions by hand, call wrap on the settings
+    /// to get back a NoiseType to call the noise functions with
+    pub fn wrap(self) -> NoiseType {
+        NoiseType::HybridMultifractal(self)
+    }
+
+    /// Generate a chunk of noise based on your settings, and the min and max value
+    /// generated, so you can scale it as you wish
+    pub fn generate(self) -> (Vec<f32>, f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            1 => get_1d_noise!(&NoiseType::HybridMultifractal(self)),
+            2 => get_2d_noise!(&NoiseType::HybridMultifractal(self)),
+            3 => get_3d_noise!(&NoiseType::HybridMultifractal(self)),
+            4 => get_4d_noise!(&NoiseType::HybridMultifractal(self)),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Generate a chunk of noise into `out`, which must hold exactly one
+    /// value per sample, instead of allocating. Returns the min and max value
+    pub fn fill(self, out: &mut [f32]) -> (f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            1 => fill_noise!(fill_1d_noise, &NoiseType::HybridMultifractal(self), out),
+            2 => fill_noise!(fill_2d_noise, &NoiseType::HybridMultifractal(self), out),
+            3 => fill_noise!(fill_3d_noise, &NoiseType::HybridMultifractal(self), out),
+            4 => fill_noise!(fill_4d_noise, &NoiseType::HybridMultifractal(self), out),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Like `fill`, but rows start `pitch` values apart in `out`, to write
+    /// into part of a larger image
+    pub fn fill_pitched(self, out: &mut [f32], pitch: usize) -> (f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            1 => {
+                let width = self.dim.width;
+                noise_helpers::check_1d_pitched_len(out.len(), width, pitch);
+                fill_noise!(
+                    fill_1d_noise,
+                    &NoiseType::HybridMultifractal(self),
+                    &mut out[..width]
+                )
+            }
+            2 => fill_noise!(
+                fill_2d_noise_pitched,
+                &NoiseType::HybridMultifractal(self),
+                out,
+                pitch
+            ),
+            3 => fill_noise!(
+                fill_3d_noise_pitched,
+                &NoiseType::HybridMultifractal(self),
+                out,
+                pitch
+            ),
+            4 => fill_noise!(
+                fill_4d_noise_pitched,
+                &NoiseType::HybridMultifractal(self),
+                out,
+                pitch
+            ),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Generate a chunk of noise with values scaled from min to max
+    pub fn generate_scaled(self, min: f32, max: f32) -> Vec<f32> {
+        let d = self.dim.dim;
+        let mut new_self = self;
+        new_self.dim.min = min;
+        new_self.dim.max = max;
+        match d {
+            1 => get_1d_scaled_noise!(&NoiseType::HybridMultifractal(new_self)),
+            2 => get_2d_scaled_noise!(&NoiseType::HybridMultifractal(new_self)),
+            3 => get_3d_scaled_noise!(&NoiseType::HybridMultifractal(new_self)),
+            4 => get_4d_scaled_noise!(&NoiseType::HybridMultifractal(new_self)),
+            _ => panic!("not implemented"),
+        }
+    }
+}
+
+/// Musgrave's heterogeneous terrain over simplex noise. Each octave is scaled
+/// by the height so far, so low areas stay smooth
+#[derive(Copy, Clone)]
+pub struct HeteroTerrainSettings {
+    dim: NoiseDimensions,
+    freq_x: f32,
+    freq_y: f32,
+    freq_z: f32,
+    freq_w: f32,
+    lacunarity: f32,
+    h: f32,
+    offset: f32,
+    octaves: u8,
+}
+
+impl DimensionalBeing for HeteroTerrainSettings {
+    fn get_dimensions(&self) -> NoiseDimensions {
+        self.dim
+    }
+}
+
+impl HeteroTerrainSettings {
+    /// Lacunarity defaults to 2.0
+    pub fn default(dim: NoiseDimensions) -> HeteroTerrainSettings {
+        HeteroTerrainSettings {
+            dim,
+            freq_x: 0.02,
+            freq_y: 0.02,
+            freq_z: 0.02,
+            freq_w: 0.02,
+            lacunarity: 2.0,
+            h: 0.25,
+            offset: 0.0,
+            octaves: 3,
+        }
+    }
+
+    pub fn with_seed(&mut self, seed: i32) -> &mut HeteroTerrainSettings {
+        self.dim.seed = seed;
+        self
+    }
+
+    /// Maps the sample positions of the block through `matrix`, see
+    /// `FbmSettings::with_transform`
+    pub fn with_transform<const N: usize>(
+        &mut self,
+        matrix: [[f32; N]; N],
+    ) -> &mut HeteroTerrainSettings {
+        self.dim.set_transform(matrix);
+        self
+    }
+
+    pub fn with_freq(&mut self, freq: f32) -> &mut HeteroTerrainSettings {
+        self.freq_x = freq;
+        self.freq_y = freq;
+        self.freq_z = freq;
+        self.freq_w = freq;
+        self
+    }
+
+    pub fn with_freq_2d(&mut self, freq_x: f32, freq_y: f32) -> &mut HeteroTerrainSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self
+    }
+
+    pub fn with_freq_3d(
+        &mut self,
+        freq_x: f32,
+        freq_y: f32,
+        freq_z: f32,
+    ) -> &mut HeteroTerrainSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self.freq_z = freq_z;
+        self
+    }
+
+    pub fn with_freq_4d(
+        &mut self,
+        freq_x: f32,
+        freq_y: f32,
+        freq_z: f32,
+        freq_w: f32,
+    ) -> &mut HeteroTerrainSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self.freq_z = freq_z;
+        self.freq_w = freq_w;
+        self
+    }
+
+    pub fn with_lacunarity(&mut self, lacunarity: f32) -> &mut HeteroTerrainSettings {
+        self.lacunarity = lacunarity;
+        self
+    }
+
+    /// The fractal increment H, octave `i` has amplitude `lacunarity^(-i * H)`.
+    /// Higher values give smoother noise, 0.25 by default
+    pub fn with_h(&mut self, h: f32) -> &mut HeteroTerrainSettings {
+        self.h = h;
+        self
+    }
+
+    /// Added to the noise of each octave, 0.0 by default
+    pub fn with_offset(&mut self, offset: f32) -> &mut HeteroTerrainSettings {
+        self.offset = offset;
+        self
+    }
+
+    pub fn with_octaves(&mut self, octaves: u8) -> &mut HeteroTerrainSettings {
+        self.octaves = octaves;
+        self
+    }
+
+    /// `lacunarity^-H`, the amplitude step between octaves
+    fn lac_h(&self) -> f32 {
+        self.lacunarity.powf(-self.h)
+    }
+
+    /// If you want to call noise functions by hand, call wrap on the settings
+    /// to get back a NoiseType to call the noise functions with
+    pub fn wrap(self) -> NoiseType {
+        NoiseType::HeteroTerrain(self)
+    }
+
+    /// Generate a chunk of noise based on your settings, and the min and max value
+    /// generated, so you can scale it as you wish
+    pub fn generate(self) -> (Vec<f32>, f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            1 => get_1d_noise!(&NoiseType::HeteroTerrain(self)),
+            2 => get_2d_noise!(&NoiseType::HeteroTerrain(self)),
+            3 => get_3d_noise!(&NoiseType::HeteroTerrain(self)),
+            4 => get_4d_noise!(&NoiseType::HeteroTerrain(self)),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Generate a chunk of noise into `out`, which must hold exactly one
+    /// value per sample, instead of allocating. Returns the min and max value
+    pub fn fill(self, out: &mut [f32]) -> (f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            1 => fill_noise!(fill_1d_noise, &NoiseType::HeteroTerrain(self), out),
+            2 => fill_noise!(fill_2d_noise, &NoiseType::HeteroTerrain(self), out),
+            3 => fill_noise!(fill_3d_noise, &NoiseType::HeteroTerrain(self), out),
+            4 => fill_noise!(fill_4d_noise, &NoiseType::HeteroTerrain(self), out),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Like `fill`, but rows start `pitch` values apart in `out`, to write
+    /// into part of a larger image
+    pub fn fill_pitched(self, out: &mut [f32], pitch: usize) -> (f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            1 => {
+                let width = self.dim.width;
+                noise_helpers::check_1d_pitched_len(out.len(), width, pitch);
+                fill_noise!(
+                    fill_1d_noise,
+                    &NoiseType::HeteroTerrain(self),
+                    &mut out[..width]
+                )
+            }
+            2 => fill_noise!(
+                fill_2d_noise_pitched,
+                &NoiseType::HeteroTerrain(self),
+                out,
+                pitch
+            ),
+            3 => fill_noise!(
+                fill_3d_noise_pitched,
+                &NoiseType::HeteroTerrain(self),
+                out,
+                pitch
+            ),
+            4 => fill_noise!(
+                fill_4d_noise_pitched,
+                &NoiseType::HeteroTerrain(self),
+                out,
+                pitch
+            ),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Generate a chunk of noise with values scaled from min to max
+    pub fn generate_scaled(self, min: f32, max: f32) -> Vec<f32> {
+        let d = self.dim.dim;
+        let mut new_self = self;
+        new_self.dim.min = min;
+        new_self.dim.max = max;
+        match d {
+            1 => get_1d_scaled_noise!(&NoiseType::HeteroTerrain(new_self)),
+            2 => get_2d_scaled_noise!(&NoiseType::HeteroTerrain(new_self)),
+            3 => get_3d_scaled_noise!(&NoiseType::HeteroTerrain(new_self)),
+            4 => get_4d_scaled_noise!(&NoiseType::HeteroTerrain(new_self)),
+            _ => panic!("not implemented"),
+        }
+    }
+}
+
+/// Musgrave's ridged multifractal over simplex noise. Squared `offset - |n|`
+/// ridges, each octave weighted by the one before it
+#[derive(Copy, Clone)]
+pub struct RidgedMultifractalSettings {
+    dim: NoiseDimensions,
+    freq_x: f32,
+    freq_y: f32,
+    freq_z: f32,
+    freq_w: f32,
+    lacunarity: f32,
+    h: f32,
+    offset: f32,
+    gain: f32,
+    octaves: u8,
+}
+
+impl DimensionalBeing for RidgedMultifractalSettings {
+    fn get_dimensions(&self) -> NoiseDimensions {
+        self.dim
+    }
+}
+
+impl RidgedMultifractalSettings {
+    /// Lacunarity defaults to 2.0
+    pub fn default(dim: NoiseDimensions) -> RidgedMultifractalSettings {
+        RidgedMultifractalSettings {
+            dim,
+            freq_x: 0.02,
+            freq_y: 0.02,
+            freq_z: 0.02,
+            freq_w: 0.02,
+            lacunarity: 2.0,
+            h: 1.0,
+            offset: 1.0,
+            gain: 2.0,
+            octaves: 3,
+        }
+    }
+
+    pub fn with_seed(&mut self, seed: i32) -> &mut RidgedMultifractalSettings {
+        self.dim.seed = seed;
+        self
+    }
+
+    /// Maps the sample positions of the block through `matrix`, see
+    /// `FbmSettings::with_transform`
+    pub fn with_transform<const N: usize>(
+        &mut self,
+        matrix: [[f32; N]; N],
+    ) -> &mut RidgedMultifractalSettings {
+        self.dim.set_transform(matrix);
+        self
+    }
+
+    pub fn with_freq(&mut self, freq: f32) -> &mut RidgedMultifractalSettings {
+        self.freq_x = freq;
+        self.freq_y = freq;
+        self.freq_z = freq;
+        self.freq_w = freq;
+        self
+    }
+
+    pub fn with_freq_2d(&mut self, freq_x: f32, freq_y: f32) -> &mut RidgedMultifractalSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self
+    }
+
+    pub fn with_freq_3d(
+        &mut self,
+        freq_x: f32,
+        freq_y: f32,
+        freq_z: f32,
+    ) -> &mut RidgedMultifractalSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self.freq_z = freq_z;
+        self
+    }
+
+    pub fn with_freq_4d(
+        &mut self,
+        freq_x: f32,
+        freq_y: f32,
+        freq_z: f32,
+        freq_w: f32,
+    ) -> &mut RidgedMultifractalSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self.freq_z = freq_z;
+        self.freq_w = freq_w;
+        self
+    }
+
+    pub fn with_lacunarity(&mut self, lacunarity: f32) -> &mut RidgedMultifractalSettings {
+        self.lacunarity = lacunarity;
+        self
+    }
+
+    /// The fractal increment H, octave `i` has amplitude `lacunarity^(-i * H)`.
+    /// Higher values give smoother noise, 1.0 by default
+    pub fn with_h(&mut self, h: f32) -> &mut RidgedMultifractalSettings {
+        self.h = h;
+        self
+    }
+
+    /// Added to the noise of each octave, 1.0 by default
+    pub fn with_offset(&mut self, offset: f32) -> &mut RidgedMultifractalSettings {
+        self.offset = offset;
+        self
+    }
+
+    /// How strongly each octave weights the next, 2.0 by default
+    pub fn with_gain(&mut self, gain: f32) -> &mut RidgedMultifractalSettings {
+        self.gain = gain;
+        self
+    }
+
+    pub fn with_octaves(&mut self, octaves: u8) -> &mut RidgedMultifractalSettings {
+        self.octaves = octaves;
+        self
+    }
+
+    /// `lacunarity^-H`, the amplitude step between octaves
+    fn lac_h(&self) -> f32 {
+        self.lacunarity.powf(-self.h)
+    }
+
+    /// If you want to call noise functions by hand, call wrap on the settings
+    /// to get back a NoiseType to call the noise functions with
+    pub fn wrap(self) -> NoiseType {
+        NoiseType::RidgedMultifractal(self)
+    }
+
+    /// Generate a chunk of noise based on your settings, and the min and max value
+    /// generated, so you can scale it as you wish
+    pub fn generate(self) -> (Vec<f32>, f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            1 => get_1d_noise!(&NoiseType::RidgedMultifractal(self)),
+            2 => get_2d_noise!(&NoiseType::RidgedMultifractal(self)),
+            3 => get_3d_noise!(&NoiseType::RidgedMultifractal(self)),
+            4 => get_4d_noise!(&NoiseType::RidgedMultifractal(self)),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Generate a chunk of noise into `out`, which must hold exactly one
+    /// value per sample, instead of allocating. Returns the min and max value
+    pub fn fill(self, out: &mut [f32]) -> (f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            1 => fill_noise!(fill_1d_noise, &NoiseType::RidgedMultifractal(self), out),
+            2 => fill_noise!(fill_2d_noise, &NoiseType::RidgedMultifractal(self), out),
+            3 => fill_noise!(fill_3d_noise, &NoiseType::RidgedMultifractal(self), out),
+            4 => fill_noise!(fill_4d_noise, &NoiseType::RidgedMultifractal(self), out),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Like `fill`, but rows start `pitch` values apart in `out`, to write
+    /// into part of a larger image
+    pub fn fill_pitched(self, out: &mut [f32], pitch: usize) -> (f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            1 => {
+                let width = self.dim.width;
+                noise_helpers::check_1d_pitched_len(out.len(), width, pitch);
+                fill_noise!(
+                    fill_1d_noise,
+                    &NoiseType::RidgedMultifractal(self),
+                    &mut out[..width]
+                )
+            }
+            2 => fill_noise!(
+                fill_2d_noise_pitched,
+                &NoiseType::RidgedMultifractal(self),
+                out,
+                pitch
+            ),
+            3 => fill_noise!(
+                fill_3d_noise_pitched,
+                &NoiseType::RidgedMultifractal(self),
+                out,
+                pitch
+            ),
+            4 => fill_noise!(
+                fill_4d_noise_pitched,
+                &NoiseType::RidgedMultifractal(self),
+                out,
+                pitch
+            ),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Generate a chunk of noise with values scaled from min to max
+    pub fn generate_scaled(self, min: f32, max: f32) -> Vec<f32> {
+        let d = self.dim.dim;
+        let mut new_self = self;
+        new_self.dim.min = min;
+        new_self.dim.max = max;
+        match d {
+            1 => get_1d_scaled_noise!(&NoiseType::RidgedMultifractal(new_self)),
+            2 => get_2d_scaled_noise!(&NoiseType::RidgedMultifractal(new_self)),
+            3 => get_3d_scaled_noise!(&NoiseType::RidgedMultifractal(new_self)),
+            4 => get_4d_scaled_noise!(&NoiseType::RidgedMultifractal(new_self)),
+            _ => panic!("not implemented"),
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct GradientSettings {
+    dim: NoiseDimensions,
+    freq_x: f32,
+    freq_y: f32,
+    freq_z: f32,
+    freq_w: f32,
+    period: Option<[f32; 3]>,
+}
+
+impl DimensionalBeing for GradientSettings {
+    fn get_dimensions(&self) -> NoiseDimensions {
+        return self.dim;
+    }
+}
+
+impl GradientSettings {
+    pub fn default(dim: NoiseDimensions) -> GradientSettings {
+        GradientSettings {
+            dim,
+            freq_x: 0.02,
+            freq_y: 0.02,
+            freq_z: 0.02,
+            freq_w: 0.02,
+            period: None,
+        }
+    }
+
+    pub fn with_seed(&mut self, seed: i32) -> &mut GradientSettings {
+        self.dim.seed = seed;
+        self
+    }
+
+    /// Maps the sample positions of the block through `matrix`, see
+    /// `FbmSettings::with_transform`
+    pub fn with_transform<const N: usize>(
+        &mut self,
+        matrix: [[f32; N]; N],
+    ) -> &mut GradientSettings {
+        if self.period.is_some() {
+            panic!("tileable noise can't be transformed");
+        }
+        self.dim.set_transform(matrix);
+        self
+    }
+
+    /// Makes 2d noise tile, repeating every `period_x` and `period_y` samples,
+    /// by sampling 4d noise on a torus. A block that size wraps exactly.
+    pub fn with_period(&mut self, period_x: f32, period_y: f32) -> &mut GradientSettings {
+        self.dim.check_period(&[period_x, period_y]);
+        self.period = Some([period_x, period_y, 0.0]);
+        self
+    }
+
+    /// Makes 3d noise tile, repeating every `period_x`, `period_y` and `period_z`
+    /// samples. The frequency is adjusted so a whole multiple of 3 lattice
+    /// cells fit in each period, the smallest period of the simplex lattice.
+    pub fn with_period_3d(
+        &mut self,
+        period_x: f32,
+        period_y: f32,
+        period_z: f32,
+    ) -> &mut GradientSettings {
+        self.dim.check_period(&[period_x, period_y, period_z]);
+        self.period = Some([period_x, period_y, period_z]);
+        self
+    }
+
+    pub fn with_freq(&mut self, freq: f32) -> &mut GradientSettings {
+        self.freq_x = freq;
+        self.freq_y = freq;
+        self.freq_z = freq;
+        self.freq_w = freq;
+        self
+    }
+
+    pub fn with_freq_2d(&mut self, freq_x: f32, freq_y: f32) -> &mut GradientSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self
+    }
+
+    pub fn with_freq_3d(&mut self, freq_x: f32, freq_y: f32, freq_z: f32) -> &mut GradientSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self.freq_z = freq_z;
+        self
+    }
+
+    pub fn with_freq_4d(
+        &mut self,
+        freq_x: f32,
+        freq_y: f32,
+        freq_z: f32,
+        freq_w: f32,
+    ) -> &mut GradientSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self.freq_z = freq_z;
+        self.freq_w = freq_w;
+        self
+    }
+
+    /// If you want to call noise functions by hand, call wrap on the settings
+    /// to get back a NoiseType to call the noise functions with
+    pub fn wrap(self) -> NoiseType {
+        NoiseType::Gradient(self)
+    }
+
+    /// Generate a chunk of noise based on your settings, and the min and max value
+    /// generated, so you can scale it as you wish
+    pub fn generate(self) -> (Vec<f32>, f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            1 => get_1d_noise!(&NoiseType::Gradient(self)),
+            2 => get_2d_noise!(&NoiseType::Gradient(self)),
+            3 => get_3d_noise!(&NoiseType::Gradient(self)),
+            4 => get_4d_noise!(&NoiseType::Gradient(self)),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Generate a chunk of noise into `out`, which must hold exactly one
+    /// value per sample, instead of allocating. Returns the min and max value
+    pub fn fill(self, out: &mut [f32]) -> (f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            1 => fill_noise!(fill_1d_noise, &NoiseType::Gradient(self), out),
+            2 => fill_noise!(fill_2d_noise, &NoiseType::Gradient(self), out),
+            3 => fill_noise!(fill_3d_noise, &NoiseType::Gradient(self), out),
+            4 => fill_noise!(fill_4d_noise, &NoiseType::Gradient(self), out),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Like `fill`, but rows start `pitch` values apart in `out`, to write
+    /// into part of a larger image
+    pub fn fill_pitched(self, out: &mut [f32], pitch: usize) -> (f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            1 => {
+                let width = self.dim.width;
+                noise_helpers::check_1d_pitched_len(out.len(), width, pitch);
+                fill_noise!(fill_1d_noise, &NoiseType::Gradient(self), &mut out[..width])
+            }
+            2 => fill_noise!(
+                fill_2d_noise_pitched,
+                &NoiseType::Gradient(self),
+                out,
+                pitch
+            ),
+            3 => fill_noise!(
+                fill_3d_noise_pitched,
+                &NoiseType::Gradient(self),
+                out,
+                pitch
+            ),
+            4 => fill_noise!(
+                fill_4d_noise_pitched,
+                &NoiseType::Gradient(self),
+                out,
+                pitch
+            ),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Generate a chunk of noise with values scaled from min to max
+    pub fn generate_scaled(self, min: f32, max: f32) -> Vec<f32> {
+        let d = self.dim.dim;
+        let mut new_self = self;
+        new_self.dim.min = min;
+        new_self.dim.max = max;
+        match d {
+            1 => get_1d_scaled_noise!(&NoiseType::Gradient(new_self)),
+            2 => get_2d_scaled_noise!(&NoiseType::Gradient(new_self)),
+            3 => get_3d_scaled_noise!(&NoiseType::Gradient(new_self)),
+            4 => get_4d_scaled_noise!(&NoiseType::Gradient(new_self)),
+            _ => panic!("not implemented"),
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct PerlinSettings {
+    dim: NoiseDimensions,
+    freq_x: f32,
+    freq_y: f32,
+    freq_z: f32,
+    freq_w: f32,
+    period: Option<[f32; 3]>,
+}
+
+impl DimensionalBeing for PerlinSettings {
+    fn get_dimensions(&self) -> NoiseDimensions {
+        self.dim
+    }
+}
+
+impl PerlinSettings {
+    pub fn default(dim: NoiseDimensions) -> PerlinSettings {
+        PerlinSettings {
+            dim,
+            freq_x: 0.02,
+            freq_y: 0.02,
+            freq_z: 0.02,
+            freq_w: 0.02,
+            period: None,
+        }
+    }
+
+    pub fn with_seed(&mut self, seed: i32) -> &mut PerlinSettings {
+        self.dim.seed = seed;
+        self
+    }
+
+    /// Maps the sample positions of the block through `matrix`, see
+    /// `FbmSettings::with_transform`
+    pub fn with_transform<const N: usize>(&mut self, matrix: [[f32; N]; N]) -> &mut PerlinSettings {
+        if self.period.is_some() {
+            panic!("tileable noise can't be transformed");
+        }
+        self.dim.set_transform(matrix);
+        self
+    }
+
+    /// Makes 2d noise tile, repeating every `period_x` and `period_y` samples,
+    /// by sampling 4d noise on a torus. A block that size wraps exactly.
+    pub fn with_period(&mut self, period_x: f32, period_y: f32) -> &mut PerlinSettings {
+        self.dim.check_period(&[period_x, period_y]);
+        self.period = Some([period_x, period_y, 0.0]);
+        self
+    }
+
+    /// Makes 3d noise tile, repeating every `period_x`, `period_y` and `period_z`
+    /// samples. The frequency is adjusted so a whole number of lattice cells
+    /// fit in each period.
+    pub fn with_period_3d(
+        &mut self,
+        period_x: f32,
+        period_y: f32,
+        period_z: f32,
+    ) -> &mut PerlinSettings {
+        self.dim.check_period(&[period_x, period_y, period_z]);
+        self.period = Some([period_x, period_y, period_z]);
+        self
+    }
+
+    pub fn with_freq(&mut self, freq: f32) -> &mut PerlinSettings {
+        self.freq_x = freq;
+        self.freq_y = freq;
+        self.freq_z = freq;
+        self.freq_w = freq;
+        self
+    }
+
+    pub fn with_freq_2d(&mut self, freq_x: f32, freq_y: f32) -> &mut PerlinSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self
+    }
+
+    pub fn with_freq_3d(&mut self, freq_x: f32, freq_y: f32, freq_z: f32) -> &mut PerlinSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self.freq_z = freq_z;
+        self
+    }
+
+    pub fn with_freq_4d(
+        &mut self,
+        freq_x: f32,
+        freq_y: f32,
+        freq_z: f32,
+        freq_w: f32,
+    ) -> &mut PerlinSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self.freq_z = freq_z;
+        self.freq_w = freq_w;
+        self
+    }
+
+    /// If you want to call noise functions by hand, call wrap on the settings
+    /// to get back a NoiseType to call the noise functions with
+    pub fn wrap(self) -> NoiseType {
+        NoiseType::Perlin(self)
+    }
+
+    /// Generate a chunk of noise based on your settings, and the min and max value
+    /// generated, so you can scale it as you wish
+    pub fn generate(self) -> (Vec<f32>, f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            1 => get_1d_noise!(&NoiseType::Perlin(self)),
+            2 => get_2d_noise!(&NoiseType::Perlin(self)),
+            3 => get_3d_noise!(&NoiseType::Perlin(self)),
+            4 => get_4d_noise!(&NoiseType::Perlin(self)),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Generate a chunk of noise into `out`, which must hold exactly one
+    /// value per sample, instead of allocating. Returns the min and max value
+    pub fn fill(self, out: &mut [f32]) -> (f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            1 => fill_noise!(fill_1d_noise, &NoiseType::Perlin(self), out),
+            2 => fill_noise!(fill_2d_noise, &NoiseType::Perlin(self), out),
+            3 => fill_noise!(fill_3d_noise, &NoiseType::Perlin(self), out),
+            4 => fill_noise!(fill_4d_noise, &NoiseType::Perlin(self), out),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Like `fill`, but rows start `pitch` values apart in `out`, to write
+    /// into part of a larger image
+    pub fn fill_pitched(self, out: &mut [f32], pitch: usize) -> (f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            1 => {
+                let width = self.dim.width;
+                noise_helpers::check_1d_pitched_len(out.len(), width, pitch);
+                fill_noise!(fill_1d_noise, &NoiseType::Perlin(self), &mut out[..width])
+            }
+            2 => fill_noise!(fill_2d_noise_pitched, &NoiseType::Perlin(self), out, pitch),
+            3 => fill_noise!(fill_3d_noise_pitched, &NoiseType::Perlin(self), out, pitch),
+            4 => fill_noise!(fill_4d_noise_pitched, &NoiseType::Perlin(self), out, pitch),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Generate a chunk of noise with values scaled from min to max
+    pub fn generate_scaled(self, min: f32, max: f32) -> Vec<f32> {
+        let d = self.dim.dim;
+        let mut new_self = self;
+        new_self.dim.min = min;
+        new_self.dim.max = max;
+        match d {
+            1 => get_1d_scaled_noise!(&NoiseType::Perlin(new_self)),
+            2 => get_2d_scaled_noise!(&NoiseType::Perlin(new_self)),
+            3 => get_3d_scaled_noise!(&NoiseType::Perlin(new_self)),
+            4 => get_4d_scaled_noise!(&NoiseType::Perlin(new_self)),
+            _ => panic!("not implemented"),
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct ValueSettings {
+    dim: NoiseDimensions,
+    freq_x: f32,
+    freq_y: f32,
+    freq_z: f32,
+    freq_w: f32,
+    interpolation: Interpolation,
+}
+
+impl DimensionalBeing for ValueSettings {
+    fn get_dimensions(&self) -> NoiseDimensions {
+        self.dim
+    }
+}
+
+impl ValueSettings {
+    pub fn default(dim: NoiseDimensions) -> ValueSettings {
+        ValueSettings {
+            dim,
+            freq_x: 0.02,
+            freq_y: 0.02,
+            freq_z: 0.02,
+            freq_w: 0.02,
+            interpolation: Interpolation::Quintic,
+        }
+    }
+
+    pub fn with_seed(&mut self, seed: i32) -> &mut ValueSettings {
+        self.dim.seed = seed;
+        self
+    }
+
+    /// Maps the sample positions of the block through `matrix`, see
+    /// `FbmSettings::with_transform`
+    pub fn with_transform<const N: usize>(&mut self, matrix: [[f32; N]; N]) -> &mut ValueSettings {
+        self.dim.set_transform(matrix);
+        self
+    }
+
+    pub fn with_freq(&mut self, freq: f32) -> &mut ValueSettings {
+        self.freq_x = freq;
+        self.freq_y = freq;
+        self.freq_z = freq;
+        self.freq_w = freq;
+        self
+    }
+
+    pub fn with_freq_2d(&mut self, freq_x: f32, freq_y: f32) -> &mut ValueSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self
+    }
+
+    pub fn with_freq_3d(&mut self, freq_x: f32, freq_y: f32, freq_z: f32) -> &mut ValueSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self.freq_z = freq_z;
+        self
+    }
+
+    pub fn with_freq_4d(
+        &mut self,
+        freq_x: f32,
+        freq_y: f32,
+        freq_z: f32,
+        freq_w: f32,
+    ) -> &mut ValueSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self.freq_z = freq_z;
+        self.freq_w = freq_w;
+        self
+    }
+
+    /// Sets the curve used to blend between lattice values, quintic by default
+    pub fn with_interpolation(&mut self, interpolation: Interpolation) -> &mut ValueSettings {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// If you want to call noise functions by hand, call wrap on the settings
+    /// to get back a NoiseType to call the noise functions with
+    pub fn wrap(self) -> NoiseType {
+        NoiseType::Value(self)
+    }
+
+    /// Generate a chunk of noise based on your settings, and the min and max value
+    /// generated, so you can scale it as you wish
+    pub fn generate(self) -> (Vec<f32>, f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            1 => get_1d_noise!(&NoiseType::Value(self)),
+            2 => get_2d_noise!(&NoiseType::Value(self)),
+            3 => get_3d_noise!(&NoiseType::Value(self)),
+            4 => get_4d_noise!(&NoiseType::Value(self)),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Generate a chunk of noise into `out`, which must hold exactly one
+    /// value per sample, instead of allocating. Returns the min and max value
+    pub fn fill(self, out: &mut [f32]) -> (f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            1 => fill_noise!(fill_1d_noise, &NoiseType::Value(self), out),
+            2 => fill_noise!(fill_2d_noise, &NoiseType::Value(self), out),
+            3 => fill_noise!(fill_3d_noise, &NoiseType::Value(self), out),
+            4 => fill_noise!(fill_4d_noise, &NoiseType::Value(self), out),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Like `fill`, but rows start `pitch` values apart in `out`, to write
+    /// into part of a larger image
+    pub fn fill_pitched(self, out: &mut [f32], pitch: usize) -> (f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            1 => {
+                let width = self.dim.width;
+                noise_helpers::check_1d_pitched_len(out.len(), width, pitch);
+                fill_noise!(fill_1d_noise, &NoiseType::Value(self), &mut out[..width])
+            }
+            2 => fill_noise!(fill_2d_noise_pitched, &NoiseType::Value(self), out, pitch),
+            3 => fill_noise!(fill_3d_noise_pitched, &NoiseType::Value(self), out, pitch),
+            4 => fill_noise!(fill_4d_noise_pitched, &NoiseType::Value(self), out, pitch),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Generate a chunk of noise with values scaled from min to max
+    pub fn generate_scaled(self, min: f32, max: f32) -> Vec<f32> {
+        let d = self.dim.dim;
+        let mut new_self = self;
+        new_self.dim.min = min;
+        new_self.dim.max = max;
+        match d {
+            1 => get_1d_scaled_noise!(&NoiseType::Value(new_self)),
+            2 => get_2d_scaled_noise!(&NoiseType::Value(new_self)),
+            3 => get_3d_scaled_noise!(&NoiseType::Value(new_self)),
+            4 => get_4d_scaled_noise!(&NoiseType::Value(new_self)),
+            _ => panic!("not implemented"),
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct DomainWarpSettings {
+    dim: NoiseDimensions,
+    freq_x: f32,
+    freq_y: f32,
+    freq_z: f32,
+    freq_w: f32,
+    lacunarity: f32,
+    gain: f32,
+    octaves: u8,
+    base: BaseNoise,
+    warps: [Warp; MAX_WARPS],
+    warp_count: usize,
+}
+
+impl DimensionalBeing for DomainWarpSettings {
+    fn get_dimensions(&self) -> NoiseDimensions {
+        self.dim
+    }
+}
+
+impl DomainWarpSettings {
+    pub fn default(dim: NoiseDimensions) -> DomainWarpSettings {
+        DomainWarpSettings {
+            dim,
+            freq_x: 0.02,
+            freq_y: 0.02,
+            freq_z: 0.02,
+            freq_w: 0.02,
+            lacunarity: 0.5,
+            gain: 2.0,
+            octaves: 1,
+            base: BaseNoise::Simplex,
+            warps: [Warp {
+                base: BaseNoise::Simplex,
+                octaves: 1,
+                amplitude: 0.0,
+                freq: 1.0,
+            }; MAX_WARPS],
+            warp_count: 0,
+        }
+    }
+
+    pub fn with_seed(&mut self, seed: i32) -> &mut DomainWarpSettings {
+        self.dim.seed = seed;
+        self
+    }
+
+    /// Maps the sample positions of the block through `matrix`, see
+    /// `FbmSettings::with_transform`
+    pub fn with_transform<const N: usize>(
+        &mut self,
+        matrix: [[f32; N]; N],
+    ) -> &mut DomainWarpSettings {
+        self.dim.set_transform(matrix);
+        self
+    }
+
+    pub fn with_freq(&mut self, freq: f32) -> &mut DomainWarpSettings {
+        self.freq_x = freq;
+        self.freq_y = freq;
+        self.freq_z = freq;
+        self.freq_w = freq;
+        self
+    }
+
+    pub fn with_freq_2d(&mut self, freq_x: f32, freq_y: f32) -> &mut DomainWarpSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self
+    }
+
+    pub fn with_freq_3d(
+        &mut self,
+        freq_x: f32,
+        freq_y: f32,
+        freq_z: f32,
+    ) -> &mut DomainWarpSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self.freq_z = freq_z;
+        self
+    }
+
+    pub fn with_freq_4d(
+        &mut self,
+        freq_x: f32,
+        freq_y: f32,
+        freq_z: f32,
+        freq_w: f32,
+    ) -> &mut DomainWarpSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self.freq_z = freq_z;
+        self.freq_w = freq_w;
+        self
+    }
+
+    /// Lacunarity of both the warped noise and the warp noises
+    pub fn with_lacunarity(&mut self, lacunarity: f32) -> &mut DomainWarpSettings {
+        self.lacunarity = lacunarity;
+        self
+    }
+
+    /// Gain of both the warped noise and the warp noises
+    pub fn with_gain(&mut self, gain: f32) -> &mut DomainWarpSettings {
+        self.gain = gain;
+        self
+    }
+
+    /// Octaves of fbm in the warped noise, 1 for the plain base noise
+    pub fn with_octaves(&mut self, octaves: u8) -> &mut DomainWarpSettings {
+        self.octaves = octaves;
+        self
+    }
+
+    /// Sets the noise that gets warped, simplex by default
+    pub fn with_base(&mut self, base: BaseNoise) -> &mut DomainWarpSettings {
+        self.base = base;
+        self
+    }
+
+    /// Adds a layer of warp. Each layer is sampled at the coordinates warped by
+    /// the layers before it, so adding the same warp twice gives the iterated
+    /// `f(p + a * w(p + a * w(p)))` warp. Panics past `MAX_WARPS` layers
+    pub fn with_warp(&mut self, warp: Warp) -> &mut DomainWarpSettings {
+        if self.warp_count == MAX_WARPS {
+            panic!("too many warp layers in domain warp noise");
+        }
+        self.warps[self.warp_count] = warp;
+        self.warp_count += 1;
+        self
+    }
+
+    fn warps(&self) -> &[Warp] {
+        &self.warps[..self.warp_count]
+    }
+
+    /// If you want to call noise functions by hand, call wrap on the settings
+    /// to get back a NoiseType to call the noise functions with
+    pub fn wrap(self) -> NoiseType {
+        NoiseType::DomainWarp(self)
+    }
+
+    /// Generate a chunk of noise based on your settings, and the min and max value
+    /// generated, so you can scale it as you wish
+    pub fn generate(self) -> (Vec<f32>, f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            1 => get_1d_noise!(&NoiseType::DomainWarp(self)),
+            2 => get_2d_noise!(&NoiseType::DomainWarp(self)),
+            3 => get_3d_noise!(&NoiseType::DomainWarp(self)),
+            4 => get_4d_noise!(&NoiseType::DomainWarp(self)),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Generate a chunk of noise into `out`, which must hold exactly one
+    /// value per sample, instead of allocating. Returns the min and max value
+    pub fn fill(self, out: &mut [f32]) -> (f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            1 => fill_noise!(fill_1d_noise, &NoiseType::DomainWarp(self), out),
+            2 => fill_noise!(fill_2d_noise, &NoiseType::DomainWarp(self), out),
+            3 => fill_noise!(fill_3d_noise, &NoiseType::DomainWarp(self), out),
+            4 => fill_noise!(fill_4d_noise, &NoiseType::DomainWarp(self), out),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Like `fill`, but rows start `pitch` values apart in `out`, to write
+    /// into part of a larger image
+    pub fn fill_pitched(self, out: &mut [f32], pitch: usize) -> (f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            1 => {
+                let width = self.dim.width;
+                noise_helpers::check_1d_pitched_len(out.len(), width, pitch);
+                fill_noise!(
+                    fill_1d_noise,
+                    &NoiseType::DomainWarp(self),
+                    &mut out[..width]
+                )
+            }
+            2 => fill_noise!(
+                fill_2d_noise_pitched,
+                &NoiseType::DomainWarp(self),
+                out,
+                pitch
+            ),
+            3 => fill_noise!(
+                fill_3d_noise_pitched,
+                &NoiseType::DomainWarp(self),
+                out,
+                pitch
+            ),
+            4 => fill_noise!(
+                fill_4d_noise_pitched,
+                &NoiseType::DomainWarp(self),
+                out,
+                pitch
+            ),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Generate a chunk of noise with values scaled from min to max
+    pub fn generate_scaled(self, min: f32, max: f32) -> Vec<f32> {
+        let d = self.dim.dim;
+        let mut new_self = self;
+        new_self.dim.min = min;
+        new_self.dim.max = max;
+        match d {
+            1 => get_1d_scaled_noise!(&NoiseType::DomainWarp(new_self)),
+            2 => get_2d_scaled_noise!(&NoiseType::DomainWarp(new_self)),
+            3 => get_3d_scaled_noise!(&NoiseType::DomainWarp(new_self)),
+            4 => get_4d_scaled_noise!(&NoiseType::DomainWarp(new_self)),
+            _ => panic!("not implemented"),
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct CurlSettings {
+    dim: NoiseDimensions,
+    freq_x: f32,
+    freq_y: f32,
+    freq_z: f32,
+    lacunarity: f32,
+    gain: f32,
+    octaves: u8,
+    layout: VectorLayout,
+}
+
+impl DimensionalBeing for CurlSettings {
+    fn get_dimensions(&self) -> NoiseDimensions {
+        self.dim
+    }
+}
+
+impl CurlSettings {
+    pub fn default(dim: NoiseDimensions) -> CurlSettings {
+        CurlSettings {
+            dim,
+            freq_x: 0.02,
+            freq_y: 0.02,
+            freq_z: 0.02,
+            lacunarity: 0.5,
+            gain: 2.0,
+            octaves: 1,
+            layout: VectorLayout::Interleaved,
+        }
+    }
+
+    pub fn with_seed(&mut self, seed: i32) -> &mut CurlSettings {
+        self.dim.seed = seed;
+        self
+    }
+
+    /// Maps the sample positions of the block through `matrix`, see
+    /// `FbmSettings::with_transform`
+    pub fn with_transform<const N: usize>(&mut self, matrix: [[f32; N]; N]) -> &mut CurlSettings {
+        self.dim.set_transform(matrix);
+        self
+    }
+
+    pub fn with_freq(&mut self, freq: f32) -> &mut CurlSettings {
+        self.freq_x = freq;
+        self.freq_y = freq;
+        self.freq_z = freq;
+        self
+    }
+
+    pub fn with_freq_2d(&mut self, freq_x: f32, freq_y: f32) -> &mut CurlSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self
+    }
+
+    pub fn with_freq_3d(&mut self, freq_x: f32, freq_y: f32, freq_z: f32) -> &mut CurlSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self.freq_z = freq_z;
+        self
+    }
+
+    pub fn with_lacunarity(&mut self, lacunarity: f32) -> &mut CurlSettings {
+        self.lacunarity = lacunarity;
+        self
+    }
+
+    pub fn with_gain(&mut self, gain: f32) -> &mut CurlSettings {
+        self.gain = gain;
+        self
+    }
+
+    /// Octaves of simplex fbm in the potential, 1 by default
+    pub fn with_octaves(&mut self, octaves: u8) -> &mut CurlSettings {
+        self.octaves = octaves;
+        self
+    }
+
+    /// Interleaved by default
+    pub fn with_layout(&mut self, layout: VectorLayout) -> &mut CurlSettings {
+        self.layout = layout;
+        self
+    }
+
+    /// Generate a block of velocities, 2 or 3 components per sample laid out
+    /// as set by `with_layout`. Results are unscaled
+    pub fn generate(self) -> Vec<f32> {
+        if is_x86_feature_detected!("avx2") {
+            unsafe { avx2::get_curl(&self) }
+        } else if is_x86_feature_detected!("sse4.1") {
+            unsafe { sse41::get_curl(&self) }
+        } else if is_x86_feature_detected!("sse2") {
+            unsafe { sse2::get_curl(&self) }
+        } else {
+            unsafe { scalar::get_curl(&self) }
+        }
+    }
+}
+
+/// Specifies what type of noise to generate and contains any relevant settings.
+#[derive(Copy, Clone)]
+pub enum NoiseType {
+    Fbm(FbmSettings),
+    Billow(BillowSettings),
+    Ridge(RidgeSettings),
+    Turbulence(TurbulenceSettings),
+    Gradient(GradientSettings),
+    Perlin(PerlinSettings),
+    Value(ValueSettings),
+    Cellular(CellularSettings),
+    Cellular2(Cellular2Settings),
+    DomainWarp(DomainWarpSettings),
+    IqFbm(IqFbmSettings),
+    Swiss(SwissSettings),
+    Jordan(JordanSettings),
+    HybridMultifractal(HybridMultifractalSettings),
+    HeteroTerrain(HeteroTerrainSettings),
+    RidgedMultifractal(RidgedMultifractalSettings),
+}
+
+impl NoiseType {
+    /// Linear map applied to generated blocks, for fractals that normalize
+    fn normalization(&self) -> Option<(f32, f32)> {
+        match self {
+            NoiseType::Fbm(s) => s.normalization(),
+            NoiseType::Billow(s) => s.normalization(),
+            NoiseType::Ridge(s) => s.normalization(),
+            NoiseType::Turbulence(s) => s.normalization(),
+            _ => None,
+        }
+    }
+
+    /// Period of tileable noise, if any
+    fn period(&self) -> Option<[f32; 3]> {
+        match self {
+            NoiseType::Gradient(s) => s.period,
+            NoiseType::Perlin(s) => s.period,
+            NoiseType::Cellular(s) => s.period,
+            NoiseType::Fbm(s) => s.period,
+            NoiseType::Billow(s) => s.period,
+            NoiseType::Ridge(s) => s.period,
+            NoiseType::Turbulence(s) => s.period,
+            _ => None,
+        }
+    }
+
+    /// Panics on settings that only make sense for a block, not for points
+    fn check_points(&self) {
+        if self.period().is_some() {
+            panic!("tileable noise (with_period) can't be evaluated at points");
+        }
+        if matches!(self, NoiseType::Fbm(s) if s.looping.is_some()) {
+            panic!("looping noise (fbm_2d_looping) can't be evaluated at points");
+        }
+    }
+
+    /// The dimensions of the wrapped settings, to move and resize the block
+    fn dimensions_mut(&mut self) -> &mut NoiseDimensions {
+        match self {
+            NoiseType::Fbm(s) => &mut s.dim,
+            NoiseType::Billow(s) => &mut s.dim,
+            NoiseType::Ridge(s) => &mut s.dim,
+            NoiseType::Turbulence(s) => &mut s.dim,
+            NoiseType::Gradient(s) => &mut s.dim,
+            NoiseType::Perlin(s) => &mut s.dim,
+            NoiseType::Value(s) => &mut s.dim,
+            NoiseType::Cellular(s) => &mut s.dim,
+            NoiseType::Cellular2(s) => &mut s.dim,
+            NoiseType::DomainWarp(s) => &mut s.dim,
+            NoiseType::IqFbm(s) => &mut s.dim,
+            NoiseType::Swiss(s) => &mut s.dim,
+            NoiseType::Jordan(s) => &mut s.dim,
+            NoiseType::HybridMultifractal(s) => &mut s.dim,
+            NoiseType::HeteroTerrain(s) => &mut s.dim,
+            NoiseType::RidgedMultifractal(s) => &mut s.dim,
+        }
+    }
+}
+
+impl DimensionalBeing for NoiseType {
+    fn get_dimensions(&self) -> NoiseDimensions {
+        match self {
+            NoiseType::Fbm(s) => s.get_dimensions(),
+            NoiseType::Billow(s) => s.get_dimensions(),
+            NoiseType::Ridge(s) => s.get_dimensions(),
+            NoiseType::Turbulence(s) => s.get_dimensions(),
+            NoiseType::Gradient(s) => s.get_dimensions(),
+            NoiseType::Perlin(s) => s.get_dimensions(),
+            NoiseType::Value(s) => s.get_dimensions(),
+            NoiseType::Cellular(s) => s.get_dimensions(),
+            NoiseType::Cellular2(s) => s.get_dimensions(),
+            NoiseType::DomainWarp(s) => s.get_dimensions(),
+            NoiseType::IqFbm(s) => s.get_dimensions(),
+            NoiseType::Swiss(s) => s.get_dimensions(),
+            NoiseType::Jordan(s) => s.get_dimensions(),
+            NoiseType::HybridMultifractal(s) => s.get_dimensions(),
+            NoiseType::HeteroTerrain(s) => s.get_dimensions(),
+            NoiseType::RidgedMultifractal(s) => s.get_dimensions(),
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+/// Generates noise in tiles at integer chunk coordinates, for streaming
+/// worlds. Every tile is scaled the same way, so tiles line up with their
+/// neighbours, where `generate_scaled` would scale each by its own min and
+/// max. The size of a tile is the width, height, depth and time of the
+/// wrapped noise, its offset is ignored. Tile origins must stay below 2^24 so
+/// sample coordinates are exact.
+pub struct ChunkedGenerator {
+    noise_type: NoiseType,
+    shared_edges: bool,
+    scale: Option<(f32, f32, f32, f32)>,
+}
+
+impl ChunkedGenerator {
+    pub fn new(noise_type: NoiseType) -> ChunkedGenerator {
+        ChunkedGenerator {
+            noise_type,
+            shared_edges: false,
+            scale: None,
+        }
+    }
+
+    /// Makes every tile one sample larger along each axis, so neighbouring
+    /// tiles both hold the samples on their shared edge, with identical
+    /// values. Off by default.
+    pub fn with_shared_edges(&mut self, shared_edges: bool) -> &mut ChunkedGenerator {
+        self.shared_edges = shared_edges;
+        self
+    }
+
+    /// Maps noise from min to max onto scaled_min to scaled_max in every tile.
+    /// Normalized fractals have a known range, see `FbmSettings::with_normalize`.
+    pub fn with_scale(
+        &mut self,
+        min: f32,
+        max: f32,
+        scaled_min: f32,
+        scaled_max: f32,
+    ) -> &mut ChunkedGenerator {
+        self.scale = Some((min, max, scaled_min, scaled_max));
+        self
+    }
+
+    /// Precomputes the scale from the min and max of every tile within `radius`
+    /// chunks of the origin, and maps that onto scaled_min to scaled_max.
+    /// Tiles further out can stray slightly outside the range. Panics if the
+    /// radius is negative.
+    pub fn with_sampled_scale(
+        &mut self,
+        radius: i32,
+        scaled_min: f32,
+        scaled_max: f32,
+    ) -> &mut ChunkedGenerator {
+        if radius < 0 {
+            panic!("sampled scale radius is negative: {}", radius);
+        }
+        let dim = self.noise_type.get_dimensions().dim as u32;
+        let side = 2 * radius as usize + 1;
+        let count = side
+            .checked_pow(dim)
+            .expect("sampled scale radius covers too many chunks");
+        let mut min = f32::MAX;
+        let mut max = f32::MIN;
+        for i in 0..count {
+            let mut chunk = [0; 4];
+            for (axis, c) in chunk.iter_mut().enumerate().take(dim as usize) {
+                *c = (i / side.pow(axis as u32) % side) as i32 - radius;
+            }
+            let (_, chunk_min, chunk_max) = self.raw_chunk(chunk);
+            min = min.min(chunk_min);
+            max = max.max(chunk_max);
+        }
+        self.with_scale(min, max, scaled_min, scaled_max)
+    }
+
+    /// Generate the 1d tile at chunk `x`
+    pub fn chunk_1d(&self, x: i32) -> Vec<f32> {
+        self.chunk(1, [x, 0, 0, 0])
+    }
+
+    /// Generate the 2d tile at chunk `x`, `y`
+    pub fn chunk_2d(&self, x: i32, y: i32) -> Vec<f32> {
+        self.chunk(2, [x, y, 0, 0])
+    }
+
+    /// Generate the 3d tile at chunk `x`, `y`, `z`
+    pub fn chunk_3d(&self, x: i32, y: i32, z: i32) -> Vec<f32> {
+        self.chunk(3, [x, y, z, 0])
+    }
+
+    /// Generate the 4d tile at chunk `x`, `y`, `z`, `w`
+    pub fn chunk_4d(&self, x: i32, y: i32, z: i32, w: i32) -> Vec<f32> {
+        self.chunk(4, [x, y, z, w])
+    }
+
+    fn chunk(&self, d: usize, chunk: [i32; 4]) -> Vec<f32> {
+        if self.noise_type.get_dimensions().dim != d {
+            panic!("dimension invalid");
+        }
+        let (mut noise, _, _) = self.raw_chunk(chunk);
+        if let Some((min, max, scaled_min, scaled_max)) = self.scale {
+            scale_fixed(&mut noise, min, max, scaled_min, scaled_max);
+        }
+        noise
+    }
+
+    /// Unscaled tile and its min and max
+    fn raw_chunk(&self, chunk: [i32; 4]) -> (Vec<f32>, f32, f32) {
+        let mut noise_type = self.noise_type;
+        let dim = noise_type.dimensions_mut();
+        let edge = if self.shared_edges { 1 } else { 0 };
+        dim.x = chunk[0] as f32 * dim.width as f32;
+        dim.width += edge;
+        if dim.dim > 1 {
+            dim.y = chunk[1] as f32 * dim.height as f32;
+            dim.height += edge;
+        }
+        if dim.dim > 2 {
+            dim.z = chunk[2] as f32 * dim.depth as f32;
+            dim.depth += edge;
+        }
+        if dim.dim > 3 {
+            dim.w = chunk[3] as f32 * dim.time as f32;
+            dim.time += edge;
+        }
+        match dim.dim {
+            1 => get_1d_noise!(&noise_type),
+            2 => get_2d_noise!(&noise_type),
+            3 => get_3d_noise!(&noise_type),
+            4 => get_4d_noise!(&noise_type),
+            _ => panic!("not implemented"),
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+/// Generates 3d noise on the surface of a sphere, for planets, as an
+/// equirectangular map or six cubemap faces. The sphere is centered on the
+/// offset of the wrapped 3d noise, and its points are scaled by the frequency
+/// like any other coordinates. Only the noise dimensions are used, the width,
+/// height and depth are ignored. Tileable noise isn't supported, the sphere
+/// already wraps around.
+pub struct SphereGenerator {
+    noise_type: NoiseType,
+    radius: f32,
+}
+
+impl SphereGenerator {
+    pub fn new(noise_type: NoiseType) -> SphereGenerator {
+        if noise_type.get_dimensions().dim != 3 {
+            panic!("dimension invalid");
+        }
+        if noise_type.period().is_some() {
+            panic!("sphere noise can't be tileable");
+        }
+        SphereGenerator {
+            noise_type,
+            radius: 1.0,
+        }
+    }
+
+    /// Sets the radius of the sphere, 1 by default
+    pub fn with_radius(&mut self, radius: f32) -> &mut SphereGenerator {
+        self.radius = radius;
+        self
+    }
+
+    /// Generate a width X height equirectangular map, longitude along x from
+    /// -180 to 180 degrees, latitude along y from the north pole to the south
+    /// pole, sampled at the center of each pixel. Returns the min and max
+    /// value, so you can scale it as you wish.
+    pub fn equirectangular(&self, width: usize, height: usize) -> (Vec<f32>, f32, f32) {
+        let pi = std::f32::consts::PI;
+        let longitudes: Vec<(f32, f32)> = (0..width)
+            .map(|i| ((i as f32 + 0.5) / width as f32 * 2.0 * pi - pi).sin_cos())
+            .collect();
+        let mut points = [
+            Vec::with_capacity(width * height),
+            Vec::with_capacity(width * height),
+            Vec::with_capacity(width * height),
+        ];
+        for j in 0..height {
+            let (sin_lat, cos_lat) = (pi / 2.0 - (j as f32 + 0.5) / height as f32 * pi).sin_cos();
+            for &(sin_lon, cos_lon) in &longitudes {
+                points[0].push(cos_lat * cos_lon);
+                points[1].push(sin_lat);
+                points[2].push(cos_lat * sin_lon);
+            }
+        }
+        self.generate(points)
+    }
+
+    /// Generate six size X size cubemap faces, one after the other in the order
+    /// +x, -x, +y, -y, +z, -z, oriented like OpenGL cubemaps. Each pixel
+    /// samples the sphere in the direction of its center, so the faces are
+    /// seamless. Returns the min and max value, so you can scale it as you wish.
+    pub fn cubemap(&self, size: usize) -> (Vec<f32>, f32, f32) {
+        let mut points = [
+            Vec::with_capacity(6 * size * size),
+            Vec::with_capacity(6 * size * size),
+            Vec::with_capacity(6 * size * size),
+        ];
+        for face in 0..6 {
+            for j in 0..size {
+                let v = (j as f32 + 0.5) / size as f32 * 2.0 - 1.0;
+                for i in 0..size {
+                    let u = (i as f32 + 0.5) / size as f32 * 2.0 - 1.0;
+                    let (x, y, z) = match face {
+                        0 => (1.0, -v, -u),
+                        1 => (-1.0, -v, u),
+                        2 => (u, 1.0, v),
+                        3 => (u, -1.0, -v),
+                        4 => (u, -v, 1.0),
+                        _ => (-u, -v, -1.0),
+                    };
+                    let inv_len = 1.0 / (x * x + y * y + z * z).sqrt();
+                    points[0].push(x * inv_len);
+                    points[1].push(y * inv_len);
+                    points[2].push(z * inv_len);
+                }
+            }
+        }
+        self.generate(points)
+    }
+
+    /// Evaluates the noise at the given unit directions
+    fn generate(&self, mut points: [Vec<f32>; 3]) -> (Vec<f32>, f32, f32) {
+        let dim = self.noise_type.get_dimensions();
+        let center = [dim.x, dim.y, dim.z];
+        for (axis, c) in points.iter_mut().zip(center.iter()) {
+            for p in axis.iter_mut() {
+                *p = *p * self.radius + c;
+            }
+        }
+        let [xs, ys, zs] = &points;
+        get_noise_at!(get_3d_noise_at, &self.noise_type, xs, ys, zs)
+    }
+}
+
+/// Evaluates 1d noise at each of the coordinates in `xs` rather than on a
+/// grid, for scattered points. Coordinates are mapped through the transform
+/// and multiplied by the frequency of the settings, the offset and size of
+/// its dimensions are ignored. Results are unscaled, use `with_normalize` for a fixed range.
+/// Panics on tileable or looping noise, which only make sense for blocks.
+pub fn get_1d_noise_at(noise_type: &NoiseType, xs: &[f32]) -> Vec<f32> {
+    get_noise_at!(get_1d_noise_at, noise_type, xs).0
+}
+
+/// Evaluates 2d noise at the points given by `xs` and `ys`, which must have
+/// the same length. See `get_1d_noise_at`.
+pub fn get_2d_noise_at(noise_type: &NoiseType, xs: &[f32], ys: &[f32]) -> Vec<f32> {
+    get_noise_at!(get_2d_noise_at, noise_type, xs, ys).0
+}
+
+/// Evaluates 2d noise at a list of points stored as `[x, y]` arrays,
+/// see `get_2d_noise_at`.
+pub fn get_2d_noise_at_points(noise_type: &NoiseType, points: &[[f32; 2]]) -> Vec<f32> {
+    let xs: Vec<f32> = points.iter().map(|p| p[0]).collect();
+    let ys: Vec<f32> = points.iter().map(|p| p[1]).collect();
+    get_2d_noise_at(noise_type, &xs, &ys)
+}
+
+/// Evaluates 3d noise at the points given by `xs`, `ys` and `zs`, which must have
+/// the same length. See `get_1d_noise_at`.
+pub fn get_3d_noise_at(noise_type: &NoiseType, xs: &[f32], ys: &[f32], zs: &[f32]) -> Vec<f32> {
+    get_noise_at!(get_3d_noise_at, noise_type, xs, ys, zs).0
+}
+
+/// Evaluates 3d noise at a list of points stored as `[x, y, z]` arrays,
+/// see `get_3d_noise_at`.
+pub fn get_3d_noise_at_points(noise_type: &NoiseType, points: &[[f32; 3]]) -> Vec<f32> {
+    let xs: Vec<f32> = points.iter().map(|p| p[0]).collect();
+    let ys: Vec<f32> = points.iter().map(|p| p[1]).collect();
+    let zs: Vec<f32> = points.iter().map(|p| p[2]).collect();
+    get_3d_noise_at(noise_type, &xs, &ys, &zs)
+}
+
+/// Evaluates 4d noise at the points given by `xs`, `ys`, `zs` and `ws`, which must have
+/// the same length. See `get_1d_noise_at`.
+pub fn get_4d_noise_at(
+    noise_type: &NoiseType,
+    xs: &[f32],
+    ys: &[f32],
+    zs: &[f32],
+    ws: &[f32],
+) -> Vec<f32> {
+    get_noise_at!(get_4d_noise_at, noise_type, xs, ys, zs, ws).0
+}
+
+/// Evaluates 4d noise at a list of points stored as `[x, y, z, w]` arrays,
+/// see `get_4d_noise_at`.
+pub fn get_4d_noise_at_points(noise_type: &NoiseType, points: &[[f32; 4]]) -> Vec<f32> {
+    let xs: Vec<f32> = points.iter().map(|p| p[0]).collect();
+    let ys: Vec<f32> = points.iter().map(|p| p[1]).collect();
+    let zs: Vec<f32> = points.iter().map(|p| p[2]).collect();
+    let ws: Vec<f32> = points.iter().map(|p| p[3]).collect();
+    get_4d_noise_at(noise_type, &xs, &ys, &zs, &ws)
+}
+
+pub struct NoiseBuilder {}
+impl NoiseBuilder {
+    pub fn cellular_1d(width: usize) -> CellularSettings {
+        let mut dim = NoiseDimensions::default(1);
+        dim.width = width;
+        CellularSettings::default(dim)
+    }
+
+    pub fn cellular_1d_offset(x_offset: f32, width: usize) -> CellularSettings {
+        let mut dim = NoiseDimensions::default(1);
+        dim.width = width;
+        dim.x = x_offset;
+        CellularSettings::default(dim)
+    }
+
+    pub fn cellular_2d(width: usize, height: usize) -> CellularSettings {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = width;
+        dim.height = height;
+        CellularSettings::default(dim)
+    }
+
+    pub fn cellular_2d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+    ) -> CellularSettings {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = width;
+        dim.height = height;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        CellularSettings::default(dim)
+    }
+
+    pub fn cellular_3d(width: usize, height: usize, depth: usize) -> CellularSettings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        CellularSettings::default(dim)
+    }
+
+    pub fn cellular_3d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+        z_offset: f32,
+        depth: usize,
+    ) -> CellularSettings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        dim.z = z_offset;
+        CellularSettings::default(dim)
+    }
+
+    pub fn cellular_4d(width: usize, height: usize, depth: usize, time: usize) -> CellularSettings {
+        let mut dim = NoiseDimensions::default(4);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.time = time;
+        CellularSettings::default(dim)
+    }
+
+    pub fn cellular_4d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+        z_offset: f32,
+        depth: usize,
+        w_offset: f32,
+        time: usize,
+    ) -> CellularSettings {
+        let mut dim = NoiseDimensions::default(4);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.time = time;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        dim.z = z_offset;
+        dim.w = w_offset;
+        CellularSettings::default(dim)
+    }
+
+    pub fn cellular2_1d(width: usize) -> Cellular2Settings {
+        let mut dim = NoiseDimensions::default(1);
+        dim.width = width;
+        Cellular2Settings::default(dim)
+    }
+
+    pub fn cellular2_1d_offset(x_offset: f32, width: usize) -> Cellular2Settings {
+        let mut dim = NoiseDimensions::default(1);
+        dim.width = width;
+        dim.x = x_offset;
+        Cellular2Settings::default(dim)
+    }
+
+    pub fn cellular2_2d(width: usize, height: usize) -> Cellular2Settings {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = width;
+        dim.height = height;
+        Cellular2Settings::default(dim)
+    }
+
+    pub fn cellular2_2d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+    ) -> Cellular2Settings {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = width;
+        dim.height = height;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        Cellular2Settings::default(dim)
+    }
+
+    pub fn cellular2_3d(width: usize, height: usize, depth: usize) -> Cellular2Settings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        Cellular2Settings::default(dim)
+    }
+
+    pub fn cellular2_3d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+        z_offset: f32,
+        depth: usize,
+    ) -> Cellular2Settings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        dim.z = z_offset;
+        Cellular2Settings::default(dim)
+    }
+
+    pub fn cellular2_4d(
+        width: usize,
+        height: usize,
+        depth: usize,
+        time: usize,
+    ) -> Cellular2Settings {
+        let mut dim = NoiseDimensions::default(4);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.time = time;
+        Cellular2Settings::default(dim)
+    }
+
+    pub fn cellular2_4d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+        z_offset: f32,
+        depth: usize,
+        w_offset: f32,
+        time: usize,
+    ) -> Cellular2Settings {
+        let mut dim = NoiseDimensions::default(4);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.time = time;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        dim.z = z_offset;
+        dim.w = w_offset;
+        Cellular2Settings::default(dim)
+    }
+
+    pub fn domain_warp_1d(width: usize) -> DomainWarpSettings {
+        let mut dim = NoiseDimensions::default(1);
+        dim.width = width;
+        DomainWarpSettings::default(dim)
+    }
+
+    pub fn domain_warp_1d_offset(x_offset: f32, width: usize) -> DomainWarpSettings {
+        let mut dim = NoiseDimensions::default(1);
+        dim.width = width;
+        dim.x = x_offset;
+        DomainWarpSettings::default(dim)
+    }
+
+    pub fn domain_warp_2d(width: usize, height: usize) -> DomainWarpSettings {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = width;
+        dim.height = height;
+        DomainWarpSettings::default(dim)
+    }
+
+    pub fn domain_warp_2d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+    ) -> DomainWarpSettings {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = width;
+        dim.height = height;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        DomainWarpSettings::default(dim)
+    }
+
+    pub fn domain_warp_3d(width: usize, height: usize, depth: usize) -> DomainWarpSettings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        DomainWarpSettings::default(dim)
+    }
+
+    pub fn domain_warp_3d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+        z_offset: f32,
+        depth: usize,
+    ) -> DomainWarpSettings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        dim.z = z_offset;
+        DomainWarpSettings::default(dim)
+    }
+
+    pub fn domain_warp_4d(
+        width: usize,
+        height: usize,
+        depth: usize,
+        time: usize,
+    ) -> DomainWarpSettings {
+        let mut dim = NoiseDimensions::default(4);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.time = time;
+        DomainWarpSettings::default(dim)
+    }
+
+    pub fn domain_warp_4d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+        z_offset: f32,
+        depth: usize,
+        w_offset: f32,
+        time: usize,
+    ) -> DomainWarpSettings {
+        let mut dim = NoiseDimensions::default(4);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.time = time;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        dim.z = z_offset;
+        dim.w = w_offset;
+        DomainWarpSettings::default(dim)
+    }
+
+    pub fn curl_2d(width: usize, height: usize) -> CurlSettings {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = width;
+        dim.height = height;
+        CurlSettings::default(dim)
+    }
+
+    pub fn curl_2d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+    ) -> CurlSettings {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = width;
+        dim.height = height;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        CurlSettings::default(dim)
+    }
+
+    pub fn curl_3d(width: usize, height: usize, depth: usize) -> CurlSettings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        CurlSettings::default(dim)
+    }
+
+    pub fn curl_3d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+        z_offset: f32,
+        depth: usize,
+    ) -> CurlSettings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        dim.z = z_offset;
+        CurlSettings::default(dim)
+    }
+
+    pub fn fbm_1d(width: usize) -> FbmSettings {
+        let mut dim = NoiseDimensions::default(1);
+        dim.width = width;
+        FbmSettings::default(dim)
+    }
+
+    pub fn fbm_1d_offset(x_offset: f32, width: usize) -> FbmSettings {
+        let mut dim = NoiseDimensions::default(1);
+        dim.width = width;
+        dim.x = x_offset;
+        FbmSettings::default(dim)
+    }
+
+    pub fn fbm_2d(width: usize, height: usize) -> FbmSettings {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = width;
+        dim.height = height;
+        FbmSettings::default(dim)
+    }
+
+    pub fn fbm_2d_offset(x_offset: f32, width: usize, y_offset: f32, height: usize) -> FbmSettings {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = width;
+        dim.height = height;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        FbmSettings::default(dim)
+    }
+
+    /// 2d fbm animated over `frames` frames that loop seamlessly, returned one
+    /// frame after the other. Time moves around a circle of `radius` in the 4d
+    /// noise, a larger radius changes more from frame to frame.
+    pub fn fbm_2d_looping(width: usize, height: usize, frames: usize, radius: f32) -> FbmSettings {
+        NoiseBuilder::fbm_2d_looping_offset(0.0, width, 0.0, height, frames, radius)
+    }
+
+    pub fn fbm_2d_looping_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+        frames: usize,
+        radius: f32,
+    ) -> FbmSettings {
+        let mut settings = NoiseBuilder::fbm_2d_offset(x_offset, width, y_offset, height);
+        settings.dim.time = frames;
+        settings.looping = Some(radius);
+        settings
+    }
+
+    pub fn fbm_3d(width: usize, height: usize, depth: usize) -> FbmSettings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        FbmSettings::default(dim)
+    }
+
+    pub fn fbm_3d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+        z_offset: f32,
+        depth: usize,
+    ) -> FbmSettings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        dim.z = z_offset;
+        FbmSettings::default(dim)
+    }
+
+    pub fn fbm_4d(width: usize, height: usize, depth: usize, time: usize) -> FbmSettings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.time = time;
+        FbmSettings::default(dim)
+    }
+
+    pub fn fbm_4d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+        z_offset: f32,
+        depth: usize,
+        w_offset: f32,
+        time: usize,
+    ) -> FbmSettings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.time = time;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        dim.z = z_offset;
+        dim.w = w_offset;
+        FbmSettings::default(dim)
+    }
+
+    pub fn billow_1d(width: usize) -> BillowSettings {
+        let mut dim = NoiseDimensions::default(1);
+        dim.width = width;
+        BillowSettings::default(dim)
+    }
+
+    pub fn billow_1d_offset(x_offset: f32, width: usize) -> BillowSettings {
+        let mut dim = NoiseDimensions::default(1);
+        dim.width = width;
+        dim.x = x_offset;
+        BillowSettings::default(dim)
+    }
+
+    pub fn billow_2d(width: usize, height: usize) -> BillowSettings {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = width;
+        dim.height = height;
+        BillowSettings::default(dim)
+    }
+
+    pub fn billow_2d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+    ) -> BillowSettings {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = width;
+        dim.height = height;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        BillowSettings::default(dim)
+    }
+
+    pub fn billow_3d(width: usize, height: usize, depth: usize) -> BillowSettings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        BillowSettings::default(dim)
+    }
+
+    pub fn billow_3d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+        z_offset: f32,
+        depth: usize,
+    ) -> BillowSettings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        dim.z = z_offset;
+        BillowSettings::default(dim)
+    }
+
+    pub fn billow_4d(width: usize, height: usize, depth: usize, time: usize) -> BillowSettings {
+        let mut dim = NoiseDimensions::default(4);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.time = time;
+        BillowSettings::default(dim)
+    }
+
+    pub fn billow_4d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+        z_offset: f32,
+        depth: usize,
+        w_offset: f32,
+        time: usize,
+    ) -> BillowSettings {
+        let mut dim = NoiseDimensions::default(4);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.time = time;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        dim.z = z_offset;
+        dim.w = w_offset;
+        BillowSettings::default(dim)
+    }
+
+    pub fn ridge_1d(width: usize) -> RidgeSettings {
+        let mut dim = NoiseDimensions::default(1);
+        dim.width = width;
+        RidgeSettings::default(dim)
+    }
+
+    pub fn ridge_1d_offset(x_offset: f32, width: usize) -> RidgeSettings {
+        let mut dim = NoiseDimensions::default(1);
+        dim.width = width;
+        dim.x = x_offset;
+        RidgeSettings::default(dim)
+    }
+
+    pub fn ridge_2d(width: usize, height: usize) -> RidgeSettings {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = width;
+        dim.height = height;
+        RidgeSettings::default(dim)
+    }
+
+    pub fn ridge_2d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+    ) -> RidgeSettings {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = width;
+        dim.height = height;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        RidgeSettings::default(dim)
+    }
+
+    pub fn ridge_3d(width: usize, height: usize, depth: usize) -> RidgeSettings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        RidgeSettings::default(dim)
+    }
+
+    pub fn ridge_3d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+        z_offset: f32,
+        depth: usize,
+    ) -> RidgeSettings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        dim.z = z_offset;
+        RidgeSettings::default(dim)
+    }
+
+    pub fn ridge_4d(width: usize, height: usize, depth: usize, time: usize) -> RidgeSettings {
+        let mut dim = NoiseDimensions::default(4);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.time = time;
+        RidgeSettings::default(dim)
+    }
+
+    pub fn ridge_4d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+        z_offset: f32,
+        depth: usize,
+        w_offset: f32,
+        time: usize,
+    ) -> RidgeSettings {
+        let mut dim = NoiseDimensions::default(4);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.time = time;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        dim.z = z_offset;
+        dim.w = w_offset;
+        RidgeSettings::default(dim)
+    }
+
+    // Turbulence Builders
+    pub fn turbulence_1d(width: usize) -> TurbulenceSettings {
+        let mut dim = NoiseDimensions::default(1);
+        dim.width = width;
+        TurbulenceSettings::default(dim)
+    }
+
+    pub fn turbulence_1d_offset(x_offset: f32, width: usize) -> TurbulenceSettings {
+        let mut dim = NoiseDimensions::default(1);
+        dim.width = width;
+        dim.x = x_offset;
+        TurbulenceSettings::default(dim)
+    }
+
+    pub fn turbulence_2d(width: usize, height: usize) -> TurbulenceSettings {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = width;
+        dim.height = height;
+        TurbulenceSettings::default(dim)
+    }
+
+    pub fn turbulence_2d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+    ) -> TurbulenceSettings {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = width;
+        dim.height = height;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        TurbulenceSettings::default(dim)
+    }
+
+    pub fn turbulence_3d(width: usize, height: usize, depth: usize) -> TurbulenceSettings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        TurbulenceSettings::default(dim)
+    }
+
+    pub fn turbulence_3d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+        z_offset: f32,
+        depth: usize,
+    ) -> TurbulenceSettings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        dim.z = z_offset;
+        TurbulenceSettings::default(dim)
+    }
+
+    pub fn turbulence_4d(
+        width: usize,
+        height: usize,
+        depth: usize,
+        time: usize,
+    ) -> TurbulenceSettings {
+        let mut dim = NoiseDimensions::default(4);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.time = time;
+        TurbulenceSettings::default(dim)
+    }
+
+    pub fn turbulence_4d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+        z_offset: f32,
+        depth: usize,
+        w_offset: f32,
+        time: usize,
+    ) -> TurbulenceSettings {
+        let mut dim = NoiseDimensions::default(4);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.time = time;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        dim.z = z_offset;
+        dim.w = w_offset;
+        TurbulenceSettings::default(dim)
+    }
+
+    // Terrain Builders
+    pub fn iq_fbm_2d(width: usize, height: usize) -> IqFbmSettings {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = width;
+        dim.height = height;
+        IqFbmSettings::default(dim)
+    }
+
+    pub fn iq_fbm_2d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+    ) -> IqFbmSettings {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = width;
+        dim.height = height;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        IqFbmSettings::default(dim)
+    }
+
+    pub fn iq_fbm_3d(width: usize, height: usize, depth: usize) -> IqFbmSettings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        IqFbmSettings::default(dim)
+    }
+
+    pub fn iq_fbm_3d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+        z_offset: f32,
+        depth: usize,
+    ) -> IqFbmSettings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        dim.z = z_offset;
+        IqFbmSettings::default(dim)
+    }
+
+    pub fn swiss_2d(width: usize, height: usize) -> SwissSettings {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = width;
+        dim.height = height;
+        SwissSettings::default(dim)
+    }
+
+    pub fn swiss_2d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+    ) -> SwissSettings {
+        let mut dim = NoiseDimensions::default(2);
         dim.width = width;
         dim.height = height;
-        CellularSettings::default(dim)
+        dim.x = x_offset;
+        dim.y = y_offset;
+        SwissSettings::default(dim)
     }
 
-    pub fn cellular_2d_offset(
+    pub fn swiss_3d(width: usize, height: usize, depth: usize) -> SwissSettings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        SwissSettings::default(dim)
+    }
+
+    pub fn swiss_3d_offset(
         x_offset: f32,
         width: usize,
         y_offset: f32,
         height: usize,
-    ) -> CellularSettings {
+        z_offset: f32,
+        depth: usize,
+    ) -> SwissSettings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        dim.z = z_offset;
+        SwissSettings::default(dim)
+    }
+
+    pub fn jordan_2d(width: usize, height: usize) -> JordanSettings {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = width;
+        dim.height = height;
+        JordanSettings::default(dim)
+    }
+
+    pub fn jordan_2d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+    ) -> JordanSettings {
         let mut dim = NoiseDimensions::default(2);
         dim.width = width;
         dim.height = height;
         dim.x = x_offset;
         dim.y = y_offset;
-        CellularSettings::default(dim)
+        JordanSettings::default(dim)
     }
 
-    pub fn cellular_3d(width: usize, height: usize, depth: usize) -> CellularSettings {
+    pub fn jordan_3d(width: usize, height: usize, depth: usize) -> JordanSettings {
         let mut dim = NoiseDimensions::default(3);
         dim.width = width;
         dim.height = height;
         dim.depth = depth;
-        CellularSettings::default(dim)
+        JordanSettings::default(dim)
     }
 
-    pub fn cellular_3d_offset(
+    pub fn jordan_3d_offset(
         x_offset: f32,
         width: usize,
         y_offset: f32,
         height: usize,
         z_offset: f32,
         depth: usize,
-    ) -> CellularSettings {
+    ) -> JordanSettings {
         let mut dim = NoiseDimensions::default(3);
         dim.width = width;
         dim.height = height;
@@ -1047,46 +5254,67 @@ impl NoiseBuilder {
         dim.x = x_offset;
         dim.y = y_offset;
         dim.z = z_offset;
-        CellularSettings::default(dim)
+        JordanSettings::default(dim)
     }
 
-    pub fn cellular2_2d(width: usize, height: usize) -> Cellular2Settings {
+    // Multifractal Builders
+    pub fn hybrid_multifractal_1d(width: usize) -> HybridMultifractalSettings {
+        let mut dim = NoiseDimensions::default(1);
+        dim.width = width;
+        HybridMultifractalSettings::default(dim)
+    }
+
+    pub fn hybrid_multifractal_1d_offset(
+        x_offset: f32,
+        width: usize,
+    ) -> HybridMultifractalSettings {
+        let mut dim = NoiseDimensions::default(1);
+        dim.width = width;
+        dim.x = x_offset;
+        HybridMultifractalSettings::default(dim)
+    }
+
+    pub fn hybrid_multifractal_2d(width: usize, height: usize) -> HybridMultifractalSettings {
         let mut dim = NoiseDimensions::default(2);
         dim.width = width;
         dim.height = height;
-        Cellular2Settings::default(dim)
+        HybridMultifractalSettings::default(dim)
     }
 
-    pub fn cellular2_2d_offset(
+    pub fn hybrid_multifractal_2d_offset(
         x_offset: f32,
         width: usize,
         y_offset: f32,
         height: usize,
-    ) -> Cellular2Settings {
+    ) -> HybridMultifractalSettings {
         let mut dim = NoiseDimensions::default(2);
         dim.width = width;
         dim.height = height;
         dim.x = x_offset;
         dim.y = y_offset;
-        Cellular2Settings::default(dim)
+        HybridMultifractalSettings::default(dim)
     }
 
-    pub fn cellular2_3d(width: usize, height: usize, depth: usize) -> Cellular2Settings {
+    pub fn hybrid_multifractal_3d(
+        width: usize,
+        height: usize,
+        depth: usize,
+    ) -> HybridMultifractalSettings {
         let mut dim = NoiseDimensions::default(3);
         dim.width = width;
         dim.height = height;
         dim.depth = depth;
-        Cellular2Settings::default(dim)
+        HybridMultifractalSettings::default(dim)
     }
 
-    pub fn cellular2_3d_offset(
+    pub fn hybrid_multifractal_3d_offset(
         x_offset: f32,
         width: usize,
         y_offset: f32,
         height: usize,
         z_offset: f32,
         depth: usize,
-    ) -> Cellular2Settings {
+    ) -> HybridMultifractalSettings {
         let mut dim = NoiseDimensions::default(3);
         dim.width = width;
         dim.height = height;
@@ -1094,54 +5322,295 @@ impl NoiseBuilder {
         dim.x = x_offset;
         dim.y = y_offset;
         dim.z = z_offset;
-        Cellular2Settings::default(dim)
+        HybridMultifractalSettings::default(dim)
     }
 
-    pub fn fbm_1d(width: usize) -> FbmSettings {
+    pub fn hybrid_multifractal_4d(
+        width: usize,
+        height: usize,
+        depth: usize,
+        time: usize,
+    ) -> HybridMultifractalSettings {
+        let mut dim = NoiseDimensions::default(4);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.time = time;
+        HybridMultifractalSettings::default(dim)
+    }
+
+    pub fn hybrid_multifractal_4d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+        z_offset: f32,
+        depth: usize,
+        w_offset: f32,
+        time: usize,
+    ) -> HybridMultifractalSettings {
+        let mut dim = NoiseDimensions::default(4);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.time = time;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        dim.z = z_offset;
+        dim.w = w_offset;
+        HybridMultifractalSettings::default(dim)
+    }
+
+    pub fn hetero_terrain_1d(width: usize) -> HeteroTerrainSettings {
+        let mut dim = NoiseDimensions::default(1);
+        dim.width = width;
+        HeteroTerrainSettings::default(dim)
+    }
+
+    pub fn hetero_terrain_1d_offset(x_offset: f32, width: usize) -> HeteroTerrainSettings {
+        let mut dim = NoiseDimensions::default(1);
+        dim.width = width;
+        dim.x = x_offset;
+        HeteroTerrainSettings::default(dim)
+    }
+
+    pub fn hetero_terrain_2d(width: usize, height: usize) -> HeteroTerrainSettings {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = width;
+        dim.height = height;
+        HeteroTerrainSettings::default(dim)
+    }
+
+    pub fn hetero_terrain_2d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+    ) -> HeteroTerrainSettings {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = width;
+        dim.height = height;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        HeteroTerrainSettings::default(dim)
+    }
+
+    pub fn hetero_terrain_3d(width: usize, height: usize, depth: usize) -> HeteroTerrainSettings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        HeteroTerrainSettings::default(dim)
+    }
+
+    pub fn hetero_terrain_3d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+        z_offset: f32,
+        depth: usize,
+    ) -> HeteroTerrainSettings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        dim.z = z_offset;
+        HeteroTerrainSettings::default(dim)
+    }
+
+    pub fn hetero_terrain_4d(
+        width: usize,
+        height: usize,
+        depth: usize,
+        time: usize,
+    ) -> HeteroTerrainSettings {
+        let mut dim = NoiseDimensions::default(4);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.time = time;
+        HeteroTerrainSettings::default(dim)
+    }
+
+    pub fn hetero_terrain_4d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+        z_offset: f32,
+        depth: usize,
+        w_offset: f32,
+        time: usize,
+    ) -> HeteroTerrainSettings {
+        let mut dim = NoiseDimensions::default(4);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.time = time;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        dim.z = z_offset;
+        dim.w = w_offset;
+        HeteroTerrainSettings::default(dim)
+    }
+
+    pub fn ridged_multifractal_1d(width: usize) -> RidgedMultifractalSettings {
+        let mut dim = NoiseDimensions::default(1);
+        dim.width = width;
+        RidgedMultifractalSettings::default(dim)
+    }
+
+    pub fn ridged_multifractal_1d_offset(
+        x_offset: f32,
+        width: usize,
+    ) -> RidgedMultifractalSettings {
+        let mut dim = NoiseDimensions::default(1);
+        dim.width = width;
+        dim.x = x_offset;
+        RidgedMultifractalSettings::default(dim)
+    }
+
+    pub fn ridged_multifractal_2d(width: usize, height: usize) -> RidgedMultifractalSettings {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = width;
+        dim.height = height;
+        RidgedMultifractalSettings::default(dim)
+    }
+
+    pub fn ridged_multifractal_2d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+    ) -> RidgedMultifractalSettings {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = width;
+        dim.height = height;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        RidgedMultifractalSettings::default(dim)
+    }
+
+    pub fn ridged_multifractal_3d(
+        width: usize,
+        height: usize,
+        depth: usize,
+    ) -> RidgedMultifractalSettings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        RidgedMultifractalSettings::default(dim)
+    }
+
+    pub fn ridged_multifractal_3d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+        z_offset: f32,
+        depth: usize,
+    ) -> RidgedMultifractalSettings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        dim.z = z_offset;
+        RidgedMultifractalSettings::default(dim)
+    }
+
+    pub fn ridged_multifractal_4d(
+        width: usize,
+        height: usize,
+        depth: usize,
+        time: usize,
+    ) -> RidgedMultifractalSettings {
+        let mut dim = NoiseDimensions::default(4);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.time = time;
+        RidgedMultifractalSettings::default(dim)
+    }
+
+    pub fn ridged_multifractal_4d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+        z_offset: f32,
+        depth: usize,
+        w_offset: f32,
+        time: usize,
+    ) -> RidgedMultifractalSettings {
+        let mut dim = NoiseDimensions::default(4);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.time = time;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        dim.z = z_offset;
+        dim.w = w_offset;
+        RidgedMultifractalSettings::default(dim)
+    }
+
+    // Gradient Builders
+    pub fn gradient_1d(width: usize) -> GradientSettings {
         let mut dim = NoiseDimensions::default(1);
         dim.width = width;
-        FbmSettings::default(dim)
+        GradientSettings::default(dim)
     }
 
-    pub fn fbm_1d_offset(x_offset: f32, width: usize) -> FbmSettings {
+    pub fn gradient_1d_offset(x_offset: f32, width: usize) -> GradientSettings {
         let mut dim = NoiseDimensions::default(1);
         dim.width = width;
         dim.x = x_offset;
-        FbmSettings::default(dim)
+        GradientSettings::default(dim)
     }
 
-    pub fn fbm_2d(width: usize, height: usize) -> FbmSettings {
+    pub fn gradient_2d(width: usize, height: usize) -> GradientSettings {
         let mut dim = NoiseDimensions::default(2);
         dim.width = width;
         dim.height = height;
-        FbmSettings::default(dim)
+        GradientSettings::default(dim)
     }
 
-    pub fn fbm_2d_offset(x_offset: f32, width: usize, y_offset: f32, height: usize) -> FbmSettings {
+    pub fn gradient_2d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+    ) -> GradientSettings {
         let mut dim = NoiseDimensions::default(2);
         dim.width = width;
         dim.height = height;
         dim.x = x_offset;
         dim.y = y_offset;
-        FbmSettings::default(dim)
+        GradientSettings::default(dim)
     }
 
-    pub fn fbm_3d(width: usize, height: usize, depth: usize) -> FbmSettings {
+    pub fn gradient_3d(width: usize, height: usize, depth: usize) -> GradientSettings {
         let mut dim = NoiseDimensions::default(3);
         dim.width = width;
         dim.height = height;
         dim.depth = depth;
-        FbmSettings::default(dim)
+        GradientSettings::default(dim)
     }
 
-    pub fn fbm_3d_offset(
+    pub fn gradient_3d_offset(
         x_offset: f32,
         width: usize,
         y_offset: f32,
         height: usize,
         z_offset: f32,
         depth: usize,
-    ) -> FbmSettings {
+    ) -> GradientSettings {
         let mut dim = NoiseDimensions::default(3);
         dim.width = width;
         dim.height = height;
@@ -1149,19 +5618,19 @@ impl NoiseBuilder {
         dim.x = x_offset;
         dim.y = y_offset;
         dim.z = z_offset;
-        FbmSettings::default(dim)
+        GradientSettings::default(dim)
     }
 
-    pub fn fbm_4d(width: usize, height: usize, depth: usize, time: usize) -> FbmSettings {
-        let mut dim = NoiseDimensions::default(3);
+    pub fn gradient_4d(width: usize, height: usize, depth: usize, time: usize) -> GradientSettings {
+        let mut dim = NoiseDimensions::default(4);
         dim.width = width;
         dim.height = height;
         dim.depth = depth;
         dim.time = time;
-        FbmSettings::default(dim)
+        GradientSettings::default(dim)
     }
 
-    pub fn fbm_4d_offset(
+    pub fn gradient_4d_offset(
         x_offset: f32,
         width: usize,
         y_offset: f32,
@@ -1170,8 +5639,8 @@ impl NoiseBuilder {
         depth: usize,
         w_offset: f32,
         time: usize,
-    ) -> FbmSettings {
-        let mut dim = NoiseDimensions::default(3);
+    ) -> GradientSettings {
+        let mut dim = NoiseDimensions::default(4);
         dim.width = width;
         dim.height = height;
         dim.depth = depth;
@@ -1180,59 +5649,60 @@ impl NoiseBuilder {
         dim.y = y_offset;
         dim.z = z_offset;
         dim.w = w_offset;
-        FbmSettings::default(dim)
+        GradientSettings::default(dim)
     }
 
-    pub fn ridge_1d(width: usize) -> RidgeSettings {
+    // Perlin Builders
+    pub fn perlin_1d(width: usize) -> PerlinSettings {
         let mut dim = NoiseDimensions::default(1);
         dim.width = width;
-        RidgeSettings::default(dim)
+        PerlinSettings::default(dim)
     }
 
-    pub fn ridge_1d_offset(x_offset: f32, width: usize) -> RidgeSettings {
+    pub fn perlin_1d_offset(x_offset: f32, width: usize) -> PerlinSettings {
         let mut dim = NoiseDimensions::default(1);
         dim.width = width;
         dim.x = x_offset;
-        RidgeSettings::default(dim)
+        PerlinSettings::default(dim)
     }
 
-    pub fn ridge_2d(width: usize, height: usize) -> RidgeSettings {
+    pub fn perlin_2d(width: usize, height: usize) -> PerlinSettings {
         let mut dim = NoiseDimensions::default(2);
         dim.width = width;
         dim.height = height;
-        RidgeSettings::default(dim)
+        PerlinSettings::default(dim)
     }
 
-    pub fn ridge_2d_offset(
+    pub fn perlin_2d_offset(
         x_offset: f32,
         width: usize,
         y_offset: f32,
         height: usize,
-    ) -> RidgeSettings {
+    ) -> PerlinSettings {
         let mut dim = NoiseDimensions::default(2);
         dim.width = width;
         dim.height = height;
         dim.x = x_offset;
         dim.y = y_offset;
-        RidgeSettings::default(dim)
+        PerlinSettings::default(dim)
     }
 
-    pub fn ridge_3d(width: usize, height: usize, depth: usize) -> RidgeSettings {
+    pub fn perlin_3d(width: usize, height: usize, depth: usize) -> PerlinSettings {
         let mut dim = NoiseDimensions::default(3);
         dim.width = width;
         dim.height = height;
         dim.depth = depth;
-        RidgeSettings::default(dim)
+        PerlinSettings::default(dim)
     }
 
-    pub fn ridge_3d_offset(
+    pub fn perlin_3d_offset(
         x_offset: f32,
         width: usize,
         y_offset: f32,
         height: usize,
         z_offset: f32,
         depth: usize,
-    ) -> RidgeSettings {
+    ) -> PerlinSettings {
         let mut dim = NoiseDimensions::default(3);
         dim.width = width;
         dim.height = height;
@@ -1240,19 +5710,19 @@ impl NoiseBuilder {
         dim.x = x_offset;
         dim.y = y_offset;
         dim.z = z_offset;
-        RidgeSettings::default(dim)
+        PerlinSettings::default(dim)
     }
 
-    pub fn ridge_4d(width: usize, height: usize, depth: usize, time: usize) -> RidgeSettings {
+    pub fn perlin_4d(width: usize, height: usize, depth: usize, time: usize) -> PerlinSettings {
         let mut dim = NoiseDimensions::default(4);
         dim.width = width;
         dim.height = height;
         dim.depth = depth;
         dim.time = time;
-        RidgeSettings::default(dim)
+        PerlinSettings::default(dim)
     }
 
-    pub fn ridge_4d_offset(
+    pub fn perlin_4d_offset(
         x_offset: f32,
         width: usize,
         y_offset: f32,
@@ -1261,7 +5731,7 @@ impl NoiseBuilder {
         depth: usize,
         w_offset: f32,
         time: usize,
-    ) -> RidgeSettings {
+    ) -> PerlinSettings {
         let mut dim = NoiseDimensions::default(4);
         dim.width = width;
         dim.height = height;
@@ -1271,60 +5741,60 @@ impl NoiseBuilder {
         dim.y = y_offset;
         dim.z = z_offset;
         dim.w = w_offset;
-        RidgeSettings::default(dim)
+        PerlinSettings::default(dim)
     }
 
-    // Turbulence Builders
-    pub fn turbulence_1d(width: usize) -> TurbulenceSettings {
+    // Value Builders
+    pub fn value_1d(width: usize) -> ValueSettings {
         let mut dim = NoiseDimensions::default(1);
         dim.width = width;
-        TurbulenceSettings::default(dim)
+        ValueSettings::default(dim)
     }
 
-    pub fn turbulence_1d_offset(x_offset: f32, width: usize) -> TurbulenceSettings {
+    pub fn value_1d_offset(x_offset: f32, width: usize) -> ValueSettings {
         let mut dim = NoiseDimensions::default(1);
         dim.width = width;
         dim.x = x_offset;
-        TurbulenceSettings::default(dim)
+        ValueSettings::default(dim)
     }
 
-    pub fn turbulence_2d(width: usize, height: usize) -> TurbulenceSettings {
+    pub fn value_2d(width: usize, height: usize) -> ValueSettings {
         let mut dim = NoiseDimensions::default(2);
         dim.width = width;
         dim.height = height;
-        TurbulenceSettings::default(dim)
+        ValueSettings::default(dim)
     }
 
-    pub fn turbulence_2d_offset(
+    pub fn value_2d_offset(
         x_offset: f32,
         width: usize,
         y_offset: f32,
         height: usize,
-    ) -> TurbulenceSettings {
+    ) -> ValueSettings {
         let mut dim = NoiseDimensions::default(2);
         dim.width = width;
         dim.height = height;
         dim.x = x_offset;
         dim.y = y_offset;
-        TurbulenceSettings::default(dim)
+        ValueSettings::default(dim)
     }
 
-    pub fn turbulence_3d(width: usize, height: usize, depth: usize) -> TurbulenceSettings {
+    pub fn value_3d(width: usize, height: usize, depth: usize) -> ValueSettings {
         let mut dim = NoiseDimensions::default(3);
         dim.width = width;
         dim.height = height;
         dim.depth = depth;
-        TurbulenceSettings::default(dim)
+        ValueSettings::default(dim)
     }
 
-    pub fn turbulence_3d_offset(
+    pub fn value_3d_offset(
         x_offset: f32,
         width: usize,
         y_offset: f32,
         height: usize,
         z_offset: f32,
         depth: usize,
-    ) -> TurbulenceSettings {
+    ) -> ValueSettings {
         let mut dim = NoiseDimensions::default(3);
         dim.width = width;
         dim.height = height;
@@ -1332,24 +5802,19 @@ impl NoiseBuilder {
         dim.x = x_offset;
         dim.y = y_offset;
         dim.z = z_offset;
-        TurbulenceSettings::default(dim)
+        ValueSettings::default(dim)
     }
 
-    pub fn turbulence_4d(
-        width: usize,
-        height: usize,
-        depth: usize,
-        time: usize,
-    ) -> TurbulenceSettings {
+    pub fn value_4d(width: usize, height: usize, depth: usize, time: usize) -> ValueSettings {
         let mut dim = NoiseDimensions::default(4);
         dim.width = width;
         dim.height = height;
         dim.depth = depth;
         dim.time = time;
-        TurbulenceSettings::default(dim)
+        ValueSettings::default(dim)
     }
 
-    pub fn turbulence_4d_offset(
+    pub fn value_4d_offset(
         x_offset: f32,
         width: usize,
         y_offset: f32,
@@ -1358,7 +5823,7 @@ impl NoiseBuilder {
         depth: usize,
         w_offset: f32,
         time: usize,
-    ) -> TurbulenceSettings {
+    ) -> ValueSettings {
         let mut dim = NoiseDimensions::default(4);
         dim.width = width;
         dim.height = height;
@@ -1368,169 +5833,1058 @@ impl NoiseBuilder {
         dim.y = y_offset;
         dim.z = z_offset;
         dim.w = w_offset;
-        TurbulenceSettings::default(dim)
+        ValueSettings::default(dim)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! assert_delta {
+        ($x:expr, $y:expr, $d:expr) => {
+            assert!(($x - $y).abs() < $d);
+        };
+    }
+
+    #[test]
+    fn small_dimensions() {
+        let _ = NoiseBuilder::gradient_2d(3, 2).generate();
+    }
+
+    #[test]
+    fn consistency_4d() {
+        let noise_setting = NoiseBuilder::fbm_4d(10, 10, 10, 10).wrap();
+        let scalar_noise = unsafe { scalar::get_4d_scaled_noise(&noise_setting) };
+        let sse2_noise = unsafe { sse2::get_4d_scaled_noise(&noise_setting) };
+        let sse41_noise = unsafe { sse41::get_4d_scaled_noise(&noise_setting) };
+        let avx2_noise = unsafe { avx2::get_4d_scaled_noise(&noise_setting) };
+
+        for i in 0..scalar_noise.len() {
+            assert_delta!(scalar_noise[i], sse2_noise[i], 0.1);
+            assert_delta!(sse2_noise[i], sse41_noise[i], 0.1);
+            assert_delta!(sse41_noise[i], avx2_noise[i], 0.1);
+        }
+    }
+
+    #[test]
+    fn consistency_3d() {
+        let noise_setting = NoiseBuilder::fbm_3d(23, 23, 23).wrap();
+        let scalar_noise = unsafe { scalar::get_3d_scaled_noise(&noise_setting) };
+        let sse2_noise = unsafe { sse2::get_3d_scaled_noise(&noise_setting) };
+        let sse41_noise = unsafe { sse41::get_3d_scaled_noise(&noise_setting) };
+        let avx2_noise = unsafe { avx2::get_3d_scaled_noise(&noise_setting) };
+
+        for i in 0..scalar_noise.len() {
+            assert_delta!(scalar_noise[i], sse2_noise[i], 0.1);
+            assert_delta!(sse2_noise[i], sse41_noise[i], 0.1);
+            assert_delta!(sse41_noise[i], avx2_noise[i], 0.1);
+        }
+    }
+
+    #[test]
+    fn consistency_2d() {
+        let noise_setting = NoiseBuilder::fbm_2d(233, 233).wrap();
+        let scalar_noise = unsafe { scalar::get_2d_scaled_noise(&noise_setting) };
+        let sse2_noise = unsafe { sse2::get_2d_scaled_noise(&noise_setting) };
+        let sse41_noise = unsafe { sse41::get_2d_scaled_noise(&noise_setting) };
+        let avx2_noise = unsafe { avx2::get_2d_scaled_noise(&noise_setting) };
+
+        for i in 0..scalar_noise.len() {
+            assert_delta!(scalar_noise[i], sse2_noise[i], 0.1);
+            assert_delta!(sse2_noise[i], sse41_noise[i], 0.1);
+            assert_delta!(sse41_noise[i], avx2_noise[i], 0.1);
+        }
+    }
+
+    #[test]
+    fn consistency_1d() {
+        let noise_setting = NoiseBuilder::fbm_1d(1000).wrap();
+        let scalar_noise = unsafe { scalar::get_1d_scaled_noise(&noise_setting) };
+        let sse2_noise = unsafe { sse2::get_1d_scaled_noise(&noise_setting) };
+        let sse41_noise = unsafe { sse41::get_1d_scaled_noise(&noise_setting) };
+        let avx2_noise = unsafe { avx2::get_1d_scaled_noise(&noise_setting) };
+
+        for i in 0..scalar_noise.len() {
+            assert_delta!(scalar_noise[i], sse2_noise[i], 0.1);
+            assert_delta!(sse2_noise[i], sse41_noise[i], 0.1);
+            assert_delta!(sse41_noise[i], avx2_noise[i], 0.1);
+        }
+    }
+
+    #[test]
+    fn perlin_consistency_3d() {
+        let noise_setting = NoiseBuilder::perlin_3d(23, 23, 23).wrap();
+        let scalar_noise = unsafe { scalar::get_3d_scaled_noise(&noise_setting) };
+        let sse2_noise = unsafe { sse2::get_3d_scaled_noise(&noise_setting) };
+        let sse41_noise = unsafe { sse41::get_3d_scaled_noise(&noise_setting) };
+        let avx2_noise = unsafe { avx2::get_3d_scaled_noise(&noise_setting) };
+
+        for i in 0..scalar_noise.len() {
+            assert_delta!(scalar_noise[i], sse2_noise[i], 0.1);
+            assert_delta!(sse2_noise[i], sse41_noise[i], 0.1);
+            assert_delta!(sse41_noise[i], avx2_noise[i], 0.1);
+        }
+    }
+
+    #[test]
+    fn opensimplex_consistency_3d() {
+        let noise_setting = NoiseBuilder::fbm_3d(23, 23, 23)
+            .with_base(BaseNoise::OpenSimplex2S)
+            .wrap();
+        let scalar_noise = unsafe { scalar::get_3d_scaled_noise(&noise_setting) };
+        let sse2_noise = unsafe { sse2::get_3d_scaled_noise(&noise_setting) };
+        let sse41_noise = unsafe { sse41::get_3d_scaled_noise(&noise_setting) };
+        let avx2_noise = unsafe { avx2::get_3d_scaled_noise(&noise_setting) };
+
+        for i in 0..scalar_noise.len() {
+            assert_delta!(scalar_noise[i], sse2_noise[i], 0.1);
+            assert_delta!(sse2_noise[i], sse41_noise[i], 0.1);
+            assert_delta!(sse41_noise[i], avx2_noise[i], 0.1);
+        }
+    }
+
+    #[test]
+    fn cellular_fbm_consistency_2d() {
+        let base = NoiseBuilder::cellular_2d(1, 1)
+            .with_return_type(CellReturnType::Distance)
+            .as_base();
+        let noise_setting = NoiseBuilder::fbm_2d(100, 100).with_base(base).wrap();
+        let scalar_noise = unsafe { scalar::get_2d_scaled_noise(&noise_setting) };
+        let sse2_noise = unsafe { sse2::get_2d_scaled_noise(&noise_setting) };
+        let sse41_noise = unsafe { sse41::get_2d_scaled_noise(&noise_setting) };
+        let avx2_noise = unsafe { avx2::get_2d_scaled_noise(&noise_setting) };
+
+        for i in 0..scalar_noise.len() {
+            assert_delta!(scalar_noise[i], sse2_noise[i], 0.1);
+            assert_delta!(sse2_noise[i], sse41_noise[i], 0.1);
+            assert_delta!(sse41_noise[i], avx2_noise[i], 0.1);
+        }
+    }
+
+    #[test]
+    fn cellular_consistency_4d() {
+        let noise_setting = NoiseBuilder::cellular_4d(10, 10, 10, 10)
+            .with_freq(0.2)
+            .wrap();
+        let scalar_noise = unsafe { scalar::get_4d_scaled_noise(&noise_setting) };
+        let sse2_noise = unsafe { sse2::get_4d_scaled_noise(&noise_setting) };
+        let sse41_noise = unsafe { sse41::get_4d_scaled_noise(&noise_setting) };
+        let avx2_noise = unsafe { avx2::get_4d_scaled_noise(&noise_setting) };
+
+        for i in 0..scalar_noise.len() {
+            assert_delta!(scalar_noise[i], sse2_noise[i], 0.1);
+            assert_delta!(sse2_noise[i], sse41_noise[i], 0.1);
+            assert_delta!(sse41_noise[i], avx2_noise[i], 0.1);
+        }
+    }
+
+    #[test]
+    fn cellular_feature_2d_matches_distance() {
+        use simdeez::scalar::*;
+        for i in 0..1000 {
+            let x = i as f32 * 0.37 - 150.0;
+            let y = i as f32 * 0.11 + 20.0;
+            let feature = unsafe {
+                cellular::cellular_feature_2d::<Scalar>(
+                    F32x1(x),
+                    F32x1(y),
+                    CellDistanceFunction::Euclidean,
+                    F32x1(0.25),
+                    1337,
+                )
+            };
+            let distance = unsafe {
+                scalar::cellular_2d(
+                    x,
+                    y,
+                    CellDistanceFunction::Euclidean,
+                    CellReturnType::Distance,
+                    0.25,
+                    1337,
+                )
+            };
+            assert_delta!(feature.distance.0, distance, 0.0001);
+            let dx = feature.feature_x.0 - x;
+            let dy = feature.feature_y.0 - y;
+            assert_delta!(dx * dx + dy * dy, distance, 0.0001);
+            assert_delta!(feature.feature_x.0 - feature.cell_x.0 as f32, 0.0, 0.3);
+            assert_delta!(feature.feature_y.0 - feature.cell_y.0 as f32, 0.0, 0.3);
+        }
+    }
+
+    #[test]
+    fn cellular_edge_consistency_2d() {
+        let noise_setting = NoiseBuilder::cellular2_2d(100, 100)
+            .with_return_type(Cell2ReturnType::EdgeDistance)
+            .wrap();
+        let scalar_noise = unsafe { scalar::get_2d_scaled_noise(&noise_setting) };
+        let sse2_noise = unsafe { sse2::get_2d_scaled_noise(&noise_setting) };
+        let sse41_noise = unsafe { sse41::get_2d_scaled_noise(&noise_setting) };
+        let avx2_noise = unsafe { avx2::get_2d_scaled_noise(&noise_setting) };
+
+        for i in 0..scalar_noise.len() {
+            assert_delta!(scalar_noise[i], sse2_noise[i], 0.1);
+            assert_delta!(sse2_noise[i], sse41_noise[i], 0.1);
+            assert_delta!(sse41_noise[i], avx2_noise[i], 0.1);
+        }
+    }
+
+    #[test]
+    fn cellular_edge_1d_4d() {
+        let noise_1d = NoiseBuilder::cellular2_1d(200)
+            .with_freq(0.1)
+            .with_return_type(Cell2ReturnType::EdgeDistance)
+            .wrap();
+        let noise_4d = NoiseBuilder::cellular2_4d(6, 6, 6, 6)
+            .with_freq(0.2)
+            .with_return_type(Cell2ReturnType::EdgeDistance)
+            .wrap();
+        let scalar_1d = unsafe { scalar::get_1d_noise(&noise_1d).0 };
+        let avx2_1d = unsafe { avx2::get_1d_noise(&noise_1d).0 };
+        let scalar_4d = unsafe { scalar::get_4d_noise(&noise_4d).0 };
+        let avx2_4d = unsafe { avx2::get_4d_noise(&noise_4d).0 };
+        for i in 0..scalar_1d.len() {
+            assert_delta!(scalar_1d[i], avx2_1d[i], 0.1);
+        }
+        for i in 0..scalar_4d.len() {
+            assert_delta!(scalar_4d[i], avx2_4d[i], 0.1);
+        }
+
+        // Inside its own cell every point is at most halfway to a neighbour
+        assert!(scalar_1d.iter().all(|&n| (-0.0001..1.5).contains(&n)));
+        assert!(scalar_1d.iter().any(|&n| n > 0.1));
+        assert!(scalar_4d.iter().all(|&n| (-0.0001..1.5).contains(&n)));
+    }
+
+    #[test]
+    fn cellular_minkowski_consistency_2d() {
+        let noise_setting = NoiseBuilder::cellular_2d(100, 100)
+            .with_distance_function(CellDistanceFunction::Minkowski(3.0))
+            .wrap();
+        let scalar_noise = unsafe { scalar::get_2d_scaled_noise(&noise_setting) };
+        let sse2_noise = unsafe { sse2::get_2d_scaled_noise(&noise_setting) };
+        let sse41_noise = unsafe { sse41::get_2d_scaled_noise(&noise_setting) };
+        let avx2_noise = unsafe { avx2::get_2d_scaled_noise(&noise_setting) };
+
+        for i in 0..scalar_noise.len() {
+            assert_delta!(scalar_noise[i], sse2_noise[i], 0.1);
+            assert_delta!(sse2_noise[i], sse41_noise[i], 0.1);
+            assert_delta!(sse41_noise[i], avx2_noise[i], 0.1);
+        }
+    }
+
+    #[test]
+    fn cellular2_distances_consistency_2d() {
+        let noise_setting = NoiseBuilder::cellular2_2d(100, 100);
+        let scalar_noise = unsafe { scalar::get_cellular2_distances(&noise_setting) };
+        let sse2_noise = unsafe { sse2::get_cellular2_distances(&noise_setting) };
+        let sse41_noise = unsafe { sse41::get_cellular2_distances(&noise_setting) };
+        let avx2_noise = unsafe { avx2::get_cellular2_distances(&noise_setting) };
+
+        for c in 0..4 {
+            for i in 0..scalar_noise[c].len() {
+                assert_delta!(scalar_noise[c][i], sse2_noise[c][i], 0.1);
+                assert_delta!(sse2_noise[c][i], sse41_noise[c][i], 0.1);
+                assert_delta!(sse41_noise[c][i], avx2_noise[c][i], 0.1);
+                if c > 0 {
+                    assert!(scalar_noise[c - 1][i] <= scalar_noise[c][i]);
+                }
+            }
+        }
     }
 
-    // Gradient Builders
-    pub fn gradient_1d(width: usize) -> GradientSettings {
-        let mut dim = NoiseDimensions::default(1);
-        dim.width = width;
-        GradientSettings::default(dim)
+    #[test]
+    fn domain_warp_consistency_2d() {
+        let warp = Warp {
+            base: BaseNoise::Perlin,
+            octaves: 2,
+            amplitude: 2.0,
+            freq: 1.5,
+        };
+        let noise_setting = NoiseBuilder::domain_warp_2d(100, 100)
+            .with_warp(warp)
+            .with_warp(warp)
+            .wrap();
+        let scalar_noise = unsafe { scalar::get_2d_scaled_noise(&noise_setting) };
+        let sse2_noise = unsafe { sse2::get_2d_scaled_noise(&noise_setting) };
+        let sse41_noise = unsafe { sse41::get_2d_scaled_noise(&noise_setting) };
+        let avx2_noise = unsafe { avx2::get_2d_scaled_noise(&noise_setting) };
+
+        for i in 0..scalar_noise.len() {
+            assert_delta!(scalar_noise[i], sse2_noise[i], 0.1);
+            assert_delta!(sse2_noise[i], sse41_noise[i], 0.1);
+            assert_delta!(sse41_noise[i], avx2_noise[i], 0.1);
+        }
     }
 
-    pub fn gradient_1d_offset(x_offset: f32, width: usize) -> GradientSettings {
-        let mut dim = NoiseDimensions::default(1);
-        dim.width = width;
-        dim.x = x_offset;
-        GradientSettings::default(dim)
+    #[test]
+    fn curl_consistency_2d() {
+        let noise_setting = *NoiseBuilder::curl_2d(100, 100).with_octaves(3);
+        let scalar_noise = unsafe { scalar::get_curl(&noise_setting) };
+        let sse2_noise = unsafe { sse2::get_curl(&noise_setting) };
+        let sse41_noise = unsafe { sse41::get_curl(&noise_setting) };
+        let avx2_noise = unsafe { avx2::get_curl(&noise_setting) };
+
+        assert_eq!(scalar_noise.len(), 100 * 100 * 2);
+        for i in 0..scalar_noise.len() {
+            assert_delta!(scalar_noise[i], sse2_noise[i], 0.1);
+            assert_delta!(sse2_noise[i], sse41_noise[i], 0.1);
+            assert_delta!(sse41_noise[i], avx2_noise[i], 0.1);
+        }
     }
 
-    pub fn gradient_2d(width: usize, height: usize) -> GradientSettings {
-        let mut dim = NoiseDimensions::default(2);
-        dim.width = width;
-        dim.height = height;
-        GradientSettings::default(dim)
+    #[test]
+    fn fbm_deriv_matches_finite_difference_2d() {
+        let eps = 0.001;
+        let fbm = |x, y| unsafe { scalar::fbm_2d(x, y, 2.0, 0.5, 3, 1337) };
+        for i in 0..1000 {
+            let x = i as f32 * 0.0137 - 5.0;
+            let y = i as f32 * 0.0311 - 8.0;
+            let (value, deriv) = unsafe { scalar::fbm_2d_deriv(x, y, 2.0, 0.5, 3, 1337) };
+            assert_delta!(value, fbm(x, y), 0.00001);
+            assert_delta!(
+                deriv[0],
+                (fbm(x + eps, y) - fbm(x - eps, y)) / (2.0 * eps),
+                0.001
+            );
+            assert_delta!(
+                deriv[1],
+                (fbm(x, y + eps) - fbm(x, y - eps)) / (2.0 * eps),
+                0.001
+            );
+        }
     }
 
-    pub fn gradient_2d_offset(
-        x_offset: f32,
-        width: usize,
-        y_offset: f32,
-        height: usize,
-    ) -> GradientSettings {
-        let mut dim = NoiseDimensions::default(2);
-        dim.width = width;
-        dim.height = height;
-        dim.x = x_offset;
-        dim.y = y_offset;
-        GradientSettings::default(dim)
+    /// Distance from a point to the nearest simplex boundary, in skewed
+    /// coordinates. 3d and 4d simplex noise jumps slightly across those
+    /// boundaries, which a finite difference straddling one would pick up
+    fn simplex_margin(p: &[f32], skew: f32, unskew: f32) -> f32 {
+        let s = p.iter().sum::<f32>() * skew;
+        let cell: Vec<f32> = p.iter().map(|c| (c + s).floor()).collect();
+        let t = cell.iter().sum::<f32>() * unskew;
+        let mut margin = f32::MAX;
+        for (c, i) in p.iter().zip(&cell) {
+            let frac = c + s - i;
+            margin = margin.min(frac).min(1.0 - frac);
+        }
+        let rel: Vec<f32> = p.iter().zip(&cell).map(|(c, i)| c - (i - t)).collect();
+        for a in 0..rel.len() {
+            for b in a + 1..rel.len() {
+                margin = margin.min((rel[a] - rel[b]).abs());
+            }
+        }
+        margin
     }
 
-    pub fn gradient_3d(width: usize, height: usize, depth: usize) -> GradientSettings {
-        let mut dim = NoiseDimensions::default(3);
-        dim.width = width;
-        dim.height = height;
-        dim.depth = depth;
-        GradientSettings::default(dim)
+    /// Compares the derivatives against central differences of the noise, at
+    /// points where every octave stays clear of the simplex boundaries
+    fn assert_deriv_matches<const N: usize>(
+        noise: impl Fn([f32; N]) -> f32,
+        noise_deriv: impl Fn([f32; N]) -> (f32, [f32; N]),
+        octaves: i32,
+        skew: f32,
+        unskew: f32,
+    ) {
+        let eps = 0.001;
+        let mut checked = 0;
+        for i in 0..1000 {
+            let mut p = [0.0; N];
+            for (a, c) in p.iter_mut().enumerate() {
+                *c = i as f32 * [0.0137, 0.0311, 0.0173, 0.0229][a] - [5.0, 8.0, 3.0, 6.0][a];
+            }
+            let clear = (0..octaves).all(|o| {
+                let scaled: Vec<f32> = p.iter().map(|c| c * 2f32.powi(o)).collect();
+                simplex_margin(&scaled, skew, unskew) > 0.02
+            });
+            if !clear {
+                continue;
+            }
+            checked += 1;
+
+            let (value, deriv) = noise_deriv(p);
+            assert_delta!(value, noise(p), 0.00001);
+            for a in 0..N {
+                let (mut above, mut below) = (p, p);
+                above[a] += eps;
+                below[a] -= eps;
+                assert_delta!(deriv[a], (noise(above) - noise(below)) / (2.0 * eps), 0.001);
+            }
+        }
+        assert!(checked > 100);
     }
 
-    pub fn gradient_3d_offset(
-        x_offset: f32,
-        width: usize,
-        y_offset: f32,
-        height: usize,
-        z_offset: f32,
-        depth: usize,
-    ) -> GradientSettings {
-        let mut dim = NoiseDimensions::default(3);
-        dim.width = width;
-        dim.height = height;
-        dim.depth = depth;
-        dim.x = x_offset;
-        dim.y = y_offset;
-        dim.z = z_offset;
-        GradientSettings::default(dim)
+    #[test]
+    fn deriv_matches_finite_difference_3d_4d() {
+        let (f3, g3) = (1.0 / 3.0, 1.0 / 6.0);
+        let (f4, g4) = (0.309016994, 0.138196601);
+        unsafe {
+            assert_deriv_matches(
+                |[x, y, z]| scalar::simplex_3d(x, y, z, 1337),
+                |[x, y, z]| scalar::simplex_3d_deriv(x, y, z, 1337),
+                1,
+                f3,
+                g3,
+            );
+            assert_deriv_matches(
+                |[x, y, z, w]| scalar::simplex_4d(x, y, z, w, 1337),
+                |[x, y, z, w]| scalar::simplex_4d_deriv(x, y, z, w, 1337),
+                1,
+                f4,
+                g4,
+            );
+            assert_deriv_matches(
+                |[x, y, z]| scalar::fbm_3d(x, y, z, 2.0, 0.5, 3, 1337),
+                |[x, y, z]| scalar::fbm_3d_deriv(x, y, z, 2.0, 0.5, 3, 1337),
+                3,
+                f3,
+                g3,
+            );
+            assert_deriv_matches(
+                |[x, y, z, w]| scalar::fbm_4d(x, y, z, w, 2.0, 0.5, 3, 1337),
+                |[x, y, z, w]| scalar::fbm_4d_deriv(x, y, z, w, 2.0, 0.5, 3, 1337),
+                3,
+                f4,
+                g4,
+            );
+        }
     }
 
-    pub fn gradient_4d(width: usize, height: usize, depth: usize, time: usize) -> GradientSettings {
-        let mut dim = NoiseDimensions::default(4);
-        dim.width = width;
-        dim.height = height;
-        dim.depth = depth;
-        dim.time = time;
-        GradientSettings::default(dim)
+    #[test]
+    fn terrain_consistency_2d() {
+        let noise_settings = [
+            NoiseBuilder::iq_fbm_2d(100, 100).with_octaves(5).wrap(),
+            NoiseBuilder::swiss_2d(100, 100).with_octaves(5).wrap(),
+            NoiseBuilder::jordan_2d(100, 100).with_octaves(5).wrap(),
+        ];
+        for noise_setting in noise_settings.iter() {
+            let scalar_noise = unsafe { scalar::get_2d_scaled_noise(noise_setting) };
+            let sse2_noise = unsafe { sse2::get_2d_scaled_noise(noise_setting) };
+            let sse41_noise = unsafe { sse41::get_2d_scaled_noise(noise_setting) };
+            let avx2_noise = unsafe { avx2::get_2d_scaled_noise(noise_setting) };
+
+            for i in 0..scalar_noise.len() {
+                assert_delta!(scalar_noise[i], sse2_noise[i], 0.1);
+                assert_delta!(sse2_noise[i], sse41_noise[i], 0.1);
+                assert_delta!(sse41_noise[i], avx2_noise[i], 0.1);
+            }
+        }
     }
 
-    pub fn gradient_4d_offset(
-        x_offset: f32,
-        width: usize,
-        y_offset: f32,
-        height: usize,
-        z_offset: f32,
-        depth: usize,
-        w_offset: f32,
-        time: usize,
-    ) -> GradientSettings {
-        let mut dim = NoiseDimensions::default(4);
-        dim.width = width;
-        dim.height = height;
-        dim.depth = depth;
-        dim.time = time;
-        dim.x = x_offset;
-        dim.y = y_offset;
-        dim.z = z_offset;
-        dim.w = w_offset;
-        GradientSettings::default(dim)
+    #[test]
+    #[should_panic(expected = "jordan noise is only 2d or 3d, not 4d")]
+    fn terrain_rejects_4d() {
+        JordanSettings::default(NoiseDimensions::default(4));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn multifractal_consistency_2d() {
+        let noise_settings = [
+            NoiseBuilder::hybrid_multifractal_2d(100, 100)
+                .with_octaves(5)
+                .wrap(),
+            NoiseBuilder::hetero_terrain_2d(100, 100)
+                .with_octaves(5)
+                .wrap(),
+            NoiseBuilder::ridged_multifractal_2d(100, 100)
+                .with_octaves(5)
+                .wrap(),
+        ];
+        for noise_setting in noise_settings.iter() {
+            let scalar_noise = unsafe { scalar::get_2d_scaled_noise(noise_setting) };
+            let sse2_noise = unsafe { sse2::get_2d_scaled_noise(noise_setting) };
+            let sse41_noise = unsafe { sse41::get_2d_scaled_noise(noise_setting) };
+            let avx2_noise = unsafe { avx2::get_2d_scaled_noise(noise_setting) };
+
+            for i in 0..scalar_noise.len() {
+                assert_delta!(scalar_noise[i], sse2_noise[i], 0.1);
+                assert_delta!(sse2_noise[i], sse41_noise[i], 0.1);
+                assert_delta!(sse41_noise[i], avx2_noise[i], 0.1);
+            }
+        }
+    }
 
-    macro_rules! assert_delta {
-        ($x:expr, $y:expr, $d:expr) => {
-            assert!(($x - $y).abs() < $d);
-        };
+    #[test]
+    fn billow_consistency_2d() {
+        let noise_setting = NoiseBuilder::billow_2d(100, 100).with_octaves(4).wrap();
+        let scalar_noise = unsafe { scalar::get_2d_scaled_noise(&noise_setting) };
+        let sse2_noise = unsafe { sse2::get_2d_scaled_noise(&noise_setting) };
+        let sse41_noise = unsafe { sse41::get_2d_scaled_noise(&noise_setting) };
+        let avx2_noise = unsafe { avx2::get_2d_scaled_noise(&noise_setting) };
+
+        for i in 0..scalar_noise.len() {
+            assert_delta!(scalar_noise[i], sse2_noise[i], 0.1);
+            assert_delta!(sse2_noise[i], sse41_noise[i], 0.1);
+            assert_delta!(sse41_noise[i], avx2_noise[i], 0.1);
+        }
+        for i in 0..100 {
+            let x = i as f32 * 0.37;
+            let y = i as f32 * 0.11;
+            let billow = unsafe { scalar::billow_2d(x, y, 0.5, 2.0, 4, 1337) };
+            let billow_f64 =
+                unsafe { scalar::billow_2d_f64(x as f64, y as f64, 0.5, 2.0, 4, 1337) };
+            assert_delta!(billow, billow_f64 as f32, 0.001);
+        }
     }
 
     #[test]
-    fn small_dimensions() {
-        let _ = NoiseBuilder::gradient_2d(3, 2).generate();
+    fn billow_4d_varies_along_w() {
+        let (noise, _, _) = NoiseBuilder::billow_4d(8, 8, 2, 2)
+            .with_freq(0.2)
+            .with_octaves(3)
+            .generate();
+        let slice = 8 * 8 * 2;
+        assert_eq!(noise.len(), 2 * slice);
+        assert_ne!(noise[..slice], noise[slice..]);
     }
 
     #[test]
-    fn consistency_4d() {
-        let noise_setting = NoiseBuilder::fbm_4d(10, 10, 10, 10).wrap();
-        let scalar_noise = unsafe { scalar::get_4d_scaled_noise(&noise_setting) };
-        let sse2_noise = unsafe { sse2::get_4d_scaled_noise(&noise_setting) };
-        let sse41_noise = unsafe { sse41::get_4d_scaled_noise(&noise_setting) };
-        let avx2_noise = unsafe { avx2::get_4d_scaled_noise(&noise_setting) };
+    fn octave_variation_consistency_2d() {
+        let noise_setting = NoiseBuilder::fbm_2d(100, 100)
+            .with_octaves(4)
+            .with_octave_seeds(true)
+            .with_octave_rotation(OctaveRotation::Seeded)
+            .wrap();
+        let scalar_noise = unsafe { scalar::get_2d_scaled_noise(&noise_setting) };
+        let sse2_noise = unsafe { sse2::get_2d_scaled_noise(&noise_setting) };
+        let sse41_noise = unsafe { sse41::get_2d_scaled_noise(&noise_setting) };
+        let avx2_noise = unsafe { avx2::get_2d_scaled_noise(&noise_setting) };
 
         for i in 0..scalar_noise.len() {
             assert_delta!(scalar_noise[i], sse2_noise[i], 0.1);
             assert_delta!(sse2_noise[i], sse41_noise[i], 0.1);
             assert_delta!(sse41_noise[i], avx2_noise[i], 0.1);
         }
+
+        // The first octave is never varied, later ones are
+        let mut plain = *NoiseBuilder::ridge_2d(32, 32).with_octaves(1);
+        let mut varied = plain;
+        varied
+            .with_octave_seeds(true)
+            .with_octave_rotation(OctaveRotation::Fixed);
+        assert_eq!(plain.generate().0, varied.generate().0);
+        let (plain, _, _) = plain.with_octaves(3).generate();
+        let (varied, _, _) = varied.with_octaves(3).generate();
+        assert!(plain.iter().zip(&varied).any(|(a, b)| (a - b).abs() > 0.01));
     }
 
     #[test]
-    fn consistency_3d() {
-        let noise_setting = NoiseBuilder::fbm_3d(23, 23, 23).wrap();
-        let scalar_noise = unsafe { scalar::get_3d_scaled_noise(&noise_setting) };
-        let sse2_noise = unsafe { sse2::get_3d_scaled_noise(&noise_setting) };
-        let sse41_noise = unsafe { sse41::get_3d_scaled_noise(&noise_setting) };
-        let avx2_noise = unsafe { avx2::get_3d_scaled_noise(&noise_setting) };
+    fn octave_variation_none_matches_plain_1d() {
+        use simdeez::{scalar::*, Simd};
+        let (lac, gain) = (F32x1(2.0), F32x1(0.5));
+        for i in 0..200 {
+            let x = F32x1(i as f32 * 0.13 - 10.0);
+            let varied = |combine: fn(F32x1, F32x1, F32x1) -> F32x1| unsafe {
+                fractal::varied_1d::<Scalar, _, _>(
+                    x,
+                    lac,
+                    gain,
+                    4,
+                    1337,
+                    OctaveVariation::NONE,
+                    |x, seed| simplex::simplex_1d::<Scalar>(x, seed),
+                    combine,
+                )
+            };
+            unsafe {
+                assert_eq!(
+                    simplex::fbm_1d::<Scalar>(x, lac, gain, 4, 1337).0,
+                    varied(|result, n, amp| Scalar::fmadd_ps(n, amp, result)).0
+                );
+                assert_eq!(
+                    simplex::ridge_1d::<Scalar>(x, lac, gain, 4, 1337).0,
+                    varied(|result, n, amp| {
+                        Scalar::add_ps(
+                            result,
+                            Scalar::fnmadd_ps(Scalar::abs_ps(n), amp, Scalar::set1_ps(1.0)),
+                        )
+                    })
+                    .0
+                );
+                assert_eq!(
+                    simplex::turbulence_1d::<Scalar>(x, lac, gain, 4, 1337).0,
+                    varied(|result, n, amp| {
+                        Scalar::add_ps(result, Scalar::abs_ps(Scalar::mul_ps(n, amp)))
+                    })
+                    .0
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn normalized_fixed_scaling_2d() {
+        // Two chunks side by side match one chunk covering both
+        let whole = NoiseBuilder::fbm_2d_offset(0.0, 64, 0.0, 16)
+            .with_octaves(5)
+            .generate_scaled_fixed(0.0, 255.0);
+        let left = NoiseBuilder::fbm_2d_offset(0.0, 32, 0.0, 16)
+            .with_octaves(5)
+            .generate_scaled_fixed(0.0, 255.0);
+        let right = NoiseBuilder::fbm_2d_offset(32.0, 32, 0.0, 16)
+            .with_octaves(5)
+            .generate_scaled_fixed(0.0, 255.0);
+        for y in 0..16 {
+            for x in 0..32 {
+                assert_delta!(whole[y * 64 + x], left[y * 32 + x], 0.001);
+                assert_delta!(whole[y * 64 + 32 + x], right[y * 32 + x], 0.001);
+            }
+        }
+
+        for octaves in 1..6 {
+            let (_, min, max) = NoiseBuilder::fbm_2d(100, 100)
+                .with_freq(0.1)
+                .with_octaves(octaves)
+                .with_lacunarity(2.0)
+                .with_gain(0.5)
+                .with_normalize(true)
+                .generate();
+            assert!(min >= -1.05 && max <= 1.05);
+            let (_, min, max) = NoiseBuilder::ridge_2d(100, 100)
+                .with_freq(0.1)
+                .with_octaves(octaves)
+                .with_normalize(true)
+                .generate();
+            assert!(min >= -0.05 && max <= 1.05);
+        }
+    }
+
+    #[test]
+    fn chunked_shared_edges_2d() {
+        let mut chunks = ChunkedGenerator::new(NoiseBuilder::fbm_2d(16, 16).with_octaves(4).wrap());
+        chunks
+            .with_shared_edges(true)
+            .with_sampled_scale(1, 0.0, 1.0);
+        let tile = chunks.chunk_2d(-1, 0);
+        let right = chunks.chunk_2d(0, 0);
+        let below = chunks.chunk_2d(-1, 1);
+        assert_eq!(tile.len(), 17 * 17);
+        for i in 0..17 {
+            assert_eq!(tile[i * 17 + 16], right[i * 17]);
+            assert_eq!(tile[16 * 17 + i], below[i]);
+        }
+        assert!(tile.iter().all(|&n| n >= 0.0 && n <= 1.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "sampled scale radius is negative: -1")]
+    fn chunked_sampled_scale_negative_radius() {
+        ChunkedGenerator::new(NoiseBuilder::fbm_2d(16, 16).wrap()).with_sampled_scale(-1, 0.0, 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "sampled scale radius covers too many chunks")]
+    fn chunked_sampled_scale_too_large() {
+        ChunkedGenerator::new(NoiseBuilder::fbm_4d(2, 2, 2, 2).wrap()).with_sampled_scale(
+            i32::MAX,
+            0.0,
+            1.0,
+        );
+    }
+
+    #[test]
+    fn periodic_wrap_2d() {
+        let noise_setting = NoiseBuilder::fbm_2d(64, 48)
+            .with_freq(0.1)
+            .with_octaves(3)
+            .with_period(32.0, 24.0)
+            .wrap();
+        let scalar_noise = unsafe { scalar::get_2d_scaled_noise(&noise_setting) };
+        let sse2_noise = unsafe { sse2::get_2d_scaled_noise(&noise_setting) };
+        let sse41_noise = unsafe { sse41::get_2d_scaled_noise(&noise_setting) };
+        let avx2_noise = unsafe { avx2::get_2d_scaled_noise(&noise_setting) };
 
         for i in 0..scalar_noise.len() {
             assert_delta!(scalar_noise[i], sse2_noise[i], 0.1);
             assert_delta!(sse2_noise[i], sse41_noise[i], 0.1);
             assert_delta!(sse41_noise[i], avx2_noise[i], 0.1);
         }
+        for y in 0..24 {
+            for x in 0..32 {
+                let i = y * 64 + x;
+                assert_eq!(avx2_noise[i], avx2_noise[i + 32]);
+                assert_eq!(avx2_noise[i], avx2_noise[i + 24 * 64]);
+            }
+        }
+
+        let (noise, _, _) = NoiseBuilder::perlin_3d(24, 16, 16)
+            .with_freq(0.3)
+            .with_period_3d(12.0, 16.0, 16.0)
+            .generate();
+        for i in 0..noise.len() - 12 {
+            if i % 24 < 12 {
+                assert_delta!(noise[i], noise[i + 12], 0.0001);
+            }
+        }
     }
 
     #[test]
-    fn consistency_2d() {
-        let noise_setting = NoiseBuilder::fbm_2d(233, 233).wrap();
+    fn periodic_wrap_3d() {
+        // Offsets keep samples off simplex ties, where 3d simplex jumps slightly.
+        let noise_settings = [
+            NoiseBuilder::gradient_3d_offset(0.3, 24, 0.7, 24, 0.4, 24)
+                .with_freq(0.3)
+                .with_period_3d(12.0, 12.0, 12.0)
+                .wrap(),
+            NoiseBuilder::cellular_3d_offset(0.3, 24, 0.7, 24, 0.4, 24)
+                .with_freq(0.3)
+                .with_period_3d(12.0, 12.0, 12.0)
+                .wrap(),
+            NoiseBuilder::billow_3d_offset(0.3, 24, 0.7, 24, 0.4, 24)
+                .with_freq(0.3)
+                .with_period_3d(12.0, 12.0, 12.0)
+                .wrap(),
+            NoiseBuilder::fbm_3d_offset(0.3, 24, 0.7, 24, 0.4, 24)
+                .with_freq(0.3)
+                .with_octave_seeds(true)
+                .with_period_3d(12.0, 12.0, 12.0)
+                .wrap(),
+            NoiseBuilder::ridge_3d_offset(0.3, 24, 0.7, 24, 0.4, 24)
+                .with_freq(0.3)
+                .with_base(BaseNoise::Perlin)
+                .with_period_3d(12.0, 12.0, 12.0)
+                .wrap(),
+            NoiseBuilder::turbulence_3d_offset(0.3, 24, 0.7, 24, 0.4, 24)
+                .with_freq(0.3)
+                .with_period_3d(12.0, 12.0, 12.0)
+                .wrap(),
+        ];
+        for noise_setting in noise_settings.iter() {
+            let scalar_noise = unsafe { scalar::get_3d_noise(noise_setting).0 };
+            let avx2_noise = unsafe { avx2::get_3d_noise(noise_setting).0 };
+            for i in 0..scalar_noise.len() {
+                assert_delta!(scalar_noise[i], avx2_noise[i], 0.001);
+            }
+            for z in 0..12 {
+                for y in 0..12 {
+                    for x in 0..12 {
+                        let i = (z * 24 + y) * 24 + x;
+                        assert_delta!(scalar_noise[i], scalar_noise[i + 12], 0.001);
+                        assert_delta!(scalar_noise[i], scalar_noise[i + 12 * 24], 0.001);
+                        assert_delta!(scalar_noise[i], scalar_noise[i + 12 * 24 * 24], 0.001);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "periods must be greater than 0")]
+    fn periodic_rejects_zero_period() {
+        NoiseBuilder::fbm_2d(8, 8).with_period(0.0, 8.0);
+    }
+
+    #[test]
+    fn looping_consistency_2d() {
+        let noise_setting = NoiseBuilder::fbm_2d_looping(30, 20, 8, 1.5)
+            .with_octaves(3)
+            .wrap();
         let scalar_noise = unsafe { scalar::get_2d_scaled_noise(&noise_setting) };
         let sse2_noise = unsafe { sse2::get_2d_scaled_noise(&noise_setting) };
         let sse41_noise = unsafe { sse41::get_2d_scaled_noise(&noise_setting) };
         let avx2_noise = unsafe { avx2::get_2d_scaled_noise(&noise_setting) };
 
+        assert_eq!(scalar_noise.len(), 30 * 20 * 8);
         for i in 0..scalar_noise.len() {
             assert_delta!(scalar_noise[i], sse2_noise[i], 0.1);
             assert_delta!(sse2_noise[i], sse41_noise[i], 0.1);
             assert_delta!(sse41_noise[i], avx2_noise[i], 0.1);
         }
+
+        // Frames sit at the same points of the circle whatever their count
+        let (eight, _, _) = NoiseBuilder::fbm_2d_looping(30, 20, 8, 1.5).generate();
+        let (four, _, _) = NoiseBuilder::fbm_2d_looping(30, 20, 4, 1.5).generate();
+        let frame = 30 * 20;
+        assert_eq!(eight[..frame], four[..frame]);
+        assert_eq!(eight[2 * frame..3 * frame], four[frame..2 * frame]);
+        assert_ne!(eight[..frame], eight[frame..2 * frame]);
     }
 
     #[test]
-    fn consistency_1d() {
-        let noise_setting = NoiseBuilder::fbm_1d(1000).wrap();
-        let scalar_noise = unsafe { scalar::get_1d_scaled_noise(&noise_setting) };
-        let sse2_noise = unsafe { sse2::get_1d_scaled_noise(&noise_setting) };
-        let sse41_noise = unsafe { sse41::get_1d_scaled_noise(&noise_setting) };
-        let avx2_noise = unsafe { avx2::get_1d_scaled_noise(&noise_setting) };
+    fn sphere_cubemap_seams() {
+        let noise_setting = NoiseBuilder::fbm_3d(1, 1, 1)
+            .with_freq(2.0)
+            .with_octaves(3)
+            .wrap();
+        let size = 32;
+        let (faces, _, _) = SphereGenerator::new(noise_setting).cubemap(size);
+        assert_eq!(faces.len(), 6 * size * size);
+        let at = |face: usize, i: usize, j: usize| faces[(face * size + j) * size + i];
+        // +x meets -z, +y meets +z, and neighbouring pixels across the edge
+        // differ no more than neighbours within a face
+        let mut across = 0.0f32;
+        let mut within = 0.0f32;
+        for k in 0..size {
+            across = across.max((at(0, size - 1, k) - at(5, 0, k)).abs());
+            across = across.max((at(2, k, size - 1) - at(4, k, 0)).abs());
+            within = within.max((at(0, size - 2, k) - at(0, size - 1, k)).abs());
+            within = within.max((at(4, k, 0) - at(4, k, 1)).abs());
+        }
+        assert!(across <= within * 1.5);
+
+        let xs: Vec<f32> = (0..37).map(|i| i as f32 * 0.3).collect();
+        let ys: Vec<f32> = (0..37).map(|i| i as f32 * -0.2).collect();
+        let zs: Vec<f32> = (0..37).map(|i| i as f32 * 0.1).collect();
+        let (scalar_noise, _, _) =
+            unsafe { scalar::get_3d_noise_at(&noise_setting, &xs, &ys, &zs) };
+        let (sse2_noise, _, _) = unsafe { sse2::get_3d_noise_at(&noise_setting, &xs, &ys, &zs) };
+        let (sse41_noise, _, _) = unsafe { sse41::get_3d_noise_at(&noise_setting, &xs, &ys, &zs) };
+        let (avx2_noise, _, _) = unsafe { avx2::get_3d_noise_at(&noise_setting, &xs, &ys, &zs) };
+        for i in 0..scalar_noise.len() {
+            assert_delta!(scalar_noise[i], sse2_noise[i], 0.1);
+            assert_delta!(sse2_noise[i], sse41_noise[i], 0.1);
+            assert_delta!(sse41_noise[i], avx2_noise[i], 0.1);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "tileable noise (with_period) can't be evaluated at points")]
+    fn points_reject_period() {
+        let noise_setting = NoiseBuilder::fbm_2d(8, 8).with_period(4.0, 4.0).wrap();
+        get_2d_noise_at(&noise_setting, &[0.5], &[0.5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "looping noise (fbm_2d_looping) can't be evaluated at points")]
+    fn points_reject_looping() {
+        let noise_setting = NoiseBuilder::fbm_2d_looping(8, 8, 4, 1.0).wrap();
+        get_2d_noise_at(&noise_setting, &[0.5], &[0.5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "sphere noise can't be tileable")]
+    fn sphere_rejects_period() {
+        SphereGenerator::new(
+            NoiseBuilder::gradient_3d(8, 8, 8)
+                .with_period_3d(4.0, 4.0, 4.0)
+                .wrap(),
+        );
+    }
+
+    #[test]
+    fn points_match_block_2d() {
+        let noise_setting = NoiseBuilder::ridge_2d_offset(3.0, 13, -2.0, 5)
+            .with_freq(0.1)
+            .with_octaves(4)
+            .wrap();
+        let (block, _, _) = get_2d_noise!(&noise_setting);
+        let mut points = Vec::new();
+        for j in 0..5 {
+            for i in 0..13 {
+                points.push([3.0 + i as f32, -2.0 + j as f32]);
+            }
+        }
+        let at_points = get_2d_noise_at_points(&noise_setting, &points);
+        assert_eq!(at_points.len(), block.len());
+        for i in 0..block.len() {
+            assert_delta!(block[i], at_points[i], 0.0001);
+        }
+
+        // lengths that leave a partial vector on every backend
+        for len in [0, 1, 3, 7, 9, 19] {
+            let xs: Vec<f32> = (0..len).map(|i| i as f32 * 0.7).collect();
+            let ys: Vec<f32> = (0..len).map(|i| i as f32 * -1.3).collect();
+            let zs: Vec<f32> = (0..len).map(|i| i as f32 * 0.4).collect();
+            let ws: Vec<f32> = (0..len).map(|i| i as f32 * 2.1).collect();
+            let simplex = NoiseBuilder::gradient_4d(1, 1, 1, 1).wrap();
+            let (scalar_noise, _, _) =
+                unsafe { scalar::get_4d_noise_at(&simplex, &xs, &ys, &zs, &ws) };
+            let (sse2_noise, _, _) = unsafe { sse2::get_4d_noise_at(&simplex, &xs, &ys, &zs, &ws) };
+            let (sse41_noise, _, _) =
+                unsafe { sse41::get_4d_noise_at(&simplex, &xs, &ys, &zs, &ws) };
+            let (avx2_noise, _, _) = unsafe { avx2::get_4d_noise_at(&simplex, &xs, &ys, &zs, &ws) };
+            assert_eq!(avx2_noise.len(), len);
+            for i in 0..len {
+                assert_delta!(scalar_noise[i], sse2_noise[i], 0.1);
+                assert_delta!(sse2_noise[i], sse41_noise[i], 0.1);
+                assert_delta!(sse41_noise[i], avx2_noise[i], 0.1);
+            }
+            let cellular = NoiseBuilder::cellular_1d(1).wrap();
+            let (scalar_noise, _, _) = unsafe { scalar::get_1d_noise_at(&cellular, &xs) };
+            let avx2_noise = get_1d_noise_at(&cellular, &xs);
+            for i in 0..len {
+                assert_delta!(scalar_noise[i], avx2_noise[i], 0.1);
+            }
+        }
+    }
+
+    #[test]
+    fn fill_pitched_2d() {
+        let noise_setting = NoiseBuilder::ridge_2d_offset(5.0, 13, 2.0, 7)
+            .with_octaves(3)
+            .with_normalize(true)
+            .wrap();
+        let (noise, min, max) = get_2d_noise!(&noise_setting);
+
+        // a 13 x 7 block at column 3, row 2 of a 20 x 10 image
+        let pitch = 20;
+        let mut image = vec![-100.0; pitch * 10];
+        let start = 2 * pitch + 3;
+        let (scalar_range, sse2_range, sse41_range, avx2_range) = unsafe {
+            (
+                scalar::fill_2d_noise_pitched(&noise_setting, &mut image[start..], pitch),
+                sse2::fill_2d_noise_pitched(&noise_setting, &mut image[start..], pitch),
+                sse41::fill_2d_noise_pitched(&noise_setting, &mut image[start..], pitch),
+                avx2::fill_2d_noise_pitched(&noise_setting, &mut image[start..], pitch),
+            )
+        };
+        for range in [scalar_range, sse2_range, sse41_range, avx2_range] {
+            assert_delta!(range.0, min, 0.1);
+            assert_delta!(range.1, max, 0.1);
+        }
+        for y in 0..10 {
+            for x in 0..pitch {
+                let value = image[y * pitch + x];
+                if (3..16).contains(&x) && (2..9).contains(&y) {
+                    assert_delta!(value, noise[(y - 2) * 13 + x - 3], 0.0001);
+                } else {
+                    assert_eq!(value, -100.0);
+                }
+            }
+        }
+
+        let mut out = vec![0.0; 13 * 7];
+        let (filled_min, filled_max) = match noise_setting {
+            NoiseType::Ridge(s) => s.fill(&mut out),
+            _ => unreachable!(),
+        };
+        assert_eq!((filled_min, filled_max), (min, max));
+        assert_eq!(out, noise);
+    }
+
+    #[test]
+    #[should_panic(expected = "1d noise is a single row, its pitch must equal the width")]
+    fn fill_pitched_1d_rejects_pitch() {
+        let mut out = vec![0.0; 32];
+        NoiseBuilder::fbm_1d(16).fill_pitched(&mut out, 32);
+    }
+
+    #[test]
+    #[should_panic(expected = "buffer is too small for the dimensions")]
+    fn fill_pitched_1d_rejects_short_buffer() {
+        let mut out = vec![0.0; 8];
+        NoiseBuilder::fbm_1d(16).fill_pitched(&mut out, 16);
+    }
+
+    #[test]
+    fn transform_swap_and_spacing_2d() {
+        let plain = NoiseBuilder::fbm_2d(24, 24).with_freq(0.07).wrap();
+        let (noise, _, _) = get_2d_noise!(&plain);
+
+        // swapping the axes transposes the block
+        let mut swapped = NoiseBuilder::fbm_2d(24, 24);
+        swapped
+            .with_freq(0.07)
+            .with_transform([[0.0, 1.0], [1.0, 0.0]]);
+        let swapped = swapped.wrap();
+        let scalar_noise = unsafe { scalar::get_2d_noise(&swapped).0 };
+        let sse2_noise = unsafe { sse2::get_2d_noise(&swapped).0 };
+        let sse41_noise = unsafe { sse41::get_2d_noise(&swapped).0 };
+        let avx2_noise = unsafe { avx2::get_2d_noise(&swapped).0 };
+        for y in 0..24 {
+            for x in 0..24 {
+                assert_delta!(scalar_noise[y * 24 + x], noise[x * 24 + y], 0.0001);
+                assert_delta!(scalar_noise[y * 24 + x], sse2_noise[y * 24 + x], 0.1);
+                assert_delta!(sse2_noise[y * 24 + x], sse41_noise[y * 24 + x], 0.1);
+                assert_delta!(sse41_noise[y * 24 + x], avx2_noise[y * 24 + x], 0.1);
+            }
+        }
+
+        // samples two apart, at the same frequency, are every other sample
+        let spaced = NoiseBuilder::fbm_2d(12, 12)
+            .with_freq(0.07)
+            .with_transform([[2.0, 0.0], [0.0, 2.0]])
+            .wrap();
+        let (spaced_noise, _, _) = get_2d_noise!(&spaced);
+        for y in 0..12 {
+            for x in 0..12 {
+                assert_delta!(spaced_noise[y * 12 + x], noise[y * 2 * 24 + x * 2], 0.0001);
+            }
+        }
+
+        // points go through the transform like the samples of a block
+        let xs: Vec<f32> = (0..24 * 24).map(|i| (i % 24) as f32).collect();
+        let ys: Vec<f32> = (0..24 * 24).map(|i| (i / 24) as f32).collect();
+        let at_points = get_2d_noise_at(&swapped, &xs, &ys);
+        for i in 0..at_points.len() {
+            assert_delta!(at_points[i], scalar_noise[i], 0.0001);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "2d noise can't take a 3x3 transform")]
+    fn transform_larger_than_noise() {
+        NoiseBuilder::fbm_2d(8, 8).with_transform([
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+        ]);
+    }
+
+    #[test]
+    fn rows_split_matches_block_3d() {
+        let noise_setting = NoiseBuilder::fbm_3d_offset(0.3, 21, 7.7, 9, -2.1, 5)
+            .with_octaves(3)
+            .with_normalize(true)
+            .wrap();
+        let rows = 9 * 5;
+        let runs = [0..5, 5..6, 6..30, 30..rows];
+        unsafe {
+            for (fill_rows, get_noise) in [
+                (
+                    scalar::fill_3d_noise_rows as noise_helpers::FillRows,
+                    scalar::get_3d_noise as unsafe fn(&NoiseType) -> (Vec<f32>, f32, f32),
+                ),
+                (sse2::fill_3d_noise_rows, sse2::get_3d_noise),
+                (sse41::fill_3d_noise_rows, sse41::get_3d_noise),
+                (avx2::fill_3d_noise_rows, avx2::get_3d_noise),
+            ] {
+                let (noise, min, max) = get_noise(&noise_setting);
+                let mut split = vec![0.0; noise.len()];
+                let (mut split_min, mut split_max) = (f32::MAX, f32::MIN);
+                for run in runs.iter().cloned() {
+                    let out = &mut split[run.start * 21..run.end * 21];
+                    let (run_min, run_max) = fill_rows(&noise_setting, out, 21, run);
+                    split_min = split_min.min(run_min);
+                    split_max = split_max.max(run_max);
+                }
+                assert_eq!(split, noise);
+                assert_eq!((split_min, split_max), (min, max));
+            }
+        }
+    }
+
+    #[test]
+    fn cellular2_index_chain() {
+        // the pair is only checked once both are set
+        let (noise, _, _) = NoiseBuilder::cellular2_2d(8, 8)
+            .with_index0(2)
+            .with_index1(3)
+            .generate();
+        assert_eq!(noise.len(), 64);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid index settings in cellular2 noise")]
+    fn cellular2_index_pair_checked_when_generating() {
+        NoiseBuilder::cellular2_2d(8, 8).with_index0(1).generate();
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid index settings in cellular2 noise")]
+    fn cellular2_indices_checked_by_setter() {
+        NoiseBuilder::cellular2_2d(8, 8).with_indices(2, 2);
+    }
+
+    #[test]
+    fn value_consistency_2d() {
+        let noise_setting = NoiseBuilder::value_2d(100, 100)
+            .with_interpolation(Interpolation::Cubic)
+            .wrap();
+        let scalar_noise = unsafe { scalar::get_2d_scaled_noise(&noise_setting) };
+        let sse2_noise = unsafe { sse2::get_2d_scaled_noise(&noise_setting) };
+        let sse41_noise = unsafe { sse41::get_2d_scaled_noise(&noise_setting) };
+        let avx2_noise = unsafe { avx2::get_2d_scaled_noise(&noise_setting) };
 
         for i in 0..scalar_noise.len() {
             assert_delta!(scalar_noise[i], sse2_noise[i], 0.1);
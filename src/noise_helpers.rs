@@ -1,15 +1,105 @@
 use self::simdeez::*;
 use super::*;
 use crate::cellular::*;
+use crate::curl::*;
+use crate::domain_warp::*;
+use crate::perlin::*;
+use crate::shared::*;
 use crate::simplex::*;
+use crate::terrain::*;
+use crate::value::*;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 use std::f32;
+use std::ops::Range;
 
-macro_rules! get_1d_noise_helper  {
-    ($Setting:expr,$f:expr $(,$arg:expr)*) => {
- {
-    let dim = $Setting.dim;
-    let freq_x = S::set1_ps($Setting.freq_x);
-    let start_x = dim.x;
+/// The transform of a block with the frequency of each axis folded into its
+/// rows, limited to the first `d` axes. Without a transform, a diagonal of
+/// frequencies.
+fn block_transform(dim: &NoiseDimensions, freq: [f32; 4], d: usize) -> [[f32; 4]; 4] {
+    let transform = dim.transform.unwrap_or(IDENTITY_TRANSFORM);
+    let mut m = [[0.0; 4]; 4];
+    for a in 0..d {
+        for b in 0..d {
+            m[a][b] = freq[a] * transform[a][b];
+        }
+    }
+    m
+}
+
+/// Where each noise coordinate starts on a row of a block at `y`, `z` and
+/// `w`, and how far it moves per sample along x
+struct BlockRow<S: Simd> {
+    step: [S::Vf32; 4],
+    start: [S::Vf32; 4],
+}
+
+impl<S: Simd> BlockRow<S> {
+    #[inline(always)]
+    unsafe fn new(m: &[[f32; 4]; 4], y: f32, z: f32, w: f32) -> BlockRow<S> {
+        BlockRow {
+            step: m.map(|row| S::set1_ps(row[0])),
+            start: m.map(|row| S::set1_ps(row[1] * y + row[2] * z + row[3] * w)),
+        }
+    }
+
+    /// The noise coordinates of the samples at the x positions `x`
+    #[inline(always)]
+    unsafe fn at(&self, x: S::Vf32) -> [S::Vf32; 4] {
+        let mut c = self.start;
+        for (c, step) in c.iter_mut().zip(self.step.iter()) {
+            *c = S::add_ps(S::mul_ps(x, *step), *c);
+        }
+        c
+    }
+}
+
+// The block and point loops below take the noise kernel as a closure. Each
+// noise type passes its own, so gets its own copy of the loop. Unoptimized
+// builds keep the copies out of line from the dispatch over noise types, so
+// its frame doesn't need room for the locals of every noise type at once.
+// Optimized builds inline them, so the SIMD intrinsics end up in the backend
+// functions that enable their target features.
+
+/// Min and max of the lanes of `min_s` and `max_s`, and of `min` and `max`
+#[inline(always)]
+unsafe fn reduce_min_max<S: Simd>(
+    min_s: S::Vf32,
+    max_s: S::Vf32,
+    mut min: f32,
+    mut max: f32,
+) -> (f32, f32) {
+    for i in 0..S::VF32_WIDTH {
+        if min_s[i] < min {
+            min = min_s[i];
+        }
+        if max_s[i] > max {
+            max = max_s[i];
+        }
+    }
+    (min, max)
+}
+
+/// The x positions of the first vector of a row starting at `start_x`
+#[inline(always)]
+unsafe fn first_x<S: Simd>(start_x: f32) -> S::Vf32 {
+    let vector_width = S::VF32_WIDTH;
+    let mut x_arr = vec![0.0; vector_width];
+    for (i, x) in x_arr.iter_mut().enumerate() {
+        *x = start_x + i as f32;
+    }
+    S::loadu_ps(&x_arr[0])
+}
+
+#[cfg_attr(not(debug_assertions), inline(always))]
+unsafe fn fill_1d_block<S: Simd, F: Fn(S::Vf32) -> S::Vf32>(
+    dim: &NoiseDimensions,
+    freq_x: f32,
+    out: &mut [f32],
+    noise: F,
+) -> (f32, f32) {
+    let m = block_transform(dim, [freq_x, 0.0, 0.0, 0.0], 1);
+    let coords = BlockRow::<S>::new(&m, 0.0, 0.0, 0.0);
     let width = dim.width;
     let mut min_s = S::set1_ps(f32::MAX);
     let mut max_s = S::set1_ps(f32::MIN);
@@ -17,30 +107,25 @@ macro_rules! get_1d_noise_helper  {
     let mut min = f32::MAX;
     let mut max = f32::MIN;
 
-    let mut result: Vec<f32> = Vec::with_capacity(width);
-    result.set_len(width);
     let mut i = 0;
     let vector_width = S::VF32_WIDTH;
     let remainder = width % vector_width;
-    let mut x_arr = Vec::with_capacity(vector_width);
-    x_arr.set_len(vector_width);
-    for i in (0..vector_width).rev() {
-        x_arr[i] = start_x + i as f32;
-    }
-    let mut x = S::loadu_ps(&x_arr[0]);
+    let mut x = first_x::<S>(dim.x);
     for _ in 0..width / vector_width {
-        let f = $f(S::mul_ps(x, freq_x) $(,$arg)*);
+        let c = coords.at(x);
+        let f = noise(c[0]);
         max_s = S::max_ps(max_s, f);
         min_s = S::min_ps(min_s, f);
-        S::storeu_ps(result.get_unchecked_mut(i), f);
+        S::storeu_ps(out.get_unchecked_mut(i), f);
         i += vector_width;
         x = S::add_ps(x, S::set1_ps(vector_width as f32));
     }
     if remainder != 0 {
-        let f = $f(S::mul_ps(x, freq_x) $(,$arg)*);
+        let c = coords.at(x);
+        let f = noise(c[0]);
         for j in 0..remainder {
             let n = f[j];
-            *result.get_unchecked_mut(i) = n;
+            *out.get_unchecked_mut(i) = n;
             // Note: This is unecessary for large images
             if n < min {
                 min = n;
@@ -51,27 +136,32 @@ macro_rules! get_1d_noise_helper  {
             i += 1;
         }
     }
-    for i in 0..vector_width {
-        if min_s[i] < min {
-            min = min_s[i];
-        }
-        if max_s[i] > max {
-            max = max_s[i];
-        }
-    }
-    (result, min, max)
- }
-    }
+    reduce_min_max::<S>(min_s, max_s, min, max)
 }
 
-macro_rules! get_2d_noise_helper {
-    ($Setting:expr,$f:expr $(,$arg:expr)*)=> {{
-    let dim = $Setting.dim;
-    let freq_x = S::set1_ps($Setting.freq_x);
-    let freq_y = S::set1_ps($Setting.freq_y);
-    let start_x = dim.x;
+macro_rules! get_1d_noise_helper {
+    ($out:expr, $pitch:expr; $Setting:expr, $f:expr $(,$arg:expr)*) => {
+        fill_1d_block::<S, _>(
+            &$Setting.dim,
+            $Setting.freq_x,
+            $out,
+            #[inline(always)]
+            |x| $f(x $(,$arg)*),
+        )
+    };
+}
+
+#[cfg_attr(not(debug_assertions), inline(always))]
+unsafe fn fill_2d_rows<S: Simd, F: Fn(S::Vf32, S::Vf32) -> S::Vf32>(
+    dim: &NoiseDimensions,
+    freq: [f32; 2],
+    out: &mut [f32],
+    pitch: usize,
+    rows: &Range<usize>,
+    noise: F,
+) -> (f32, f32) {
+    let m = block_transform(dim, [freq[0], freq[1], 0.0, 0.0], 2);
     let width = dim.width;
-    let start_y = dim.y;
     let height = dim.height;
 
     let mut min_s = S::set1_ps(f32::MAX);
@@ -79,32 +169,33 @@ macro_rules! get_2d_noise_helper {
     let mut min = f32::MAX;
     let mut max = f32::MIN;
 
-    let mut result = Vec::with_capacity(width * height);
-    result.set_len(width * height);
-    let mut y = S::set1_ps(start_y);
-    let mut i = 0;
+    let mut y = dim.y;
     let vector_width = S::VF32_WIDTH;
     let remainder = width % vector_width;
-    let mut x_arr = Vec::with_capacity(vector_width);
-    x_arr.set_len(vector_width);
-    for i in (0..vector_width).rev() {
-        x_arr[i] = start_x + i as f32;
-    }
-    for _ in 0..height {
-        let mut x = S::loadu_ps(&x_arr[0]);
+    let start = first_x::<S>(dim.x);
+    for row in 0..height {
+        if !rows.contains(&row) {
+            y += 1.0;
+            continue;
+        }
+        let mut i = (row - rows.start) * pitch;
+        let coords = BlockRow::<S>::new(&m, y, 0.0, 0.0);
+        let mut x = start;
         for _ in 0..width / vector_width {
-            let f = $f(S::mul_ps(x, freq_x), S::mul_ps(y, freq_y) $(,$arg)*);
+            let c = coords.at(x);
+            let f = noise(c[0], c[1]);
             max_s = S::max_ps(max_s, f);
             min_s = S::min_ps(min_s, f);
-            S::storeu_ps(result.get_unchecked_mut(i), f);
+            S::storeu_ps(out.get_unchecked_mut(i), f);
             i += vector_width;
             x = S::add_ps(x, S::set1_ps(vector_width as f32));
         }
         if remainder != 0 {
-            let f = $f(S::mul_ps(x, freq_x), S::mul_ps(y, freq_y) $(,$arg)*);
+            let c = coords.at(x);
+            let f = noise(c[0], c[1]);
             for j in 0..remainder {
                 let n = f[j];
-                *result.get_unchecked_mut(i) = n;
+                *out.get_unchecked_mut(i) = n;
                 if n < min {
                     min = n;
                 }
@@ -114,68 +205,168 @@ macro_rules! get_2d_noise_helper {
                 i += 1;
             }
         }
-        y = S::add_ps(y, S::set1_ps(1.0));
+        y += 1.0;
     }
-    for i in 0..vector_width {
-        if min_s[i] < min {
-            min = min_s[i];
+    reduce_min_max::<S>(min_s, max_s, min, max)
+}
+
+macro_rules! get_2d_noise_helper {
+    ($out:expr, $pitch:expr, $rows:expr; $Setting:expr, $f:expr $(,$arg:expr)*) => {
+        fill_2d_rows::<S, _>(
+            &$Setting.dim,
+            [$Setting.freq_x, $Setting.freq_y],
+            $out,
+            $pitch,
+            &$rows,
+            #[inline(always)]
+            |x, y| $f(x, y $(,$arg)*),
+        )
+    };
+}
+
+/// Like `fill_2d_rows`, but the block repeats every `period` samples.
+/// Each axis is wrapped around a circle as long as its period, and the 4d
+/// kernel `noise` is sampled on the torus they make, so features keep their
+/// size. The circles only depend on the row and column, so their points are
+/// worked out once, with scalar trig.
+#[cfg_attr(not(debug_assertions), inline(always))]
+unsafe fn fill_2d_periodic_rows<S: Simd, F: Fn(S::Vf32, S::Vf32, S::Vf32, S::Vf32) -> S::Vf32>(
+    dim: &NoiseDimensions,
+    (period, freq): ([f32; 2], [f32; 2]),
+    out: &mut [f32],
+    pitch: usize,
+    rows: &Range<usize>,
+    noise: F,
+) -> (f32, f32) {
+    let width = dim.width;
+    let height = dim.height;
+    let vector_width = S::VF32_WIDTH;
+    let circle = |start: f32, len: usize, period: f32, freq: f32| -> (Vec<f32>, Vec<f32>) {
+        let radius = period * freq / (f32::consts::PI * 2.0);
+        (0..len)
+            .map(|i| {
+                let angle = (start + i as f32).rem_euclid(period) / period * f32::consts::PI * 2.0;
+                (radius * angle.cos(), radius * angle.sin())
+            })
+            .unzip()
+    };
+    // Padded to whole vectors, so the last one can be loaded
+    let (x_cos, x_sin) = circle(dim.x, width + vector_width, period[0], freq[0]);
+    let (y_cos, y_sin) = circle(dim.y, height, period[1], freq[1]);
+
+    let mut min_s = S::set1_ps(f32::MAX);
+    let mut max_s = S::set1_ps(f32::MIN);
+    let mut min = f32::MAX;
+    let mut max = f32::MIN;
+
+    for row in 0..height {
+        if !rows.contains(&row) {
+            continue;
         }
-        if max_s[i] > max {
-            max = max_s[i];
+        let mut i = (row - rows.start) * pitch;
+        let z = S::set1_ps(y_cos[row]);
+        let w = S::set1_ps(y_sin[row]);
+        let mut column = 0;
+        while column < width {
+            let f = noise(
+                S::loadu_ps(&x_cos[column]),
+                S::loadu_ps(&x_sin[column]),
+                z,
+                w,
+            );
+            if width - column >= vector_width {
+                max_s = S::max_ps(max_s, f);
+                min_s = S::min_ps(min_s, f);
+                S::storeu_ps(out.get_unchecked_mut(i), f);
+                i += vector_width;
+            } else {
+                for j in 0..width - column {
+                    let n = f[j];
+                    *out.get_unchecked_mut(i) = n;
+                    if n < min {
+                        min = n;
+                    }
+                    if n > max {
+                        max = n;
+                    }
+                    i += 1;
+                }
+            }
+            column += vector_width;
         }
     }
-    (result, min, max)
+    reduce_min_max::<S>(min_s, max_s, min, max)
+}
 
-}};
+macro_rules! get_2d_periodic_noise_helper {
+    ($out:expr, $pitch:expr, $rows:expr; $Setting:expr, $f:expr $(,$arg:expr)*) => {{
+        let period = $Setting.period.unwrap();
+        fill_2d_periodic_rows::<S, _>(
+            &$Setting.dim,
+            ([period[0], period[1]], [$Setting.freq_x, $Setting.freq_y]),
+            $out,
+            $pitch,
+            &$rows,
+            #[inline(always)]
+            |x, y, z, w| $f(x, y, z, w $(,$arg)*),
+        )
+    }};
 }
 
-macro_rules! get_3d_noise_helper {
-    ($Setting:expr,$f:expr $(,$arg:expr)*) => {{
-    let dim = $Setting.dim;
-    let freq_x = S::set1_ps($Setting.freq_x);
-    let freq_y = S::set1_ps($Setting.freq_y);
-    let freq_z = S::set1_ps($Setting.freq_z);
-    let start_x = dim.x;
+/// Like `fill_2d_rows`, but generates `dim.time` frames one after the
+/// other, that loop back to the first. Each frame samples the 4d kernel `noise`
+/// with z and w on a circle of radius `radius`, a full turn over all frames.
+#[cfg_attr(not(debug_assertions), inline(always))]
+unsafe fn fill_2d_looping_rows<S: Simd, F: Fn(S::Vf32, S::Vf32, S::Vf32, S::Vf32) -> S::Vf32>(
+    dim: &NoiseDimensions,
+    (radius, freq): (f32, [f32; 2]),
+    out: &mut [f32],
+    pitch: usize,
+    rows: &Range<usize>,
+    noise: F,
+) -> (f32, f32) {
+    let m = block_transform(dim, [freq[0], freq[1], 0.0, 0.0], 2);
     let width = dim.width;
-    let start_y = dim.y;
     let height = dim.height;
-    let start_z = dim.z;
-    let depth = dim.depth;
+    let frames = dim.time;
 
     let mut min_s = S::set1_ps(f32::MAX);
     let mut max_s = S::set1_ps(f32::MIN);
     let mut min = f32::MAX;
     let mut max = f32::MIN;
 
-    let mut result = Vec::with_capacity(width * height * depth);
-    result.set_len(width * height * depth);
-    let mut i = 0;
     let vector_width = S::VF32_WIDTH;
     let remainder = width % vector_width;
-    let mut x_arr = Vec::with_capacity(vector_width);
-    x_arr.set_len(vector_width);
-    for i in (0..vector_width).rev() {
-        x_arr[i] = start_x + i as f32;
-    }
-
-    let mut z = S::set1_ps(start_z);
-    for _ in 0..depth {
-        let mut y = S::set1_ps(start_y);
-        for _ in 0..height {
-            let mut x = S::loadu_ps(&x_arr[0]);
+    let start = first_x::<S>(dim.x);
+    for frame in 0..frames {
+        let angle = frame as f32 / frames as f32 * f32::consts::PI * 2.0;
+        let z = S::set1_ps(radius * angle.cos());
+        let w = S::set1_ps(radius * angle.sin());
+        let mut y = dim.y;
+        for row in 0..height {
+            let flat = frame * height + row;
+            if !rows.contains(&flat) {
+                y += 1.0;
+                continue;
+            }
+            let mut i = (flat - rows.start) * pitch;
+            let coords = BlockRow::<S>::new(&m, y, 0.0, 0.0);
+            let mut x = start;
             for _ in 0..width / vector_width {
-                let f = $f(S::mul_ps(x, freq_x), S::mul_ps(y, freq_y), S::mul_ps(z, freq_z) $(,$arg)*);
+                let c = coords.at(x);
+                let f = noise(c[0], c[1], z, w);
                 max_s = S::max_ps(max_s, f);
                 min_s = S::min_ps(min_s, f);
-                S::storeu_ps(result.get_unchecked_mut(i), f);
+                S::storeu_ps(out.get_unchecked_mut(i), f);
                 i += vector_width;
                 x = S::add_ps(x, S::set1_ps(vector_width as f32));
             }
             if remainder != 0 {
-            let f = $f(S::mul_ps(x, freq_x), S::mul_ps(y, freq_y), S::mul_ps(z, freq_z) $(,$arg)*);
+                let c = coords.at(x);
+                let f = noise(c[0], c[1], z, w);
                 for j in 0..remainder {
                     let n = f[j];
-                    *result.get_unchecked_mut(i) = n;
+                    *out.get_unchecked_mut(i) = n;
                     if n < min {
                         min = n;
                     }
@@ -185,36 +376,235 @@ macro_rules! get_3d_noise_helper {
                     i += 1;
                 }
             }
-            y = S::add_ps(y, S::set1_ps(1.0));
+            y += 1.0;
         }
-        z = S::add_ps(z, S::set1_ps(1.0));
     }
-    for i in 0..vector_width {
-        if min_s[i] < min {
-            min = min_s[i];
+    reduce_min_max::<S>(min_s, max_s, min, max)
+}
+
+macro_rules! get_2d_looping_noise_helper {
+    ($out:expr, $pitch:expr, $rows:expr; $Setting:expr, $f:expr $(,$arg:expr)*) => {
+        fill_2d_looping_rows::<S, _>(
+            &$Setting.dim,
+            ($Setting.looping.unwrap(), [$Setting.freq_x, $Setting.freq_y]),
+            $out,
+            $pitch,
+            &$rows,
+            #[inline(always)]
+            |x, y, z, w| $f(x, y, z, w $(,$arg)*),
+        )
+    };
+}
+
+/// Maps a vector of points through the first `N` rows and columns of `m`
+#[inline(always)]
+unsafe fn transform_points<S: Simd, const N: usize>(
+    m: &[[f32; 4]; 4],
+    p: [S::Vf32; N],
+) -> [S::Vf32; N] {
+    std::array::from_fn(|a| {
+        let mut c = S::mul_ps(p[0], S::set1_ps(m[a][0]));
+        for (b, &axis) in p.iter().enumerate().skip(1) {
+            c = S::add_ps(c, S::mul_ps(axis, S::set1_ps(m[a][b])));
         }
-        if max_s[i] > max {
-            max = max_s[i];
+        c
+    })
+}
+
+/// Evaluates `noise` at lists of points rather than on a grid, one list per
+/// axis, mapped through the transform of `dim` and scaled by the frequency of
+/// each axis in `freq`. The last vector is padded with zeros when the points
+/// don't fill it.
+#[cfg_attr(not(debug_assertions), inline(always))]
+unsafe fn get_points<S: Simd, const N: usize, F: Fn([S::Vf32; N]) -> S::Vf32>(
+    dim: &NoiseDimensions,
+    axes: [&[f32]; N],
+    freq: [f32; N],
+    noise: F,
+) -> (Vec<f32>, f32, f32) {
+    let mut freq_4d = [0.0; 4];
+    freq_4d[..N].copy_from_slice(&freq);
+    let m = block_transform(dim, freq_4d, N);
+    let len = axes[0].len();
+    let vector_width = S::VF32_WIDTH;
+
+    let mut min_s = S::set1_ps(f32::MAX);
+    let mut max_s = S::set1_ps(f32::MIN);
+    let mut min = f32::MAX;
+    let mut max = f32::MIN;
+
+    let mut result = vec![0.0; len];
+    let mut i = 0;
+    while i + vector_width <= len {
+        let f = noise(transform_points::<S, N>(
+            &m,
+            std::array::from_fn(|a| S::loadu_ps(&axes[a][i])),
+        ));
+        max_s = S::max_ps(max_s, f);
+        min_s = S::min_ps(min_s, f);
+        S::storeu_ps(result.get_unchecked_mut(i), f);
+        i += vector_width;
+    }
+    if i < len {
+        let padded = axes.map(|axis| {
+            let mut padded = vec![0.0; vector_width];
+            padded[..len - i].copy_from_slice(&axis[i..]);
+            padded
+        });
+        let f = noise(transform_points::<S, N>(
+            &m,
+            std::array::from_fn(|a| S::loadu_ps(&padded[a][0])),
+        ));
+        for j in 0..len - i {
+            let n = f[j];
+            *result.get_unchecked_mut(i + j) = n;
+            if n < min {
+                min = n;
+            }
+            if n > max {
+                max = n;
+            }
         }
     }
+    let (min, max) = reduce_min_max::<S>(min_s, max_s, min, max);
     (result, min, max)
-}};
 }
 
-macro_rules! get_4d_noise_helper {
-    ($Setting:expr,$f:expr $(,$arg:expr)*) => {{
-    let dim = $Setting.dim;
-    let freq_x = S::set1_ps($Setting.freq_x);
-    let freq_y = S::set1_ps($Setting.freq_y);
-    let freq_z = S::set1_ps($Setting.freq_z);
-    let freq_w = S::set1_ps($Setting.freq_w);
-    let start_x = dim.x;
+/// Evaluates `$f` at the points in the lists named by `$axis`, each scaled by
+/// the frequency field of the settings named after it, see `get_points`
+macro_rules! get_points_helper {
+    ($Setting:expr, [$($axis:ident * $freq:ident),*], $f:expr $(,$arg:expr)*) => {
+        get_points::<S, _, _>(
+            &$Setting.dim,
+            [$($axis),*],
+            [$($Setting.$freq),*],
+            #[inline(always)]
+            |[$($axis),*]| $f($($axis),* $(,$arg)*),
+        )
+    };
+}
+
+#[cfg_attr(not(debug_assertions), inline(always))]
+unsafe fn fill_3d_rows<S: Simd, F: Fn(S::Vf32, S::Vf32, S::Vf32) -> S::Vf32>(
+    dim: &NoiseDimensions,
+    freq: [f32; 3],
+    out: &mut [f32],
+    pitch: usize,
+    rows: &Range<usize>,
+    noise: F,
+) -> (f32, f32) {
+    let m = block_transform(dim, [freq[0], freq[1], freq[2], 0.0], 3);
+    let width = dim.width;
+    let height = dim.height;
+    let depth = dim.depth;
+
+    let mut min_s = S::set1_ps(f32::MAX);
+    let mut max_s = S::set1_ps(f32::MIN);
+    let mut min = f32::MAX;
+    let mut max = f32::MIN;
+
+    let vector_width = S::VF32_WIDTH;
+    let remainder = width % vector_width;
+    let start = first_x::<S>(dim.x);
+
+    let mut z = dim.z;
+    for layer in 0..depth {
+        let mut y = dim.y;
+        for row in 0..height {
+            let flat = layer * height + row;
+            if !rows.contains(&flat) {
+                y += 1.0;
+                continue;
+            }
+            let mut i = (flat - rows.start) * pitch;
+            let coords = BlockRow::<S>::new(&m, y, z, 0.0);
+            let mut x = start;
+            for _ in 0..width / vector_width {
+                let c = coords.at(x);
+                let f = noise(c[0], c[1], c[2]);
+                max_s = S::max_ps(max_s, f);
+                min_s = S::min_ps(min_s, f);
+                S::storeu_ps(out.get_unchecked_mut(i), f);
+                i += vector_width;
+                x = S::add_ps(x, S::set1_ps(vector_width as f32));
+            }
+            if remainder != 0 {
+                let c = coords.at(x);
+                let f = noise(c[0], c[1], c[2]);
+                for j in 0..remainder {
+                    let n = f[j];
+                    *out.get_unchecked_mut(i) = n;
+                    if n < min {
+                        min = n;
+                    }
+                    if n > max {
+                        max = n;
+                    }
+                    i += 1;
+                }
+            }
+            y += 1.0;
+        }
+        z += 1.0;
+    }
+    reduce_min_max::<S>(min_s, max_s, min, max)
+}
+
+macro_rules! get_3d_noise_helper {
+    ($out:expr, $pitch:expr, $rows:expr; $Setting:expr, $f:expr $(,$arg:expr)*) => {
+        fill_3d_rows::<S, _>(
+            &$Setting.dim,
+            [$Setting.freq_x, $Setting.freq_y, $Setting.freq_z],
+            $out,
+            $pitch,
+            &$rows,
+            #[inline(always)]
+            |x, y, z| $f(x, y, z $(,$arg)*),
+        )
+    };
+}
+
+/// Like `get_3d_noise_helper`, for kernels on a lattice that repeats every
+/// `period` cells, passed to `$f` after the coordinates. The frequency is
+/// adjusted so a whole multiple of `$step` cells fit in the period of the
+/// settings.
+macro_rules! get_3d_periodic_noise_helper {
+    ($out:expr, $pitch:expr, $rows:expr; $Setting:expr, $step:expr, $f:expr $(,$arg:expr)*) => {{
+        let period = $Setting.period.unwrap();
+        let step = $step;
+        let cells = [
+            (period[0] * $Setting.freq_x / step).round().max(1.0) * step,
+            (period[1] * $Setting.freq_y / step).round().max(1.0) * step,
+            (period[2] * $Setting.freq_z / step).round().max(1.0) * step,
+        ];
+        fill_3d_rows::<S, _>(
+            &$Setting.dim,
+            [
+                cells[0] / period[0],
+                cells[1] / period[1],
+                cells[2] / period[2],
+            ],
+            $out,
+            $pitch,
+            &$rows,
+            #[inline(always)]
+            |x, y, z| $f(x, y, z, cells $(,$arg)*),
+        )
+    }};
+}
+#[cfg_attr(not(debug_assertions), inline(always))]
+unsafe fn fill_4d_rows<S: Simd, F: Fn(S::Vf32, S::Vf32, S::Vf32, S::Vf32) -> S::Vf32>(
+    dim: &NoiseDimensions,
+    freq: [f32; 4],
+    out: &mut [f32],
+    pitch: usize,
+    rows: &Range<usize>,
+    noise: F,
+) -> (f32, f32) {
+    let m = block_transform(dim, freq, 4);
     let width = dim.width;
-    let start_y = dim.y;
     let height = dim.height;
-    let start_z = dim.z;
     let depth = dim.depth;
-    let start_w = dim.w;
     let time = dim.time;
 
     let mut min_s = S::set1_ps(f32::MAX);
@@ -222,36 +612,38 @@ macro_rules! get_4d_noise_helper {
     let mut min = f32::MAX;
     let mut max = f32::MIN;
 
-    let mut result = Vec::with_capacity(width * height * depth * time);
-    result.set_len(width * height * depth * time);
-    let mut i = 0;
     let vector_width = S::VF32_WIDTH;
     let remainder = width % vector_width;
-    let mut x_arr = Vec::with_capacity(vector_width);
-    x_arr.set_len(vector_width);
-    for i in (0..vector_width).rev() {
-        x_arr[i] = start_x + i as f32;
-    }
-    let mut w = S::set1_ps(start_w);
-    for _ in 0..time {
-        let mut z = S::set1_ps(start_z);
-        for _ in 0..depth {
-            let mut y = S::set1_ps(start_y);
-            for _ in 0..height {
-                let mut x = S::loadu_ps(&x_arr[0]);
+    let start = first_x::<S>(dim.x);
+    let mut w = dim.w;
+    for t in 0..time {
+        let mut z = dim.z;
+        for layer in 0..depth {
+            let mut y = dim.y;
+            for row in 0..height {
+                let flat = (t * depth + layer) * height + row;
+                if !rows.contains(&flat) {
+                    y += 1.0;
+                    continue;
+                }
+                let mut i = (flat - rows.start) * pitch;
+                let coords = BlockRow::<S>::new(&m, y, z, w);
+                let mut x = start;
                 for _ in 0..width / vector_width {
-                    let f = $f(S::mul_ps(x, freq_x), S::mul_ps(y, freq_y), S::mul_ps(z, freq_z), S::mul_ps(w, freq_w) $(,$arg)*);
+                    let c = coords.at(x);
+                    let f = noise(c[0], c[1], c[2], c[3]);
                     max_s = S::max_ps(max_s, f);
                     min_s = S::min_ps(min_s, f);
-                    S::storeu_ps(result.get_unchecked_mut(i), f);
+                    S::storeu_ps(out.get_unchecked_mut(i), f);
                     i += vector_width;
                     x = S::add_ps(x, S::set1_ps(vector_width as f32));
                 }
                 if remainder != 0 {
-                    let f = $f(S::mul_ps(x, freq_x), S::mul_ps(y, freq_y), S::mul_ps(z, freq_z), S::mul_ps(w, freq_w) $(,$arg)*);
+                    let c = coords.at(x);
+                    let f = noise(c[0], c[1], c[2], c[3]);
                     for j in 0..remainder {
                         let n = f[j];
-                        *result.get_unchecked_mut(i) = n;
+                        *out.get_unchecked_mut(i) = n;
                         // Note: This is unecessary for large images
                         if n < min {
                             min = n;
@@ -262,59 +654,535 @@ macro_rules! get_4d_noise_helper {
                         i += 1;
                     }
                 }
-                y = S::add_ps(y, S::set1_ps(1.0));
+                y += 1.0;
             }
-            z = S::add_ps(z, S::set1_ps(1.0));
+            z += 1.0;
         }
-        w = S::add_ps(w, S::set1_ps(1.0));
+        w += 1.0;
     }
-    for i in 0..vector_width {
-        if min_s[i] < min {
-            min = min_s[i];
+    reduce_min_max::<S>(min_s, max_s, min, max)
+}
+
+macro_rules! get_4d_noise_helper {
+    ($out:expr, $pitch:expr, $rows:expr; $Setting:expr, $f:expr $(,$arg:expr)*) => {
+        fill_4d_rows::<S, _>(
+            &$Setting.dim,
+            [$Setting.freq_x, $Setting.freq_y, $Setting.freq_z, $Setting.freq_w],
+            $out,
+            $pitch,
+            &$rows,
+            #[inline(always)]
+            |x, y, z, w| $f(x, y, z, w $(,$arg)*),
+        )
+    };
+}
+
+/// Every 1d noise type, each generated with `$helper`, so blocks and lists of
+/// points share one dispatch
+macro_rules! noise_1d_match {
+    ($noise_type:expr, $helper:ident) => {
+        match $noise_type {
+            NoiseType::Fbm(s) if s.variation.is_varied() => $helper!(
+                s,
+                fractal::varied_1d::<S, _, _>,
+                S::set1_ps(s.lacunarity),
+                S::set1_ps(s.gain),
+                s.octaves,
+                s.dim.seed,
+                s.variation,
+                |x, seed| fractal::base_1d::<S>(s.base, x, seed),
+                |result, n, amp| S::fmadd_ps(n, amp, result)
+            ),
+            NoiseType::Fbm(s) => match s.base {
+                BaseNoise::Simplex => $helper!(
+                    s,
+                    fbm_1d::<S>,
+                    S::set1_ps(s.lacunarity),
+                    S::set1_ps(s.gain),
+                    s.octaves,
+                    s.dim.seed
+                ),
+                base => $helper!(
+                    s,
+                    fractal::fbm_1d::<S, _>,
+                    S::set1_ps(s.lacunarity),
+                    S::set1_ps(s.gain),
+                    s.octaves,
+                    s.dim.seed,
+                    |x, seed| fractal::base_1d::<S>(base, x, seed)
+                ),
+            },
+            NoiseType::Billow(s) if s.variation.is_varied() => $helper!(
+                s,
+                fractal::varied_1d::<S, _, _>,
+                S::set1_ps(s.lacunarity),
+                S::set1_ps(s.gain),
+                s.octaves,
+                s.dim.seed,
+                s.variation,
+                |x, seed| simplex_1d::<S>(x, seed),
+                |result, n, amp| {
+                    let billow = S::abs_ps(S::mul_ps(n, S::set1_ps(2.0 * SIMPLEX_1D_SCALE)));
+                    S::fmadd_ps(S::sub_ps(billow, S::set1_ps(1.0)), amp, result)
+                }
+            ),
+            NoiseType::Billow(s) => $helper!(
+                s,
+                billow_1d::<S>,
+                S::set1_ps(s.lacunarity),
+                S::set1_ps(s.gain),
+                s.octaves,
+                s.dim.seed
+            ),
+            NoiseType::Ridge(s) if s.variation.is_varied() => $helper!(
+                s,
+                fractal::varied_1d::<S, _, _>,
+                S::set1_ps(s.lacunarity),
+                S::set1_ps(s.gain),
+                s.octaves,
+                s.dim.seed,
+                s.variation,
+                |x, seed| fractal::base_1d::<S>(s.base, x, seed),
+                |result, n, amp| {
+                    S::add_ps(result, S::fnmadd_ps(S::abs_ps(n), amp, S::set1_ps(1.0)))
+                }
+            ),
+            NoiseType::Ridge(s) => match s.base {
+                BaseNoise::Simplex => $helper!(
+                    s,
+                    ridge_1d::<S>,
+                    S::set1_ps(s.lacunarity),
+                    S::set1_ps(s.gain),
+                    s.octaves,
+                    s.dim.seed
+                ),
+                base => $helper!(
+                    s,
+                    fractal::ridge_1d::<S, _>,
+                    S::set1_ps(s.lacunarity),
+                    S::set1_ps(s.gain),
+                    s.octaves,
+                    s.dim.seed,
+                    |x, seed| fractal::base_1d::<S>(base, x, seed)
+                ),
+            },
+            NoiseType::Turbulence(s) if s.variation.is_varied() => $helper!(
+                s,
+                fractal::varied_1d::<S, _, _>,
+                S::set1_ps(s.lacunarity),
+                S::set1_ps(s.gain),
+                s.octaves,
+                s.dim.seed,
+                s.variation,
+                |x, seed| fractal::base_1d::<S>(s.base, x, seed),
+                |result, n, amp| S::add_ps(result, S::abs_ps(S::mul_ps(n, amp)))
+            ),
+            NoiseType::Turbulence(s) => match s.base {
+                BaseNoise::Simplex => $helper!(
+                    s,
+                    turbulence_1d::<S>,
+                    S::set1_ps(s.lacunarity),
+                    S::set1_ps(s.gain),
+                    s.octaves,
+                    s.dim.seed
+                ),
+                base => $helper!(
+                    s,
+                    fractal::turbulence_1d::<S, _>,
+                    S::set1_ps(s.lacunarity),
+                    S::set1_ps(s.gain),
+                    s.octaves,
+                    s.dim.seed,
+                    |x, seed| fractal::base_1d::<S>(base, x, seed)
+                ),
+            },
+            NoiseType::HybridMultifractal(s) => $helper!(s, hybrid_multifractal_1d::<S>, s),
+            NoiseType::HeteroTerrain(s) => $helper!(s, hetero_terrain_1d::<S>, s),
+            NoiseType::RidgedMultifractal(s) => $helper!(s, ridged_multifractal_1d::<S>, s),
+            NoiseType::Gradient(s) => $helper!(s, simplex_1d::<S>, s.dim.seed),
+            NoiseType::Perlin(s) => $helper!(s, perlin_1d::<S>, s.dim.seed),
+            NoiseType::Value(s) => {
+                $helper!(s, value_1d::<S>, s.interpolation, s.dim.seed)
+            }
+            NoiseType::Cellular(s) => $helper!(
+                s,
+                cellular_1d::<S>,
+                s.distance_function,
+                s.return_type,
+                S::set1_ps(s.jitter),
+                s.dim.seed
+            ),
+            NoiseType::Cellular2(s) => $helper!(
+                s,
+                cellular2_1d::<S>,
+                s.distance_function,
+                s.return_type,
+                S::set1_ps(s.jitter),
+                s.index0,
+                s.index1,
+                s.dim.seed
+            ),
+            NoiseType::DomainWarp(s) => $helper!(
+                s,
+                domain_warp_1d::<S>,
+                s.base,
+                S::set1_ps(s.lacunarity),
+                S::set1_ps(s.gain),
+                s.octaves,
+                s.warps(),
+                s.dim.seed
+            ),
+            NoiseType::IqFbm(_) | NoiseType::Swiss(_) | NoiseType::Jordan(_) => {
+                unreachable!("terrain fractal settings are 2d or 3d")
+            }
         }
-        if max_s[i] > max {
-            max = max_s[i];
+    };
+}
+
+/// Panics unless `len` is exactly one value per sample of the block
+fn check_block_len(len: usize, width: usize, rows: usize) {
+    if len != width * rows {
+        panic!("buffer length doesn't match the dimensions");
+    }
+}
+
+/// Panics unless a buffer of `len` values has room for `rows` rows of
+/// `width` values, each starting `pitch` values after the last
+fn check_pitched_len(len: usize, width: usize, rows: usize, pitch: usize) {
+    if pitch < width {
+        panic!("pitch is smaller than the width");
+    }
+    if rows > 0 && width > 0 && len < (rows - 1) * pitch + width {
+        panic!("buffer is too small for the dimensions");
+    }
+}
+
+/// Panics unless `pitch` is the width, 1d noise being a single row, and a
+/// buffer of `len` values has room for it
+pub(crate) fn check_1d_pitched_len(len: usize, width: usize, pitch: usize) {
+    if pitch != width {
+        panic!("1d noise is a single row, its pitch must equal the width");
+    }
+    check_pitched_len(len, width, 1, pitch);
+}
+
+/// Applies the normalization of the settings, if any, to each row of a
+/// filled block, and to its min and max. Values between rows are left alone.
+unsafe fn normalize_rows<S: Simd>(
+    noise_type: &NoiseType,
+    out: &mut [f32],
+    (width, rows, pitch): (usize, usize, usize),
+    min: f32,
+    max: f32,
+) -> (f32, f32) {
+    match noise_type.normalization() {
+        Some((multiplier, offset)) => {
+            for row in 0..rows {
+                transform_noise::<S>(
+                    multiplier,
+                    offset,
+                    &mut out[row * pitch..row * pitch + width],
+                );
+            }
+            (min * multiplier + offset, max * multiplier + offset)
         }
+        None => (min, max),
     }
-    (result, min, max)
-}};
+}
+
+/// Fills the rows of a block given as a range, see `fill_2d_noise_rows`.
+/// Backends pass their own, compiled for their instruction set.
+pub type FillRows = unsafe fn(&NoiseType, &mut [f32], usize, Range<usize>) -> (f32, f32);
+
+/// Fills every row of a block with `fill_rows`. With the `rayon` feature the
+/// rows are split into runs filled on separate threads. Rows come out the
+/// same either way, and the min and max of the runs are combined.
+unsafe fn fill_block(
+    noise_type: &NoiseType,
+    out: &mut [f32],
+    (rows, pitch): (usize, usize),
+    fill_rows: FillRows,
+) -> (f32, f32) {
+    #[cfg(feature = "rayon")]
+    {
+        let threads = rayon::current_num_threads();
+        if threads > 1 && rows > 1 && pitch > 0 {
+            // a few runs per thread, so slower runs even out
+            let runs = threads * 4;
+            let run_rows = rows.div_ceil(runs);
+            return out
+                .par_chunks_mut(run_rows * pitch)
+                .take(rows.div_ceil(run_rows))
+                .enumerate()
+                .map(|(run, out)| {
+                    let first = run * run_rows;
+                    fill_rows(noise_type, out, pitch, first..rows.min(first + run_rows))
+                })
+                .reduce(|| (f32::MAX, f32::MIN), |a, b| (a.0.min(b.0), a.1.max(b.1)));
+        }
+    }
+    fill_rows(noise_type, out, pitch, 0..rows)
 }
 
 #[inline(always)]
 pub unsafe fn get_1d_noise<S: Simd>(noise_type: &NoiseType) -> (Vec<f32>, f32, f32) {
-    match noise_type {
-        NoiseType::Fbm(s) => get_1d_noise_helper!(
-            s,
-            fbm_1d::<S>,
-            S::set1_ps(s.lacunarity),
-            S::set1_ps(s.gain),
-            s.octaves,
-            s.dim.seed
-        ),
-        NoiseType::Ridge(s) => get_1d_noise_helper!(
-            s,
-            ridge_1d::<S>,
-            S::set1_ps(s.lacunarity),
-            S::set1_ps(s.gain),
-            s.octaves,
-            s.dim.seed
-        ),
-        NoiseType::Turbulence(s) => get_1d_noise_helper!(
-            s,
-            turbulence_1d::<S>,
-            S::set1_ps(s.lacunarity),
-            S::set1_ps(s.gain),
-            s.octaves,
-            s.dim.seed
-        ),
-        NoiseType::Gradient(s) => get_1d_noise_helper!(s, simplex_1d::<S>, s.dim.seed),
-        NoiseType::Cellular(_) => {
-            panic!("not implemented");
-        }
-        NoiseType::Cellular2(_) => {
-            panic!("not implemented");
-        }
+    let mut noise = vec![0.0; noise_type.get_dimensions().width];
+    let (min, max) = fill_1d_noise::<S>(noise_type, &mut noise);
+    (noise, min, max)
+}
+
+/// Fills `out`, which must hold exactly one value per sample, with a block of
+/// 1d noise, and returns the min and max value, like `get_1d_noise`.
+#[inline(always)]
+pub unsafe fn fill_1d_noise<S: Simd>(noise_type: &NoiseType, out: &mut [f32]) -> (f32, f32) {
+    let width = noise_type.get_dimensions().width;
+    check_block_len(out.len(), width, 1);
+    macro_rules! block_helper {
+        ($($arg:tt)*) => {
+            get_1d_noise_helper!(out, width; $($arg)*)
+        };
     }
+    let (min, max) = noise_1d_match!(noise_type, block_helper);
+    normalize_rows::<S>(noise_type, out, (width, 1, width), min, max)
+}
+
+/// Every 2d noise type, each generated with `$helper`, or `$periodic_helper`
+/// for tileable noise and `$looping_helper` for looping noise, so blocks and
+/// lists of points share one dispatch
+macro_rules! noise_2d_match {
+    ($noise_type:expr, $helper:ident, $periodic_helper:ident, $looping_helper:ident) => {
+        match $noise_type {
+            NoiseType::Fbm(s) if s.looping.is_some() => {
+                $looping_helper!(
+                    s,
+                    fractal::varied_4d::<S, _, _>,
+                    S::set1_ps(s.lacunarity),
+                    S::set1_ps(s.gain),
+                    s.octaves,
+                    s.dim.seed,
+                    s.variation,
+                    |x, y, z, w, seed| fractal::base_4d::<S>(s.base, x, y, z, w, seed),
+                    |result, n, amp| S::fmadd_ps(n, amp, result)
+                )
+            }
+            NoiseType::Gradient(s) if s.period.is_some() => {
+                $periodic_helper!(s, simplex_4d::<S>, s.dim.seed)
+            }
+            NoiseType::Perlin(s) if s.period.is_some() => {
+                $periodic_helper!(s, perlin_4d::<S>, s.dim.seed)
+            }
+            NoiseType::Cellular(s) if s.period.is_some() => $periodic_helper!(
+                s,
+                cellular_4d::<S>,
+                s.distance_function,
+                s.return_type,
+                S::set1_ps(s.jitter),
+                s.dim.seed
+            ),
+            NoiseType::Fbm(s) if s.period.is_some() => $periodic_helper!(
+                s,
+                fractal::varied_4d::<S, _, _>,
+                S::set1_ps(s.lacunarity),
+                S::set1_ps(s.gain),
+                s.octaves,
+                s.dim.seed,
+                s.variation,
+                |x, y, z, w, seed| fractal::base_4d::<S>(s.base, x, y, z, w, seed),
+                |result, n, amp| S::fmadd_ps(n, amp, result)
+            ),
+            NoiseType::Billow(s) if s.period.is_some() => $periodic_helper!(
+                s,
+                fractal::varied_4d::<S, _, _>,
+                S::set1_ps(s.lacunarity),
+                S::set1_ps(s.gain),
+                s.octaves,
+                s.dim.seed,
+                s.variation,
+                |x, y, z, w, seed| simplex_4d::<S>(x, y, z, w, seed),
+                |result, n, amp| {
+                    let billow = S::abs_ps(S::mul_ps(n, S::set1_ps(2.0 * SIMPLEX_4D_SCALE)));
+                    S::fmadd_ps(S::sub_ps(billow, S::set1_ps(1.0)), amp, result)
+                }
+            ),
+            NoiseType::Ridge(s) if s.period.is_some() => $periodic_helper!(
+                s,
+                fractal::varied_4d::<S, _, _>,
+                S::set1_ps(s.lacunarity),
+                S::set1_ps(s.gain),
+                s.octaves,
+                s.dim.seed,
+                s.variation,
+                |x, y, z, w, seed| fractal::base_4d::<S>(s.base, x, y, z, w, seed),
+                |result, n, amp| {
+                    S::add_ps(result, S::fnmadd_ps(S::abs_ps(n), amp, S::set1_ps(1.0)))
+                }
+            ),
+            NoiseType::Turbulence(s) if s.period.is_some() => $periodic_helper!(
+                s,
+                fractal::varied_4d::<S, _, _>,
+                S::set1_ps(s.lacunarity),
+                S::set1_ps(s.gain),
+                s.octaves,
+                s.dim.seed,
+                s.variation,
+                |x, y, z, w, seed| fractal::base_4d::<S>(s.base, x, y, z, w, seed),
+                |result, n, amp| S::add_ps(result, S::abs_ps(S::mul_ps(n, amp)))
+            ),
+            NoiseType::Fbm(s) if s.variation.is_varied() => $helper!(
+                s,
+                fractal::varied_2d::<S, _, _>,
+                S::set1_ps(s.lacunarity),
+                S::set1_ps(s.gain),
+                s.octaves,
+                s.dim.seed,
+                s.variation,
+                |x, y, seed| fractal::base_2d::<S>(s.base, x, y, seed),
+                |result, n, amp| S::fmadd_ps(n, amp, result)
+            ),
+            NoiseType::Fbm(s) => match s.base {
+                BaseNoise::Simplex => $helper!(
+                    s,
+                    fbm_2d::<S>,
+                    S::set1_ps(s.lacunarity),
+                    S::set1_ps(s.gain),
+                    s.octaves,
+                    s.dim.seed
+                ),
+                base => $helper!(
+                    s,
+                    fractal::fbm_2d::<S, _>,
+                    S::set1_ps(s.lacunarity),
+                    S::set1_ps(s.gain),
+                    s.octaves,
+                    s.dim.seed,
+                    |x, y, seed| fractal::base_2d::<S>(base, x, y, seed)
+                ),
+            },
+            NoiseType::Billow(s) if s.variation.is_varied() => $helper!(
+                s,
+                fractal::varied_2d::<S, _, _>,
+                S::set1_ps(s.lacunarity),
+                S::set1_ps(s.gain),
+                s.octaves,
+                s.dim.seed,
+                s.variation,
+                |x, y, seed| simplex_2d::<S>(x, y, seed),
+                |result, n, amp| {
+                    let billow = S::abs_ps(S::mul_ps(n, S::set1_ps(2.0 * SIMPLEX_2D_SCALE)));
+                    S::fmadd_ps(S::sub_ps(billow, S::set1_ps(1.0)), amp, result)
+                }
+            ),
+            NoiseType::Billow(s) => $helper!(
+                s,
+                billow_2d::<S>,
+                S::set1_ps(s.lacunarity),
+                S::set1_ps(s.gain),
+                s.octaves,
+                s.dim.seed
+            ),
+            NoiseType::Ridge(s) if s.variation.is_varied() => $helper!(
+                s,
+                fractal::varied_2d::<S, _, _>,
+                S::set1_ps(s.lacunarity),
+                S::set1_ps(s.gain),
+                s.octaves,
+                s.dim.seed,
+                s.variation,
+                |x, y, seed| fractal::base_2d::<S>(s.base, x, y, seed),
+                |result, n, amp| {
+                    S::add_ps(result, S::fnmadd_ps(S::abs_ps(n), amp, S::set1_ps(1.0)))
+                }
+            ),
+            NoiseType::Ridge(s) => match s.base {
+                BaseNoise::Simplex => $helper!(
+                    s,
+                    ridge_2d::<S>,
+                    S::set1_ps(s.lacunarity),
+                    S::set1_ps(s.gain),
+                    s.octaves,
+                    s.dim.seed
+                ),
+                base => $helper!(
+                    s,
+                    fractal::ridge_2d::<S, _>,
+                    S::set1_ps(s.lacunarity),
+                    S::set1_ps(s.gain),
+                    s.octaves,
+                    s.dim.seed,
+                    |x, y, seed| fractal::base_2d::<S>(base, x, y, seed)
+                ),
+            },
+            NoiseType::Turbulence(s) if s.variation.is_varied() => $helper!(
+                s,
+                fractal::varied_2d::<S, _, _>,
+                S::set1_ps(s.lacunarity),
+                S::set1_ps(s.gain),
+                s.octaves,
+                s.dim.seed,
+                s.variation,
+                |x, y, seed| fractal::base_2d::<S>(s.base, x, y, seed),
+                |result, n, amp| S::add_ps(result, S::abs_ps(S::mul_ps(n, amp)))
+            ),
+            NoiseType::Turbulence(s) => match s.base {
+                BaseNoise::Simplex => $helper!(
+                    s,
+                    turbulence_2d::<S>,
+                    S::set1_ps(s.lacunarity),
+                    S::set1_ps(s.gain),
+                    s.octaves,
+                    s.dim.seed
+                ),
+                base => $helper!(
+                    s,
+                    fractal::turbulence_2d::<S, _>,
+                    S::set1_ps(s.lacunarity),
+                    S::set1_ps(s.gain),
+                    s.octaves,
+                    s.dim.seed,
+                    |x, y, seed| fractal::base_2d::<S>(base, x, y, seed)
+                ),
+            },
+            NoiseType::HybridMultifractal(s) => $helper!(s, hybrid_multifractal_2d::<S>, s),
+            NoiseType::HeteroTerrain(s) => $helper!(s, hetero_terrain_2d::<S>, s),
+            NoiseType::RidgedMultifractal(s) => $helper!(s, ridged_multifractal_2d::<S>, s),
+            NoiseType::Gradient(s) => $helper!(s, simplex_2d::<S>, s.dim.seed),
+            NoiseType::Perlin(s) => $helper!(s, perlin_2d::<S>, s.dim.seed),
+            NoiseType::Value(s) => {
+                $helper!(s, value_2d::<S>, s.interpolation, s.dim.seed)
+            }
+            NoiseType::Cellular(s) => $helper!(
+                s,
+                cellular_2d::<S>,
+                s.distance_function,
+                s.return_type,
+                S::set1_ps(s.jitter),
+                s.dim.seed
+            ),
+            NoiseType::Cellular2(s) => $helper!(
+                s,
+                cellular2_2d::<S>,
+                s.distance_function,
+                s.return_type,
+                S::set1_ps(s.jitter),
+                s.index0,
+                s.index1,
+                s.dim.seed
+            ),
+            NoiseType::DomainWarp(s) => $helper!(
+                s,
+                domain_warp_2d::<S>,
+                s.base,
+                S::set1_ps(s.lacunarity),
+                S::set1_ps(s.gain),
+                s.octaves,
+                s.warps(),
+                s.dim.seed
+            ),
+            NoiseType::IqFbm(s) => $helper!(s, iq_fbm_2d::<S>, s),
+            NoiseType::Swiss(s) => $helper!(s, swiss_2d::<S>, s),
+            NoiseType::Jordan(s) => $helper!(s, jordan_2d::<S>, s),
+        }
+    };
 }
 
 /// Gets a width X height sized block of 2d noise, unscaled.
@@ -323,141 +1191,864 @@ pub unsafe fn get_1d_noise<S: Simd>(noise_type: &NoiseType) -> (Vec<f32>, f32, f
 /// are returned so you can scale and transform the noise as you see fit
 /// in a single pass.
 #[inline(always)]
-pub unsafe fn get_2d_noise<S: Simd>(noise_type: &NoiseType) -> (Vec<f32>, f32, f32) {
+pub unsafe fn get_2d_noise(noise_type: &NoiseType, fill_rows: FillRows) -> (Vec<f32>, f32, f32) {
+    let width = noise_type.get_dimensions().width;
+    let mut noise = vec![0.0; width * block_rows_2d(noise_type)];
+    let (min, max) = fill_2d_noise_pitched(noise_type, &mut noise, width, fill_rows);
+    (noise, min, max)
+}
+
+/// Rows of `width` samples in a block of 2d noise
+fn block_rows_2d(noise_type: &NoiseType) -> usize {
+    let dim = noise_type.get_dimensions();
     match noise_type {
-        NoiseType::Fbm(s) => get_2d_noise_helper!(
-            s,
-            fbm_2d::<S>,
-            S::set1_ps(s.lacunarity),
-            S::set1_ps(s.gain),
-            s.octaves,
-            s.dim.seed
-        ),
-        NoiseType::Ridge(s) => get_2d_noise_helper!(
-            s,
-            ridge_2d::<S>,
-            S::set1_ps(s.lacunarity),
-            S::set1_ps(s.gain),
-            s.octaves,
-            s.dim.seed
-        ),
-        NoiseType::Turbulence(s) => get_2d_noise_helper!(
-            s,
-            turbulence_2d::<S>,
-            S::set1_ps(s.lacunarity),
-            S::set1_ps(s.gain),
-            s.octaves,
-            s.dim.seed
-        ),
-        NoiseType::Gradient(s) => get_2d_noise_helper!(s, simplex_2d::<S>, s.dim.seed),
-        NoiseType::Cellular(s) => get_2d_noise_helper!(
-            s,
-            cellular_2d::<S>,
-            s.distance_function,
-            s.return_type,
-            S::set1_ps(s.jitter),
-            s.dim.seed
-        ),
-        NoiseType::Cellular2(s) => get_2d_noise_helper!(
-            s,
-            cellular2_2d::<S>,
-            s.distance_function,
-            s.return_type,
-            S::set1_ps(s.jitter),
-            s.index0,
-            s.index1,
-            s.dim.seed
-        ),
+        NoiseType::Fbm(s) if s.looping.is_some() => dim.height * dim.time,
+        _ => dim.height,
     }
 }
 
+/// Fills `out`, which must hold exactly one value per sample, with a block of
+/// 2d noise, and returns the min and max value, like `get_2d_noise`.
+#[inline(always)]
+pub unsafe fn fill_2d_noise(
+    noise_type: &NoiseType,
+    out: &mut [f32],
+    fill_rows: FillRows,
+) -> (f32, f32) {
+    let width = noise_type.get_dimensions().width;
+    check_block_len(out.len(), width, block_rows_2d(noise_type));
+    fill_2d_noise_pitched(noise_type, out, width, fill_rows)
+}
+
+/// Like `fill_2d_noise`, but each row of the block starts `pitch` values
+/// after the last, to write into part of a larger image.
+#[inline(always)]
+pub unsafe fn fill_2d_noise_pitched(
+    noise_type: &NoiseType,
+    out: &mut [f32],
+    pitch: usize,
+    fill_rows: FillRows,
+) -> (f32, f32) {
+    let width = noise_type.get_dimensions().width;
+    let rows = block_rows_2d(noise_type);
+    check_pitched_len(out.len(), width, rows, pitch);
+    fill_block(noise_type, out, (rows, pitch), fill_rows)
+}
+
+/// Fills the rows `rows` of a block of 2d noise, counted across the whole
+/// block, into `out`, which starts at the first of them. Each row comes out
+/// the same as in a whole block.
+#[inline(always)]
+pub unsafe fn fill_2d_noise_rows<S: Simd>(
+    noise_type: &NoiseType,
+    out: &mut [f32],
+    pitch: usize,
+    rows: Range<usize>,
+) -> (f32, f32) {
+    let width = noise_type.get_dimensions().width;
+    if rows.end > block_rows_2d(noise_type) {
+        panic!("rows are outside the block");
+    }
+    check_pitched_len(out.len(), width, rows.len(), pitch);
+    macro_rules! block_helper {
+        ($($arg:tt)*) => {
+            get_2d_noise_helper!(out, pitch, rows; $($arg)*)
+        };
+    }
+    macro_rules! periodic_block_helper {
+        ($($arg:tt)*) => {
+            get_2d_periodic_noise_helper!(out, pitch, rows; $($arg)*)
+        };
+    }
+    macro_rules! looping_block_helper {
+        ($($arg:tt)*) => {
+            get_2d_looping_noise_helper!(out, pitch, rows; $($arg)*)
+        };
+    }
+    let (min, max) = noise_2d_match!(
+        noise_type,
+        block_helper,
+        periodic_block_helper,
+        looping_block_helper
+    );
+    normalize_rows::<S>(noise_type, out, (width, rows.len(), pitch), min, max)
+}
+
+/// Every 3d noise type, each generated with `$helper`, or `$periodic_helper`
+/// for tileable noise, so blocks and lists of points share one dispatch
+macro_rules! noise_3d_match {
+    ($noise_type:expr, $helper:ident, $periodic_helper:ident) => {
+        match $noise_type {
+            NoiseType::Gradient(s) if s.period.is_some() => {
+                $periodic_helper!(
+                    s,
+                    SIMPLEX_3D_PERIOD_STEP,
+                    simplex_3d_periodic::<S>,
+                    s.dim.seed
+                )
+            }
+            NoiseType::Perlin(s) if s.period.is_some() => {
+                $periodic_helper!(s, 1.0, perlin_3d_periodic::<S>, s.dim.seed)
+            }
+            NoiseType::Cellular(s) if s.period.is_some() => $periodic_helper!(
+                s,
+                1.0,
+                cellular_3d_periodic::<S>,
+                s.distance_function,
+                s.return_type,
+                S::set1_ps(s.jitter),
+                s.dim.seed
+            ),
+            NoiseType::Fbm(s) if s.period.is_some() => $periodic_helper!(
+                s,
+                fractal::periodic_step_3d(s.base),
+                fractal::fractal_3d_periodic::<S, _, _>,
+                fractal::periodic_step_3d(s.base),
+                s.lacunarity,
+                S::set1_ps(s.gain),
+                s.octaves,
+                s.dim.seed,
+                s.variation,
+                |x, y, z, cells, seed| fractal::base_3d_periodic::<S>(s.base, x, y, z, cells, seed),
+                |result, n, amp| S::fmadd_ps(n, amp, result)
+            ),
+            NoiseType::Billow(s) if s.period.is_some() => $periodic_helper!(
+                s,
+                SIMPLEX_3D_PERIOD_STEP,
+                fractal::fractal_3d_periodic::<S, _, _>,
+                SIMPLEX_3D_PERIOD_STEP,
+                s.lacunarity,
+                S::set1_ps(s.gain),
+                s.octaves,
+                s.dim.seed,
+                s.variation,
+                |x, y, z, cells, seed| simplex_3d_periodic::<S>(x, y, z, cells, seed),
+                |result, n, amp| {
+                    let billow = S::abs_ps(S::mul_ps(n, S::set1_ps(2.0 * SIMPLEX_3D_SCALE)));
+                    S::fmadd_ps(S::sub_ps(billow, S::set1_ps(1.0)), amp, result)
+                }
+            ),
+            NoiseType::Ridge(s) if s.period.is_some() => $periodic_helper!(
+                s,
+                fractal::periodic_step_3d(s.base),
+                fractal::fractal_3d_periodic::<S, _, _>,
+                fractal::periodic_step_3d(s.base),
+                s.lacunarity,
+                S::set1_ps(s.gain),
+                s.octaves,
+                s.dim.seed,
+                s.variation,
+                |x, y, z, cells, seed| fractal::base_3d_periodic::<S>(s.base, x, y, z, cells, seed),
+                |result, n, amp| {
+                    S::add_ps(result, S::fnmadd_ps(S::abs_ps(n), amp, S::set1_ps(1.0)))
+                }
+            ),
+            NoiseType::Turbulence(s) if s.period.is_some() => $periodic_helper!(
+                s,
+                fractal::periodic_step_3d(s.base),
+                fractal::fractal_3d_periodic::<S, _, _>,
+                fractal::periodic_step_3d(s.base),
+                s.lacunarity,
+                S::set1_ps(s.gain),
+                s.octaves,
+                s.dim.seed,
+                s.variation,
+                |x, y, z, cells, seed| fractal::base_3d_periodic::<S>(s.base, x, y, z, cells, seed),
+                |result, n, amp| S::add_ps(result, S::abs_ps(S::mul_ps(n, amp)))
+            ),
+            NoiseType::Fbm(s) if s.variation.is_varied() => $helper!(
+                s,
+                fractal::varied_3d::<S, _, _>,
+                S::set1_ps(s.lacunarity),
+                S::set1_ps(s.gain),
+                s.octaves,
+                s.dim.seed,
+                s.variation,
+                |x, y, z, seed| fractal::base_3d::<S>(s.base, x, y, z, seed),
+                |result, n, amp| S::fmadd_ps(n, amp, result)
+            ),
+            NoiseType::Fbm(s) => match s.base {
+                BaseNoise::Simplex => $helper!(
+                    s,
+                    fbm_3d::<S>,
+                    S::set1_ps(s.lacunarity),
+                    S::set1_ps(s.gain),
+                    s.octaves,
+                    s.dim.seed
+                ),
+                base => $helper!(
+                    s,
+                    fractal::fbm_3d::<S, _>,
+                    S::set1_ps(s.lacunarity),
+                    S::set1_ps(s.gain),
+                    s.octaves,
+                    s.dim.seed,
+                    |x, y, z, seed| fractal::base_3d::<S>(base, x, y, z, seed)
+                ),
+            },
+            NoiseType::Billow(s) if s.variation.is_varied() => $helper!(
+                s,
+                fractal::varied_3d::<S, _, _>,
+                S::set1_ps(s.lacunarity),
+                S::set1_ps(s.gain),
+                s.octaves,
+                s.dim.seed,
+                s.variation,
+                |x, y, z, seed| simplex_3d::<S>(x, y, z, seed),
+                |result, n, amp| {
+                    let billow = S::abs_ps(S::mul_ps(n, S::set1_ps(2.0 * SIMPLEX_3D_SCALE)));
+                    S::fmadd_ps(S::sub_ps(billow, S::set1_ps(1.0)), amp, result)
+                }
+            ),
+            NoiseType::Billow(s) => $helper!(
+                s,
+                billow_3d::<S>,
+                S::set1_ps(s.lacunarity),
+                S::set1_ps(s.gain),
+                s.octaves,
+                s.dim.seed
+            ),
+            NoiseType::Ridge(s) if s.variation.is_varied() => $helper!(
+                s,
+                fractal::varied_3d::<S, _, _>,
+                S::set1_ps(s.lacunarity),
+                S::set1_ps(s.gain),
+                s.octaves,
+                s.dim.seed,
+                s.variation,
+                |x, y, z, seed| fractal::base_3d::<S>(s.base, x, y, z, seed),
+                |result, n, amp| {
+                    S::add_ps(result, S::fnmadd_ps(S::abs_ps(n), amp, S::set1_ps(1.0)))
+                }
+            ),
+            NoiseType::Ridge(s) => match s.base {
+                BaseNoise::Simplex => $helper!(
+                    s,
+                    ridge_3d::<S>,
+                    S::set1_ps(s.lacunarity),
+                    S::set1_ps(s.gain),
+                    s.octaves,
+                    s.dim.seed
+                ),
+                base => $helper!(
+                    s,
+                    fractal::ridge_3d::<S, _>,
+                    S::set1_ps(s.lacunarity),
+                    S::set1_ps(s.gain),
+                    s.octaves,
+                    s.dim.seed,
+                    |x, y, z, seed| fractal::base_3d::<S>(base, x, y, z, seed)
+                ),
+            },
+            NoiseType::Turbulence(s) if s.variation.is_varied() => $helper!(
+                s,
+                fractal::varied_3d::<S, _, _>,
+                S::set1_ps(s.lacunarity),
+                S::set1_ps(s.gain),
+                s.octaves,
+                s.dim.seed,
+                s.variation,
+                |x, y, z, seed| fractal::base_3d::<S>(s.base, x, y, z, seed),
+                |result, n, amp| S::add_ps(result, S::abs_ps(S::mul_ps(n, amp)))
+            ),
+            NoiseType::Turbulence(s) => match s.base {
+                BaseNoise::Simplex => $helper!(
+                    s,
+                    turbulence_3d::<S>,
+                    S::set1_ps(s.lacunarity),
+                    S::set1_ps(s.gain),
+                    s.octaves,
+                    s.dim.seed
+                ),
+                base => $helper!(
+                    s,
+                    fractal::turbulence_3d::<S, _>,
+                    S::set1_ps(s.lacunarity),
+                    S::set1_ps(s.gain),
+                    s.octaves,
+                    s.dim.seed,
+                    |x, y, z, seed| fractal::base_3d::<S>(base, x, y, z, seed)
+                ),
+            },
+            NoiseType::HybridMultifractal(s) => $helper!(s, hybrid_multifractal_3d::<S>, s),
+            NoiseType::HeteroTerrain(s) => $helper!(s, hetero_terrain_3d::<S>, s),
+            NoiseType::RidgedMultifractal(s) => $helper!(s, ridged_multifractal_3d::<S>, s),
+            NoiseType::Gradient(s) => $helper!(s, simplex_3d::<S>, s.dim.seed),
+            NoiseType::Perlin(s) => $helper!(s, perlin_3d::<S>, s.dim.seed),
+            NoiseType::Value(s) => {
+                $helper!(s, value_3d::<S>, s.interpolation, s.dim.seed)
+            }
+            NoiseType::Cellular(s) => $helper!(
+                s,
+                cellular_3d::<S>,
+                s.distance_function,
+                s.return_type,
+                S::set1_ps(s.jitter),
+                s.dim.seed
+            ),
+            NoiseType::Cellular2(s) => $helper!(
+                s,
+                cellular2_3d::<S>,
+                s.distance_function,
+                s.return_type,
+                S::set1_ps(s.jitter),
+                s.index0,
+                s.index1,
+                s.dim.seed
+            ),
+            NoiseType::DomainWarp(s) => $helper!(
+                s,
+                domain_warp_3d::<S>,
+                s.base,
+                S::set1_ps(s.lacunarity),
+                S::set1_ps(s.gain),
+                s.octaves,
+                s.warps(),
+                s.dim.seed
+            ),
+            NoiseType::IqFbm(s) => $helper!(s, iq_fbm_3d::<S>, s),
+            NoiseType::Swiss(s) => $helper!(s, swiss_3d::<S>, s),
+            NoiseType::Jordan(s) => $helper!(s, jordan_3d::<S>, s),
+        }
+    };
+}
+
 /// Gets a width X height X depth sized block of 3d noise, unscaled,
 /// `start_x`,`start_y` and `start_z` can be used to provide an offset in the
 /// coordinates. Results are unscaled, 'min' and 'max' noise values
 /// are returned so you can scale and transform the noise as you see fit
 /// in a single pass.
 #[inline(always)]
-pub unsafe fn get_3d_noise<S: Simd>(noise_type: &NoiseType) -> (Vec<f32>, f32, f32) {
-    match noise_type {
-        NoiseType::Fbm(s) => get_3d_noise_helper!(
-            s,
-            fbm_3d::<S>,
-            S::set1_ps(s.lacunarity),
-            S::set1_ps(s.gain),
-            s.octaves,
-            s.dim.seed
-        ),
-        NoiseType::Ridge(s) => get_3d_noise_helper!(
-            s,
-            ridge_3d::<S>,
-            S::set1_ps(s.lacunarity),
-            S::set1_ps(s.gain),
-            s.octaves,
-            s.dim.seed
-        ),
-        NoiseType::Turbulence(s) => get_3d_noise_helper!(
-            s,
-            turbulence_3d::<S>,
-            S::set1_ps(s.lacunarity),
-            S::set1_ps(s.gain),
-            s.octaves,
-            s.dim.seed
-        ),
-        NoiseType::Gradient(s) => get_3d_noise_helper!(s, simplex_3d::<S>, s.dim.seed),
-        NoiseType::Cellular(s) => get_3d_noise_helper!(
-            s,
-            cellular_3d::<S>,
-            s.distance_function,
-            s.return_type,
-            S::set1_ps(s.jitter),
-            s.dim.seed
-        ),
-        NoiseType::Cellular2(s) => get_3d_noise_helper!(
-            s,
-            cellular2_3d::<S>,
-            s.distance_function,
-            s.return_type,
-            S::set1_ps(s.jitter),
-            s.index0,
-            s.index1,
-            s.dim.seed
-        ),
+pub unsafe fn get_3d_noise(noise_type: &NoiseType, fill_rows: FillRows) -> (Vec<f32>, f32, f32) {
+    let width = noise_type.get_dimensions().width;
+    let mut noise = vec![0.0; width * block_rows_3d(noise_type)];
+    let (min, max) = fill_3d_noise_pitched(noise_type, &mut noise, width, fill_rows);
+    (noise, min, max)
+}
+
+/// Rows of `width` samples in a block of 3d noise
+fn block_rows_3d(noise_type: &NoiseType) -> usize {
+    let dim = noise_type.get_dimensions();
+    dim.height * dim.depth
+}
+
+/// Fills `out`, which must hold exactly one value per sample, with a block of
+/// 3d noise, and returns the min and max value, like `get_3d_noise`.
+#[inline(always)]
+pub unsafe fn fill_3d_noise(
+    noise_type: &NoiseType,
+    out: &mut [f32],
+    fill_rows: FillRows,
+) -> (f32, f32) {
+    let width = noise_type.get_dimensions().width;
+    check_block_len(out.len(), width, block_rows_3d(noise_type));
+    fill_3d_noise_pitched(noise_type, out, width, fill_rows)
+}
+
+/// Like `fill_3d_noise`, but each row of the block starts `pitch` values
+/// after the last, to write into part of a larger image.
+#[inline(always)]
+pub unsafe fn fill_3d_noise_pitched(
+    noise_type: &NoiseType,
+    out: &mut [f32],
+    pitch: usize,
+    fill_rows: FillRows,
+) -> (f32, f32) {
+    let width = noise_type.get_dimensions().width;
+    let rows = block_rows_3d(noise_type);
+    check_pitched_len(out.len(), width, rows, pitch);
+    fill_block(noise_type, out, (rows, pitch), fill_rows)
+}
+
+/// Fills the rows `rows` of a block of 3d noise, counted across the whole
+/// block, into `out`, which starts at the first of them. Each row comes out
+/// the same as in a whole block.
+#[inline(always)]
+pub unsafe fn fill_3d_noise_rows<S: Simd>(
+    noise_type: &NoiseType,
+    out: &mut [f32],
+    pitch: usize,
+    rows: Range<usize>,
+) -> (f32, f32) {
+    let width = noise_type.get_dimensions().width;
+    if rows.end > block_rows_3d(noise_type) {
+        panic!("rows are outside the block");
     }
+    check_pitched_len(out.len(), width, rows.len(), pitch);
+    macro_rules! block_helper {
+        ($($arg:tt)*) => {
+            get_3d_noise_helper!(out, pitch, rows; $($arg)*)
+        };
+    }
+    macro_rules! periodic_block_helper {
+        ($($arg:tt)*) => {
+            get_3d_periodic_noise_helper!(out, pitch, rows; $($arg)*)
+        };
+    }
+    let (min, max) = noise_3d_match!(noise_type, block_helper, periodic_block_helper);
+    normalize_rows::<S>(noise_type, out, (width, rows.len(), pitch), min, max)
+}
+
+/// Every 4d noise type, each generated with `$helper`, so blocks and lists of
+/// points share one dispatch
+macro_rules! noise_4d_match {
+    ($noise_type:expr, $helper:ident) => {
+        match $noise_type {
+            NoiseType::Fbm(s) if s.variation.is_varied() => $helper!(
+                s,
+                fractal::varied_4d::<S, _, _>,
+                S::set1_ps(s.lacunarity),
+                S::set1_ps(s.gain),
+                s.octaves,
+                s.dim.seed,
+                s.variation,
+                |x, y, z, w, seed| fractal::base_4d::<S>(s.base, x, y, z, w, seed),
+                |result, n, amp| S::fmadd_ps(n, amp, result)
+            ),
+            NoiseType::Fbm(s) => match s.base {
+                BaseNoise::Simplex => $helper!(
+                    s,
+                    fbm_4d::<S>,
+                    S::set1_ps(s.lacunarity),
+                    S::set1_ps(s.gain),
+                    s.octaves,
+                    s.dim.seed
+                ),
+                base => $helper!(
+                    s,
+                    fractal::fbm_4d::<S, _>,
+                    S::set1_ps(s.lacunarity),
+                    S::set1_ps(s.gain),
+                    s.octaves,
+                    s.dim.seed,
+                    |x, y, z, w, seed| fractal::base_4d::<S>(base, x, y, z, w, seed)
+                ),
+            },
+            NoiseType::Billow(s) if s.variation.is_varied() => $helper!(
+                s,
+                fractal::varied_4d::<S, _, _>,
+                S::set1_ps(s.lacunarity),
+                S::set1_ps(s.gain),
+                s.octaves,
+                s.dim.seed,
+                s.variation,
+                |x, y, z, w, seed| simplex_4d::<S>(x, y, z, w, seed),
+                |result, n, amp| {
+                    let billow = S::abs_ps(S::mul_ps(n, S::set1_ps(2.0 * SIMPLEX_4D_SCALE)));
+                    S::fmadd_ps(S::sub_ps(billow, S::set1_ps(1.0)), amp, result)
+                }
+            ),
+            NoiseType::Billow(s) => $helper!(
+                s,
+                billow_4d::<S>,
+                S::set1_ps(s.lacunarity),
+                S::set1_ps(s.gain),
+                s.octaves,
+                s.dim.seed
+            ),
+            NoiseType::Ridge(s) if s.variation.is_varied() => $helper!(
+                s,
+                fractal::varied_4d::<S, _, _>,
+                S::set1_ps(s.lacunarity),
+                S::set1_ps(s.gain),
+                s.octaves,
+                s.dim.seed,
+                s.variation,
+                |x, y, z, w, seed| fractal::base_4d::<S>(s.base, x, y, z, w, seed),
+                |result, n, amp| {
+                    S::add_ps(result, S::fnmadd_ps(S::abs_ps(n), amp, S::set1_ps(1.0)))
+                }
+            ),
+            NoiseType::Ridge(s) => match s.base {
+                BaseNoise::Simplex => $helper!(
+                    s,
+                    ridge_4d::<S>,
+                    S::set1_ps(s.lacunarity),
+                    S::set1_ps(s.gain),
+                    s.octaves,
+                    s.dim.seed
+                ),
+                base => $helper!(
+                    s,
+                    fractal::ridge_4d::<S, _>,
+                    S::set1_ps(s.lacunarity),
+                    S::set1_ps(s.gain),
+                    s.octaves,
+                    s.dim.seed,
+                    |x, y, z, w, seed| fractal::base_4d::<S>(base, x, y, z, w, seed)
+                ),
+            },
+            NoiseType::Turbulence(s) if s.variation.is_varied() => $helper!(
+                s,
+                fractal::varied_4d::<S, _, _>,
+                S::set1_ps(s.lacunarity),
+                S::set1_ps(s.gain),
+                s.octaves,
+                s.dim.seed,
+                s.variation,
+                |x, y, z, w, seed| fractal::base_4d::<S>(s.base, x, y, z, w, seed),
+                |result, n, amp| S::add_ps(result, S::abs_ps(S::mul_ps(n, amp)))
+            ),
+            NoiseType::Turbulence(s) => match s.base {
+                BaseNoise::Simplex => $helper!(
+                    s,
+                    turbulence_4d::<S>,
+                    S::set1_ps(s.lacunarity),
+                    S::set1_ps(s.gain),
+                    s.octaves,
+                    s.dim.seed
+                ),
+                base => $helper!(
+                    s,
+                    fractal::turbulence_4d::<S, _>,
+                    S::set1_ps(s.lacunarity),
+                    S::set1_ps(s.gain),
+                    s.octaves,
+                    s.dim.seed,
+                    |x, y, z, w, seed| fractal::base_4d::<S>(base, x, y, z, w, seed)
+                ),
+            },
+            NoiseType::HybridMultifractal(s) => $helper!(s, hybrid_multifractal_4d::<S>, s),
+            NoiseType::HeteroTerrain(s) => $helper!(s, hetero_terrain_4d::<S>, s),
+            NoiseType::RidgedMultifractal(s) => $helper!(s, ridged_multifractal_4d::<S>, s),
+            NoiseType::Gradient(s) => $helper!(s, simplex_4d::<S>, s.dim.seed),
+            NoiseType::Perlin(s) => $helper!(s, perlin_4d::<S>, s.dim.seed),
+            NoiseType::Value(s) => {
+                $helper!(s, value_4d::<S>, s.interpolation, s.dim.seed)
+            }
+            NoiseType::Cellular(s) => $helper!(
+                s,
+                cellular_4d::<S>,
+                s.distance_function,
+                s.return_type,
+                S::set1_ps(s.jitter),
+                s.dim.seed
+            ),
+            NoiseType::Cellular2(s) => $helper!(
+                s,
+                cellular2_4d::<S>,
+                s.distance_function,
+                s.return_type,
+                S::set1_ps(s.jitter),
+                s.index0,
+                s.index1,
+                s.dim.seed
+            ),
+            NoiseType::DomainWarp(s) => $helper!(
+                s,
+                domain_warp_4d::<S>,
+                s.base,
+                S::set1_ps(s.lacunarity),
+                S::set1_ps(s.gain),
+                s.octaves,
+                s.warps(),
+                s.dim.seed
+            ),
+            NoiseType::IqFbm(_) | NoiseType::Swiss(_) | NoiseType::Jordan(_) => {
+                unreachable!("terrain fractal settings are 2d or 3d")
+            }
+        }
+    };
 }
 
 #[inline(always)]
-pub unsafe fn get_4d_noise<S: Simd>(noise_type: &NoiseType) -> (Vec<f32>, f32, f32) {
-    match noise_type {
-        NoiseType::Fbm(s) => get_4d_noise_helper!(
-            s,
-            fbm_4d::<S>,
-            S::set1_ps(s.lacunarity),
-            S::set1_ps(s.gain),
-            s.octaves,
-            s.dim.seed
-        ),
-        NoiseType::Ridge(s) => get_4d_noise_helper!(
-            s,
-            ridge_4d::<S>,
-            S::set1_ps(s.lacunarity),
-            S::set1_ps(s.gain),
-            s.octaves,
-            s.dim.seed
-        ),
-        NoiseType::Turbulence(s) => get_4d_noise_helper!(
-            s,
-            turbulence_4d::<S>,
-            S::set1_ps(s.lacunarity),
-            S::set1_ps(s.gain),
-            s.octaves,
-            s.dim.seed
-        ),
-        NoiseType::Gradient(s) => get_4d_noise_helper!(s, simplex_4d::<S>, s.dim.seed),
-        NoiseType::Cellular(_) => {
-            panic!("not implemented");
+pub unsafe fn get_4d_noise(noise_type: &NoiseType, fill_rows: FillRows) -> (Vec<f32>, f32, f32) {
+    let width = noise_type.get_dimensions().width;
+    let mut noise = vec![0.0; width * block_rows_4d(noise_type)];
+    let (min, max) = fill_4d_noise_pitched(noise_type, &mut noise, width, fill_rows);
+    (noise, min, max)
+}
+
+/// Rows of `width` samples in a block of 4d noise
+fn block_rows_4d(noise_type: &NoiseType) -> usize {
+    let dim = noise_type.get_dimensions();
+    dim.height * dim.depth * dim.time
+}
+
+/// Fills `out`, which must hold exactly one value per sample, with a block of
+/// 4d noise, and returns the min and max value, like `get_4d_noise`.
+#[inline(always)]
+pub unsafe fn fill_4d_noise(
+    noise_type: &NoiseType,
+    out: &mut [f32],
+    fill_rows: FillRows,
+) -> (f32, f32) {
+    let width = noise_type.get_dimensions().width;
+    check_block_len(out.len(), width, block_rows_4d(noise_type));
+    fill_4d_noise_pitched(noise_type, out, width, fill_rows)
+}
+
+/// Like `fill_4d_noise`, but each row of the block starts `pitch` values
+/// after the last, to write into part of a larger image.
+#[inline(always)]
+pub unsafe fn fill_4d_noise_pitched(
+    noise_type: &NoiseType,
+    out: &mut [f32],
+    pitch: usize,
+    fill_rows: FillRows,
+) -> (f32, f32) {
+    let width = noise_type.get_dimensions().width;
+    let rows = block_rows_4d(noise_type);
+    check_pitched_len(out.len(), width, rows, pitch);
+    fill_block(noise_type, out, (rows, pitch), fill_rows)
+}
+
+/// Fills the rows `rows` of a block of 4d noise, counted across the whole
+/// block, into `out`, which starts at the first of them. Each row comes out
+/// the same as in a whole block.
+#[inline(always)]
+pub unsafe fn fill_4d_noise_rows<S: Simd>(
+    noise_type: &NoiseType,
+    out: &mut [f32],
+    pitch: usize,
+    rows: Range<usize>,
+) -> (f32, f32) {
+    let width = noise_type.get_dimensions().width;
+    if rows.end > block_rows_4d(noise_type) {
+        panic!("rows are outside the block");
+    }
+    check_pitched_len(out.len(), width, rows.len(), pitch);
+    macro_rules! block_helper {
+        ($($arg:tt)*) => {
+            get_4d_noise_helper!(out, pitch, rows; $($arg)*)
+        };
+    }
+    let (min, max) = noise_4d_match!(noise_type, block_helper);
+    normalize_rows::<S>(noise_type, out, (width, rows.len(), pitch), min, max)
+}
+
+/// Panics in place of the helpers for tileable and looping noise, which the
+/// point functions reject before dispatching
+macro_rules! unreachable_points_helper {
+    ($($arg:tt)*) => {
+        unreachable!()
+    };
+}
+
+/// Evaluates 1d noise at the points `xs`, transformed and scaled by the frequency of
+/// the settings, with the min and max value. Tileable noise is not supported,
+/// the period only makes sense for blocks.
+#[inline(always)]
+pub unsafe fn get_1d_noise_at<S: Simd>(noise_type: &NoiseType, xs: &[f32]) -> (Vec<f32>, f32, f32) {
+    noise_type.check_points();
+    macro_rules! points_helper {
+        ($Setting:expr, $f:expr $(,$arg:expr)*) => {
+            get_points_helper!($Setting, [xs * freq_x], $f $(,$arg)*)
+        };
+    }
+    let (mut noise, min, max) = noise_1d_match!(noise_type, points_helper);
+    match noise_type.normalization() {
+        Some((multiplier, offset)) => {
+            transform_noise::<S>(multiplier, offset, &mut noise);
+            (noise, min * multiplier + offset, max * multiplier + offset)
         }
-        NoiseType::Cellular2(_) => {
-            panic!("not implemented");
+        None => (noise, min, max),
+    }
+}
+
+/// Evaluates 2d noise at the points `xs` and `ys`, transformed and scaled by the frequency of
+/// the settings, with the min and max value. Tileable noise is not supported,
+/// the period only makes sense for blocks. Looping noise isn't supported either.
+#[inline(always)]
+pub unsafe fn get_2d_noise_at<S: Simd>(
+    noise_type: &NoiseType,
+    xs: &[f32],
+    ys: &[f32],
+) -> (Vec<f32>, f32, f32) {
+    if xs.len() != ys.len() {
+        panic!("coordinate lists differ in length");
+    }
+    noise_type.check_points();
+    macro_rules! points_helper {
+        ($Setting:expr, $f:expr $(,$arg:expr)*) => {
+            get_points_helper!($Setting, [xs * freq_x, ys * freq_y], $f $(,$arg)*)
+        };
+    }
+    let (mut noise, min, max) = noise_2d_match!(
+        noise_type,
+        points_helper,
+        unreachable_points_helper,
+        unreachable_points_helper
+    );
+    match noise_type.normalization() {
+        Some((multiplier, offset)) => {
+            transform_noise::<S>(multiplier, offset, &mut noise);
+            (noise, min * multiplier + offset, max * multiplier + offset)
+        }
+        None => (noise, min, max),
+    }
+}
+
+/// Evaluates 3d noise at the points `xs`, `ys` and `zs`, transformed and scaled by the frequency of
+/// the settings, with the min and max value. Tileable noise is not supported,
+/// the period only makes sense for blocks.
+#[inline(always)]
+pub unsafe fn get_3d_noise_at<S: Simd>(
+    noise_type: &NoiseType,
+    xs: &[f32],
+    ys: &[f32],
+    zs: &[f32],
+) -> (Vec<f32>, f32, f32) {
+    if xs.len() != ys.len() || xs.len() != zs.len() {
+        panic!("coordinate lists differ in length");
+    }
+    noise_type.check_points();
+    macro_rules! points_helper {
+        ($Setting:expr, $f:expr $(,$arg:expr)*) => {
+            get_points_helper!($Setting, [xs * freq_x, ys * freq_y, zs * freq_z], $f $(,$arg)*)
+        };
+    }
+    let (mut noise, min, max) =
+        noise_3d_match!(noise_type, points_helper, unreachable_points_helper);
+    match noise_type.normalization() {
+        Some((multiplier, offset)) => {
+            transform_noise::<S>(multiplier, offset, &mut noise);
+            (noise, min * multiplier + offset, max * multiplier + offset)
+        }
+        None => (noise, min, max),
+    }
+}
+
+/// Evaluates 4d noise at the points `xs`, `ys`, `zs` and `ws`, transformed and scaled by the frequency of
+/// the settings, with the min and max value. Tileable noise is not supported,
+/// the period only makes sense for blocks.
+#[inline(always)]
+pub unsafe fn get_4d_noise_at<S: Simd>(
+    noise_type: &NoiseType,
+    xs: &[f32],
+    ys: &[f32],
+    zs: &[f32],
+    ws: &[f32],
+) -> (Vec<f32>, f32, f32) {
+    if xs.len() != ys.len() || xs.len() != zs.len() || xs.len() != ws.len() {
+        panic!("coordinate lists differ in length");
+    }
+    noise_type.check_points();
+    macro_rules! points_helper {
+        ($Setting:expr, $f:expr $(,$arg:expr)*) => {
+            get_points_helper!($Setting, [xs * freq_x, ys * freq_y, zs * freq_z, ws * freq_w], $f $(,$arg)*)
+        };
+    }
+    let (mut noise, min, max) = noise_4d_match!(noise_type, points_helper);
+    match noise_type.normalization() {
+        Some((multiplier, offset)) => {
+            transform_noise::<S>(multiplier, offset, &mut noise);
+            (noise, min * multiplier + offset, max * multiplier + offset)
         }
+        None => (noise, min, max),
     }
 }
+
+/// Walks a block in the same order as the single channel helpers, calling `f`
+/// with the transformed, frequency scaled coordinates of each vector of
+/// samples, the index of its first sample, and how many of its lanes fall
+/// inside the block
+unsafe fn for_each_vector<S, F>(dim: NoiseDimensions, freq: [f32; 4], mut f: F)
+where
+    S: Simd,
+    F: FnMut(S::Vf32, S::Vf32, S::Vf32, S::Vf32, usize, usize),
+{
+    let (height, depth, time) = match dim.dim {
+        1 => (1, 1, 1),
+        2 => (dim.height, 1, 1),
+        3 => (dim.height, dim.depth, 1),
+        _ => (dim.height, dim.depth, dim.time),
+    };
+    let m = block_transform(&dim, freq, dim.dim);
+    let vector_width = S::VF32_WIDTH;
+    let mut x_arr = vec![0.0; vector_width];
+    for (i, x) in x_arr.iter_mut().enumerate() {
+        *x = dim.x + i as f32;
+    }
+
+    let mut i = 0;
+    for t in 0..time {
+        let w = dim.w + t as f32;
+        for k in 0..depth {
+            let z = dim.z + k as f32;
+            for j in 0..height {
+                let row = BlockRow::<S>::new(&m, dim.y + j as f32, z, w);
+                let mut x = S::loadu_ps(&x_arr[0]);
+                let mut remaining = dim.width;
+                while remaining > 0 {
+                    let n = remaining.min(vector_width);
+                    let c = row.at(x);
+                    f(c[0], c[1], c[2], c[3], i, n);
+                    i += n;
+                    remaining -= n;
+                    x = S::add_ps(x, S::set1_ps(vector_width as f32));
+                }
+            }
+        }
+    }
+}
+
+/// Gets all four sorted distances, F1 to F4, of a block of cellular2 noise
+/// in a single pass. Each channel is laid out like the single channel blocks
+/// of the same dimension. Results are unscaled.
+pub unsafe fn get_cellular2_distances<S: Simd>(s: &Cellular2Settings) -> [Vec<f32>; 4] {
+    let dim = s.dim;
+    let len = dim.width
+        * match dim.dim {
+            1 => 1,
+            2 => dim.height,
+            3 => dim.height * dim.depth,
+            _ => dim.height * dim.depth * dim.time,
+        };
+    let mut result = [
+        vec![0.0; len],
+        vec![0.0; len],
+        vec![0.0; len],
+        vec![0.0; len],
+    ];
+    let jitter = S::set1_ps(s.jitter);
+    let freq = [s.freq_x, s.freq_y, s.freq_z, s.freq_w];
+    for_each_vector::<S, _>(dim, freq, |x, y, z, w, i, n| {
+        let f = match dim.dim {
+            1 => cellular2_distances_1d::<S>(x, s.distance_function, jitter, dim.seed),
+            2 => cellular2_distances_2d::<S>(x, y, s.distance_function, jitter, dim.seed),
+            3 => cellular2_distances_3d::<S>(x, y, z, s.distance_function, jitter, dim.seed),
+            _ => cellular2_distances_4d::<S>(x, y, z, w, s.distance_function, jitter, dim.seed),
+        };
+        for (channel, distance) in result.iter_mut().zip(f.iter()) {
+            for l in 0..n {
+                channel[i + l] = distance[l];
+            }
+        }
+    });
+    result
+}
+
+/// Gets a block of 2d or 3d curl noise, with the vector components either
+/// interleaved per sample or in one plane per component. Results are unscaled.
+pub unsafe fn get_curl<S: Simd>(s: &CurlSettings) -> Vec<f32> {
+    let dim = s.dim;
+    let (components, len) = match dim.dim {
+        2 => (2, dim.width * dim.height),
+        3 => (3, dim.width * dim.height * dim.depth),
+        _ => panic!("not implemented"),
+    };
+    let mut result = vec![0.0; len * components];
+    let lac = S::set1_ps(s.lacunarity);
+    let gain = S::set1_ps(s.gain);
+    let freq = [s.freq_x, s.freq_y, s.freq_z, 0.0];
+    for_each_vector::<S, _>(dim, freq, |x, y, z, _, i, n| {
+        let v = if components == 2 {
+            let (vx, vy) = curl_2d::<S>(x, y, lac, gain, s.octaves, dim.seed);
+            [vx, vy, S::setzero_ps()]
+        } else {
+            let (vx, vy, vz) = curl_3d::<S>(x, y, z, lac, gain, s.octaves, dim.seed);
+            [vx, vy, vz]
+        };
+        for (c, v) in v.iter().take(components).enumerate() {
+            for l in 0..n {
+                let index = match s.layout {
+                    VectorLayout::Interleaved => (i + l) * components + c,
+                    VectorLayout::Planar => c * len + i + l,
+                };
+                result[index] = v[l];
+            }
+        }
+    });
+    result
+}
@@ -282,7 +282,32 @@ macro_rules! get_4d_noise_helper_f64 {
 
 #[inline(always)]
 pub unsafe fn get_1d_noise_f64<S: Simd>(noise_type: &NoiseType) -> (Vec<f64>, f64, f64) {
+    if noise_type.period().is_some() || noise_type.get_dimensions().transform.is_some() {
+        panic!("not implemented");
+    }
     match noise_type {
+        NoiseType::Fbm(FbmSettings {
+            variation,
+            normalize,
+            ..
+        })
+        | NoiseType::Billow(BillowSettings {
+            variation,
+            normalize,
+            ..
+        })
+        | NoiseType::Ridge(RidgeSettings {
+            variation,
+            normalize,
+            ..
+        })
+        | NoiseType::Turbulence(TurbulenceSettings {
+            variation,
+            normalize,
+            ..
+        }) if variation.is_varied() || *normalize => {
+            panic!("not implemented");
+        }
         NoiseType::Fbm(s) => get_1d_noise_helper_f64!(
             s,
             fbm_1d::<S>,
@@ -291,6 +316,14 @@ pub unsafe fn get_1d_noise_f64<S: Simd>(noise_type: &NoiseType) -> (Vec<f64>, f6
             s.octaves,
             s.dim.seed as i64
         ),
+        NoiseType::Billow(s) => get_1d_noise_helper_f64!(
+            s,
+            billow_1d::<S>,
+            S::set1_pd(s.lacunarity as f64),
+            S::set1_pd(s.gain as f64),
+            s.octaves,
+            s.dim.seed as i64
+        ),
         NoiseType::Ridge(s) => get_1d_noise_helper_f64!(
             s,
             ridge_1d::<S>,
@@ -307,13 +340,32 @@ pub unsafe fn get_1d_noise_f64<S: Simd>(noise_type: &NoiseType) -> (Vec<f64>, f6
             s.octaves,
             s.dim.seed as i64
         ),
+        NoiseType::HybridMultifractal(s) => {
+            get_1d_noise_helper_f64!(s, hybrid_multifractal_1d::<S>, s)
+        }
+        NoiseType::HeteroTerrain(s) => get_1d_noise_helper_f64!(s, hetero_terrain_1d::<S>, s),
+        NoiseType::RidgedMultifractal(s) => {
+            get_1d_noise_helper_f64!(s, ridged_multifractal_1d::<S>, s)
+        }
         NoiseType::Gradient(s) => get_1d_noise_helper_f64!(s, simplex_1d::<S>, s.dim.seed as i64),
+        NoiseType::Perlin(_) => {
+            panic!("not implemented");
+        }
+        NoiseType::Value(_) => {
+            panic!("not implemented");
+        }
         NoiseType::Cellular(_) => {
             panic!("not implemented");
         }
         NoiseType::Cellular2(_) => {
             panic!("not implemented");
         }
+        NoiseType::DomainWarp(_) => {
+            panic!("not implemented");
+        }
+        NoiseType::IqFbm(_) | NoiseType::Swiss(_) | NoiseType::Jordan(_) => {
+            panic!("not implemented");
+        }
     }
 }
 
@@ -324,7 +376,32 @@ pub unsafe fn get_1d_noise_f64<S: Simd>(noise_type: &NoiseType) -> (Vec<f64>, f6
 /// in a single pass.
 #[inline(always)]
 pub unsafe fn get_2d_noise_f64<S: Simd>(noise_type: &NoiseType) -> (Vec<f64>, f64, f64) {
+    if noise_type.period().is_some() || noise_type.get_dimensions().transform.is_some() {
+        panic!("not implemented");
+    }
     match noise_type {
+        NoiseType::Fbm(FbmSettings {
+            variation,
+            normalize,
+            ..
+        })
+        | NoiseType::Billow(BillowSettings {
+            variation,
+            normalize,
+            ..
+        })
+        | NoiseType::Ridge(RidgeSettings {
+            variation,
+            normalize,
+            ..
+        })
+        | NoiseType::Turbulence(TurbulenceSettings {
+            variation,
+            normalize,
+            ..
+        }) if variation.is_varied() || *normalize => {
+            panic!("not implemented");
+        }
         NoiseType::Fbm(s) => get_2d_noise_helper_f64!(
             s,
             fbm_2d::<S>,
@@ -333,6 +410,14 @@ pub unsafe fn get_2d_noise_f64<S: Simd>(noise_type: &NoiseType) -> (Vec<f64>, f6
             s.octaves,
             s.dim.seed as i64
         ),
+        NoiseType::Billow(s) => get_2d_noise_helper_f64!(
+            s,
+            billow_2d::<S>,
+            S::set1_pd(s.lacunarity as f64),
+            S::set1_pd(s.gain as f64),
+            s.octaves,
+            s.dim.seed as i64
+        ),
         NoiseType::Ridge(s) => get_2d_noise_helper_f64!(
             s,
             ridge_2d::<S>,
@@ -349,7 +434,20 @@ pub unsafe fn get_2d_noise_f64<S: Simd>(noise_type: &NoiseType) -> (Vec<f64>, f6
             s.octaves,
             s.dim.seed as i64
         ),
+        NoiseType::HybridMultifractal(s) => {
+            get_2d_noise_helper_f64!(s, hybrid_multifractal_2d::<S>, s)
+        }
+        NoiseType::HeteroTerrain(s) => get_2d_noise_helper_f64!(s, hetero_terrain_2d::<S>, s),
+        NoiseType::RidgedMultifractal(s) => {
+            get_2d_noise_helper_f64!(s, ridged_multifractal_2d::<S>, s)
+        }
         NoiseType::Gradient(s) => get_2d_noise_helper_f64!(s, simplex_2d::<S>, s.dim.seed as i64),
+        NoiseType::Perlin(_) => {
+            panic!("not implemented");
+        }
+        NoiseType::Value(_) => {
+            panic!("not implemented");
+        }
         NoiseType::Cellular(s) => get_2d_noise_helper_f64!(
             s,
             cellular_2d::<S>,
@@ -368,6 +466,12 @@ pub unsafe fn get_2d_noise_f64<S: Simd>(noise_type: &NoiseType) -> (Vec<f64>, f6
             s.index1,
             s.dim.seed as i64
         ),
+        NoiseType::DomainWarp(_) => {
+            panic!("not implemented");
+        }
+        NoiseType::IqFbm(_) | NoiseType::Swiss(_) | NoiseType::Jordan(_) => {
+            panic!("not implemented");
+        }
     }
 }
 
@@ -378,7 +482,32 @@ pub unsafe fn get_2d_noise_f64<S: Simd>(noise_type: &NoiseType) -> (Vec<f64>, f6
 /// in a single pass.
 #[inline(always)]
 pub unsafe fn get_3d_noise_f64<S: Simd>(noise_type: &NoiseType) -> (Vec<f64>, f64, f64) {
+    if noise_type.period().is_some() || noise_type.get_dimensions().transform.is_some() {
+        panic!("not implemented");
+    }
     match noise_type {
+        NoiseType::Fbm(FbmSettings {
+            variation,
+            normalize,
+            ..
+        })
+        | NoiseType::Billow(BillowSettings {
+            variation,
+            normalize,
+            ..
+        })
+        | NoiseType::Ridge(RidgeSettings {
+            variation,
+            normalize,
+            ..
+        })
+        | NoiseType::Turbulence(TurbulenceSettings {
+            variation,
+            normalize,
+            ..
+        }) if variation.is_varied() || *normalize => {
+            panic!("not implemented");
+        }
         NoiseType::Fbm(s) => get_3d_noise_helper_f64!(
             s,
             fbm_3d::<S>,
@@ -387,6 +516,14 @@ pub unsafe fn get_3d_noise_f64<S: Simd>(noise_type: &NoiseType) -> (Vec<f64>, f6
             s.octaves,
             s.dim.seed as i64
         ),
+        NoiseType::Billow(s) => get_3d_noise_helper_f64!(
+            s,
+            billow_3d::<S>,
+            S::set1_pd(s.lacunarity as f64),
+            S::set1_pd(s.gain as f64),
+            s.octaves,
+            s.dim.seed as i64
+        ),
         NoiseType::Ridge(s) => get_3d_noise_helper_f64!(
             s,
             ridge_3d::<S>,
@@ -403,7 +540,20 @@ pub unsafe fn get_3d_noise_f64<S: Simd>(noise_type: &NoiseType) -> (Vec<f64>, f6
             s.octaves,
             s.dim.seed as i64
         ),
+        NoiseType::HybridMultifractal(s) => {
+            get_3d_noise_helper_f64!(s, hybrid_multifractal_3d::<S>, s)
+        }
+        NoiseType::HeteroTerrain(s) => get_3d_noise_helper_f64!(s, hetero_terrain_3d::<S>, s),
+        NoiseType::RidgedMultifractal(s) => {
+            get_3d_noise_helper_f64!(s, ridged_multifractal_3d::<S>, s)
+        }
         NoiseType::Gradient(s) => get_3d_noise_helper_f64!(s, simplex_3d::<S>, s.dim.seed as i64),
+        NoiseType::Perlin(_) => {
+            panic!("not implemented");
+        }
+        NoiseType::Value(_) => {
+            panic!("not implemented");
+        }
         NoiseType::Cellular(s) => get_3d_noise_helper_f64!(
             s,
             cellular_3d::<S>,
@@ -422,12 +572,43 @@ pub unsafe fn get_3d_noise_f64<S: Simd>(noise_type: &NoiseType) -> (Vec<f64>, f6
             s.index1,
             s.dim.seed as i64
         ),
+        NoiseType::DomainWarp(_) => {
+            panic!("not implemented");
+        }
+        NoiseType::IqFbm(_) | NoiseType::Swiss(_) | NoiseType::Jordan(_) => {
+            panic!("not implemented");
+        }
     }
 }
 
 #[inline(always)]
 pub unsafe fn get_4d_noise_f64<S: Simd>(noise_type: &NoiseType) -> (Vec<f64>, f64, f64) {
+    if noise_type.period().is_some() || noise_type.get_dimensions().transform.is_some() {
+        panic!("not implemented");
+    }
     match noise_type {
+        NoiseType::Fbm(FbmSettings {
+            variation,
+            normalize,
+            ..
+        })
+        | NoiseType::Billow(BillowSettings {
+            variation,
+            normalize,
+            ..
+        })
+        | NoiseType::Ridge(RidgeSettings {
+            variation,
+            normalize,
+            ..
+        })
+        | NoiseType::Turbulence(TurbulenceSettings {
+            variation,
+            normalize,
+            ..
+        }) if variation.is_varied() || *normalize => {
+            panic!("not implemented");
+        }
         NoiseType::Fbm(s) => get_4d_noise_helper_f64!(
             s,
             fbm_4d::<S>,
@@ -436,6 +617,14 @@ pub unsafe fn get_4d_noise_f64<S: Simd>(noise_type: &NoiseType) -> (Vec<f64>, f6
             s.octaves,
             s.dim.seed as i64
         ),
+        NoiseType::Billow(s) => get_4d_noise_helper_f64!(
+            s,
+            billow_4d::<S>,
+            S::set1_pd(s.lacunarity as f64),
+            S::set1_pd(s.gain as f64),
+            s.octaves,
+            s.dim.seed as i64
+        ),
         NoiseType::Ridge(s) => get_4d_noise_helper_f64!(
             s,
             ridge_4d::<S>,
@@ -452,12 +641,31 @@ pub unsafe fn get_4d_noise_f64<S: Simd>(noise_type: &NoiseType) -> (Vec<f64>, f6
             s.octaves,
             s.dim.seed as i64
         ),
+        NoiseType::HybridMultifractal(s) => {
+            get_4d_noise_helper_f64!(s, hybrid_multifractal_4d::<S>, s)
+        }
+        NoiseType::HeteroTerrain(s) => get_4d_noise_helper_f64!(s, hetero_terrain_4d::<S>, s),
+        NoiseType::RidgedMultifractal(s) => {
+            get_4d_noise_helper_f64!(s, ridged_multifractal_4d::<S>, s)
+        }
         NoiseType::Gradient(s) => get_4d_noise_helper_f64!(s, simplex_4d::<S>, s.dim.seed as i64),
+        NoiseType::Perlin(_) => {
+            panic!("not implemented");
+        }
+        NoiseType::Value(_) => {
+            panic!("not implemented");
+        }
         NoiseType::Cellular(_) => {
             panic!("not implemented");
         }
         NoiseType::Cellular2(_) => {
             panic!("not implemented");
         }
+        NoiseType::DomainWarp(_) => {
+            panic!("not implemented");
+        }
+        NoiseType::IqFbm(_) | NoiseType::Swiss(_) | NoiseType::Jordan(_) => {
+            panic!("not implemented");
+        }
     }
 }
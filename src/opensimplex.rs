@@ -0,0 +1,762 @@
+//! OpenSimplex2 noise, in a fast (OpenSimplex2) and a smooth (OpenSimplex2S)
+//! flavor.
+//!
+//! 3D noise is evaluated on a rotated body centered cubic lattice and 4D noise
+//! on offset copies of the A4 lattice, so slices along an axis don't show the
+//! grid aligned artifacts of `simplex_3d` and `simplex_4d`. The smooth flavor
+//! uses a larger kernel radius and sums more lattice points, so it is slower.
+use self::simdeez::*;
+use super::*;
+use crate::simplex::grad4;
+use std::f32;
+
+const X_PRIME: i32 = 501125321;
+const Y_PRIME: i32 = 1136930381;
+const Z_PRIME: i32 = 1720413743;
+const W_PRIME: i32 = 1066037191;
+const HASH_MULTIPLIER: i32 = 0x27d4_eb2d;
+
+const F2: f32 = 0.36602542;
+const G2: f32 = 0.21132487;
+const ROTATE_3D: f32 = 2.0 / 3.0;
+const F4: f32 = 0.309017;
+const G4: f32 = 0.1381966;
+
+const SEED_OFFSET_3D: i32 = 1293373;
+const SEED_OFFSET_4D: i32 = 0x0E83_DC3E;
+const LATTICE_STEP_4D: f32 = 0.2;
+
+// Kernel radii. The fast 3d and 4d kernels only visit the closest points of
+// each lattice, 0.5 is the largest radius for which that is seamless.
+const RSQUARED_FAST: f32 = 0.5;
+const RSQUARED_2D_SMOOTH: f32 = 2.0 / 3.0;
+const RSQUARED_3D_SMOOTH: f32 = 0.75;
+const RSQUARED_4D_SMOOTH: f32 = 0.8;
+
+// Scale factors that bring each variant to roughly [-1, 1]
+const SCALE_2D: f32 = 99.83685;
+const SCALE_2D_SMOOTH: f32 = 18.241962;
+const SCALE_3D: f32 = 76.88;
+const SCALE_3D_SMOOTH: f32 = 9.046026;
+const SCALE_4D: f32 = 62.78;
+const SCALE_4D_SMOOTH: f32 = 5.533;
+
+// Skewed lattice offsets, relative to the base of the containing cell, of
+// every point within range of the smooth 4d kernel. Entries are ordered from
+// the axis with the largest fractional coordinate to the smallest.
+const LATTICE_4D_SMOOTH: [[i32; 4]; 30] = [
+    [0, -1, -1, -1],
+    [0, -1, 0, 0],
+    [0, 0, -1, -1],
+    [0, 0, -1, 0],
+    [0, 0, 0, -1],
+    [0, 0, 0, 0],
+    [0, 0, 0, 1],
+    [0, 0, 1, 0],
+    [0, 1, 0, 0],
+    [0, 1, 1, 0],
+    [0, 1, 1, 1],
+    [1, 0, -1, 0],
+    [1, 0, 0, -1],
+    [1, 0, 0, 0],
+    [1, 0, 0, 1],
+    [1, 0, 1, 0],
+    [1, 0, 1, 1],
+    [1, 1, 0, -1],
+    [1, 1, 0, 0],
+    [1, 1, 0, 1],
+    [1, 1, 1, 0],
+    [1, 1, 1, 1],
+    [1, 1, 2, 1],
+    [1, 2, 1, 0],
+    [1, 2, 1, 1],
+    [2, 1, 0, 0],
+    [2, 1, 1, 0],
+    [2, 1, 1, 1],
+    [2, 2, 1, 1],
+    [2, 2, 2, 1],
+];
+
+// 24 evenly spaced unit directions, repeated to fill 128 entries
+#[rustfmt::skip]
+const GRAD_2D: [f32; 256] = [
+    0.9914449, 0.13052619, 0.9238795, 0.38268343, 0.7933533, 0.6087614, 0.6087614, 0.7933533,
+    0.38268343, 0.9238795, 0.13052619, 0.9914449, -0.13052619, 0.9914449, -0.38268343, 0.9238795,
+    -0.6087614, 0.7933533, -0.7933533, 0.6087614, -0.9238795, 0.38268343, -0.9914449, 0.13052619,
+    -0.9914449, -0.13052619, -0.9238795, -0.38268343, -0.7933533, -0.6087614, -0.6087614, -0.7933533,
+    -0.38268343, -0.9238795, -0.13052619, -0.9914449, 0.13052619, -0.9914449, 0.38268343, -0.9238795,
+    0.6087614, -0.7933533, 0.7933533, -0.6087614, 0.9238795, -0.38268343, 0.9914449, -0.13052619,
+    0.9914449, 0.13052619, 0.9238795, 0.38268343, 0.7933533, 0.6087614, 0.6087614, 0.7933533,
+    0.38268343, 0.9238795, 0.13052619, 0.9914449, -0.13052619, 0.9914449, -0.38268343, 0.9238795,
+    -0.6087614, 0.7933533, -0.7933533, 0.6087614, -0.9238795, 0.38268343, -0.9914449, 0.13052619,
+    -0.9914449, -0.13052619, -0.9238795, -0.38268343, -0.7933533, -0.6087614, -0.6087614, -0.7933533,
+    -0.38268343, -0.9238795, -0.13052619, -0.9914449, 0.13052619, -0.9914449, 0.38268343, -0.9238795,
+    0.6087614, -0.7933533, 0.7933533, -0.6087614, 0.9238795, -0.38268343, 0.9914449, -0.13052619,
+    0.9914449, 0.13052619, 0.9238795, 0.38268343, 0.7933533, 0.6087614, 0.6087614, 0.7933533,
+    0.38268343, 0.9238795, 0.13052619, 0.9914449, -0.13052619, 0.9914449, -0.38268343, 0.9238795,
+    -0.6087614, 0.7933533, -0.7933533, 0.6087614, -0.9238795, 0.38268343, -0.9914449, 0.13052619,
+    -0.9914449, -0.13052619, -0.9238795, -0.38268343, -0.7933533, -0.6087614, -0.6087614, -0.7933533,
+    -0.38268343, -0.9238795, -0.13052619, -0.9914449, 0.13052619, -0.9914449, 0.38268343, -0.9238795,
+    0.6087614, -0.7933533, 0.7933533, -0.6087614, 0.9238795, -0.38268343, 0.9914449, -0.13052619,
+    0.9914449, 0.13052619, 0.9238795, 0.38268343, 0.7933533, 0.6087614, 0.6087614, 0.7933533,
+    0.38268343, 0.9238795, 0.13052619, 0.9914449, -0.13052619, 0.9914449, -0.38268343, 0.9238795,
+    -0.6087614, 0.7933533, -0.7933533, 0.6087614, -0.9238795, 0.38268343, -0.9914449, 0.13052619,
+    -0.9914449, -0.13052619, -0.9238795, -0.38268343, -0.7933533, -0.6087614, -0.6087614, -0.7933533,
+    -0.38268343, -0.9238795, -0.13052619, -0.9914449, 0.13052619, -0.9914449, 0.38268343, -0.9238795,
+    0.6087614, -0.7933533, 0.7933533, -0.6087614, 0.9238795, -0.38268343, 0.9914449, -0.13052619,
+    0.9914449, 0.13052619, 0.9238795, 0.38268343, 0.7933533, 0.6087614, 0.6087614, 0.7933533,
+    0.38268343, 0.9238795, 0.13052619, 0.9914449, -0.13052619, 0.9914449, -0.38268343, 0.9238795,
+    -0.6087614, 0.7933533, -0.7933533, 0.6087614, -0.9238795, 0.38268343, -0.9914449, 0.13052619,
+    -0.9914449, -0.13052619, -0.9238795, -0.38268343, -0.7933533, -0.6087614, -0.6087614, -0.7933533,
+    -0.38268343, -0.9238795, -0.13052619, -0.9914449, 0.13052619, -0.9914449, 0.38268343, -0.9238795,
+    0.6087614, -0.7933533, 0.7933533, -0.6087614, 0.9238795, -0.38268343, 0.9914449, -0.13052619,
+    0.9914449, 0.13052619, 0.9238795, 0.38268343, 0.7933533, 0.6087614, 0.6087614, 0.7933533,
+    0.38268343, 0.9238795, 0.13052619, 0.9914449, -0.13052619, 0.9914449, -0.38268343, 0.9238795,
+];
+
+// The 12 cube edge directions, repeated to fill 64 entries padded to 4 lanes
+#[rustfmt::skip]
+const GRAD_3D: [f32; 256] = [
+    1.0, 1.0, 0.0, 0.0, -1.0, 1.0, 0.0, 0.0, 1.0, -1.0, 0.0, 0.0, -1.0, -1.0, 0.0, 0.0,
+    1.0, 0.0, 1.0, 0.0, -1.0, 0.0, 1.0, 0.0, 1.0, 0.0, -1.0, 0.0, -1.0, 0.0, -1.0, 0.0,
+    0.0, 1.0, 1.0, 0.0, 0.0, -1.0, 1.0, 0.0, 0.0, 1.0, -1.0, 0.0, 0.0, -1.0, -1.0, 0.0,
+    1.0, 1.0, 0.0, 0.0, -1.0, 1.0, 0.0, 0.0, 1.0, -1.0, 0.0, 0.0, -1.0, -1.0, 0.0, 0.0,
+    1.0, 0.0, 1.0, 0.0, -1.0, 0.0, 1.0, 0.0, 1.0, 0.0, -1.0, 0.0, -1.0, 0.0, -1.0, 0.0,
+    0.0, 1.0, 1.0, 0.0, 0.0, -1.0, 1.0, 0.0, 0.0, 1.0, -1.0, 0.0, 0.0, -1.0, -1.0, 0.0,
+    1.0, 1.0, 0.0, 0.0, -1.0, 1.0, 0.0, 0.0, 1.0, -1.0, 0.0, 0.0, -1.0, -1.0, 0.0, 0.0,
+    1.0, 0.0, 1.0, 0.0, -1.0, 0.0, 1.0, 0.0, 1.0, 0.0, -1.0, 0.0, -1.0, 0.0, -1.0, 0.0,
+    0.0, 1.0, 1.0, 0.0, 0.0, -1.0, 1.0, 0.0, 0.0, 1.0, -1.0, 0.0, 0.0, -1.0, -1.0, 0.0,
+    1.0, 1.0, 0.0, 0.0, -1.0, 1.0, 0.0, 0.0, 1.0, -1.0, 0.0, 0.0, -1.0, -1.0, 0.0, 0.0,
+    1.0, 0.0, 1.0, 0.0, -1.0, 0.0, 1.0, 0.0, 1.0, 0.0, -1.0, 0.0, -1.0, 0.0, -1.0, 0.0,
+    0.0, 1.0, 1.0, 0.0, 0.0, -1.0, 1.0, 0.0, 0.0, 1.0, -1.0, 0.0, 0.0, -1.0, -1.0, 0.0,
+    1.0, 1.0, 0.0, 0.0, -1.0, 1.0, 0.0, 0.0, 1.0, -1.0, 0.0, 0.0, -1.0, -1.0, 0.0, 0.0,
+    1.0, 0.0, 1.0, 0.0, -1.0, 0.0, 1.0, 0.0, 1.0, 0.0, -1.0, 0.0, -1.0, 0.0, -1.0, 0.0,
+    0.0, 1.0, 1.0, 0.0, 0.0, -1.0, 1.0, 0.0, 0.0, 1.0, -1.0, 0.0, 0.0, -1.0, -1.0, 0.0,
+    1.0, 1.0, 0.0, 0.0, -1.0, 1.0, 0.0, 0.0, 1.0, -1.0, 0.0, 0.0, -1.0, -1.0, 0.0, 0.0,
+];
+
+#[inline(always)]
+unsafe fn hash<S: Simd>(seed: S::Vi32, coords: S::Vi32) -> S::Vi32 {
+    let hash = S::mullo_epi32(S::xor_epi32(seed, coords), S::set1_epi32(HASH_MULTIPLIER));
+    S::xor_epi32(hash, S::srai_epi32(hash, 15))
+}
+
+#[inline(always)]
+unsafe fn falloff<S: Simd>(r2: f32, d2: S::Vf32) -> S::Vf32 {
+    let a = S::max_ps(S::sub_ps(S::set1_ps(r2), d2), S::setzero_ps());
+    let a = S::mul_ps(a, a);
+    S::mul_ps(a, a)
+}
+
+/// Contribution of the 2d lattice point at skewed offset (di, dj) from the
+/// cell base (i, j), where (x0, y0) is the unskewed position in the cell
+#[inline(always)]
+unsafe fn vertex_2d<S: Simd>(
+    seed: S::Vi32,
+    i: S::Vi32,
+    j: S::Vi32,
+    x0: S::Vf32,
+    y0: S::Vf32,
+    di: S::Vi32,
+    dj: S::Vi32,
+    r2: f32,
+) -> S::Vf32 {
+    let dif = S::cvtepi32_ps(di);
+    let djf = S::cvtepi32_ps(dj);
+    let t = S::mul_ps(S::add_ps(dif, djf), S::set1_ps(G2));
+    let dx = S::add_ps(S::sub_ps(x0, dif), t);
+    let dy = S::add_ps(S::sub_ps(y0, djf), t);
+
+    let iv = S::add_epi32(i, S::mullo_epi32(di, S::set1_epi32(X_PRIME)));
+    let jv = S::add_epi32(j, S::mullo_epi32(dj, S::set1_epi32(Y_PRIME)));
+    let gi = S::and_epi32(
+        hash::<S>(seed, S::xor_epi32(iv, jv)),
+        S::set1_epi32(127 << 1),
+    );
+    let gx = S::i32gather_ps(&GRAD_2D, gi);
+    let gy = S::i32gather_ps(&GRAD_2D, S::add_epi32(gi, S::set1_epi32(1)));
+    let grad = S::fmadd_ps(gx, dx, S::mul_ps(gy, dy));
+
+    let d2 = S::fmadd_ps(dx, dx, S::mul_ps(dy, dy));
+    S::mul_ps(falloff::<S>(r2, d2), grad)
+}
+
+/// Contribution of the 3d lattice point with premultiplied coordinates
+/// (i, j, k), at offset (x, y, z) from the sample
+#[inline(always)]
+unsafe fn vertex_3d<S: Simd>(
+    seed: S::Vi32,
+    i: S::Vi32,
+    j: S::Vi32,
+    k: S::Vi32,
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    r2: f32,
+) -> S::Vf32 {
+    let gi = S::and_epi32(
+        hash::<S>(seed, S::xor_epi32(S::xor_epi32(i, j), k)),
+        S::set1_epi32(63 << 2),
+    );
+    let gx = S::i32gather_ps(&GRAD_3D, gi);
+    let gy = S::i32gather_ps(&GRAD_3D, S::add_epi32(gi, S::set1_epi32(1)));
+    let gz = S::i32gather_ps(&GRAD_3D, S::add_epi32(gi, S::set1_epi32(2)));
+    let grad = S::fmadd_ps(gx, x, S::fmadd_ps(gy, y, S::mul_ps(gz, z)));
+
+    let d2 = S::fmadd_ps(x, x, S::fmadd_ps(y, y, S::mul_ps(z, z)));
+    S::mul_ps(falloff::<S>(r2, d2), grad)
+}
+
+/// Contribution of the 4d lattice point with premultiplied coordinates
+/// (i, j, k, l), at offset (x, y, z, w) from the sample
+#[inline(always)]
+unsafe fn vertex_4d<S: Simd>(
+    seed: S::Vi32,
+    i: S::Vi32,
+    j: S::Vi32,
+    k: S::Vi32,
+    l: S::Vi32,
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    w: S::Vf32,
+    r2: f32,
+) -> S::Vf32 {
+    let h = hash::<S>(seed, S::xor_epi32(S::xor_epi32(i, j), S::xor_epi32(k, l)));
+    let grad = grad4::<S>(0, h, x, y, z, w);
+
+    let d2 = S::fmadd_ps(x, x, S::fmadd_ps(y, y, S::fmadd_ps(z, z, S::mul_ps(w, w))));
+    S::mul_ps(falloff::<S>(r2, d2), grad)
+}
+
+/// 2d OpenSimplex2 noise, the fast flavor
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn opensimplex2_2d<S: Simd>(x: S::Vf32, y: S::Vf32, seed: i32) -> S::Vf32 {
+    let s = S::mul_ps(S::set1_ps(F2), S::add_ps(x, y));
+    let xs = S::add_ps(x, s);
+    let ys = S::add_ps(y, s);
+    let xsb = S::fast_floor_ps(xs);
+    let ysb = S::fast_floor_ps(ys);
+    let xi = S::sub_ps(xs, xsb);
+    let yi = S::sub_ps(ys, ysb);
+
+    let i = S::mullo_epi32(S::cvtps_epi32(xsb), S::set1_epi32(X_PRIME));
+    let j = S::mullo_epi32(S::cvtps_epi32(ysb), S::set1_epi32(Y_PRIME));
+
+    let t = S::mul_ps(S::add_ps(xi, yi), S::set1_ps(G2));
+    let x0 = S::sub_ps(xi, t);
+    let y0 = S::sub_ps(yi, t);
+
+    let seed = S::set1_epi32(seed);
+    let zero = S::setzero_epi32();
+    let one = S::set1_epi32(1);
+    let r2 = RSQUARED_FAST;
+
+    // The two corners on the diagonal, then whichever of the other two
+    // shares the triangle we are in
+    let upper = S::castps_epi32(S::cmpgt_ps(y0, x0));
+    let di = S::blendv_epi32(one, zero, upper);
+    let dj = S::blendv_epi32(zero, one, upper);
+
+    let n0 = vertex_2d::<S>(seed, i, j, x0, y0, zero, zero, r2);
+    let n1 = vertex_2d::<S>(seed, i, j, x0, y0, one, one, r2);
+    let n2 = vertex_2d::<S>(seed, i, j, x0, y0, di, dj, r2);
+
+    S::mul_ps(S::add_ps(n0, S::add_ps(n1, n2)), S::set1_ps(SCALE_2D))
+}
+
+/// 2d OpenSimplex2S noise, the smooth flavor
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn opensimplex2s_2d<S: Simd>(x: S::Vf32, y: S::Vf32, seed: i32) -> S::Vf32 {
+    let s = S::mul_ps(S::set1_ps(F2), S::add_ps(x, y));
+    let xs = S::add_ps(x, s);
+    let ys = S::add_ps(y, s);
+    let xsb = S::fast_floor_ps(xs);
+    let ysb = S::fast_floor_ps(ys);
+    let xi = S::sub_ps(xs, xsb);
+    let yi = S::sub_ps(ys, ysb);
+
+    let i = S::mullo_epi32(S::cvtps_epi32(xsb), S::set1_epi32(X_PRIME));
+    let j = S::mullo_epi32(S::cvtps_epi32(ysb), S::set1_epi32(Y_PRIME));
+
+    let t = S::mul_ps(S::add_ps(xi, yi), S::set1_ps(G2));
+    let x0 = S::sub_ps(xi, t);
+    let y0 = S::sub_ps(yi, t);
+
+    let seed = S::set1_epi32(seed);
+    let neg_one = S::set1_epi32(-1);
+    let zero = S::setzero_epi32();
+    let one = S::set1_epi32(1);
+    let two = S::set1_epi32(2);
+    let r2 = RSQUARED_2D_SMOOTH;
+
+    // Besides the two corners on the diagonal, the kernel reaches one more
+    // point on each side of it. Which ones depends on the half of the cell
+    // we are in and how close we are to its edges.
+    let xmyi = S::sub_ps(xi, yi);
+    let upper = S::castps_epi32(S::cmpgt_ps(t, S::set1_ps(G2)));
+
+    let far_x = S::castps_epi32(S::cmpgt_ps(S::add_ps(xi, xmyi), S::set1_ps(1.0)));
+    let near_x = S::castps_epi32(S::cmplt_ps(S::add_ps(xi, xmyi), S::setzero_ps()));
+    let di2 = S::blendv_epi32(
+        S::blendv_epi32(one, neg_one, near_x),
+        S::blendv_epi32(zero, two, far_x),
+        upper,
+    );
+    let dj2 = S::blendv_epi32(zero, one, upper);
+
+    let far_y = S::castps_epi32(S::cmpgt_ps(S::sub_ps(yi, xmyi), S::set1_ps(1.0)));
+    let near_y = S::castps_epi32(S::cmplt_ps(yi, xmyi));
+    let di3 = S::blendv_epi32(zero, one, upper);
+    let dj3 = S::blendv_epi32(
+        S::blendv_epi32(one, neg_one, near_y),
+        S::blendv_epi32(zero, two, far_y),
+        upper,
+    );
+
+    let n0 = vertex_2d::<S>(seed, i, j, x0, y0, zero, zero, r2);
+    let n1 = vertex_2d::<S>(seed, i, j, x0, y0, one, one, r2);
+    let n2 = vertex_2d::<S>(seed, i, j, x0, y0, di2, dj2, r2);
+    let n3 = vertex_2d::<S>(seed, i, j, x0, y0, di3, dj3, r2);
+
+    S::mul_ps(
+        S::add_ps(S::add_ps(n0, n1), S::add_ps(n2, n3)),
+        S::set1_ps(SCALE_2D_SMOOTH),
+    )
+}
+
+/// Sums the points of one of the two cubic lattices that make up the body
+/// centered cubic lattice. (x0, y0, z0) is the offset of the sample from the
+/// closest point (i, j, k) of that lattice.
+#[inline(always)]
+unsafe fn lattice_3d<S: Simd>(
+    seed: S::Vi32,
+    i: S::Vi32,
+    j: S::Vi32,
+    k: S::Vi32,
+    x0: S::Vf32,
+    y0: S::Vf32,
+    z0: S::Vf32,
+    smooth: bool,
+) -> S::Vf32 {
+    // The neighbors on the side of the sample along each axis
+    let px = S::cmpge_ps(x0, S::setzero_ps());
+    let py = S::cmpge_ps(y0, S::setzero_ps());
+    let pz = S::cmpge_ps(z0, S::setzero_ps());
+    let x1 = S::sub_ps(x0, S::blendv_ps(S::set1_ps(-1.0), S::set1_ps(1.0), px));
+    let y1 = S::sub_ps(y0, S::blendv_ps(S::set1_ps(-1.0), S::set1_ps(1.0), py));
+    let z1 = S::sub_ps(z0, S::blendv_ps(S::set1_ps(-1.0), S::set1_ps(1.0), pz));
+    let i1 = S::add_epi32(
+        i,
+        S::blendv_epi32(
+            S::set1_epi32(-X_PRIME),
+            S::set1_epi32(X_PRIME),
+            S::castps_epi32(px),
+        ),
+    );
+    let j1 = S::add_epi32(
+        j,
+        S::blendv_epi32(
+            S::set1_epi32(-Y_PRIME),
+            S::set1_epi32(Y_PRIME),
+            S::castps_epi32(py),
+        ),
+    );
+    let k1 = S::add_epi32(
+        k,
+        S::blendv_epi32(
+            S::set1_epi32(-Z_PRIME),
+            S::set1_epi32(Z_PRIME),
+            S::castps_epi32(pz),
+        ),
+    );
+
+    if smooth {
+        let r2 = RSQUARED_3D_SMOOTH;
+        let n0 = vertex_3d::<S>(seed, i, j, k, x0, y0, z0, r2);
+        let n1 = vertex_3d::<S>(seed, i1, j, k, x1, y0, z0, r2);
+        let n2 = vertex_3d::<S>(seed, i, j1, k, x0, y1, z0, r2);
+        let n3 = vertex_3d::<S>(seed, i, j, k1, x0, y0, z1, r2);
+        let n4 = vertex_3d::<S>(seed, i1, j1, k, x1, y1, z0, r2);
+        let n5 = vertex_3d::<S>(seed, i1, j, k1, x1, y0, z1, r2);
+        let n6 = vertex_3d::<S>(seed, i, j1, k1, x0, y1, z1, r2);
+        S::add_ps(
+            S::add_ps(S::add_ps(n0, n1), S::add_ps(n2, n3)),
+            S::add_ps(S::add_ps(n4, n5), n6),
+        )
+    } else {
+        // The closest point and its neighbor along the axis we are furthest
+        // out on
+        let ax = S::abs_ps(x0);
+        let ay = S::abs_ps(y0);
+        let az = S::abs_ps(z0);
+        let fx = S::and_ps(S::cmpge_ps(ax, ay), S::cmpge_ps(ax, az));
+        let fy = S::andnot_ps(fx, S::cmpge_ps(ay, az));
+        let fz = S::andnot_ps(S::or_ps(fx, fy), S::castepi32_ps(S::set1_epi32(-1)));
+
+        let xf = S::blendv_ps(x0, x1, fx);
+        let yf = S::blendv_ps(y0, y1, fy);
+        let zf = S::blendv_ps(z0, z1, fz);
+        let iv = S::blendv_epi32(i, i1, S::castps_epi32(fx));
+        let jv = S::blendv_epi32(j, j1, S::castps_epi32(fy));
+        let kv = S::blendv_epi32(k, k1, S::castps_epi32(fz));
+
+        let r2 = RSQUARED_FAST;
+        let n0 = vertex_3d::<S>(seed, i, j, k, x0, y0, z0, r2);
+        let n1 = vertex_3d::<S>(seed, iv, jv, kv, xf, yf, zf, r2);
+        S::add_ps(n0, n1)
+    }
+}
+
+#[inline(always)]
+unsafe fn opensimplex_3d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    seed: i32,
+    smooth: bool,
+) -> S::Vf32 {
+    // Rotate so that the main diagonal of the lattice points up the z axis
+    let r = S::mul_ps(S::add_ps(S::add_ps(x, y), z), S::set1_ps(ROTATE_3D));
+    let xr = S::sub_ps(r, x);
+    let yr = S::sub_ps(r, y);
+    let zr = S::sub_ps(r, z);
+
+    // First lattice, on the integer coordinates
+    let xb = S::fast_floor_ps(S::add_ps(xr, S::set1_ps(0.5)));
+    let yb = S::fast_floor_ps(S::add_ps(yr, S::set1_ps(0.5)));
+    let zb = S::fast_floor_ps(S::add_ps(zr, S::set1_ps(0.5)));
+    let i = S::mullo_epi32(S::cvtps_epi32(xb), S::set1_epi32(X_PRIME));
+    let j = S::mullo_epi32(S::cvtps_epi32(yb), S::set1_epi32(Y_PRIME));
+    let k = S::mullo_epi32(S::cvtps_epi32(zb), S::set1_epi32(Z_PRIME));
+    let a = lattice_3d::<S>(
+        S::set1_epi32(seed),
+        i,
+        j,
+        k,
+        S::sub_ps(xr, xb),
+        S::sub_ps(yr, yb),
+        S::sub_ps(zr, zb),
+        smooth,
+    );
+
+    // Second lattice, offset by half a cell, hashed with a different seed
+    let xb = S::fast_floor_ps(xr);
+    let yb = S::fast_floor_ps(yr);
+    let zb = S::fast_floor_ps(zr);
+    let i = S::mullo_epi32(S::cvtps_epi32(xb), S::set1_epi32(X_PRIME));
+    let j = S::mullo_epi32(S::cvtps_epi32(yb), S::set1_epi32(Y_PRIME));
+    let k = S::mullo_epi32(S::cvtps_epi32(zb), S::set1_epi32(Z_PRIME));
+    let seed = if smooth {
+        seed.wrapping_add(SEED_OFFSET_3D)
+    } else {
+        !seed
+    };
+    let half = S::set1_ps(0.5);
+    let b = lattice_3d::<S>(
+        S::set1_epi32(seed),
+        S::add_epi32(i, S::set1_epi32(X_PRIME)),
+        S::add_epi32(j, S::set1_epi32(Y_PRIME)),
+        S::add_epi32(k, S::set1_epi32(Z_PRIME)),
+        S::sub_ps(S::sub_ps(xr, xb), half),
+        S::sub_ps(S::sub_ps(yr, yb), half),
+        S::sub_ps(S::sub_ps(zr, zb), half),
+        smooth,
+    );
+
+    S::add_ps(a, b)
+}
+
+/// 3d OpenSimplex2 noise, the fast flavor
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn opensimplex2_3d<S: Simd>(x: S::Vf32, y: S::Vf32, z: S::Vf32, seed: i32) -> S::Vf32 {
+    S::mul_ps(
+        opensimplex_3d::<S>(x, y, z, seed, false),
+        S::set1_ps(SCALE_3D),
+    )
+}
+
+/// 3d OpenSimplex2S noise, the smooth flavor
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn opensimplex2s_3d<S: Simd>(x: S::Vf32, y: S::Vf32, z: S::Vf32, seed: i32) -> S::Vf32 {
+    S::mul_ps(
+        opensimplex_3d::<S>(x, y, z, seed, true),
+        S::set1_ps(SCALE_3D_SMOOTH),
+    )
+}
+
+/// 4d OpenSimplex2 noise, the fast flavor
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn opensimplex2_4d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    w: S::Vf32,
+    seed: i32,
+) -> S::Vf32 {
+    // Skew onto the A4 lattice, the dual of the one simplex noise uses
+    let s = S::mul_ps(S::set1_ps(-G4), S::add_ps(S::add_ps(x, y), S::add_ps(z, w)));
+    let xs = S::add_ps(x, s);
+    let ys = S::add_ps(y, s);
+    let zs = S::add_ps(z, s);
+    let ws = S::add_ps(w, s);
+    let xsb = S::fast_floor_ps(xs);
+    let ysb = S::fast_floor_ps(ys);
+    let zsb = S::fast_floor_ps(zs);
+    let wsb = S::fast_floor_ps(ws);
+    let mut xsi = S::sub_ps(xs, xsb);
+    let mut ysi = S::sub_ps(ys, ysb);
+    let mut zsi = S::sub_ps(zs, zsb);
+    let mut wsi = S::sub_ps(ws, wsb);
+
+    // Five copies of the lattice, each shifted along the main diagonal, add
+    // one point each. Start on the copy that is sure to contribute and walk
+    // down through the others, wrapping around to the top.
+    let si_sum = S::add_ps(S::add_ps(xsi, ysi), S::add_ps(zsi, wsi));
+    let start = S::cvtps_epi32(S::fast_floor_ps(S::mul_ps(si_sum, S::set1_ps(1.25))));
+    let mut seed = S::add_epi32(
+        S::set1_epi32(seed),
+        S::mullo_epi32(start, S::set1_epi32(SEED_OFFSET_4D)),
+    );
+    let offset = S::mul_ps(S::cvtepi32_ps(start), S::set1_ps(-LATTICE_STEP_4D));
+    xsi = S::add_ps(xsi, offset);
+    ysi = S::add_ps(ysi, offset);
+    zsi = S::add_ps(zsi, offset);
+    wsi = S::add_ps(wsi, offset);
+    let mut ssi = S::mul_ps(S::fmadd_ps(offset, S::set1_ps(4.0), si_sum), S::set1_ps(F4));
+
+    let mut i = S::mullo_epi32(S::cvtps_epi32(xsb), S::set1_epi32(X_PRIME));
+    let mut j = S::mullo_epi32(S::cvtps_epi32(ysb), S::set1_epi32(Y_PRIME));
+    let mut k = S::mullo_epi32(S::cvtps_epi32(zsb), S::set1_epi32(Z_PRIME));
+    let mut l = S::mullo_epi32(S::cvtps_epi32(wsb), S::set1_epi32(W_PRIME));
+
+    let mut value = S::setzero_ps();
+    for copy in 0..5 {
+        // Step to the closest vertex of the simplex based at the current point
+        let score0 = S::fnmadd_ps(ssi, S::set1_ps(1.0 / F4), S::set1_ps(1.0));
+        let step_x = S::and_ps(
+            S::and_ps(S::cmpge_ps(xsi, ysi), S::cmpge_ps(xsi, zsi)),
+            S::and_ps(S::cmpge_ps(xsi, wsi), S::cmpge_ps(xsi, score0)),
+        );
+        let step_y = S::and_ps(
+            S::and_ps(S::cmpgt_ps(ysi, xsi), S::cmpge_ps(ysi, zsi)),
+            S::and_ps(S::cmpge_ps(ysi, wsi), S::cmpge_ps(ysi, score0)),
+        );
+        let step_z = S::and_ps(
+            S::and_ps(S::cmpgt_ps(zsi, xsi), S::cmpgt_ps(zsi, ysi)),
+            S::and_ps(S::cmpge_ps(zsi, wsi), S::cmpge_ps(zsi, score0)),
+        );
+        let step_w = S::and_ps(
+            S::and_ps(S::cmpgt_ps(wsi, xsi), S::cmpgt_ps(wsi, ysi)),
+            S::and_ps(S::cmpgt_ps(wsi, zsi), S::cmpge_ps(wsi, score0)),
+        );
+        let one = S::set1_ps(1.0);
+        xsi = S::sub_ps(xsi, S::and_ps(step_x, one));
+        ysi = S::sub_ps(ysi, S::and_ps(step_y, one));
+        zsi = S::sub_ps(zsi, S::and_ps(step_z, one));
+        wsi = S::sub_ps(wsi, S::and_ps(step_w, one));
+        let stepped = S::or_ps(S::or_ps(step_x, step_y), S::or_ps(step_z, step_w));
+        ssi = S::sub_ps(ssi, S::and_ps(stepped, S::set1_ps(F4)));
+        i = S::add_epi32(
+            i,
+            S::and_epi32(S::castps_epi32(step_x), S::set1_epi32(X_PRIME)),
+        );
+        j = S::add_epi32(
+            j,
+            S::and_epi32(S::castps_epi32(step_y), S::set1_epi32(Y_PRIME)),
+        );
+        k = S::add_epi32(
+            k,
+            S::and_epi32(S::castps_epi32(step_z), S::set1_epi32(Z_PRIME)),
+        );
+        l = S::add_epi32(
+            l,
+            S::and_epi32(S::castps_epi32(step_w), S::set1_epi32(W_PRIME)),
+        );
+
+        let dx = S::add_ps(xsi, ssi);
+        let dy = S::add_ps(ysi, ssi);
+        let dz = S::add_ps(zsi, ssi);
+        let dw = S::add_ps(wsi, ssi);
+        value = S::add_ps(
+            value,
+            vertex_4d::<S>(seed, i, j, k, l, dx, dy, dz, dw, RSQUARED_FAST),
+        );
+
+        if copy == 4 {
+            break;
+        }
+
+        // Move down to the next copy of the lattice
+        let step = S::set1_ps(LATTICE_STEP_4D);
+        xsi = S::add_ps(xsi, step);
+        ysi = S::add_ps(ysi, step);
+        zsi = S::add_ps(zsi, step);
+        wsi = S::add_ps(wsi, step);
+        ssi = S::add_ps(ssi, S::set1_ps(LATTICE_STEP_4D * 4.0 * F4));
+        seed = S::sub_epi32(seed, S::set1_epi32(SEED_OFFSET_4D));
+
+        // Wrap from the bottom copy to the top one, a cell further down
+        let wrap = S::cmpeq_epi32(start, S::set1_epi32(copy));
+        i = S::sub_epi32(i, S::and_epi32(wrap, S::set1_epi32(X_PRIME)));
+        j = S::sub_epi32(j, S::and_epi32(wrap, S::set1_epi32(Y_PRIME)));
+        k = S::sub_epi32(k, S::and_epi32(wrap, S::set1_epi32(Z_PRIME)));
+        l = S::sub_epi32(l, S::and_epi32(wrap, S::set1_epi32(W_PRIME)));
+        seed = S::add_epi32(seed, S::and_epi32(wrap, S::set1_epi32(SEED_OFFSET_4D * 5)));
+    }
+
+    S::mul_ps(value, S::set1_ps(SCALE_4D))
+}
+
+/// Picks the entry of a rank ordered lattice offset that applies to an axis,
+/// given masks of the axis having each rank from highest to lowest. Returns
+/// the entry premultiplied by the axis prime, and as a float.
+#[inline(always)]
+unsafe fn pick_offset<S: Simd>(
+    rank_masks: &[S::Vi32; 4],
+    offset: &[i32; 4],
+    prime: i32,
+) -> (S::Vi32, S::Vf32) {
+    let mut primed = S::setzero_epi32();
+    let mut float = S::setzero_ps();
+    for (mask, &o) in rank_masks.iter().zip(offset.iter()) {
+        primed = S::or_epi32(
+            primed,
+            S::and_epi32(*mask, S::set1_epi32(o.wrapping_mul(prime))),
+        );
+        float = S::or_ps(
+            float,
+            S::and_ps(S::castepi32_ps(*mask), S::set1_ps(o as f32)),
+        );
+    }
+    (primed, float)
+}
+
+#[inline(always)]
+unsafe fn rank_masks<S: Simd>(rank: S::Vi32) -> [S::Vi32; 4] {
+    [
+        S::cmpeq_epi32(rank, S::set1_epi32(3)),
+        S::cmpeq_epi32(rank, S::set1_epi32(2)),
+        S::cmpeq_epi32(rank, S::set1_epi32(1)),
+        S::cmpeq_epi32(rank, S::setzero_epi32()),
+    ]
+}
+
+/// 4d OpenSimplex2S noise, the smooth flavor
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn opensimplex2s_4d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    w: S::Vf32,
+    seed: i32,
+) -> S::Vf32 {
+    let s = S::mul_ps(S::set1_ps(F4), S::add_ps(S::add_ps(x, y), S::add_ps(z, w)));
+    let xs = S::add_ps(x, s);
+    let ys = S::add_ps(y, s);
+    let zs = S::add_ps(z, s);
+    let ws = S::add_ps(w, s);
+    let xsb = S::fast_floor_ps(xs);
+    let ysb = S::fast_floor_ps(ys);
+    let zsb = S::fast_floor_ps(zs);
+    let wsb = S::fast_floor_ps(ws);
+    let xsi = S::sub_ps(xs, xsb);
+    let ysi = S::sub_ps(ys, ysb);
+    let zsi = S::sub_ps(zs, zsb);
+    let wsi = S::sub_ps(ws, wsb);
+    let si_sum = S::add_ps(S::add_ps(xsi, ysi), S::add_ps(zsi, wsi));
+
+    let i = S::mullo_epi32(S::cvtps_epi32(xsb), S::set1_epi32(X_PRIME));
+    let j = S::mullo_epi32(S::cvtps_epi32(ysb), S::set1_epi32(Y_PRIME));
+    let k = S::mullo_epi32(S::cvtps_epi32(zsb), S::set1_epi32(Z_PRIME));
+    let l = S::mullo_epi32(S::cvtps_epi32(wsb), S::set1_epi32(W_PRIME));
+
+    // Rank the axes by fractional coordinate, ties go to the later axis
+    let mut rank_x = S::setzero_epi32();
+    let mut rank_y = S::setzero_epi32();
+    let mut rank_z = S::setzero_epi32();
+    let mut rank_w = S::setzero_epi32();
+    let one = S::set1_epi32(1);
+
+    let cond = S::castps_epi32(S::cmpgt_ps(xsi, ysi));
+    rank_x = S::add_epi32(rank_x, S::and_epi32(cond, one));
+    rank_y = S::add_epi32(rank_y, S::andnot_epi32(cond, one));
+    let cond = S::castps_epi32(S::cmpgt_ps(xsi, zsi));
+    rank_x = S::add_epi32(rank_x, S::and_epi32(cond, one));
+    rank_z = S::add_epi32(rank_z, S::andnot_epi32(cond, one));
+    let cond = S::castps_epi32(S::cmpgt_ps(xsi, wsi));
+    rank_x = S::add_epi32(rank_x, S::and_epi32(cond, one));
+    rank_w = S::add_epi32(rank_w, S::andnot_epi32(cond, one));
+    let cond = S::castps_epi32(S::cmpgt_ps(ysi, zsi));
+    rank_y = S::add_epi32(rank_y, S::and_epi32(cond, one));
+    rank_z = S::add_epi32(rank_z, S::andnot_epi32(cond, one));
+    let cond = S::castps_epi32(S::cmpgt_ps(ysi, wsi));
+    rank_y = S::add_epi32(rank_y, S::and_epi32(cond, one));
+    rank_w = S::add_epi32(rank_w, S::andnot_epi32(cond, one));
+    let cond = S::castps_epi32(S::cmpgt_ps(zsi, wsi));
+    rank_z = S::add_epi32(rank_z, S::and_epi32(cond, one));
+    rank_w = S::add_epi32(rank_w, S::andnot_epi32(cond, one));
+
+    let masks_x = rank_masks::<S>(rank_x);
+    let masks_y = rank_masks::<S>(rank_y);
+    let masks_z = rank_masks::<S>(rank_z);
+    let masks_w = rank_masks::<S>(rank_w);
+
+    let seed = S::set1_epi32(seed);
+    let mut value = S::setzero_ps();
+    for offset in LATTICE_4D_SMOOTH.iter() {
+        let (di, dxf) = pick_offset::<S>(&masks_x, offset, X_PRIME);
+        let (dj, dyf) = pick_offset::<S>(&masks_y, offset, Y_PRIME);
+        let (dk, dzf) = pick_offset::<S>(&masks_z, offset, Z_PRIME);
+        let (dl, dwf) = pick_offset::<S>(&masks_w, offset, W_PRIME);
+
+        let offset_sum = (offset[0] + offset[1] + offset[2] + offset[3]) as f32;
+        let t = S::mul_ps(S::sub_ps(si_sum, S::set1_ps(offset_sum)), S::set1_ps(G4));
+        let dx = S::sub_ps(S::sub_ps(xsi, dxf), t);
+        let dy = S::sub_ps(S::sub_ps(ysi, dyf), t);
+        let dz = S::sub_ps(S::sub_ps(zsi, dzf), t);
+        let dw = S::sub_ps(S::sub_ps(wsi, dwf), t);
+
+        value = S::add_ps(
+            value,
+            vertex_4d::<S>(
+                seed,
+                S::add_epi32(i, di),
+                S::add_epi32(j, dj),
+                S::add_epi32(k, dk),
+                S::add_epi32(l, dl),
+                dx,
+                dy,
+                dz,
+                dw,
+                RSQUARED_4D_SMOOTH,
+            ),
+        );
+    }
+
+    S::mul_ps(value, S::set1_ps(SCALE_4D_SMOOTH))
+}
@@ -0,0 +1,320 @@
+use self::simdeez::*;
+use super::*;
+use crate::shared::*;
+use crate::simplex::{grad1, grad2, grad4};
+use std::f32;
+
+// Scale factors that bring each dimension to roughly [-1, 1]
+const SCALE_1D: f32 = 0.188;
+const SCALE_2D: f32 = 0.507;
+const SCALE_3D: f32 = 0.936;
+const SCALE_4D: f32 = 0.87;
+
+/// Quintic interpolant 6t^5 - 15t^4 + 10t^3 from improved Perlin noise
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn fade<S: Simd>(t: S::Vf32) -> S::Vf32 {
+    let t3 = S::mul_ps(S::mul_ps(t, t), t);
+    S::mul_ps(
+        t3,
+        S::fmadd_ps(
+            t,
+            S::fmadd_ps(t, S::set1_ps(6.0), S::set1_ps(-15.0)),
+            S::set1_ps(10.0),
+        ),
+    )
+}
+
+/// Linear interpolation from `a` to `b` by `t`
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn lerp<S: Simd>(t: S::Vf32, a: S::Vf32, b: S::Vf32) -> S::Vf32 {
+    S::fmadd_ps(t, S::sub_ps(b, a), a)
+}
+
+#[inline(always)]
+unsafe fn grad3<S: Simd>(seed: i32, hash: S::Vi32, x: S::Vf32, y: S::Vf32, z: S::Vf32) -> S::Vf32 {
+    let h = S::and_epi32(S::xor_epi32(hash, S::set1_epi32(seed)), S::set1_epi32(15));
+    let mask = S::castepi32_ps(S::cmpgt_epi32(S::set1_epi32(8), h));
+    let u = S::blendv_ps(y, x, mask);
+
+    let h_lt_4 = S::castepi32_ps(S::cmpgt_epi32(S::set1_epi32(4), h));
+    let h_12_or_14 = S::castepi32_ps(S::cmpeq_epi32(
+        S::set1_epi32(12),
+        S::and_epi32(h, S::set1_epi32(13)),
+    ));
+    let v = S::blendv_ps(S::blendv_ps(z, x, h_12_or_14), y, h_lt_4);
+
+    let h_and_1 = S::castepi32_ps(S::cmpeq_epi32(
+        S::setzero_epi32(),
+        S::and_epi32(h, S::set1_epi32(1)),
+    ));
+    let h_and_2 = S::castepi32_ps(S::cmpeq_epi32(
+        S::setzero_epi32(),
+        S::and_epi32(h, S::set1_epi32(2)),
+    ));
+
+    S::add_ps(
+        S::blendv_ps(S::sub_ps(S::setzero_ps(), u), u, h_and_1),
+        S::blendv_ps(S::sub_ps(S::setzero_ps(), v), v, h_and_2),
+    )
+}
+
+/// 1d improved Perlin noise, scaled to roughly -1 to 1
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn perlin_1d<S: Simd>(x: S::Vf32, seed: i32) -> S::Vf32 {
+    let ips = S::fast_floor_ps(x);
+    let i0 = S::and_epi32(S::cvtps_epi32(ips), S::set1_epi32(0xff));
+    let i1 = S::and_epi32(S::add_epi32(i0, S::set1_epi32(1)), S::set1_epi32(0xff));
+
+    let x0 = S::sub_ps(x, ips);
+    let x1 = S::sub_ps(x0, S::set1_ps(1.0));
+
+    let n0 = grad1::<S>(seed, S::i32gather_epi32(&PERM, i0), x0);
+    let n1 = grad1::<S>(seed, S::i32gather_epi32(&PERM, i1), x1);
+
+    S::mul_ps(S::set1_ps(SCALE_1D), lerp::<S>(fade::<S>(x0), n0, n1))
+}
+
+/// 2d improved Perlin noise, scaled to roughly -1 to 1
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn perlin_2d<S: Simd>(x: S::Vf32, y: S::Vf32, seed: i32) -> S::Vf32 {
+    let ips = S::fast_floor_ps(x);
+    let jps = S::fast_floor_ps(y);
+
+    let i0 = S::and_epi32(S::cvtps_epi32(ips), S::set1_epi32(0xff));
+    let j0 = S::and_epi32(S::cvtps_epi32(jps), S::set1_epi32(0xff));
+    let i1 = S::and_epi32(S::add_epi32(i0, S::set1_epi32(1)), S::set1_epi32(0xff));
+    let j1 = S::and_epi32(S::add_epi32(j0, S::set1_epi32(1)), S::set1_epi32(0xff));
+
+    let x0 = S::sub_ps(x, ips);
+    let y0 = S::sub_ps(y, jps);
+    let x1 = S::sub_ps(x0, S::set1_ps(1.0));
+    let y1 = S::sub_ps(y0, S::set1_ps(1.0));
+
+    let pj0 = S::i32gather_epi32(&PERM, j0);
+    let pj1 = S::i32gather_epi32(&PERM, j1);
+
+    let h00 = S::i32gather_epi32(&PERM, S::add_epi32(i0, pj0));
+    let h01 = S::i32gather_epi32(&PERM, S::add_epi32(i0, pj1));
+    let h10 = S::i32gather_epi32(&PERM, S::add_epi32(i1, pj0));
+    let h11 = S::i32gather_epi32(&PERM, S::add_epi32(i1, pj1));
+
+    let n00 = grad2::<S>(seed, h00, x0, y0);
+    let n01 = grad2::<S>(seed, h01, x0, y1);
+    let n10 = grad2::<S>(seed, h10, x1, y0);
+    let n11 = grad2::<S>(seed, h11, x1, y1);
+
+    let u = fade::<S>(x0);
+    let v = fade::<S>(y0);
+
+    let nx0 = lerp::<S>(v, n00, n01);
+    let nx1 = lerp::<S>(v, n10, n11);
+
+    S::mul_ps(S::set1_ps(SCALE_2D), lerp::<S>(u, nx0, nx1))
+}
+
+/// 3d improved Perlin noise, scaled to roughly -1 to 1
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn perlin_3d<S: Simd>(x: S::Vf32, y: S::Vf32, z: S::Vf32, seed: i32) -> S::Vf32 {
+    let ips = S::fast_floor_ps(x);
+    let jps = S::fast_floor_ps(y);
+    let kps = S::fast_floor_ps(z);
+
+    let i0 = S::and_epi32(S::cvtps_epi32(ips), S::set1_epi32(0xff));
+    let j0 = S::and_epi32(S::cvtps_epi32(jps), S::set1_epi32(0xff));
+    let k0 = S::and_epi32(S::cvtps_epi32(kps), S::set1_epi32(0xff));
+    let i1 = S::and_epi32(S::add_epi32(i0, S::set1_epi32(1)), S::set1_epi32(0xff));
+    let j1 = S::and_epi32(S::add_epi32(j0, S::set1_epi32(1)), S::set1_epi32(0xff));
+    let k1 = S::and_epi32(S::add_epi32(k0, S::set1_epi32(1)), S::set1_epi32(0xff));
+
+    let x0 = S::sub_ps(x, ips);
+    let y0 = S::sub_ps(y, jps);
+    let z0 = S::sub_ps(z, kps);
+    lattice_3d::<S>([i0, j0, k0], [i1, j1, k1], x0, y0, z0, seed)
+}
+
+/// Wraps the lattice cell below `x`, and the one after it, into a period of
+/// `period` cells, masked to index the permutation table
+#[inline(always)]
+unsafe fn wrap_cell<S: Simd>(x: S::Vf32, period: f32) -> (S::Vi32, S::Vi32) {
+    let p = S::set1_ps(period);
+    let mut c0 = S::fast_floor_ps(S::sub_ps(
+        x,
+        S::mul_ps(p, S::fast_floor_ps(S::div_ps(x, p))),
+    ));
+    // Rounding in the division can land a hair outside of the period
+    c0 = S::blendv_ps(c0, S::sub_ps(c0, p), S::cmpge_ps(c0, p));
+    c0 = S::blendv_ps(c0, S::add_ps(c0, p), S::cmplt_ps(c0, S::setzero_ps()));
+    let mut c1 = S::add_ps(c0, S::set1_ps(1.0));
+    c1 = S::blendv_ps(c1, S::setzero_ps(), S::cmpge_ps(c1, p));
+    (
+        S::and_epi32(S::cvtps_epi32(c0), S::set1_epi32(0xff)),
+        S::and_epi32(S::cvtps_epi32(c1), S::set1_epi32(0xff)),
+    )
+}
+
+/// 3d Perlin noise that repeats every `period` lattice cells along each axis.
+/// Periods should be whole numbers.
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn perlin_3d_periodic<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    period: [f32; 3],
+    seed: i32,
+) -> S::Vf32 {
+    let (i0, i1) = wrap_cell::<S>(x, period[0]);
+    let (j0, j1) = wrap_cell::<S>(y, period[1]);
+    let (k0, k1) = wrap_cell::<S>(z, period[2]);
+
+    let x0 = S::sub_ps(x, S::fast_floor_ps(x));
+    let y0 = S::sub_ps(y, S::fast_floor_ps(y));
+    let z0 = S::sub_ps(z, S::fast_floor_ps(z));
+    lattice_3d::<S>([i0, j0, k0], [i1, j1, k1], x0, y0, z0, seed)
+}
+
+/// Blends the gradients at the corners of the cells `c0` to `c1`, `x0`, `y0`
+/// and `z0` being the offsets into the cell
+#[inline(always)]
+unsafe fn lattice_3d<S: Simd>(
+    c0: [S::Vi32; 3],
+    c1: [S::Vi32; 3],
+    x0: S::Vf32,
+    y0: S::Vf32,
+    z0: S::Vf32,
+    seed: i32,
+) -> S::Vf32 {
+    let [i0, j0, k0] = c0;
+    let [i1, j1, k1] = c1;
+    let x1 = S::sub_ps(x0, S::set1_ps(1.0));
+    let y1 = S::sub_ps(y0, S::set1_ps(1.0));
+    let z1 = S::sub_ps(z0, S::set1_ps(1.0));
+
+    let pk0 = S::i32gather_epi32(&PERM, k0);
+    let pk1 = S::i32gather_epi32(&PERM, k1);
+    let pj0k0 = S::i32gather_epi32(&PERM, S::add_epi32(j0, pk0));
+    let pj0k1 = S::i32gather_epi32(&PERM, S::add_epi32(j0, pk1));
+    let pj1k0 = S::i32gather_epi32(&PERM, S::add_epi32(j1, pk0));
+    let pj1k1 = S::i32gather_epi32(&PERM, S::add_epi32(j1, pk1));
+
+    let h000 = S::i32gather_epi32(&PERM, S::add_epi32(i0, pj0k0));
+    let h001 = S::i32gather_epi32(&PERM, S::add_epi32(i0, pj0k1));
+    let h010 = S::i32gather_epi32(&PERM, S::add_epi32(i0, pj1k0));
+    let h011 = S::i32gather_epi32(&PERM, S::add_epi32(i0, pj1k1));
+    let h100 = S::i32gather_epi32(&PERM, S::add_epi32(i1, pj0k0));
+    let h101 = S::i32gather_epi32(&PERM, S::add_epi32(i1, pj0k1));
+    let h110 = S::i32gather_epi32(&PERM, S::add_epi32(i1, pj1k0));
+    let h111 = S::i32gather_epi32(&PERM, S::add_epi32(i1, pj1k1));
+
+    let n000 = grad3::<S>(seed, h000, x0, y0, z0);
+    let n001 = grad3::<S>(seed, h001, x0, y0, z1);
+    let n010 = grad3::<S>(seed, h010, x0, y1, z0);
+    let n011 = grad3::<S>(seed, h011, x0, y1, z1);
+    let n100 = grad3::<S>(seed, h100, x1, y0, z0);
+    let n101 = grad3::<S>(seed, h101, x1, y0, z1);
+    let n110 = grad3::<S>(seed, h110, x1, y1, z0);
+    let n111 = grad3::<S>(seed, h111, x1, y1, z1);
+
+    let u = fade::<S>(x0);
+    let v = fade::<S>(y0);
+    let w = fade::<S>(z0);
+
+    let nxy0 = lerp::<S>(w, n000, n001);
+    let nxy1 = lerp::<S>(w, n010, n011);
+    let nx0 = lerp::<S>(v, nxy0, nxy1);
+
+    let nxy0 = lerp::<S>(w, n100, n101);
+    let nxy1 = lerp::<S>(w, n110, n111);
+    let nx1 = lerp::<S>(v, nxy0, nxy1);
+
+    S::mul_ps(S::set1_ps(SCALE_3D), lerp::<S>(u, nx0, nx1))
+}
+
+/// 4d improved Perlin noise, scaled to roughly -1 to 1
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn perlin_4d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    w: S::Vf32,
+    seed: i32,
+) -> S::Vf32 {
+    let ips = S::fast_floor_ps(x);
+    let jps = S::fast_floor_ps(y);
+    let kps = S::fast_floor_ps(z);
+    let lps = S::fast_floor_ps(w);
+
+    let i0 = S::and_epi32(S::cvtps_epi32(ips), S::set1_epi32(0xff));
+    let j0 = S::and_epi32(S::cvtps_epi32(jps), S::set1_epi32(0xff));
+    let k0 = S::and_epi32(S::cvtps_epi32(kps), S::set1_epi32(0xff));
+    let l0 = S::and_epi32(S::cvtps_epi32(lps), S::set1_epi32(0xff));
+    let i1 = S::and_epi32(S::add_epi32(i0, S::set1_epi32(1)), S::set1_epi32(0xff));
+    let j1 = S::and_epi32(S::add_epi32(j0, S::set1_epi32(1)), S::set1_epi32(0xff));
+    let k1 = S::and_epi32(S::add_epi32(k0, S::set1_epi32(1)), S::set1_epi32(0xff));
+    let l1 = S::and_epi32(S::add_epi32(l0, S::set1_epi32(1)), S::set1_epi32(0xff));
+
+    let x0 = S::sub_ps(x, ips);
+    let y0 = S::sub_ps(y, jps);
+    let z0 = S::sub_ps(z, kps);
+    let w0 = S::sub_ps(w, lps);
+    let x1 = S::sub_ps(x0, S::set1_ps(1.0));
+    let y1 = S::sub_ps(y0, S::set1_ps(1.0));
+    let z1 = S::sub_ps(z0, S::set1_ps(1.0));
+    let w1 = S::sub_ps(w0, S::set1_ps(1.0));
+
+    let u = fade::<S>(x0);
+    let v = fade::<S>(y0);
+    let s = fade::<S>(z0);
+    let t = fade::<S>(w0);
+
+    // Hash the w, z and y lattice coordinates once, then walk the 16 corners
+    let pl = [S::i32gather_epi32(&PERM, l0), S::i32gather_epi32(&PERM, l1)];
+    let mut nx = [S::setzero_ps(); 2];
+    for (a, (ia, xa)) in [(i0, x0), (i1, x1)].iter().enumerate() {
+        let mut ny = [S::setzero_ps(); 2];
+        for (b, (jb, yb)) in [(j0, y0), (j1, y1)].iter().enumerate() {
+            let mut nz = [S::setzero_ps(); 2];
+            for (c, (kc, zc)) in [(k0, z0), (k1, z1)].iter().enumerate() {
+                let mut nw = [S::setzero_ps(); 2];
+                for (d, wd) in [w0, w1].iter().enumerate() {
+                    let pkl = S::i32gather_epi32(&PERM, S::add_epi32(*kc, pl[d]));
+                    let pjkl = S::i32gather_epi32(&PERM, S::add_epi32(*jb, pkl));
+                    let hash = S::i32gather_epi32(&PERM, S::add_epi32(*ia, pjkl));
+                    nw[d] = grad4::<S>(seed, hash, *xa, *yb, *zc, *wd);
+                }
+                nz[c] = lerp::<S>(t, nw[0], nw[1]);
+            }
+            ny[b] = lerp::<S>(s, nz[0], nz[1]);
+        }
+        nx[a] = lerp::<S>(v, ny[0], ny[1]);
+    }
+
+    S::mul_ps(S::set1_ps(SCALE_4D), lerp::<S>(u, nx[0], nx[1]))
+}
@@ -2,6 +2,29 @@ use super::*;
 use crate::shared::*;
 use simdeez::scalar::*;
 use std::f32;
+use std::ops::Range;
+
+/// Get a single value of 1d cellular/voroni noise
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn cellular_1d(
+    x: f32,
+    distance_function: CellDistanceFunction,
+    return_type: CellReturnType,
+    jitter: f32,
+    seed: i32,
+) -> f32 {
+    cellular::cellular_1d::<Scalar>(
+        F32x1(x),
+        distance_function,
+        return_type,
+        F32x1(jitter),
+        seed,
+    )
+    .0
+}
 
 /// Get a single value of 2d cellular/voroni noise
 
@@ -47,6 +70,34 @@ pub unsafe fn cellular_3d(
     .0
 }
 
+/// Get a single value of 4d cellular/voroni noise
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn cellular_4d(
+    x: f32,
+    y: f32,
+    z: f32,
+    w: f32,
+    distance_function: CellDistanceFunction,
+    return_type: CellReturnType,
+    jitter: f32,
+    seed: i32,
+) -> f32 {
+    cellular::cellular_4d::<Scalar>(
+        F32x1(x),
+        F32x1(y),
+        F32x1(z),
+        F32x1(w),
+        distance_function,
+        return_type,
+        F32x1(jitter),
+        seed,
+    )
+    .0
+}
+
 /// Get a single value of 2d cellular/voroni noise
 
 pub unsafe fn cellular_2d_f64(
@@ -97,6 +148,25 @@ pub unsafe fn simplex_1d(x: f32, seed: i32) -> f32 {
     simplex::simplex_1d::<Scalar>(F32x1(x), seed).0
 }
 
+/// Get a single value of 1d perlin noise, scaled to roughly
+/// -1 to 1.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn perlin_1d(x: f32, seed: i32) -> f32 {
+    perlin::perlin_1d::<Scalar>(F32x1(x), seed).0
+}
+
+/// Get a single value of 1d value noise, from -1 to 1.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn value_1d(x: f32, interpolation: Interpolation, seed: i32) -> f32 {
+    value::value_1d::<Scalar>(F32x1(x), interpolation, seed).0
+}
+
 /// Get a single value of 1d fractal brownian motion.
 
 pub unsafe fn fbm_1d(x: f32, lacunarity: f32, gain: f32, octaves: u8, seed: i32) -> f32 {
@@ -115,6 +185,15 @@ pub unsafe fn turbulence_1d(x: f32, lacunarity: f32, gain: f32, octaves: u8, see
     simplex::turbulence_1d::<Scalar>(F32x1(x), F32x1(lacunarity), F32x1(gain), octaves, seed).0
 }
 
+/// Get a single value of 1d billow noise.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn billow_1d(x: f32, lacunarity: f32, gain: f32, octaves: u8, seed: i32) -> f32 {
+    simplex::billow_1d::<Scalar>(F32x1(x), F32x1(lacunarity), F32x1(gain), octaves, seed).0
+}
+
 /// Get a single value of 1d simplex noise, results
 /// are not scaled.
 pub unsafe fn simplex_1d_f64(x: f64, seed: i64) -> f64 {
@@ -139,6 +218,15 @@ pub unsafe fn turbulence_1d_f64(x: f64, lacunarity: f64, gain: f64, octaves: u8,
     simplex_64::turbulence_1d::<Scalar>(F64x1(x), F64x1(lacunarity), F64x1(gain), octaves, seed).0
 }
 
+/// Get a single value of 1d billow noise.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn billow_1d_f64(x: f64, lacunarity: f64, gain: f64, octaves: u8, seed: i64) -> f64 {
+    simplex_64::billow_1d::<Scalar>(F64x1(x), F64x1(lacunarity), F64x1(gain), octaves, seed).0
+}
+
 /// Gets a width sized block of 1d noise, unscaled.
 /// `start_x` can be used to provide an offset in the
 /// coordinates. Results are unscaled, 'min' and 'max' noise values
@@ -161,6 +249,28 @@ pub unsafe fn get_1d_scaled_noise(noise_type: &NoiseType) -> Vec<f32> {
     noise
 }
 
+/// Evaluates 1d noise at the points `xs`, rather than on a
+/// grid. Coordinates are scaled by the frequency of the settings, results are
+/// unscaled, with the min and max value.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn get_1d_noise_at(noise_type: &NoiseType, xs: &[f32]) -> (Vec<f32>, f32, f32) {
+    noise_helpers::get_1d_noise_at::<Scalar>(noise_type, xs)
+}
+
+/// Fills `out`, which must hold exactly one value per sample, with a block of
+/// 1d noise instead of allocating. Results are unscaled, the min and max
+/// value are returned.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn fill_1d_noise(noise_type: &NoiseType, out: &mut [f32]) -> (f32, f32) {
+    noise_helpers::fill_1d_noise::<Scalar>(noise_type, out)
+}
+
 /// Get a single value of 2d simplex noise, results
 /// are not scaled.
 
@@ -168,6 +278,56 @@ pub unsafe fn simplex_2d(x: f32, y: f32, seed: i32) -> f32 {
     simplex::simplex_2d::<Scalar>(F32x1(x), F32x1(y), seed).0
 }
 
+/// Get a single value of 2d simplex noise along with its partial
+/// derivatives `[d/dx, d/dy]`, results are not scaled.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn simplex_2d_deriv(x: f32, y: f32, seed: i32) -> (f32, [f32; 2]) {
+    let (value, d) = simplex::simplex_2d_deriv::<Scalar>(F32x1(x), F32x1(y), seed);
+    (value.0, [d[0].0, d[1].0])
+}
+
+/// Get a single value of 2d perlin noise, scaled to roughly
+/// -1 to 1.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn perlin_2d(x: f32, y: f32, seed: i32) -> f32 {
+    perlin::perlin_2d::<Scalar>(F32x1(x), F32x1(y), seed).0
+}
+
+/// Get a single value of 2d value noise, from -1 to 1.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn value_2d(x: f32, y: f32, interpolation: Interpolation, seed: i32) -> f32 {
+    value::value_2d::<Scalar>(F32x1(x), F32x1(y), interpolation, seed).0
+}
+
+/// Get a single value of 2d OpenSimplex2 noise, results
+/// are not scaled.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn opensimplex2_2d(x: f32, y: f32, seed: i32) -> f32 {
+    opensimplex::opensimplex2_2d::<Scalar>(F32x1(x), F32x1(y), seed).0
+}
+
+/// Get a single value of 2d OpenSimplex2S noise, results
+/// are not scaled.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn opensimplex2s_2d(x: f32, y: f32, seed: i32) -> f32 {
+    opensimplex::opensimplex2s_2d::<Scalar>(F32x1(x), F32x1(y), seed).0
+}
+
 /// Get a single value of 2d simplex noise, results
 /// are not scaled.
 
@@ -181,6 +341,37 @@ pub unsafe fn fbm_2d(x: f32, y: f32, lac: f32, gain: f32, octaves: u8, seed: i32
     simplex::fbm_2d::<Scalar>(F32x1(x), F32x1(y), F32x1(lac), F32x1(gain), octaves, seed).0
 }
 
+/// Get a single value of 2d fractal brownian motion along with its partial
+/// derivatives `[d/dx, d/dy]`.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn fbm_2d_deriv(
+    x: f32,
+    y: f32,
+    lac: f32,
+    gain: f32,
+    octaves: u8,
+    seed: i32,
+) -> (f32, [f32; 2]) {
+    let (value, d) =
+        simplex::fbm_2d_deriv::<Scalar>(F32x1(x), F32x1(y), F32x1(lac), F32x1(gain), octaves, seed);
+    (value.0, [d[0].0, d[1].0])
+}
+
+/// Get a single 2d curl noise velocity, the curl of a simplex fbm
+/// potential. Results are unscaled.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn curl_2d(x: f32, y: f32, lac: f32, gain: f32, octaves: u8, seed: i32) -> (f32, f32) {
+    let (vx, vy) =
+        curl::curl_2d::<Scalar>(F32x1(x), F32x1(y), F32x1(lac), F32x1(gain), octaves, seed);
+    (vx.0, vy.0)
+}
+
 /// Get a single value of 2d fractal brownian motion.
 
 pub unsafe fn fbm_2d_f64(x: f64, y: f64, lac: f64, gain: f64, octaves: u8, seed: i64) -> f64 {
@@ -205,6 +396,15 @@ pub unsafe fn turbulence_2d(x: f32, y: f32, lac: f32, gain: f32, octaves: u8, se
     simplex::turbulence_2d::<Scalar>(F32x1(x), F32x1(y), F32x1(lac), F32x1(gain), octaves, seed).0
 }
 
+/// Get a single value of 2d billow noise.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn billow_2d(x: f32, y: f32, lac: f32, gain: f32, octaves: u8, seed: i32) -> f32 {
+    simplex::billow_2d::<Scalar>(F32x1(x), F32x1(y), F32x1(lac), F32x1(gain), octaves, seed).0
+}
+
 /// Get a single value of 2d turbulence.
 
 pub unsafe fn turbulence_2d_f64(
@@ -219,6 +419,15 @@ pub unsafe fn turbulence_2d_f64(
         .0
 }
 
+/// Get a single value of 2d billow noise.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn billow_2d_f64(x: f64, y: f64, lac: f64, gain: f64, octaves: u8, seed: i64) -> f64 {
+    simplex_64::billow_2d::<Scalar>(F64x1(x), F64x1(y), F64x1(lac), F64x1(gain), octaves, seed).0
+}
+
 /// Gets a width X height sized block of 2d noise, unscaled.
 /// `start_x` and `start_y` can be used to provide an offset in the
 /// coordinates. Results are unscaled, 'min' and 'max' noise values
@@ -226,7 +435,7 @@ pub unsafe fn turbulence_2d_f64(
 /// in a single pass.
 
 pub unsafe fn get_2d_noise(noise_type: &NoiseType) -> (Vec<f32>, f32, f32) {
-    noise_helpers::get_2d_noise::<Scalar>(noise_type)
+    noise_helpers::get_2d_noise(noise_type, fill_2d_noise_rows)
 }
 
 /// Gets a width X height sized block of scaled 2d noise
@@ -241,6 +450,64 @@ pub unsafe fn get_2d_scaled_noise(noise_type: &NoiseType) -> Vec<f32> {
     noise
 }
 
+/// Evaluates 2d noise at the points `xs` and `ys`, rather than on a
+/// grid. Coordinates are scaled by the frequency of the settings, results are
+/// unscaled, with the min and max value.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn get_2d_noise_at(
+    noise_type: &NoiseType,
+    xs: &[f32],
+    ys: &[f32],
+) -> (Vec<f32>, f32, f32) {
+    noise_helpers::get_2d_noise_at::<Scalar>(noise_type, xs, ys)
+}
+
+/// Fills `out`, which must hold exactly one value per sample, with a block of
+/// 2d noise instead of allocating. Results are unscaled, the min and max
+/// value are returned.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn fill_2d_noise(noise_type: &NoiseType, out: &mut [f32]) -> (f32, f32) {
+    noise_helpers::fill_2d_noise(noise_type, out, fill_2d_noise_rows)
+}
+
+/// Like `fill_2d_noise`, but rows start `pitch` values apart, to write into
+/// part of a larger image. Values between rows are left alone.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn fill_2d_noise_pitched(
+    noise_type: &NoiseType,
+    out: &mut [f32],
+    pitch: usize,
+) -> (f32, f32) {
+    noise_helpers::fill_2d_noise_pitched(noise_type, out, pitch, fill_2d_noise_rows)
+}
+
+/// Fills the rows `rows` of a block of 2d noise, counted across the whole
+/// block, into `out`, which starts at the first of them, with rows `pitch`
+/// values apart. Rows come out exactly as they do in a whole block, so a
+/// block can be split between threads. Results are unscaled, the min and max
+/// value are returned.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn fill_2d_noise_rows(
+    noise_type: &NoiseType,
+    out: &mut [f32],
+    pitch: usize,
+    rows: Range<usize>,
+) -> (f32, f32) {
+    noise_helpers::fill_2d_noise_rows::<Scalar>(noise_type, out, pitch, rows)
+}
+
 /// Get a single value of 3d simplex noise, results
 /// are not scaled.
 
@@ -248,6 +515,56 @@ pub unsafe fn simplex_3d(x: f32, y: f32, z: f32, seed: i32) -> f32 {
     simplex::simplex_3d::<Scalar>(F32x1(x), F32x1(y), F32x1(z), seed).0
 }
 
+/// Get a single value of 3d simplex noise along with its partial
+/// derivatives `[d/dx, d/dy, d/dz]`, results are not scaled.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn simplex_3d_deriv(x: f32, y: f32, z: f32, seed: i32) -> (f32, [f32; 3]) {
+    let (value, d) = simplex::simplex_3d_deriv::<Scalar>(F32x1(x), F32x1(y), F32x1(z), seed);
+    (value.0, [d[0].0, d[1].0, d[2].0])
+}
+
+/// Get a single value of 3d perlin noise, scaled to roughly
+/// -1 to 1.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn perlin_3d(x: f32, y: f32, z: f32, seed: i32) -> f32 {
+    perlin::perlin_3d::<Scalar>(F32x1(x), F32x1(y), F32x1(z), seed).0
+}
+
+/// Get a single value of 3d value noise, from -1 to 1.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn value_3d(x: f32, y: f32, z: f32, interpolation: Interpolation, seed: i32) -> f32 {
+    value::value_3d::<Scalar>(F32x1(x), F32x1(y), F32x1(z), interpolation, seed).0
+}
+
+/// Get a single value of 3d OpenSimplex2 noise, results
+/// are not scaled.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn opensimplex2_3d(x: f32, y: f32, z: f32, seed: i32) -> f32 {
+    opensimplex::opensimplex2_3d::<Scalar>(F32x1(x), F32x1(y), F32x1(z), seed).0
+}
+
+/// Get a single value of 3d OpenSimplex2S noise, results
+/// are not scaled.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn opensimplex2s_3d(x: f32, y: f32, z: f32, seed: i32) -> f32 {
+    opensimplex::opensimplex2s_3d::<Scalar>(F32x1(x), F32x1(y), F32x1(z), seed).0
+}
+
 pub unsafe fn simplex_3d_f64(x: f64, y: f64, z: f64, seed: i64) -> f64 {
     simplex_64::simplex_3d::<Scalar>(F64x1(x), F64x1(y), F64x1(z), seed).0
 }
@@ -267,6 +584,60 @@ pub unsafe fn fbm_3d(x: f32, y: f32, z: f32, lac: f32, gain: f32, octaves: u8, s
     .0
 }
 
+/// Get a single value of 3d fractal brownian motion along with its partial
+/// derivatives `[d/dx, d/dy, d/dz]`.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn fbm_3d_deriv(
+    x: f32,
+    y: f32,
+    z: f32,
+    lac: f32,
+    gain: f32,
+    octaves: u8,
+    seed: i32,
+) -> (f32, [f32; 3]) {
+    let (value, d) = simplex::fbm_3d_deriv::<Scalar>(
+        F32x1(x),
+        F32x1(y),
+        F32x1(z),
+        F32x1(lac),
+        F32x1(gain),
+        octaves,
+        seed,
+    );
+    (value.0, [d[0].0, d[1].0, d[2].0])
+}
+
+/// Get a single 3d curl noise velocity, the curl of a vector of three simplex
+/// fbm potentials. Results are unscaled.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn curl_3d(
+    x: f32,
+    y: f32,
+    z: f32,
+    lac: f32,
+    gain: f32,
+    octaves: u8,
+    seed: i32,
+) -> (f32, f32, f32) {
+    let (vx, vy, vz) = curl::curl_3d::<Scalar>(
+        F32x1(x),
+        F32x1(y),
+        F32x1(z),
+        F32x1(lac),
+        F32x1(gain),
+        octaves,
+        seed,
+    );
+    (vx.0, vy.0, vz.0)
+}
+
 /// Get a single value of 3d fractal brownian motion.
 
 pub unsafe fn fbm_3d_f64(
@@ -350,6 +721,32 @@ pub unsafe fn turbulence_3d(
     .0
 }
 
+/// Get a single value of 3d billow noise.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn billow_3d(
+    x: f32,
+    y: f32,
+    z: f32,
+    lac: f32,
+    gain: f32,
+    octaves: u8,
+    seed: i32,
+) -> f32 {
+    simplex::billow_3d::<Scalar>(
+        F32x1(x),
+        F32x1(y),
+        F32x1(z),
+        F32x1(lac),
+        F32x1(gain),
+        octaves,
+        seed,
+    )
+    .0
+}
+
 /// Get a single value of 3d turbulence.
 pub unsafe fn turbulence_3d_f64(
     x: f64,
@@ -372,13 +769,39 @@ pub unsafe fn turbulence_3d_f64(
     .0
 }
 
+/// Get a single value of 3d billow noise.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn billow_3d_f64(
+    x: f64,
+    y: f64,
+    z: f64,
+    lac: f64,
+    gain: f64,
+    octaves: u8,
+    seed: i64,
+) -> f64 {
+    simplex_64::billow_3d::<Scalar>(
+        F64x1(x),
+        F64x1(y),
+        F64x1(z),
+        F64x1(lac),
+        F64x1(gain),
+        octaves,
+        seed,
+    )
+    .0
+}
+
 /// Gets a width X height X depth sized block of 3d noise, unscaled,
 /// `start_x`,`start_y` and `start_z` can be used to provide an offset in the
 /// coordinates. Results are unscaled, 'min' and 'max' noise values
 /// are returned so you can scale and transform the noise as you see fit
 /// in a single pass.
 pub unsafe fn get_3d_noise(noise_type: &NoiseType) -> (Vec<f32>, f32, f32) {
-    noise_helpers::get_3d_noise::<Scalar>(noise_type)
+    noise_helpers::get_3d_noise(noise_type, fill_3d_noise_rows)
 }
 
 /// Gets a width X height X depth sized block of scaled 3d noise
@@ -392,6 +815,65 @@ pub unsafe fn get_3d_scaled_noise(noise_type: &NoiseType) -> Vec<f32> {
     noise
 }
 
+/// Evaluates 3d noise at the points `xs`, `ys` and `zs`, rather than on a
+/// grid. Coordinates are scaled by the frequency of the settings, results are
+/// unscaled, with the min and max value.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn get_3d_noise_at(
+    noise_type: &NoiseType,
+    xs: &[f32],
+    ys: &[f32],
+    zs: &[f32],
+) -> (Vec<f32>, f32, f32) {
+    noise_helpers::get_3d_noise_at::<Scalar>(noise_type, xs, ys, zs)
+}
+
+/// Fills `out`, which must hold exactly one value per sample, with a block of
+/// 3d noise instead of allocating. Results are unscaled, the min and max
+/// value are returned.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn fill_3d_noise(noise_type: &NoiseType, out: &mut [f32]) -> (f32, f32) {
+    noise_helpers::fill_3d_noise(noise_type, out, fill_3d_noise_rows)
+}
+
+/// Like `fill_3d_noise`, but rows start `pitch` values apart, to write into
+/// part of a larger image. Values between rows are left alone.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn fill_3d_noise_pitched(
+    noise_type: &NoiseType,
+    out: &mut [f32],
+    pitch: usize,
+) -> (f32, f32) {
+    noise_helpers::fill_3d_noise_pitched(noise_type, out, pitch, fill_3d_noise_rows)
+}
+
+/// Fills the rows `rows` of a block of 3d noise, counted across the whole
+/// block, into `out`, which starts at the first of them, with rows `pitch`
+/// values apart. Rows come out exactly as they do in a whole block, so a
+/// block can be split between threads. Results are unscaled, the min and max
+/// value are returned.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn fill_3d_noise_rows(
+    noise_type: &NoiseType,
+    out: &mut [f32],
+    pitch: usize,
+    rows: Range<usize>,
+) -> (f32, f32) {
+    noise_helpers::fill_3d_noise_rows::<Scalar>(noise_type, out, pitch, rows)
+}
+
 /// Get a single value of 4d simplex noise, results
 /// are not scaled.
 
@@ -399,6 +881,64 @@ pub unsafe fn simplex_4d(x: f32, y: f32, z: f32, w: f32, seed: i32) -> f32 {
     simplex::simplex_4d::<Scalar>(F32x1(x), F32x1(y), F32x1(z), F32x1(w), seed).0
 }
 
+/// Get a single value of 4d simplex noise along with its partial
+/// derivatives `[d/dx, d/dy, d/dz, d/dw]`, results are not scaled.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn simplex_4d_deriv(x: f32, y: f32, z: f32, w: f32, seed: i32) -> (f32, [f32; 4]) {
+    let (value, d) =
+        simplex::simplex_4d_deriv::<Scalar>(F32x1(x), F32x1(y), F32x1(z), F32x1(w), seed);
+    (value.0, [d[0].0, d[1].0, d[2].0, d[3].0])
+}
+
+/// Get a single value of 4d perlin noise, scaled to roughly
+/// -1 to 1.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn perlin_4d(x: f32, y: f32, z: f32, w: f32, seed: i32) -> f32 {
+    perlin::perlin_4d::<Scalar>(F32x1(x), F32x1(y), F32x1(z), F32x1(w), seed).0
+}
+
+/// Get a single value of 4d value noise, from -1 to 1.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn value_4d(
+    x: f32,
+    y: f32,
+    z: f32,
+    w: f32,
+    interpolation: Interpolation,
+    seed: i32,
+) -> f32 {
+    value::value_4d::<Scalar>(F32x1(x), F32x1(y), F32x1(z), F32x1(w), interpolation, seed).0
+}
+
+/// Get a single value of 4d OpenSimplex2 noise, results
+/// are not scaled.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn opensimplex2_4d(x: f32, y: f32, z: f32, w: f32, seed: i32) -> f32 {
+    opensimplex::opensimplex2_4d::<Scalar>(F32x1(x), F32x1(y), F32x1(z), F32x1(w), seed).0
+}
+
+/// Get a single value of 4d OpenSimplex2S noise, results
+/// are not scaled.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn opensimplex2s_4d(x: f32, y: f32, z: f32, w: f32, seed: i32) -> f32 {
+    opensimplex::opensimplex2s_4d::<Scalar>(F32x1(x), F32x1(y), F32x1(z), F32x1(w), seed).0
+}
+
 /// Get a single value of 4d simplex noise, results
 /// are not scaled.
 
@@ -431,6 +971,35 @@ pub unsafe fn fbm_4d(
     .0
 }
 
+/// Get a single value of 4d fractal brownian motion along with its partial
+/// derivatives `[d/dx, d/dy, d/dz, d/dw]`.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn fbm_4d_deriv(
+    x: f32,
+    y: f32,
+    z: f32,
+    w: f32,
+    lac: f32,
+    gain: f32,
+    octaves: u8,
+    seed: i32,
+) -> (f32, [f32; 4]) {
+    let (value, d) = simplex::fbm_4d_deriv::<Scalar>(
+        F32x1(x),
+        F32x1(y),
+        F32x1(z),
+        F32x1(w),
+        F32x1(lac),
+        F32x1(gain),
+        octaves,
+        seed,
+    );
+    (value.0, [d[0].0, d[1].0, d[2].0, d[3].0])
+}
+
 /// Get a single value of 4d fractal brownian motion.
 
 pub unsafe fn fbm_4d_f64(
@@ -531,6 +1100,34 @@ pub unsafe fn turbulence_4d(
     .0
 }
 
+/// Get a single value of 4d billow noise.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn billow_4d(
+    x: f32,
+    y: f32,
+    z: f32,
+    w: f32,
+    lac: f32,
+    gain: f32,
+    octaves: u8,
+    seed: i32,
+) -> f32 {
+    simplex::billow_4d::<Scalar>(
+        F32x1(x),
+        F32x1(y),
+        F32x1(z),
+        F32x1(w),
+        F32x1(lac),
+        F32x1(gain),
+        octaves,
+        seed,
+    )
+    .0
+}
+
 /// Get a single value of 4d turbulence.
 
 pub unsafe fn turbulence_4d_f64(
@@ -556,6 +1153,34 @@ pub unsafe fn turbulence_4d_f64(
     .0
 }
 
+/// Get a single value of 4d billow noise.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn billow_4d_f64(
+    x: f64,
+    y: f64,
+    z: f64,
+    w: f64,
+    lac: f64,
+    gain: f64,
+    octaves: u8,
+    seed: i64,
+) -> f64 {
+    simplex_64::billow_4d::<Scalar>(
+        F64x1(x),
+        F64x1(y),
+        F64x1(z),
+        F64x1(w),
+        F64x1(lac),
+        F64x1(gain),
+        octaves,
+        seed,
+    )
+    .0
+}
+
 /// Gets a width X height X depth x time sized block of 4d noise, unscaled,
 /// `start_*` can be used to provide an offset in the
 /// coordinates. Results are unscaled, 'min' and 'max' noise values
@@ -563,7 +1188,7 @@ pub unsafe fn turbulence_4d_f64(
 /// in a single pass.
 
 pub unsafe fn get_4d_noise(noise_type: &NoiseType) -> (Vec<f32>, f32, f32) {
-    noise_helpers::get_4d_noise::<Scalar>(noise_type)
+    noise_helpers::get_4d_noise(noise_type, fill_4d_noise_rows)
 }
 
 /// Gets a width X height X depth X time sized block of scaled 4d noise
@@ -577,3 +1202,83 @@ pub unsafe fn get_4d_scaled_noise(noise_type: &NoiseType) -> Vec<f32> {
     scale_noise::<Scalar>(dim.min, dim.max, min, max, &mut noise);
     noise
 }
+
+/// Evaluates 4d noise at the points `xs`, `ys`, `zs` and `ws`, rather than on a
+/// grid. Coordinates are scaled by the frequency of the settings, results are
+/// unscaled, with the min and max value.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn get_4d_noise_at(
+    noise_type: &NoiseType,
+    xs: &[f32],
+    ys: &[f32],
+    zs: &[f32],
+    ws: &[f32],
+) -> (Vec<f32>, f32, f32) {
+    noise_helpers::get_4d_noise_at::<Scalar>(noise_type, xs, ys, zs, ws)
+}
+
+/// Fills `out`, which must hold exactly one value per sample, with a block of
+/// 4d noise instead of allocating. Results are unscaled, the min and max
+/// value are returned.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn fill_4d_noise(noise_type: &NoiseType, out: &mut [f32]) -> (f32, f32) {
+    noise_helpers::fill_4d_noise(noise_type, out, fill_4d_noise_rows)
+}
+
+/// Like `fill_4d_noise`, but rows start `pitch` values apart, to write into
+/// part of a larger image. Values between rows are left alone.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn fill_4d_noise_pitched(
+    noise_type: &NoiseType,
+    out: &mut [f32],
+    pitch: usize,
+) -> (f32, f32) {
+    noise_helpers::fill_4d_noise_pitched(noise_type, out, pitch, fill_4d_noise_rows)
+}
+
+/// Fills the rows `rows` of a block of 4d noise, counted across the whole
+/// block, into `out`, which starts at the first of them, with rows `pitch`
+/// values apart. Rows come out exactly as they do in a whole block, so a
+/// block can be split between threads. Results are unscaled, the min and max
+/// value are returned.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn fill_4d_noise_rows(
+    noise_type: &NoiseType,
+    out: &mut [f32],
+    pitch: usize,
+    rows: Range<usize>,
+) -> (f32, f32) {
+    noise_helpers::fill_4d_noise_rows::<Scalar>(noise_type, out, pitch, rows)
+}
+
+/// Gets all four sorted distances, F1 to F4, of a block of cellular2 noise.
+/// Results are unscaled.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn get_cellular2_distances(settings: &Cellular2Settings) -> [Vec<f32>; 4] {
+    noise_helpers::get_cellular2_distances::<Scalar>(settings)
+}
+
+/// Gets a block of 2d or 3d curl noise, see `CurlSettings::generate`.
+/// Results are unscaled.
+///
+/// # Safety
+///
+/// Always safe to call, only unsafe to match the SIMD backends.
+pub unsafe fn get_curl(settings: &CurlSettings) -> Vec<f32> {
+    noise_helpers::get_curl::<Scalar>(settings)
+}
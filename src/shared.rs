@@ -68,6 +68,12 @@ pub unsafe fn scale_noise<S: Simd>(
     let range = max - min;
     let multiplier = scale_range / range;
     let offset = scale_min - min * multiplier;
+    transform_noise::<S>(multiplier, offset, data);
+}
+
+/// Replaces every value `n` with `n * multiplier + offset`
+#[inline(always)]
+pub unsafe fn transform_noise<S: Simd>(multiplier: f32, offset: f32, data: &mut [f32]) {
     let vector_width = S::VF32_WIDTH;
     let mut i = 0;
     if data.len() >= vector_width {
@@ -19,6 +19,18 @@ const X_PRIME: i32 = 1619;
 const Y_PRIME: i32 = 31337;
 const Z_PRIME: i32 = 6791;
 
+/// Brings `simplex_1d` to roughly -1 to 1
+pub(crate) const SIMPLEX_1D_SCALE: f32 = 0.45;
+/// Brings `simplex_2d` to roughly -1 to 1
+pub(crate) const SIMPLEX_2D_SCALE: f32 = 45.0;
+/// Brings `simplex_3d` to roughly -1 to 1
+pub(crate) const SIMPLEX_3D_SCALE: f32 = 32.0;
+/// Brings `simplex_4d` to roughly -1 to 1
+pub(crate) const SIMPLEX_4D_SCALE: f32 = 62.0;
+/// Periods of `simplex_3d_periodic` must be whole multiples of this many
+/// cells, the smallest period along an axis that the skewed lattice shares
+pub const SIMPLEX_3D_PERIOD_STEP: f32 = 3.0;
+
 #[inline(always)]
 pub unsafe fn grad1<S: Simd>(seed: i32, hash: S::Vi32, x: S::Vf32) -> S::Vf32 {
     let h = S::and_epi32(S::xor_epi32(S::set1_epi32(seed), hash), S::set1_epi32(15));
@@ -72,7 +84,7 @@ pub unsafe fn fbm_1d<S: Simd>(
     for _ in 1..octaves {
         x = S::mul_ps(x, lacunarity);
         amp = S::mul_ps(amp, gain);
-        result = S::add_ps(result, simplex_1d::<S>(x, seed));
+        result = S::add_ps(S::mul_ps(simplex_1d::<S>(x, seed), amp), result);
     }
 
     result
@@ -94,7 +106,10 @@ pub unsafe fn ridge_1d<S: Simd>(
         amp = S::mul_ps(amp, gain);
         result = S::add_ps(
             result,
-            S::sub_ps(S::set1_ps(1.0), S::abs_ps(simplex_1d::<S>(x, seed))),
+            S::sub_ps(
+                S::set1_ps(1.0),
+                S::mul_ps(S::abs_ps(simplex_1d::<S>(x, seed)), amp),
+            ),
         );
     }
 
@@ -115,14 +130,160 @@ pub unsafe fn turbulence_1d<S: Simd>(
     for _ in 1..octaves {
         x = S::mul_ps(x, lacunarity);
         amp = S::mul_ps(amp, gain);
-        result = S::add_ps(result, S::abs_ps(simplex_1d::<S>(x, seed)));
+        result = S::add_ps(result, S::abs_ps(S::mul_ps(simplex_1d::<S>(x, seed), amp)));
+    }
+
+    result
+}
+
+/// 1d billow, `2|n| - 1` of simplex noise summed over `octaves` layers like
+/// `fbm_1d`
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn billow_1d<S: Simd>(
+    mut x: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+) -> S::Vf32 {
+    let scale = S::set1_ps(SIMPLEX_1D_SCALE);
+    let one = S::set1_ps(1.0);
+    // 2|n| - 1, with the noise scaled to roughly -1 to 1 so billows are centered
+    let billow = |n| {
+        S::sub_ps(
+            S::mul_ps(S::set1_ps(2.0), S::abs_ps(S::mul_ps(n, scale))),
+            one,
+        )
+    };
+    let mut result = billow(simplex_1d::<S>(x, seed));
+    let mut amp = one;
+
+    for _ in 1..octaves {
+        x = S::mul_ps(x, lac);
+        amp = S::mul_ps(amp, gain);
+        result = S::fmadd_ps(billow(simplex_1d::<S>(x, seed)), amp, result);
+    }
+
+    result
+}
+
+/// Musgrave's hybrid multifractal, each octave weighted by the ones before it
+/// so valleys are smooth and peaks rough
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub unsafe fn hybrid_multifractal_1d<S: Simd>(
+    mut x: S::Vf32,
+    s: &HybridMultifractalSettings,
+) -> S::Vf32 {
+    let lac = S::set1_ps(s.lacunarity);
+    let lac_h = S::set1_ps(s.lac_h());
+    let offset = S::set1_ps(s.offset);
+    let gain = S::set1_ps(s.gain);
+    let seed = s.dim.seed;
+    let scale = S::set1_ps(SIMPLEX_1D_SCALE);
+    let one = S::set1_ps(1.0);
+    let threshold = S::set1_ps(0.001);
+    let mut result = S::setzero_ps();
+    let mut weight = one;
+    let mut pwr = one;
+    // Lanes stop adding octaves once their weight falls to about zero
+    let mut active = S::cmpgt_ps(weight, threshold);
+
+    for _ in 0..s.octaves {
+        active = S::and_ps(active, S::cmpgt_ps(weight, threshold));
+        weight = S::min_ps(weight, one);
+        let signal = S::mul_ps(
+            S::add_ps(S::mul_ps(simplex_1d::<S>(x, seed), scale), offset),
+            pwr,
+        );
+        result = S::add_ps(result, S::and_ps(active, S::mul_ps(weight, signal)));
+        weight = S::mul_ps(weight, S::mul_ps(gain, signal));
+        pwr = S::mul_ps(pwr, lac_h);
+        x = S::mul_ps(x, lac);
+    }
+
+    result
+}
+
+/// Musgrave's heterogeneous terrain, each octave scaled by the height so far
+/// so low areas stay smooth
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub unsafe fn hetero_terrain_1d<S: Simd>(mut x: S::Vf32, s: &HeteroTerrainSettings) -> S::Vf32 {
+    let lac = S::set1_ps(s.lacunarity);
+    let lac_h = S::set1_ps(s.lac_h());
+    let offset = S::set1_ps(s.offset);
+    let seed = s.dim.seed;
+    let scale = S::set1_ps(SIMPLEX_1D_SCALE);
+    let mut result = S::add_ps(offset, S::mul_ps(simplex_1d::<S>(x, seed), scale));
+    let mut pwr = lac_h;
+
+    for _ in 1..s.octaves {
+        x = S::mul_ps(x, lac);
+        let increment = S::mul_ps(
+            S::add_ps(S::mul_ps(simplex_1d::<S>(x, seed), scale), offset),
+            pwr,
+        );
+        result = S::add_ps(result, S::mul_ps(increment, result));
+        pwr = S::mul_ps(pwr, lac_h);
+    }
+
+    result
+}
+
+/// Musgrave's ridged multifractal, squared `offset - |n|` ridges with each
+/// octave weighted by the one before it
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub unsafe fn ridged_multifractal_1d<S: Simd>(
+    mut x: S::Vf32,
+    s: &RidgedMultifractalSettings,
+) -> S::Vf32 {
+    let lac = S::set1_ps(s.lacunarity);
+    let lac_h = S::set1_ps(s.lac_h());
+    let offset = S::set1_ps(s.offset);
+    let gain = S::set1_ps(s.gain);
+    let seed = s.dim.seed;
+    let scale = S::set1_ps(SIMPLEX_1D_SCALE);
+    let one = S::set1_ps(1.0);
+    let mut signal = S::sub_ps(
+        offset,
+        S::abs_ps(S::mul_ps(simplex_1d::<S>(x, seed), scale)),
+    );
+    signal = S::mul_ps(signal, signal);
+    let mut result = signal;
+    let mut pwr = lac_h;
+
+    for _ in 1..s.octaves {
+        x = S::mul_ps(x, lac);
+        let weight = S::min_ps(S::max_ps(S::mul_ps(signal, gain), S::setzero_ps()), one);
+        signal = S::sub_ps(
+            offset,
+            S::abs_ps(S::mul_ps(simplex_1d::<S>(x, seed), scale)),
+        );
+        signal = S::mul_ps(S::mul_ps(signal, signal), weight);
+        result = S::add_ps(result, S::mul_ps(signal, pwr));
+        pwr = S::mul_ps(pwr, lac_h);
     }
 
     result
 }
 
 #[inline(always)]
-unsafe fn grad2<S: Simd>(seed: i32, hash: S::Vi32, x: S::Vf32, y: S::Vf32) -> S::Vf32 {
+pub(crate) unsafe fn grad2<S: Simd>(seed: i32, hash: S::Vi32, x: S::Vf32, y: S::Vf32) -> S::Vf32 {
     let h = S::and_epi32(S::xor_epi32(hash, S::set1_epi32(seed)), S::set1_epi32(7));
     let mask = S::castepi32_ps(S::cmpgt_epi32(S::set1_epi32(4), h));
     let u = S::blendv_ps(y, x, mask);
@@ -212,6 +373,119 @@ pub unsafe fn simplex_2d<S: Simd>(x: S::Vf32, y: S::Vf32, seed: i32) -> S::Vf32
 
     S::add_ps(n0, S::add_ps(n1, n2))
 }
+
+/// One corner's contribution `t^4 * dot` and its gradient, masked to zero where
+/// the falloff `t` is negative. `g` is the corner gradient and `d` the offset
+/// from the corner, so `dot` is `g . d` and `t` is `r^2 - d . d`.
+#[inline(always)]
+unsafe fn corner_deriv<S: Simd, const N: usize>(
+    t: S::Vf32,
+    dot: S::Vf32,
+    g: [S::Vf32; N],
+    d: [S::Vf32; N],
+) -> (S::Vf32, [S::Vf32; N]) {
+    let outside = S::cmplt_ps(t, S::setzero_ps());
+    let t2 = S::mul_ps(t, t);
+    let t4 = S::mul_ps(t2, t2);
+    // d/dp t^4 * (g . d) = -8 t^3 (g . d) d + t^4 g
+    let falloff = S::mul_ps(S::set1_ps(-8.0), S::mul_ps(S::mul_ps(t2, t), dot));
+    let mut deriv = g;
+    for (deriv, d) in deriv.iter_mut().zip(d.iter()) {
+        *deriv = S::andnot_ps(outside, S::fmadd_ps(falloff, *d, S::mul_ps(t4, *deriv)));
+    }
+    (S::andnot_ps(outside, S::mul_ps(t4, dot)), deriv)
+}
+
+/// Sums per corner partial derivatives
+#[inline(always)]
+unsafe fn sum_deriv<S: Simd, const N: usize>(corners: &[[S::Vf32; N]]) -> [S::Vf32; N] {
+    let mut sum = corners[0];
+    for corner in &corners[1..] {
+        for (sum, d) in sum.iter_mut().zip(corner.iter()) {
+            *sum = S::add_ps(*sum, *d);
+        }
+    }
+    sum
+}
+
+/// 2d simplex noise along with its exact partial derivatives `[d/dx, d/dy]`.
+/// The value is the same as `simplex_2d`.
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn simplex_2d_deriv<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    seed: i32,
+) -> (S::Vf32, [S::Vf32; 2]) {
+    let s = S::mul_ps(S::set1_ps(F2), S::add_ps(x, y));
+    let ips = S::floor_ps(S::add_ps(x, s));
+    let jps = S::floor_ps(S::add_ps(y, s));
+
+    let i = S::cvtps_epi32(ips);
+    let j = S::cvtps_epi32(jps);
+
+    let t = S::mul_ps(S::cvtepi32_ps(S::add_epi32(i, j)), S::set1_ps(G2));
+
+    let x0 = S::sub_ps(x, S::sub_ps(ips, t));
+    let y0 = S::sub_ps(y, S::sub_ps(jps, t));
+
+    let i1 = S::castps_epi32(S::cmpge_ps(x0, y0));
+
+    let j1 = S::castps_epi32(S::cmpgt_ps(y0, x0));
+
+    let x1 = S::add_ps(S::add_ps(x0, S::cvtepi32_ps(i1)), S::set1_ps(G2));
+    let y1 = S::add_ps(S::add_ps(y0, S::cvtepi32_ps(j1)), S::set1_ps(G2));
+    let x2 = S::add_ps(S::add_ps(x0, S::set1_ps(-1.0)), S::set1_ps(G22));
+    let y2 = S::add_ps(S::add_ps(y0, S::set1_ps(-1.0)), S::set1_ps(G22));
+
+    let ii = S::and_epi32(i, S::set1_epi32(0xff));
+    let jj = S::and_epi32(j, S::set1_epi32(0xff));
+
+    let gi0 = S::i32gather_epi32(&PERM, S::add_epi32(ii, S::i32gather_epi32(&PERM, jj)));
+
+    let gi1 = S::i32gather_epi32(
+        &PERM,
+        S::add_epi32(
+            S::sub_epi32(ii, i1),
+            S::i32gather_epi32(&PERM, S::sub_epi32(jj, j1)),
+        ),
+    );
+
+    let gi2 = S::i32gather_epi32(
+        &PERM,
+        S::add_epi32(
+            S::sub_epi32(ii, S::set1_epi32(-1)),
+            S::i32gather_epi32(&PERM, S::sub_epi32(jj, S::set1_epi32(-1))),
+        ),
+    );
+
+    // These FMA operations are equivalent to: let t = 0.5 - x*x - y*y
+    let t0 = S::fnmadd_ps(y0, y0, S::fnmadd_ps(x0, x0, S::set1_ps(0.5)));
+    let t1 = S::fnmadd_ps(y1, y1, S::fnmadd_ps(x1, x1, S::set1_ps(0.5)));
+    let t2 = S::fnmadd_ps(y2, y2, S::fnmadd_ps(x2, x2, S::set1_ps(0.5)));
+
+    let one = S::set1_ps(1.0);
+    let zero = S::setzero_ps();
+    // The gradients are linear, so the gradient vector is grad2 of the unit axes
+    let corner = |gi, x, y, t| {
+        let g = [
+            grad2::<S>(seed, gi, one, zero),
+            grad2::<S>(seed, gi, zero, one),
+        ];
+        corner_deriv::<S, 2>(t, grad2::<S>(seed, gi, x, y), g, [x, y])
+    };
+    let (n0, d0) = corner(gi0, x0, y0, t0);
+    let (n1, d1) = corner(gi1, x1, y1, t1);
+    let (n2, d2) = corner(gi2, x2, y2, t2);
+
+    (
+        S::add_ps(n0, S::add_ps(n1, n2)),
+        sum_deriv::<S, 2>(&[d0, d1, d2]),
+    )
+}
 #[inline(always)]
 pub unsafe fn fbm_2d<S: Simd>(
     mut x: S::Vf32,
@@ -234,6 +508,42 @@ pub unsafe fn fbm_2d<S: Simd>(
     result
 }
 
+/// Fractal brownian motion over `simplex_2d_deriv`, with the derivatives of
+/// each octave scaled by its amplitude and frequency so they are the exact
+/// derivatives of the sum. The value is the same as `fbm_2d`.
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn fbm_2d_deriv<S: Simd>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+) -> (S::Vf32, [S::Vf32; 2]) {
+    let (mut result, mut deriv) = simplex_2d_deriv::<S>(x, y, seed);
+    let mut amp = S::set1_ps(1.0);
+    let mut freq = S::set1_ps(1.0);
+
+    for _ in 1..octaves {
+        x = S::mul_ps(x, lac);
+        y = S::mul_ps(y, lac);
+        amp = S::mul_ps(amp, gain);
+        freq = S::mul_ps(freq, lac);
+        let (value, octave_deriv) = simplex_2d_deriv::<S>(x, y, seed);
+        result = S::add_ps(S::mul_ps(value, amp), result);
+        let scale = S::mul_ps(amp, freq);
+        for (deriv, d) in deriv.iter_mut().zip(octave_deriv.iter()) {
+            *deriv = S::fmadd_ps(*d, scale, *deriv);
+        }
+    }
+
+    (result, deriv)
+}
+
 #[inline(always)]
 pub unsafe fn ridge_2d<S: Simd>(
     mut x: S::Vf32,
@@ -284,6 +594,163 @@ pub unsafe fn turbulence_2d<S: Simd>(
     result
 }
 
+/// 2d billow, `2|n| - 1` of simplex noise summed over `octaves` layers like
+/// `fbm_2d`
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn billow_2d<S: Simd>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+) -> S::Vf32 {
+    let scale = S::set1_ps(SIMPLEX_2D_SCALE);
+    let one = S::set1_ps(1.0);
+    // 2|n| - 1, with the noise scaled to roughly -1 to 1 so billows are centered
+    let billow = |n| {
+        S::sub_ps(
+            S::mul_ps(S::set1_ps(2.0), S::abs_ps(S::mul_ps(n, scale))),
+            one,
+        )
+    };
+    let mut result = billow(simplex_2d::<S>(x, y, seed));
+    let mut amp = one;
+
+    for _ in 1..octaves {
+        x = S::mul_ps(x, lac);
+        y = S::mul_ps(y, lac);
+        amp = S::mul_ps(amp, gain);
+        result = S::fmadd_ps(billow(simplex_2d::<S>(x, y, seed)), amp, result);
+    }
+
+    result
+}
+
+/// Musgrave's hybrid multifractal, each octave weighted by the ones before it
+/// so valleys are smooth and peaks rough
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub unsafe fn hybrid_multifractal_2d<S: Simd>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    s: &HybridMultifractalSettings,
+) -> S::Vf32 {
+    let lac = S::set1_ps(s.lacunarity);
+    let lac_h = S::set1_ps(s.lac_h());
+    let offset = S::set1_ps(s.offset);
+    let gain = S::set1_ps(s.gain);
+    let seed = s.dim.seed;
+    let scale = S::set1_ps(SIMPLEX_2D_SCALE);
+    let one = S::set1_ps(1.0);
+    let threshold = S::set1_ps(0.001);
+    let mut result = S::setzero_ps();
+    let mut weight = one;
+    let mut pwr = one;
+    // Lanes stop adding octaves once their weight falls to about zero
+    let mut active = S::cmpgt_ps(weight, threshold);
+
+    for _ in 0..s.octaves {
+        active = S::and_ps(active, S::cmpgt_ps(weight, threshold));
+        weight = S::min_ps(weight, one);
+        let signal = S::mul_ps(
+            S::add_ps(S::mul_ps(simplex_2d::<S>(x, y, seed), scale), offset),
+            pwr,
+        );
+        result = S::add_ps(result, S::and_ps(active, S::mul_ps(weight, signal)));
+        weight = S::mul_ps(weight, S::mul_ps(gain, signal));
+        pwr = S::mul_ps(pwr, lac_h);
+        x = S::mul_ps(x, lac);
+        y = S::mul_ps(y, lac);
+    }
+
+    result
+}
+
+/// Musgrave's heterogeneous terrain, each octave scaled by the height so far
+/// so low areas stay smooth
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub unsafe fn hetero_terrain_2d<S: Simd>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    s: &HeteroTerrainSettings,
+) -> S::Vf32 {
+    let lac = S::set1_ps(s.lacunarity);
+    let lac_h = S::set1_ps(s.lac_h());
+    let offset = S::set1_ps(s.offset);
+    let seed = s.dim.seed;
+    let scale = S::set1_ps(SIMPLEX_2D_SCALE);
+    let mut result = S::add_ps(offset, S::mul_ps(simplex_2d::<S>(x, y, seed), scale));
+    let mut pwr = lac_h;
+
+    for _ in 1..s.octaves {
+        x = S::mul_ps(x, lac);
+        y = S::mul_ps(y, lac);
+        let increment = S::mul_ps(
+            S::add_ps(S::mul_ps(simplex_2d::<S>(x, y, seed), scale), offset),
+            pwr,
+        );
+        result = S::add_ps(result, S::mul_ps(increment, result));
+        pwr = S::mul_ps(pwr, lac_h);
+    }
+
+    result
+}
+
+/// Musgrave's ridged multifractal, squared `offset - |n|` ridges with each
+/// octave weighted by the one before it
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub unsafe fn ridged_multifractal_2d<S: Simd>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    s: &RidgedMultifractalSettings,
+) -> S::Vf32 {
+    let lac = S::set1_ps(s.lacunarity);
+    let lac_h = S::set1_ps(s.lac_h());
+    let offset = S::set1_ps(s.offset);
+    let gain = S::set1_ps(s.gain);
+    let seed = s.dim.seed;
+    let scale = S::set1_ps(SIMPLEX_2D_SCALE);
+    let one = S::set1_ps(1.0);
+    let mut signal = S::sub_ps(
+        offset,
+        S::abs_ps(S::mul_ps(simplex_2d::<S>(x, y, seed), scale)),
+    );
+    signal = S::mul_ps(signal, signal);
+    let mut result = signal;
+    let mut pwr = lac_h;
+
+    for _ in 1..s.octaves {
+        x = S::mul_ps(x, lac);
+        y = S::mul_ps(y, lac);
+        let weight = S::min_ps(S::max_ps(S::mul_ps(signal, gain), S::setzero_ps()), one);
+        signal = S::sub_ps(
+            offset,
+            S::abs_ps(S::mul_ps(simplex_2d::<S>(x, y, seed), scale)),
+        );
+        signal = S::mul_ps(S::mul_ps(signal, signal), weight);
+        result = S::add_ps(result, S::mul_ps(signal, pwr));
+        pwr = S::mul_ps(pwr, lac_h);
+    }
+
+    result
+}
+
 #[inline(always)]
 unsafe fn grad3d<S: Simd>(
     seed: i32,
@@ -422,69 +889,340 @@ pub unsafe fn simplex_3d<S: Simd>(x: S::Vf32, y: S::Vf32, z: S::Vf32, seed: i32)
     S::add_ps(p2, S::and_ps(n0, v0))
 }
 
+/// Moves the skewed lattice corner `c` back into the first period and returns
+/// its hash inputs. Moving a corner a whole period along an axis moves it by
+/// `period` cells along that axis, and a third of that along the others, in
+/// the skewed lattice. Everything is done on whole numbers so it is exact.
 #[inline(always)]
-pub unsafe fn fbm_3d<S: Simd>(
-    mut x: S::Vf32,
-    mut y: S::Vf32,
-    mut z: S::Vf32,
-    lac: S::Vf32,
-    gain: S::Vf32,
-    octaves: u8,
-    seed: i32,
-) -> S::Vf32 {
-    let mut result = simplex_3d::<S>(x, y, z, seed);
-    let mut amp = S::set1_ps(1.0);
-
-    for _ in 1..octaves {
-        x = S::mul_ps(x, lac);
-        y = S::mul_ps(y, lac);
-        z = S::mul_ps(z, lac);
-        amp = S::mul_ps(amp, gain);
-        result = S::add_ps(S::mul_ps(simplex_3d::<S>(x, y, z, seed), amp), result);
+unsafe fn wrap_corner_3d<S: Simd>(c: [S::Vf32; 3], period: [f32; 3]) -> [S::Vi32; 3] {
+    let sum = S::add_ps(S::add_ps(c[0], c[1]), c[2]);
+    let mut periods = [S::setzero_ps(); 3];
+    let mut thirds = S::setzero_ps();
+    for a in 0..3 {
+        // Six times the unskewed corner, over six periods, keeps to whole numbers
+        let u = S::sub_ps(S::mul_ps(c[a], S::set1_ps(6.0)), sum);
+        let p = S::set1_ps(period[a] * 6.0);
+        let mut n = S::fast_floor_ps(S::div_ps(u, p));
+        let r = S::sub_ps(u, S::mul_ps(n, p));
+        n = S::blendv_ps(n, S::add_ps(n, S::set1_ps(1.0)), S::cmpge_ps(r, p));
+        n = S::blendv_ps(
+            n,
+            S::sub_ps(n, S::set1_ps(1.0)),
+            S::cmplt_ps(r, S::setzero_ps()),
+        );
+        periods[a] = S::mul_ps(n, S::set1_ps(period[a]));
+        thirds = S::fmadd_ps(n, S::set1_ps(period[a] / 3.0), thirds);
     }
-
-    result
+    let wrap = |a: usize, prime: i32| {
+        let wrapped = S::sub_ps(S::sub_ps(c[a], periods[a]), thirds);
+        S::mullo_epi32(S::cvtps_epi32(wrapped), S::set1_epi32(prime))
+    };
+    [wrap(0, X_PRIME), wrap(1, Y_PRIME), wrap(2, Z_PRIME)]
 }
 
+/// 3d simplex noise that repeats every `period` cells along each axis, see
+/// `simplex_3d`. Periods must be whole multiples of `SIMPLEX_3D_PERIOD_STEP`.
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
 #[inline(always)]
-pub unsafe fn ridge_3d<S: Simd>(
-    mut x: S::Vf32,
-    mut y: S::Vf32,
-    mut z: S::Vf32,
-    lac: S::Vf32,
-    gain: S::Vf32,
-    octaves: u8,
+pub unsafe fn simplex_3d_periodic<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    period: [f32; 3],
     seed: i32,
 ) -> S::Vf32 {
-    let mut result = S::sub_ps(S::set1_ps(1.0), S::abs_ps(simplex_3d::<S>(x, y, z, seed)));
-    let mut amp = S::set1_ps(1.0);
+    let f = S::mul_ps(S::set1_ps(F3), S::add_ps(S::add_ps(x, y), z));
+    let xs = S::fast_floor_ps(S::add_ps(x, f));
+    let ys = S::fast_floor_ps(S::add_ps(y, f));
+    let zs = S::fast_floor_ps(S::add_ps(z, f));
 
-    for _ in 1..octaves {
-        x = S::mul_ps(x, lac);
-        y = S::mul_ps(y, lac);
-        z = S::mul_ps(z, lac);
-        amp = S::mul_ps(amp, gain);
-        result = S::add_ps(
-            result,
-            S::fnmadd_ps(
-                S::abs_ps(simplex_3d::<S>(x, y, z, seed)),
-                amp,
-                S::set1_ps(1.0),
+    let g = S::mul_ps(S::set1_ps(G3), S::add_ps(S::add_ps(xs, ys), zs));
+    let x0 = S::sub_ps(x, S::sub_ps(xs, g));
+    let y0 = S::sub_ps(y, S::sub_ps(ys, g));
+    let z0 = S::sub_ps(z, S::sub_ps(zs, g));
+
+    let x0_ge_y0 = S::cmpge_ps(x0, y0);
+    let y0_ge_z0 = S::cmpge_ps(y0, z0);
+    let x0_ge_z0 = S::cmpge_ps(x0, z0);
+
+    let i1 = x0_ge_y0 & x0_ge_z0;
+    let j1 = S::andnot_ps(x0_ge_y0, y0_ge_z0);
+    let k1 = S::andnot_ps(x0_ge_z0, !y0_ge_z0);
+
+    let i2 = x0_ge_y0 | x0_ge_z0;
+    let j2 = (!x0_ge_y0) | y0_ge_z0;
+    let k2 = !(x0_ge_z0 & y0_ge_z0);
+
+    let one = S::set1_ps(1.0);
+    let corners = [
+        ([xs, ys, zs], [x0, y0, z0]),
+        (
+            [
+                S::add_ps(xs, i1 & one),
+                S::add_ps(ys, j1 & one),
+                S::add_ps(zs, k1 & one),
+            ],
+            [
+                S::add_ps(S::sub_ps(x0, i1 & one), S::set1_ps(G3)),
+                S::add_ps(S::sub_ps(y0, j1 & one), S::set1_ps(G3)),
+                S::add_ps(S::sub_ps(z0, k1 & one), S::set1_ps(G3)),
+            ],
+        ),
+        (
+            [
+                S::add_ps(xs, i2 & one),
+                S::add_ps(ys, j2 & one),
+                S::add_ps(zs, k2 & one),
+            ],
+            [
+                S::add_ps(S::sub_ps(x0, i2 & one), S::set1_ps(F3)),
+                S::add_ps(S::sub_ps(y0, j2 & one), S::set1_ps(F3)),
+                S::add_ps(S::sub_ps(z0, k2 & one), S::set1_ps(F3)),
+            ],
+        ),
+        (
+            [S::add_ps(xs, one), S::add_ps(ys, one), S::add_ps(zs, one)],
+            [
+                S::add_ps(x0, S::set1_ps(G33)),
+                S::add_ps(y0, S::set1_ps(G33)),
+                S::add_ps(z0, S::set1_ps(G33)),
+            ],
+        ),
+    ];
+
+    let mut result = S::setzero_ps();
+    for (corner, [xd, yd, zd]) in corners {
+        let t = S::sub_ps(
+            S::sub_ps(
+                S::sub_ps(S::set1_ps(0.6), S::mul_ps(xd, xd)),
+                S::mul_ps(yd, yd),
             ),
+            S::mul_ps(zd, zd),
         );
+        let [i, j, k] = wrap_corner_3d::<S>(corner, period);
+        let t2 = t * t;
+        let v = (t2 * t2) * grad3d::<S>(seed, i, j, k, xd, yd, zd);
+        result = S::add_ps(result, S::and_ps(S::cmpge_ps(t, S::setzero_ps()), v));
     }
-
     result
 }
 
+/// 3d simplex noise along with its exact partial derivatives
+/// `[d/dx, d/dy, d/dz]`. The value is the same as `simplex_3d`, whose corner
+/// falloff reaches slightly past the neighbouring simplices, so the noise has
+/// small jumps at some simplex boundaries that the derivatives don't see.
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
 #[inline(always)]
-pub unsafe fn turbulence_3d<S: Simd>(
-    mut x: S::Vf32,
-    mut y: S::Vf32,
-    mut z: S::Vf32,
-    lac: S::Vf32,
-    gain: S::Vf32,
-    octaves: u8,
+pub unsafe fn simplex_3d_deriv<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    seed: i32,
+) -> (S::Vf32, [S::Vf32; 3]) {
+    let f = S::mul_ps(S::set1_ps(F3), S::add_ps(S::add_ps(x, y), z));
+    let mut x0 = S::fast_floor_ps(S::add_ps(x, f));
+    let mut y0 = S::fast_floor_ps(S::add_ps(y, f));
+    let mut z0 = S::fast_floor_ps(S::add_ps(z, f));
+
+    let i = S::mullo_epi32(S::cvtps_epi32(x0), S::set1_epi32(X_PRIME));
+    let j = S::mullo_epi32(S::cvtps_epi32(y0), S::set1_epi32(Y_PRIME));
+    let k = S::mullo_epi32(S::cvtps_epi32(z0), S::set1_epi32(Z_PRIME));
+
+    let g = S::mul_ps(S::set1_ps(G3), S::add_ps(S::add_ps(x0, y0), z0));
+    x0 = S::sub_ps(x, S::sub_ps(x0, g));
+    y0 = S::sub_ps(y, S::sub_ps(y0, g));
+    z0 = S::sub_ps(z, S::sub_ps(z0, g));
+
+    let x0_ge_y0 = S::cmpge_ps(x0, y0);
+    let y0_ge_z0 = S::cmpge_ps(y0, z0);
+    let x0_ge_z0 = S::cmpge_ps(x0, z0);
+
+    let i1 = x0_ge_y0 & x0_ge_z0;
+    let j1 = S::andnot_ps(x0_ge_y0, y0_ge_z0);
+    let k1 = S::andnot_ps(x0_ge_z0, !y0_ge_z0);
+
+    let i2 = x0_ge_y0 | x0_ge_z0;
+    let j2 = (!x0_ge_y0) | y0_ge_z0;
+    let k2 = !(x0_ge_z0 & y0_ge_z0);
+
+    let x1 = S::add_ps(S::sub_ps(x0, i1 & S::set1_ps(1.0)), S::set1_ps(G3));
+    let y1 = S::add_ps(S::sub_ps(y0, j1 & S::set1_ps(1.0)), S::set1_ps(G3));
+    let z1 = S::add_ps(S::sub_ps(z0, k1 & S::set1_ps(1.0)), S::set1_ps(G3));
+
+    let x2 = S::add_ps(S::sub_ps(x0, i2 & S::set1_ps(1.0)), S::set1_ps(F3));
+    let y2 = S::add_ps(S::sub_ps(y0, j2 & S::set1_ps(1.0)), S::set1_ps(F3));
+    let z2 = S::add_ps(S::sub_ps(z0, k2 & S::set1_ps(1.0)), S::set1_ps(F3));
+
+    let x3 = S::add_ps(x0, S::set1_ps(G33));
+    let y3 = S::add_ps(y0, S::set1_ps(G33));
+    let z3 = S::add_ps(z0, S::set1_ps(G33));
+
+    let t0 = S::sub_ps(
+        S::sub_ps(
+            S::sub_ps(S::set1_ps(0.6), S::mul_ps(x0, x0)),
+            S::mul_ps(y0, y0),
+        ),
+        S::mul_ps(z0, z0),
+    );
+    let t1 = S::sub_ps(
+        S::sub_ps(
+            S::sub_ps(S::set1_ps(0.6), S::mul_ps(x1, x1)),
+            S::mul_ps(y1, y1),
+        ),
+        S::mul_ps(z1, z1),
+    );
+    let t2 = S::sub_ps(
+        S::sub_ps(
+            S::sub_ps(S::set1_ps(0.6), S::mul_ps(x2, x2)),
+            S::mul_ps(y2, y2),
+        ),
+        S::mul_ps(z2, z2),
+    );
+    let t3 = S::sub_ps(
+        S::sub_ps(
+            S::sub_ps(S::set1_ps(0.6), S::mul_ps(x3, x3)),
+            S::mul_ps(y3, y3),
+        ),
+        S::mul_ps(z3, z3),
+    );
+
+    let v1x = S::add_epi32(i, S::and_epi32(S::castps_epi32(i1), S::set1_epi32(X_PRIME)));
+    let v1y = S::add_epi32(j, S::and_epi32(S::castps_epi32(j1), S::set1_epi32(Y_PRIME)));
+    let v1z = S::add_epi32(k, S::and_epi32(S::castps_epi32(k1), S::set1_epi32(Z_PRIME)));
+    let v2x = S::add_epi32(i, S::and_epi32(S::castps_epi32(i2), S::set1_epi32(X_PRIME)));
+    let v2y = S::add_epi32(j, S::and_epi32(S::castps_epi32(j2), S::set1_epi32(Y_PRIME)));
+    let v2z = S::add_epi32(k, S::and_epi32(S::castps_epi32(k2), S::set1_epi32(Z_PRIME)));
+    let v3x = S::add_epi32(i, S::set1_epi32(X_PRIME));
+    let v3y = S::add_epi32(j, S::set1_epi32(Y_PRIME));
+    let v3z = S::add_epi32(k, S::set1_epi32(Z_PRIME));
+
+    let one = S::set1_ps(1.0);
+    let zero = S::setzero_ps();
+    // The gradients are linear, so the gradient vector is grad3d of the unit axes
+    let corner = |i, j, k, x, y, z, t| {
+        let g = [
+            grad3d::<S>(seed, i, j, k, one, zero, zero),
+            grad3d::<S>(seed, i, j, k, zero, one, zero),
+            grad3d::<S>(seed, i, j, k, zero, zero, one),
+        ];
+        corner_deriv::<S, 3>(t, grad3d::<S>(seed, i, j, k, x, y, z), g, [x, y, z])
+    };
+    let (v0, d0) = corner(i, j, k, x0, y0, z0, t0);
+    let (v1, d1) = corner(v1x, v1y, v1z, x1, y1, z1, t1);
+    let (v2, d2) = corner(v2x, v2y, v2z, x2, y2, z2, t2);
+    let (v3, d3) = corner(v3x, v3y, v3z, x3, y3, z3, t3);
+
+    let value = S::add_ps(S::add_ps(S::add_ps(v3, v2), v1), v0);
+    (value, sum_deriv::<S, 3>(&[d0, d1, d2, d3]))
+}
+
+#[inline(always)]
+pub unsafe fn fbm_3d<S: Simd>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    mut z: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+) -> S::Vf32 {
+    let mut result = simplex_3d::<S>(x, y, z, seed);
+    let mut amp = S::set1_ps(1.0);
+
+    for _ in 1..octaves {
+        x = S::mul_ps(x, lac);
+        y = S::mul_ps(y, lac);
+        z = S::mul_ps(z, lac);
+        amp = S::mul_ps(amp, gain);
+        result = S::add_ps(S::mul_ps(simplex_3d::<S>(x, y, z, seed), amp), result);
+    }
+
+    result
+}
+
+/// Fractal brownian motion over `simplex_3d_deriv`, with the derivatives of
+/// each octave scaled by its amplitude and frequency so they are the exact
+/// derivatives of the sum. The value is the same as `fbm_3d`.
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn fbm_3d_deriv<S: Simd>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    mut z: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+) -> (S::Vf32, [S::Vf32; 3]) {
+    let (mut result, mut deriv) = simplex_3d_deriv::<S>(x, y, z, seed);
+    let mut amp = S::set1_ps(1.0);
+    let mut freq = S::set1_ps(1.0);
+
+    for _ in 1..octaves {
+        x = S::mul_ps(x, lac);
+        y = S::mul_ps(y, lac);
+        z = S::mul_ps(z, lac);
+        amp = S::mul_ps(amp, gain);
+        freq = S::mul_ps(freq, lac);
+        let (value, octave_deriv) = simplex_3d_deriv::<S>(x, y, z, seed);
+        result = S::add_ps(S::mul_ps(value, amp), result);
+        let scale = S::mul_ps(amp, freq);
+        for (deriv, d) in deriv.iter_mut().zip(octave_deriv.iter()) {
+            *deriv = S::fmadd_ps(*d, scale, *deriv);
+        }
+    }
+
+    (result, deriv)
+}
+
+#[inline(always)]
+pub unsafe fn ridge_3d<S: Simd>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    mut z: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+) -> S::Vf32 {
+    let mut result = S::sub_ps(S::set1_ps(1.0), S::abs_ps(simplex_3d::<S>(x, y, z, seed)));
+    let mut amp = S::set1_ps(1.0);
+
+    for _ in 1..octaves {
+        x = S::mul_ps(x, lac);
+        y = S::mul_ps(y, lac);
+        z = S::mul_ps(z, lac);
+        amp = S::mul_ps(amp, gain);
+        result = S::add_ps(
+            result,
+            S::fnmadd_ps(
+                S::abs_ps(simplex_3d::<S>(x, y, z, seed)),
+                amp,
+                S::set1_ps(1.0),
+            ),
+        );
+    }
+
+    result
+}
+
+#[inline(always)]
+pub unsafe fn turbulence_3d<S: Simd>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    mut z: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
     seed: i32,
 ) -> S::Vf32 {
     let mut result = S::abs_ps(simplex_3d::<S>(x, y, z, seed));
@@ -504,8 +1242,173 @@ pub unsafe fn turbulence_3d<S: Simd>(
     result
 }
 
+/// 3d billow, `2|n| - 1` of simplex noise summed over `octaves` layers like
+/// `fbm_3d`
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
 #[inline(always)]
-unsafe fn grad4<S: Simd>(
+pub unsafe fn billow_3d<S: Simd>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    mut z: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+) -> S::Vf32 {
+    let scale = S::set1_ps(SIMPLEX_3D_SCALE);
+    let one = S::set1_ps(1.0);
+    // 2|n| - 1, with the noise scaled to roughly -1 to 1 so billows are centered
+    let billow = |n| {
+        S::sub_ps(
+            S::mul_ps(S::set1_ps(2.0), S::abs_ps(S::mul_ps(n, scale))),
+            one,
+        )
+    };
+    let mut result = billow(simplex_3d::<S>(x, y, z, seed));
+    let mut amp = one;
+
+    for _ in 1..octaves {
+        x = S::mul_ps(x, lac);
+        y = S::mul_ps(y, lac);
+        z = S::mul_ps(z, lac);
+        amp = S::mul_ps(amp, gain);
+        result = S::fmadd_ps(billow(simplex_3d::<S>(x, y, z, seed)), amp, result);
+    }
+
+    result
+}
+
+/// Musgrave's hybrid multifractal, each octave weighted by the ones before it
+/// so valleys are smooth and peaks rough
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub unsafe fn hybrid_multifractal_3d<S: Simd>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    mut z: S::Vf32,
+    s: &HybridMultifractalSettings,
+) -> S::Vf32 {
+    let lac = S::set1_ps(s.lacunarity);
+    let lac_h = S::set1_ps(s.lac_h());
+    let offset = S::set1_ps(s.offset);
+    let gain = S::set1_ps(s.gain);
+    let seed = s.dim.seed;
+    let scale = S::set1_ps(SIMPLEX_3D_SCALE);
+    let one = S::set1_ps(1.0);
+    let threshold = S::set1_ps(0.001);
+    let mut result = S::setzero_ps();
+    let mut weight = one;
+    let mut pwr = one;
+    // Lanes stop adding octaves once their weight falls to about zero
+    let mut active = S::cmpgt_ps(weight, threshold);
+
+    for _ in 0..s.octaves {
+        active = S::and_ps(active, S::cmpgt_ps(weight, threshold));
+        weight = S::min_ps(weight, one);
+        let signal = S::mul_ps(
+            S::add_ps(S::mul_ps(simplex_3d::<S>(x, y, z, seed), scale), offset),
+            pwr,
+        );
+        result = S::add_ps(result, S::and_ps(active, S::mul_ps(weight, signal)));
+        weight = S::mul_ps(weight, S::mul_ps(gain, signal));
+        pwr = S::mul_ps(pwr, lac_h);
+        x = S::mul_ps(x, lac);
+        y = S::mul_ps(y, lac);
+        z = S::mul_ps(z, lac);
+    }
+
+    result
+}
+
+/// Musgrave's heterogeneous terrain, each octave scaled by the height so far
+/// so low areas stay smooth
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub unsafe fn hetero_terrain_3d<S: Simd>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    mut z: S::Vf32,
+    s: &HeteroTerrainSettings,
+) -> S::Vf32 {
+    let lac = S::set1_ps(s.lacunarity);
+    let lac_h = S::set1_ps(s.lac_h());
+    let offset = S::set1_ps(s.offset);
+    let seed = s.dim.seed;
+    let scale = S::set1_ps(SIMPLEX_3D_SCALE);
+    let mut result = S::add_ps(offset, S::mul_ps(simplex_3d::<S>(x, y, z, seed), scale));
+    let mut pwr = lac_h;
+
+    for _ in 1..s.octaves {
+        x = S::mul_ps(x, lac);
+        y = S::mul_ps(y, lac);
+        z = S::mul_ps(z, lac);
+        let increment = S::mul_ps(
+            S::add_ps(S::mul_ps(simplex_3d::<S>(x, y, z, seed), scale), offset),
+            pwr,
+        );
+        result = S::add_ps(result, S::mul_ps(increment, result));
+        pwr = S::mul_ps(pwr, lac_h);
+    }
+
+    result
+}
+
+/// Musgrave's ridged multifractal, squared `offset - |n|` ridges with each
+/// octave weighted by the one before it
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub unsafe fn ridged_multifractal_3d<S: Simd>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    mut z: S::Vf32,
+    s: &RidgedMultifractalSettings,
+) -> S::Vf32 {
+    let lac = S::set1_ps(s.lacunarity);
+    let lac_h = S::set1_ps(s.lac_h());
+    let offset = S::set1_ps(s.offset);
+    let gain = S::set1_ps(s.gain);
+    let seed = s.dim.seed;
+    let scale = S::set1_ps(SIMPLEX_3D_SCALE);
+    let one = S::set1_ps(1.0);
+    let mut signal = S::sub_ps(
+        offset,
+        S::abs_ps(S::mul_ps(simplex_3d::<S>(x, y, z, seed), scale)),
+    );
+    signal = S::mul_ps(signal, signal);
+    let mut result = signal;
+    let mut pwr = lac_h;
+
+    for _ in 1..s.octaves {
+        x = S::mul_ps(x, lac);
+        y = S::mul_ps(y, lac);
+        z = S::mul_ps(z, lac);
+        let weight = S::min_ps(S::max_ps(S::mul_ps(signal, gain), S::setzero_ps()), one);
+        signal = S::sub_ps(
+            offset,
+            S::abs_ps(S::mul_ps(simplex_3d::<S>(x, y, z, seed), scale)),
+        );
+        signal = S::mul_ps(S::mul_ps(signal, signal), weight);
+        result = S::add_ps(result, S::mul_ps(signal, pwr));
+        pwr = S::mul_ps(pwr, lac_h);
+    }
+
+    result
+}
+
+#[inline(always)]
+pub(crate) unsafe fn grad4<S: Simd>(
     seed: i32,
     hash: S::Vi32,
     x: S::Vf32,
@@ -751,6 +1654,211 @@ pub unsafe fn simplex_4d<S: Simd>(
 
     S::add_ps(n0, S::add_ps(n1, S::add_ps(n2, S::add_ps(n3, n4))))
 }
+
+/// 4d simplex noise along with its exact partial derivatives
+/// `[d/dx, d/dy, d/dz, d/dw]`. The value is the same as `simplex_4d`.
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn simplex_4d_deriv<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    w: S::Vf32,
+    seed: i32,
+) -> (S::Vf32, [S::Vf32; 4]) {
+    let s = S::mul_ps(S::set1_ps(F4), S::add_ps(x, S::add_ps(y, S::add_ps(z, w))));
+
+    let ips = S::floor_ps(S::add_ps(x, s));
+    let jps = S::floor_ps(S::add_ps(y, s));
+    let kps = S::floor_ps(S::add_ps(z, s));
+    let lps = S::floor_ps(S::add_ps(w, s));
+
+    let i = S::cvtps_epi32(ips);
+    let j = S::cvtps_epi32(jps);
+    let k = S::cvtps_epi32(kps);
+    let l = S::cvtps_epi32(lps);
+
+    let t = S::mul_ps(
+        S::cvtepi32_ps(S::add_epi32(i, S::add_epi32(j, S::add_epi32(k, l)))),
+        S::set1_ps(G4),
+    );
+    let x0 = S::sub_ps(x, S::sub_ps(ips, t));
+    let y0 = S::sub_ps(y, S::sub_ps(jps, t));
+    let z0 = S::sub_ps(z, S::sub_ps(kps, t));
+    let w0 = S::sub_ps(w, S::sub_ps(lps, t));
+
+    let mut rank_x = S::setzero_epi32();
+    let mut rank_y = S::setzero_epi32();
+    let mut rank_z = S::setzero_epi32();
+    let mut rank_w = S::setzero_epi32();
+
+    let cond = S::castps_epi32(S::cmpgt_ps(x0, y0));
+    rank_x = S::add_epi32(rank_x, S::and_epi32(cond, S::set1_epi32(1)));
+    rank_y = S::add_epi32(rank_y, S::andnot_epi32(cond, S::set1_epi32(1)));
+    let cond = S::castps_epi32(S::cmpgt_ps(x0, z0));
+    rank_x = S::add_epi32(rank_x, S::and_epi32(cond, S::set1_epi32(1)));
+    rank_z = S::add_epi32(rank_z, S::andnot_epi32(cond, S::set1_epi32(1)));
+    let cond = S::castps_epi32(S::cmpgt_ps(x0, w0));
+    rank_x = S::add_epi32(rank_x, S::and_epi32(cond, S::set1_epi32(1)));
+    rank_w = S::add_epi32(rank_w, S::andnot_epi32(cond, S::set1_epi32(1)));
+    let cond = S::castps_epi32(S::cmpgt_ps(y0, z0));
+    rank_y = S::add_epi32(rank_y, S::and_epi32(cond, S::set1_epi32(1)));
+    rank_z = S::add_epi32(rank_z, S::andnot_epi32(cond, S::set1_epi32(1)));
+    let cond = S::castps_epi32(S::cmpgt_ps(y0, w0));
+    rank_y = S::add_epi32(rank_y, S::and_epi32(cond, S::set1_epi32(1)));
+    rank_w = S::add_epi32(rank_w, S::andnot_epi32(cond, S::set1_epi32(1)));
+    let cond = S::castps_epi32(S::cmpgt_ps(z0, w0));
+    rank_z = S::add_epi32(rank_z, S::and_epi32(cond, S::set1_epi32(1)));
+    rank_w = S::add_epi32(rank_w, S::andnot_epi32(cond, S::set1_epi32(1)));
+
+    let cond = S::cmpgt_epi32(rank_x, S::set1_epi32(2));
+    let i1 = S::and_epi32(S::set1_epi32(1), cond);
+    let cond = S::cmpgt_epi32(rank_y, S::set1_epi32(2));
+    let j1 = S::and_epi32(S::set1_epi32(1), cond);
+    let cond = S::cmpgt_epi32(rank_z, S::set1_epi32(2));
+    let k1 = S::and_epi32(S::set1_epi32(1), cond);
+    let cond = S::cmpgt_epi32(rank_w, S::set1_epi32(2));
+    let l1 = S::and_epi32(S::set1_epi32(1), cond);
+
+    let cond = S::cmpgt_epi32(rank_x, S::set1_epi32(1));
+    let i2 = S::and_epi32(S::set1_epi32(1), cond);
+    let cond = S::cmpgt_epi32(rank_y, S::set1_epi32(1));
+    let j2 = S::and_epi32(S::set1_epi32(1), cond);
+    let cond = S::cmpgt_epi32(rank_z, S::set1_epi32(1));
+    let k2 = S::and_epi32(S::set1_epi32(1), cond);
+    let cond = S::cmpgt_epi32(rank_w, S::set1_epi32(1));
+    let l2 = S::and_epi32(S::set1_epi32(1), cond);
+
+    let cond = S::cmpgt_epi32(rank_x, S::setzero_epi32());
+    let i3 = S::and_epi32(S::set1_epi32(1), cond);
+    let cond = S::cmpgt_epi32(rank_y, S::setzero_epi32());
+    let j3 = S::and_epi32(S::set1_epi32(1), cond);
+    let cond = S::cmpgt_epi32(rank_z, S::setzero_epi32());
+    let k3 = S::and_epi32(S::set1_epi32(1), cond);
+    let cond = S::cmpgt_epi32(rank_w, S::setzero_epi32());
+    let l3 = S::and_epi32(S::set1_epi32(1), cond);
+
+    let x1 = S::add_ps(S::sub_ps(x0, S::cvtepi32_ps(i1)), S::set1_ps(G4));
+    let y1 = S::add_ps(S::sub_ps(y0, S::cvtepi32_ps(j1)), S::set1_ps(G4));
+    let z1 = S::add_ps(S::sub_ps(z0, S::cvtepi32_ps(k1)), S::set1_ps(G4));
+    let w1 = S::add_ps(S::sub_ps(w0, S::cvtepi32_ps(l1)), S::set1_ps(G4));
+    let x2 = S::add_ps(S::sub_ps(x0, S::cvtepi32_ps(i2)), S::set1_ps(G24));
+    let y2 = S::add_ps(S::sub_ps(y0, S::cvtepi32_ps(j2)), S::set1_ps(G24));
+    let z2 = S::add_ps(S::sub_ps(z0, S::cvtepi32_ps(k2)), S::set1_ps(G24));
+    let w2 = S::add_ps(S::sub_ps(w0, S::cvtepi32_ps(l2)), S::set1_ps(G24));
+    let x3 = S::add_ps(S::sub_ps(x0, S::cvtepi32_ps(i3)), S::set1_ps(G34));
+    let y3 = S::add_ps(S::sub_ps(y0, S::cvtepi32_ps(j3)), S::set1_ps(G34));
+    let z3 = S::add_ps(S::sub_ps(z0, S::cvtepi32_ps(k3)), S::set1_ps(G34));
+    let w3 = S::add_ps(S::sub_ps(w0, S::cvtepi32_ps(l3)), S::set1_ps(G34));
+    let x4 = S::add_ps(S::sub_ps(x0, S::set1_ps(1.0)), S::set1_ps(G44));
+    let y4 = S::add_ps(S::sub_ps(y0, S::set1_ps(1.0)), S::set1_ps(G44));
+    let z4 = S::add_ps(S::sub_ps(z0, S::set1_ps(1.0)), S::set1_ps(G44));
+    let w4 = S::add_ps(S::sub_ps(w0, S::set1_ps(1.0)), S::set1_ps(G44));
+
+    let ii = S::and_epi32(i, S::set1_epi32(0xff));
+    let jj = S::and_epi32(j, S::set1_epi32(0xff));
+    let kk = S::and_epi32(k, S::set1_epi32(0xff));
+    let ll = S::and_epi32(l, S::set1_epi32(0xff));
+
+    let lp = S::i32gather_epi32(&PERM, ll);
+    let kp = S::i32gather_epi32(&PERM, S::add_epi32(kk, lp));
+    let jp = S::i32gather_epi32(&PERM, S::add_epi32(jj, kp));
+    let gi0 = S::i32gather_epi32(&PERM, S::add_epi32(ii, jp));
+
+    let lp = S::i32gather_epi32(&PERM, S::add_epi32(ll, l1));
+    let kp = S::i32gather_epi32(&PERM, S::add_epi32(S::add_epi32(kk, k1), lp));
+    let jp = S::i32gather_epi32(&PERM, S::add_epi32(S::add_epi32(jj, j1), kp));
+    let gi1 = S::i32gather_epi32(&PERM, S::add_epi32(S::add_epi32(ii, i1), jp));
+
+    let lp = S::i32gather_epi32(&PERM, S::add_epi32(ll, l2));
+    let kp = S::i32gather_epi32(&PERM, S::add_epi32(S::add_epi32(kk, k2), lp));
+    let jp = S::i32gather_epi32(&PERM, S::add_epi32(S::add_epi32(jj, j2), kp));
+    let gi2 = S::i32gather_epi32(&PERM, S::add_epi32(S::add_epi32(ii, i2), jp));
+
+    let lp = S::i32gather_epi32(&PERM, S::add_epi32(ll, l3));
+    let kp = S::i32gather_epi32(&PERM, S::add_epi32(S::add_epi32(kk, k3), lp));
+    let jp = S::i32gather_epi32(&PERM, S::add_epi32(S::add_epi32(jj, j3), kp));
+    let gi3 = S::i32gather_epi32(&PERM, S::add_epi32(S::add_epi32(ii, i3), jp));
+
+    let lp = S::i32gather_epi32(&PERM, S::add_epi32(ll, S::set1_epi32(1)));
+    let kp = S::i32gather_epi32(&PERM, S::add_epi32(S::add_epi32(kk, S::set1_epi32(1)), lp));
+    let jp = S::i32gather_epi32(&PERM, S::add_epi32(S::add_epi32(jj, S::set1_epi32(1)), kp));
+    let gi4 = S::i32gather_epi32(&PERM, S::add_epi32(S::add_epi32(ii, S::set1_epi32(1)), jp));
+
+    let t0 = S::sub_ps(
+        S::sub_ps(
+            S::sub_ps(
+                S::sub_ps(S::set1_ps(0.5), S::mul_ps(x0, x0)),
+                S::mul_ps(y0, y0),
+            ),
+            S::mul_ps(z0, z0),
+        ),
+        S::mul_ps(w0, w0),
+    );
+    let t1 = S::sub_ps(
+        S::sub_ps(
+            S::sub_ps(
+                S::sub_ps(S::set1_ps(0.5), S::mul_ps(x1, x1)),
+                S::mul_ps(y1, y1),
+            ),
+            S::mul_ps(z1, z1),
+        ),
+        S::mul_ps(w1, w1),
+    );
+    let t2 = S::sub_ps(
+        S::sub_ps(
+            S::sub_ps(
+                S::sub_ps(S::set1_ps(0.5), S::mul_ps(x2, x2)),
+                S::mul_ps(y2, y2),
+            ),
+            S::mul_ps(z2, z2),
+        ),
+        S::mul_ps(w2, w2),
+    );
+    let t3 = S::sub_ps(
+        S::sub_ps(
+            S::sub_ps(
+                S::sub_ps(S::set1_ps(0.5), S::mul_ps(x3, x3)),
+                S::mul_ps(y3, y3),
+            ),
+            S::mul_ps(z3, z3),
+        ),
+        S::mul_ps(w3, w3),
+    );
+    let t4 = S::sub_ps(
+        S::sub_ps(
+            S::sub_ps(
+                S::sub_ps(S::set1_ps(0.5), S::mul_ps(x4, x4)),
+                S::mul_ps(y4, y4),
+            ),
+            S::mul_ps(z4, z4),
+        ),
+        S::mul_ps(w4, w4),
+    );
+    let one = S::set1_ps(1.0);
+    let zero = S::setzero_ps();
+    // The gradients are linear, so the gradient vector is grad4 of the unit axes
+    let corner = |gi, x, y, z, w, t| {
+        let g = [
+            grad4::<S>(seed, gi, one, zero, zero, zero),
+            grad4::<S>(seed, gi, zero, one, zero, zero),
+            grad4::<S>(seed, gi, zero, zero, one, zero),
+            grad4::<S>(seed, gi, zero, zero, zero, one),
+        ];
+        corner_deriv::<S, 4>(t, grad4::<S>(seed, gi, x, y, z, w), g, [x, y, z, w])
+    };
+    let (n0, d0) = corner(gi0, x0, y0, z0, w0, t0);
+    let (n1, d1) = corner(gi1, x1, y1, z1, w1, t1);
+    let (n2, d2) = corner(gi2, x2, y2, z2, w2, t2);
+    let (n3, d3) = corner(gi3, x3, y3, z3, w3, t3);
+    let (n4, d4) = corner(gi4, x4, y4, z4, w4, t4);
+
+    let value = S::add_ps(n0, S::add_ps(n1, S::add_ps(n2, S::add_ps(n3, n4))));
+    (value, sum_deriv::<S, 4>(&[d0, d1, d2, d3, d4]))
+}
 #[inline(always)]
 pub unsafe fn fbm_4d<S: Simd>(
     mut x: S::Vf32,
@@ -777,6 +1885,46 @@ pub unsafe fn fbm_4d<S: Simd>(
     result
 }
 
+/// Fractal brownian motion over `simplex_4d_deriv`, with the derivatives of
+/// each octave scaled by its amplitude and frequency so they are the exact
+/// derivatives of the sum. The value is the same as `fbm_4d`.
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn fbm_4d_deriv<S: Simd>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    mut z: S::Vf32,
+    mut w: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+) -> (S::Vf32, [S::Vf32; 4]) {
+    let (mut result, mut deriv) = simplex_4d_deriv::<S>(x, y, z, w, seed);
+    let mut amp = S::set1_ps(1.0);
+    let mut freq = S::set1_ps(1.0);
+
+    for _ in 1..octaves {
+        x = S::mul_ps(x, lac);
+        y = S::mul_ps(y, lac);
+        z = S::mul_ps(z, lac);
+        w = S::mul_ps(w, lac);
+        amp = S::mul_ps(amp, gain);
+        freq = S::mul_ps(freq, lac);
+        let (value, octave_deriv) = simplex_4d_deriv::<S>(x, y, z, w, seed);
+        result = S::add_ps(S::mul_ps(value, amp), result);
+        let scale = S::mul_ps(amp, freq);
+        for (deriv, d) in deriv.iter_mut().zip(octave_deriv.iter()) {
+            *deriv = S::fmadd_ps(*d, scale, *deriv);
+        }
+    }
+
+    (result, deriv)
+}
+
 #[inline(always)]
 pub unsafe fn ridge_4d<S: Simd>(
     mut x: S::Vf32,
@@ -840,3 +1988,176 @@ pub unsafe fn turbulence_4d<S: Simd>(
 
     result
 }
+
+/// 4d billow, `2|n| - 1` of simplex noise summed over `octaves` layers like
+/// `fbm_4d`
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn billow_4d<S: Simd>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    mut z: S::Vf32,
+    mut w: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+) -> S::Vf32 {
+    let scale = S::set1_ps(SIMPLEX_4D_SCALE);
+    let one = S::set1_ps(1.0);
+    // 2|n| - 1, with the noise scaled to roughly -1 to 1 so billows are centered
+    let billow = |n| {
+        S::sub_ps(
+            S::mul_ps(S::set1_ps(2.0), S::abs_ps(S::mul_ps(n, scale))),
+            one,
+        )
+    };
+    let mut result = billow(simplex_4d::<S>(x, y, z, w, seed));
+    let mut amp = one;
+
+    for _ in 1..octaves {
+        x = S::mul_ps(x, lac);
+        y = S::mul_ps(y, lac);
+        z = S::mul_ps(z, lac);
+        w = S::mul_ps(w, lac);
+        amp = S::mul_ps(amp, gain);
+        result = S::fmadd_ps(billow(simplex_4d::<S>(x, y, z, w, seed)), amp, result);
+    }
+
+    result
+}
+
+/// Musgrave's hybrid multifractal, each octave weighted by the ones before it
+/// so valleys are smooth and peaks rough
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub unsafe fn hybrid_multifractal_4d<S: Simd>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    mut z: S::Vf32,
+    mut w: S::Vf32,
+    s: &HybridMultifractalSettings,
+) -> S::Vf32 {
+    let lac = S::set1_ps(s.lacunarity);
+    let lac_h = S::set1_ps(s.lac_h());
+    let offset = S::set1_ps(s.offset);
+    let gain = S::set1_ps(s.gain);
+    let seed = s.dim.seed;
+    let scale = S::set1_ps(SIMPLEX_4D_SCALE);
+    let one = S::set1_ps(1.0);
+    let threshold = S::set1_ps(0.001);
+    let mut result = S::setzero_ps();
+    let mut weight = one;
+    let mut pwr = one;
+    // Lanes stop adding octaves once their weight falls to about zero
+    let mut active = S::cmpgt_ps(weight, threshold);
+
+    for _ in 0..s.octaves {
+        active = S::and_ps(active, S::cmpgt_ps(weight, threshold));
+        weight = S::min_ps(weight, one);
+        let signal = S::mul_ps(
+            S::add_ps(S::mul_ps(simplex_4d::<S>(x, y, z, w, seed), scale), offset),
+            pwr,
+        );
+        result = S::add_ps(result, S::and_ps(active, S::mul_ps(weight, signal)));
+        weight = S::mul_ps(weight, S::mul_ps(gain, signal));
+        pwr = S::mul_ps(pwr, lac_h);
+        x = S::mul_ps(x, lac);
+        y = S::mul_ps(y, lac);
+        z = S::mul_ps(z, lac);
+        w = S::mul_ps(w, lac);
+    }
+
+    result
+}
+
+/// Musgrave's heterogeneous terrain, each octave scaled by the height so far
+/// so low areas stay smooth
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub unsafe fn hetero_terrain_4d<S: Simd>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    mut z: S::Vf32,
+    mut w: S::Vf32,
+    s: &HeteroTerrainSettings,
+) -> S::Vf32 {
+    let lac = S::set1_ps(s.lacunarity);
+    let lac_h = S::set1_ps(s.lac_h());
+    let offset = S::set1_ps(s.offset);
+    let seed = s.dim.seed;
+    let scale = S::set1_ps(SIMPLEX_4D_SCALE);
+    let mut result = S::add_ps(offset, S::mul_ps(simplex_4d::<S>(x, y, z, w, seed), scale));
+    let mut pwr = lac_h;
+
+    for _ in 1..s.octaves {
+        x = S::mul_ps(x, lac);
+        y = S::mul_ps(y, lac);
+        z = S::mul_ps(z, lac);
+        w = S::mul_ps(w, lac);
+        let increment = S::mul_ps(
+            S::add_ps(S::mul_ps(simplex_4d::<S>(x, y, z, w, seed), scale), offset),
+            pwr,
+        );
+        result = S::add_ps(result, S::mul_ps(increment, result));
+        pwr = S::mul_ps(pwr, lac_h);
+    }
+
+    result
+}
+
+/// Musgrave's ridged multifractal, squared `offset - |n|` ridges with each
+/// octave weighted by the one before it
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub unsafe fn ridged_multifractal_4d<S: Simd>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    mut z: S::Vf32,
+    mut w: S::Vf32,
+    s: &RidgedMultifractalSettings,
+) -> S::Vf32 {
+    let lac = S::set1_ps(s.lacunarity);
+    let lac_h = S::set1_ps(s.lac_h());
+    let offset = S::set1_ps(s.offset);
+    let gain = S::set1_ps(s.gain);
+    let seed = s.dim.seed;
+    let scale = S::set1_ps(SIMPLEX_4D_SCALE);
+    let one = S::set1_ps(1.0);
+    let mut signal = S::sub_ps(
+        offset,
+        S::abs_ps(S::mul_ps(simplex_4d::<S>(x, y, z, w, seed), scale)),
+    );
+    signal = S::mul_ps(signal, signal);
+    let mut result = signal;
+    let mut pwr = lac_h;
+
+    for _ in 1..s.octaves {
+        x = S::mul_ps(x, lac);
+        y = S::mul_ps(y, lac);
+        z = S::mul_ps(z, lac);
+        w = S::mul_ps(w, lac);
+        let weight = S::min_ps(S::max_ps(S::mul_ps(signal, gain), S::setzero_ps()), one);
+        signal = S::sub_ps(
+            offset,
+            S::abs_ps(S::mul_ps(simplex_4d::<S>(x, y, z, w, seed), scale)),
+        );
+        signal = S::mul_ps(S::mul_ps(signal, signal), weight);
+        result = S::add_ps(result, S::mul_ps(signal, pwr));
+        pwr = S::mul_ps(pwr, lac_h);
+    }
+
+    result
+}
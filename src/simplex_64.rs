@@ -13,6 +13,15 @@ const G24: f64 = 2.0 * G4;
 const G34: f64 = 3.0 * G4;
 const G44: f64 = 4.0 * G4;
 
+/// Brings `simplex_1d` to roughly -1 to 1
+pub(crate) const SIMPLEX_1D_SCALE: f64 = 0.45;
+/// Brings `simplex_2d` to roughly -1 to 1
+pub(crate) const SIMPLEX_2D_SCALE: f64 = 45.0;
+/// Brings `simplex_3d` to roughly -1 to 1
+pub(crate) const SIMPLEX_3D_SCALE: f64 = 76.0;
+/// Brings `simplex_4d` to roughly -1 to 1
+pub(crate) const SIMPLEX_4D_SCALE: f64 = 62.0;
+
 #[inline(always)]
 pub unsafe fn grad1<S: Simd>(seed: i64, hash: S::Vi64, x: S::Vf64) -> S::Vf64 {
     let h = S::and_epi64(S::xor_epi64(S::set1_epi64(seed), hash), S::set1_epi64(15));
@@ -40,7 +49,7 @@ pub unsafe fn fbm_1d<S: Simd>(
     for _ in 1..octaves {
         x = S::mul_pd(x, lacunarity);
         amp = S::mul_pd(amp, gain);
-        result = S::add_pd(result, simplex_1d::<S>(x, seed));
+        result = S::add_pd(S::mul_pd(simplex_1d::<S>(x, seed), amp), result);
     }
 
     result
@@ -62,7 +71,10 @@ pub unsafe fn ridge_1d<S: Simd>(
         amp = S::mul_pd(amp, gain);
         result = S::add_pd(
             result,
-            S::sub_pd(S::set1_pd(1.0), S::abs_pd(simplex_1d::<S>(x, seed))),
+            S::sub_pd(
+                S::set1_pd(1.0),
+                S::mul_pd(S::abs_pd(simplex_1d::<S>(x, seed)), amp),
+            ),
         );
     }
 
@@ -83,7 +95,153 @@ pub unsafe fn turbulence_1d<S: Simd>(
     for _ in 1..octaves {
         x = S::mul_pd(x, lacunarity);
         amp = S::mul_pd(amp, gain);
-        result = S::add_pd(result, S::abs_pd(simplex_1d::<S>(x, seed)));
+        result = S::add_pd(result, S::abs_pd(S::mul_pd(simplex_1d::<S>(x, seed), amp)));
+    }
+
+    result
+}
+
+/// 1d billow, `2|n| - 1` of simplex noise summed over `octaves` layers like
+/// `fbm_1d`
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn billow_1d<S: Simd>(
+    mut x: S::Vf64,
+    lac: S::Vf64,
+    gain: S::Vf64,
+    octaves: u8,
+    seed: i64,
+) -> S::Vf64 {
+    let scale = S::set1_pd(SIMPLEX_1D_SCALE);
+    let one = S::set1_pd(1.0);
+    // 2|n| - 1, with the noise scaled to roughly -1 to 1 so billows are centered
+    let billow = |n| {
+        S::sub_pd(
+            S::mul_pd(S::set1_pd(2.0), S::abs_pd(S::mul_pd(n, scale))),
+            one,
+        )
+    };
+    let mut result = billow(simplex_1d::<S>(x, seed));
+    let mut amp = one;
+
+    for _ in 1..octaves {
+        x = S::mul_pd(x, lac);
+        amp = S::mul_pd(amp, gain);
+        result = S::fmadd_pd(billow(simplex_1d::<S>(x, seed)), amp, result);
+    }
+
+    result
+}
+
+/// Musgrave's hybrid multifractal, each octave weighted by the ones before it
+/// so valleys are smooth and peaks rough
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub unsafe fn hybrid_multifractal_1d<S: Simd>(
+    mut x: S::Vf64,
+    s: &HybridMultifractalSettings,
+) -> S::Vf64 {
+    let lac = S::set1_pd(s.lacunarity as f64);
+    let lac_h = S::set1_pd((s.lacunarity as f64).powf(-s.h as f64));
+    let offset = S::set1_pd(s.offset as f64);
+    let gain = S::set1_pd(s.gain as f64);
+    let seed = s.dim.seed as i64;
+    let scale = S::set1_pd(SIMPLEX_1D_SCALE);
+    let one = S::set1_pd(1.0);
+    let threshold = S::set1_pd(0.001);
+    let mut result = S::setzero_pd();
+    let mut weight = one;
+    let mut pwr = one;
+    // Lanes stop adding octaves once their weight falls to about zero
+    let mut active = S::cmpgt_pd(weight, threshold);
+
+    for _ in 0..s.octaves {
+        active = S::and_pd(active, S::cmpgt_pd(weight, threshold));
+        weight = S::min_pd(weight, one);
+        let signal = S::mul_pd(
+            S::add_pd(S::mul_pd(simplex_1d::<S>(x, seed), scale), offset),
+            pwr,
+        );
+        result = S::add_pd(result, S::and_pd(active, S::mul_pd(weight, signal)));
+        weight = S::mul_pd(weight, S::mul_pd(gain, signal));
+        pwr = S::mul_pd(pwr, lac_h);
+        x = S::mul_pd(x, lac);
+    }
+
+    result
+}
+
+/// Musgrave's heterogeneous terrain, each octave scaled by the height so far
+/// so low areas stay smooth
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub unsafe fn hetero_terrain_1d<S: Simd>(mut x: S::Vf64, s: &HeteroTerrainSettings) -> S::Vf64 {
+    let lac = S::set1_pd(s.lacunarity as f64);
+    let lac_h = S::set1_pd((s.lacunarity as f64).powf(-s.h as f64));
+    let offset = S::set1_pd(s.offset as f64);
+    let seed = s.dim.seed as i64;
+    let scale = S::set1_pd(SIMPLEX_1D_SCALE);
+    let mut result = S::add_pd(offset, S::mul_pd(simplex_1d::<S>(x, seed), scale));
+    let mut pwr = lac_h;
+
+    for _ in 1..s.octaves {
+        x = S::mul_pd(x, lac);
+        let increment = S::mul_pd(
+            S::add_pd(S::mul_pd(simplex_1d::<S>(x, seed), scale), offset),
+            pwr,
+        );
+        result = S::add_pd(result, S::mul_pd(increment, result));
+        pwr = S::mul_pd(pwr, lac_h);
+    }
+
+    result
+}
+
+/// Musgrave's ridged multifractal, squared `offset - |n|` ridges with each
+/// octave weighted by the one before it
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub unsafe fn ridged_multifractal_1d<S: Simd>(
+    mut x: S::Vf64,
+    s: &RidgedMultifractalSettings,
+) -> S::Vf64 {
+    let lac = S::set1_pd(s.lacunarity as f64);
+    let lac_h = S::set1_pd((s.lacunarity as f64).powf(-s.h as f64));
+    let offset = S::set1_pd(s.offset as f64);
+    let gain = S::set1_pd(s.gain as f64);
+    let seed = s.dim.seed as i64;
+    let scale = S::set1_pd(SIMPLEX_1D_SCALE);
+    let one = S::set1_pd(1.0);
+    let mut signal = S::sub_pd(
+        offset,
+        S::abs_pd(S::mul_pd(simplex_1d::<S>(x, seed), scale)),
+    );
+    signal = S::mul_pd(signal, signal);
+    let mut result = signal;
+    let mut pwr = lac_h;
+
+    for _ in 1..s.octaves {
+        x = S::mul_pd(x, lac);
+        let weight = S::min_pd(S::max_pd(S::mul_pd(signal, gain), S::setzero_pd()), one);
+        signal = S::sub_pd(
+            offset,
+            S::abs_pd(S::mul_pd(simplex_1d::<S>(x, seed), scale)),
+        );
+        signal = S::mul_pd(S::mul_pd(signal, signal), weight);
+        result = S::add_pd(result, S::mul_pd(signal, pwr));
+        pwr = S::mul_pd(pwr, lac_h);
     }
 
     result
@@ -279,6 +437,163 @@ pub unsafe fn turbulence_2d<S: Simd>(
     result
 }
 
+/// 2d billow, `2|n| - 1` of simplex noise summed over `octaves` layers like
+/// `fbm_2d`
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn billow_2d<S: Simd>(
+    mut x: S::Vf64,
+    mut y: S::Vf64,
+    lac: S::Vf64,
+    gain: S::Vf64,
+    octaves: u8,
+    seed: i64,
+) -> S::Vf64 {
+    let scale = S::set1_pd(SIMPLEX_2D_SCALE);
+    let one = S::set1_pd(1.0);
+    // 2|n| - 1, with the noise scaled to roughly -1 to 1 so billows are centered
+    let billow = |n| {
+        S::sub_pd(
+            S::mul_pd(S::set1_pd(2.0), S::abs_pd(S::mul_pd(n, scale))),
+            one,
+        )
+    };
+    let mut result = billow(simplex_2d::<S>(x, y, seed));
+    let mut amp = one;
+
+    for _ in 1..octaves {
+        x = S::mul_pd(x, lac);
+        y = S::mul_pd(y, lac);
+        amp = S::mul_pd(amp, gain);
+        result = S::fmadd_pd(billow(simplex_2d::<S>(x, y, seed)), amp, result);
+    }
+
+    result
+}
+
+/// Musgrave's hybrid multifractal, each octave weighted by the ones before it
+/// so valleys are smooth and peaks rough
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub unsafe fn hybrid_multifractal_2d<S: Simd>(
+    mut x: S::Vf64,
+    mut y: S::Vf64,
+    s: &HybridMultifractalSettings,
+) -> S::Vf64 {
+    let lac = S::set1_pd(s.lacunarity as f64);
+    let lac_h = S::set1_pd((s.lacunarity as f64).powf(-s.h as f64));
+    let offset = S::set1_pd(s.offset as f64);
+    let gain = S::set1_pd(s.gain as f64);
+    let seed = s.dim.seed as i64;
+    let scale = S::set1_pd(SIMPLEX_2D_SCALE);
+    let one = S::set1_pd(1.0);
+    let threshold = S::set1_pd(0.001);
+    let mut result = S::setzero_pd();
+    let mut weight = one;
+    let mut pwr = one;
+    // Lanes stop adding octaves once their weight falls to about zero
+    let mut active = S::cmpgt_pd(weight, threshold);
+
+    for _ in 0..s.octaves {
+        active = S::and_pd(active, S::cmpgt_pd(weight, threshold));
+        weight = S::min_pd(weight, one);
+        let signal = S::mul_pd(
+            S::add_pd(S::mul_pd(simplex_2d::<S>(x, y, seed), scale), offset),
+            pwr,
+        );
+        result = S::add_pd(result, S::and_pd(active, S::mul_pd(weight, signal)));
+        weight = S::mul_pd(weight, S::mul_pd(gain, signal));
+        pwr = S::mul_pd(pwr, lac_h);
+        x = S::mul_pd(x, lac);
+        y = S::mul_pd(y, lac);
+    }
+
+    result
+}
+
+/// Musgrave's heterogeneous terrain, each octave scaled by the height so far
+/// so low areas stay smooth
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub unsafe fn hetero_terrain_2d<S: Simd>(
+    mut x: S::Vf64,
+    mut y: S::Vf64,
+    s: &HeteroTerrainSettings,
+) -> S::Vf64 {
+    let lac = S::set1_pd(s.lacunarity as f64);
+    let lac_h = S::set1_pd((s.lacunarity as f64).powf(-s.h as f64));
+    let offset = S::set1_pd(s.offset as f64);
+    let seed = s.dim.seed as i64;
+    let scale = S::set1_pd(SIMPLEX_2D_SCALE);
+    let mut result = S::add_pd(offset, S::mul_pd(simplex_2d::<S>(x, y, seed), scale));
+    let mut pwr = lac_h;
+
+    for _ in 1..s.octaves {
+        x = S::mul_pd(x, lac);
+        y = S::mul_pd(y, lac);
+        let increment = S::mul_pd(
+            S::add_pd(S::mul_pd(simplex_2d::<S>(x, y, seed), scale), offset),
+            pwr,
+        );
+        result = S::add_pd(result, S::mul_pd(increment, result));
+        pwr = S::mul_pd(pwr, lac_h);
+    }
+
+    result
+}
+
+/// Musgrave's ridged multifractal, squared `offset - |n|` ridges with each
+/// octave weighted by the one before it
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub unsafe fn ridged_multifractal_2d<S: Simd>(
+    mut x: S::Vf64,
+    mut y: S::Vf64,
+    s: &RidgedMultifractalSettings,
+) -> S::Vf64 {
+    let lac = S::set1_pd(s.lacunarity as f64);
+    let lac_h = S::set1_pd((s.lacunarity as f64).powf(-s.h as f64));
+    let offset = S::set1_pd(s.offset as f64);
+    let gain = S::set1_pd(s.gain as f64);
+    let seed = s.dim.seed as i64;
+    let scale = S::set1_pd(SIMPLEX_2D_SCALE);
+    let one = S::set1_pd(1.0);
+    let mut signal = S::sub_pd(
+        offset,
+        S::abs_pd(S::mul_pd(simplex_2d::<S>(x, y, seed), scale)),
+    );
+    signal = S::mul_pd(signal, signal);
+    let mut result = signal;
+    let mut pwr = lac_h;
+
+    for _ in 1..s.octaves {
+        x = S::mul_pd(x, lac);
+        y = S::mul_pd(y, lac);
+        let weight = S::min_pd(S::max_pd(S::mul_pd(signal, gain), S::setzero_pd()), one);
+        signal = S::sub_pd(
+            offset,
+            S::abs_pd(S::mul_pd(simplex_2d::<S>(x, y, seed), scale)),
+        );
+        signal = S::mul_pd(S::mul_pd(signal, signal), weight);
+        result = S::add_pd(result, S::mul_pd(signal, pwr));
+        pwr = S::mul_pd(pwr, lac_h);
+    }
+
+    result
+}
+
 #[inline(always)]
 unsafe fn grad3d<S: Simd>(seed: i64, hash: S::Vi64, x: S::Vf64, y: S::Vf64, z: S::Vf64) -> S::Vf64 {
     let h = S::and_epi64(S::xor_epi64(S::set1_epi64(seed), hash), S::set1_epi64(15));
@@ -572,6 +887,171 @@ pub unsafe fn turbulence_3d<S: Simd>(
     result
 }
 
+/// 3d billow, `2|n| - 1` of simplex noise summed over `octaves` layers like
+/// `fbm_3d`
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn billow_3d<S: Simd>(
+    mut x: S::Vf64,
+    mut y: S::Vf64,
+    mut z: S::Vf64,
+    lac: S::Vf64,
+    gain: S::Vf64,
+    octaves: u8,
+    seed: i64,
+) -> S::Vf64 {
+    let scale = S::set1_pd(SIMPLEX_3D_SCALE);
+    let one = S::set1_pd(1.0);
+    // 2|n| - 1, with the noise scaled to roughly -1 to 1 so billows are centered
+    let billow = |n| {
+        S::sub_pd(
+            S::mul_pd(S::set1_pd(2.0), S::abs_pd(S::mul_pd(n, scale))),
+            one,
+        )
+    };
+    let mut result = billow(simplex_3d::<S>(x, y, z, seed));
+    let mut amp = one;
+
+    for _ in 1..octaves {
+        x = S::mul_pd(x, lac);
+        y = S::mul_pd(y, lac);
+        z = S::mul_pd(z, lac);
+        amp = S::mul_pd(amp, gain);
+        result = S::fmadd_pd(billow(simplex_3d::<S>(x, y, z, seed)), amp, result);
+    }
+
+    result
+}
+
+/// Musgrave's hybrid multifractal, each octave weighted by the ones before it
+/// so valleys are smooth and peaks rough
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub unsafe fn hybrid_multifractal_3d<S: Simd>(
+    mut x: S::Vf64,
+    mut y: S::Vf64,
+    mut z: S::Vf64,
+    s: &HybridMultifractalSettings,
+) -> S::Vf64 {
+    let lac = S::set1_pd(s.lacunarity as f64);
+    let lac_h = S::set1_pd((s.lacunarity as f64).powf(-s.h as f64));
+    let offset = S::set1_pd(s.offset as f64);
+    let gain = S::set1_pd(s.gain as f64);
+    let seed = s.dim.seed as i64;
+    let scale = S::set1_pd(SIMPLEX_3D_SCALE);
+    let one = S::set1_pd(1.0);
+    let threshold = S::set1_pd(0.001);
+    let mut result = S::setzero_pd();
+    let mut weight = one;
+    let mut pwr = one;
+    // Lanes stop adding octaves once their weight falls to about zero
+    let mut active = S::cmpgt_pd(weight, threshold);
+
+    for _ in 0..s.octaves {
+        active = S::and_pd(active, S::cmpgt_pd(weight, threshold));
+        weight = S::min_pd(weight, one);
+        let signal = S::mul_pd(
+            S::add_pd(S::mul_pd(simplex_3d::<S>(x, y, z, seed), scale), offset),
+            pwr,
+        );
+        result = S::add_pd(result, S::and_pd(active, S::mul_pd(weight, signal)));
+        weight = S::mul_pd(weight, S::mul_pd(gain, signal));
+        pwr = S::mul_pd(pwr, lac_h);
+        x = S::mul_pd(x, lac);
+        y = S::mul_pd(y, lac);
+        z = S::mul_pd(z, lac);
+    }
+
+    result
+}
+
+/// Musgrave's heterogeneous terrain, each octave scaled by the height so far
+/// so low areas stay smooth
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub unsafe fn hetero_terrain_3d<S: Simd>(
+    mut x: S::Vf64,
+    mut y: S::Vf64,
+    mut z: S::Vf64,
+    s: &HeteroTerrainSettings,
+) -> S::Vf64 {
+    let lac = S::set1_pd(s.lacunarity as f64);
+    let lac_h = S::set1_pd((s.lacunarity as f64).powf(-s.h as f64));
+    let offset = S::set1_pd(s.offset as f64);
+    let seed = s.dim.seed as i64;
+    let scale = S::set1_pd(SIMPLEX_3D_SCALE);
+    let mut result = S::add_pd(offset, S::mul_pd(simplex_3d::<S>(x, y, z, seed), scale));
+    let mut pwr = lac_h;
+
+    for _ in 1..s.octaves {
+        x = S::mul_pd(x, lac);
+        y = S::mul_pd(y, lac);
+        z = S::mul_pd(z, lac);
+        let increment = S::mul_pd(
+            S::add_pd(S::mul_pd(simplex_3d::<S>(x, y, z, seed), scale), offset),
+            pwr,
+        );
+        result = S::add_pd(result, S::mul_pd(increment, result));
+        pwr = S::mul_pd(pwr, lac_h);
+    }
+
+    result
+}
+
+/// Musgrave's ridged multifractal, squared `offset - |n|` ridges with each
+/// octave weighted by the one before it
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub unsafe fn ridged_multifractal_3d<S: Simd>(
+    mut x: S::Vf64,
+    mut y: S::Vf64,
+    mut z: S::Vf64,
+    s: &RidgedMultifractalSettings,
+) -> S::Vf64 {
+    let lac = S::set1_pd(s.lacunarity as f64);
+    let lac_h = S::set1_pd((s.lacunarity as f64).powf(-s.h as f64));
+    let offset = S::set1_pd(s.offset as f64);
+    let gain = S::set1_pd(s.gain as f64);
+    let seed = s.dim.seed as i64;
+    let scale = S::set1_pd(SIMPLEX_3D_SCALE);
+    let one = S::set1_pd(1.0);
+    let mut signal = S::sub_pd(
+        offset,
+        S::abs_pd(S::mul_pd(simplex_3d::<S>(x, y, z, seed), scale)),
+    );
+    signal = S::mul_pd(signal, signal);
+    let mut result = signal;
+    let mut pwr = lac_h;
+
+    for _ in 1..s.octaves {
+        x = S::mul_pd(x, lac);
+        y = S::mul_pd(y, lac);
+        z = S::mul_pd(z, lac);
+        let weight = S::min_pd(S::max_pd(S::mul_pd(signal, gain), S::setzero_pd()), one);
+        signal = S::sub_pd(
+            offset,
+            S::abs_pd(S::mul_pd(simplex_3d::<S>(x, y, z, seed), scale)),
+        );
+        signal = S::mul_pd(S::mul_pd(signal, signal), weight);
+        result = S::add_pd(result, S::mul_pd(signal, pwr));
+        pwr = S::mul_pd(pwr, lac_h);
+    }
+
+    result
+}
+
 #[inline(always)]
 unsafe fn grad4<S: Simd>(
     seed: i64,
@@ -917,3 +1397,176 @@ pub unsafe fn turbulence_4d<S: Simd>(
 
     result
 }
+
+/// 4d billow, `2|n| - 1` of simplex noise summed over `octaves` layers like
+/// `fbm_4d`
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn billow_4d<S: Simd>(
+    mut x: S::Vf64,
+    mut y: S::Vf64,
+    mut z: S::Vf64,
+    mut w: S::Vf64,
+    lac: S::Vf64,
+    gain: S::Vf64,
+    octaves: u8,
+    seed: i64,
+) -> S::Vf64 {
+    let scale = S::set1_pd(SIMPLEX_4D_SCALE);
+    let one = S::set1_pd(1.0);
+    // 2|n| - 1, with the noise scaled to roughly -1 to 1 so billows are centered
+    let billow = |n| {
+        S::sub_pd(
+            S::mul_pd(S::set1_pd(2.0), S::abs_pd(S::mul_pd(n, scale))),
+            one,
+        )
+    };
+    let mut result = billow(simplex_4d::<S>(x, y, z, w, seed));
+    let mut amp = one;
+
+    for _ in 1..octaves {
+        x = S::mul_pd(x, lac);
+        y = S::mul_pd(y, lac);
+        z = S::mul_pd(z, lac);
+        w = S::mul_pd(w, lac);
+        amp = S::mul_pd(amp, gain);
+        result = S::fmadd_pd(billow(simplex_4d::<S>(x, y, z, w, seed)), amp, result);
+    }
+
+    result
+}
+
+/// Musgrave's hybrid multifractal, each octave weighted by the ones before it
+/// so valleys are smooth and peaks rough
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub unsafe fn hybrid_multifractal_4d<S: Simd>(
+    mut x: S::Vf64,
+    mut y: S::Vf64,
+    mut z: S::Vf64,
+    mut w: S::Vf64,
+    s: &HybridMultifractalSettings,
+) -> S::Vf64 {
+    let lac = S::set1_pd(s.lacunarity as f64);
+    let lac_h = S::set1_pd((s.lacunarity as f64).powf(-s.h as f64));
+    let offset = S::set1_pd(s.offset as f64);
+    let gain = S::set1_pd(s.gain as f64);
+    let seed = s.dim.seed as i64;
+    let scale = S::set1_pd(SIMPLEX_4D_SCALE);
+    let one = S::set1_pd(1.0);
+    let threshold = S::set1_pd(0.001);
+    let mut result = S::setzero_pd();
+    let mut weight = one;
+    let mut pwr = one;
+    // Lanes stop adding octaves once their weight falls to about zero
+    let mut active = S::cmpgt_pd(weight, threshold);
+
+    for _ in 0..s.octaves {
+        active = S::and_pd(active, S::cmpgt_pd(weight, threshold));
+        weight = S::min_pd(weight, one);
+        let signal = S::mul_pd(
+            S::add_pd(S::mul_pd(simplex_4d::<S>(x, y, z, w, seed), scale), offset),
+            pwr,
+        );
+        result = S::add_pd(result, S::and_pd(active, S::mul_pd(weight, signal)));
+        weight = S::mul_pd(weight, S::mul_pd(gain, signal));
+        pwr = S::mul_pd(pwr, lac_h);
+        x = S::mul_pd(x, lac);
+        y = S::mul_pd(y, lac);
+        z = S::mul_pd(z, lac);
+        w = S::mul_pd(w, lac);
+    }
+
+    result
+}
+
+/// Musgrave's heterogeneous terrain, each octave scaled by the height so far
+/// so low areas stay smooth
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub unsafe fn hetero_terrain_4d<S: Simd>(
+    mut x: S::Vf64,
+    mut y: S::Vf64,
+    mut z: S::Vf64,
+    mut w: S::Vf64,
+    s: &HeteroTerrainSettings,
+) -> S::Vf64 {
+    let lac = S::set1_pd(s.lacunarity as f64);
+    let lac_h = S::set1_pd((s.lacunarity as f64).powf(-s.h as f64));
+    let offset = S::set1_pd(s.offset as f64);
+    let seed = s.dim.seed as i64;
+    let scale = S::set1_pd(SIMPLEX_4D_SCALE);
+    let mut result = S::add_pd(offset, S::mul_pd(simplex_4d::<S>(x, y, z, w, seed), scale));
+    let mut pwr = lac_h;
+
+    for _ in 1..s.octaves {
+        x = S::mul_pd(x, lac);
+        y = S::mul_pd(y, lac);
+        z = S::mul_pd(z, lac);
+        w = S::mul_pd(w, lac);
+        let increment = S::mul_pd(
+            S::add_pd(S::mul_pd(simplex_4d::<S>(x, y, z, w, seed), scale), offset),
+            pwr,
+        );
+        result = S::add_pd(result, S::mul_pd(increment, result));
+        pwr = S::mul_pd(pwr, lac_h);
+    }
+
+    result
+}
+
+/// Musgrave's ridged multifractal, squared `offset - |n|` ridges with each
+/// octave weighted by the one before it
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub unsafe fn ridged_multifractal_4d<S: Simd>(
+    mut x: S::Vf64,
+    mut y: S::Vf64,
+    mut z: S::Vf64,
+    mut w: S::Vf64,
+    s: &RidgedMultifractalSettings,
+) -> S::Vf64 {
+    let lac = S::set1_pd(s.lacunarity as f64);
+    let lac_h = S::set1_pd((s.lacunarity as f64).powf(-s.h as f64));
+    let offset = S::set1_pd(s.offset as f64);
+    let gain = S::set1_pd(s.gain as f64);
+    let seed = s.dim.seed as i64;
+    let scale = S::set1_pd(SIMPLEX_4D_SCALE);
+    let one = S::set1_pd(1.0);
+    let mut signal = S::sub_pd(
+        offset,
+        S::abs_pd(S::mul_pd(simplex_4d::<S>(x, y, z, w, seed), scale)),
+    );
+    signal = S::mul_pd(signal, signal);
+    let mut result = signal;
+    let mut pwr = lac_h;
+
+    for _ in 1..s.octaves {
+        x = S::mul_pd(x, lac);
+        y = S::mul_pd(y, lac);
+        z = S::mul_pd(z, lac);
+        w = S::mul_pd(w, lac);
+        let weight = S::min_pd(S::max_pd(S::mul_pd(signal, gain), S::setzero_pd()), one);
+        signal = S::sub_pd(
+            offset,
+            S::abs_pd(S::mul_pd(simplex_4d::<S>(x, y, z, w, seed), scale)),
+        );
+        signal = S::mul_pd(S::mul_pd(signal, signal), weight);
+        result = S::add_pd(result, S::mul_pd(signal, pwr));
+        pwr = S::mul_pd(pwr, lac_h);
+    }
+
+    result
+}
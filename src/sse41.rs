@@ -13,6 +13,30 @@ use std::arch::x86::*;
 #[cfg(target_arch = "x86_64")]
 use std::arch::x86_64::*;
 use std::f32;
+use std::ops::Range;
+
+/// Get a single value of 1d cellular/voroni noise
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn cellular_1d(
+    x: __m128,
+    distance_function: CellDistanceFunction,
+    return_type: CellReturnType,
+    jitter: __m128,
+    seed: i32,
+) -> __m128 {
+    cellular::cellular_1d::<Sse41>(
+        F32x4(x),
+        distance_function,
+        return_type,
+        F32x4(jitter),
+        seed,
+    )
+    .0
+}
 
 /// Get a single value of 2d cellular/voroni noise
 #[target_feature(enable = "sse4.1")]
@@ -58,6 +82,35 @@ pub unsafe fn cellular_3d(
     .0
 }
 
+/// Get a single value of 4d cellular/voroni noise
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn cellular_4d(
+    x: __m128,
+    y: __m128,
+    z: __m128,
+    w: __m128,
+    distance_function: CellDistanceFunction,
+    return_type: CellReturnType,
+    jitter: __m128,
+    seed: i32,
+) -> __m128 {
+    cellular::cellular_4d::<Sse41>(
+        F32x4(x),
+        F32x4(y),
+        F32x4(z),
+        F32x4(w),
+        distance_function,
+        return_type,
+        F32x4(jitter),
+        seed,
+    )
+    .0
+}
+
 /// Get a single value of 2d cellular/voroni noise
 #[target_feature(enable = "sse4.1")]
 pub unsafe fn cellular_2d_f64(
@@ -109,6 +162,27 @@ pub unsafe fn simplex_1d(x: __m128, seed: i32) -> __m128 {
     simplex::simplex_1d::<Sse41>(F32x4(x), seed).0
 }
 
+/// Get a single value of 1d perlin noise, scaled to roughly
+/// -1 to 1.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn perlin_1d(x: __m128, seed: i32) -> __m128 {
+    perlin::perlin_1d::<Sse41>(F32x4(x), seed).0
+}
+
+/// Get a single value of 1d value noise, from -1 to 1.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn value_1d(x: __m128, interpolation: Interpolation, seed: i32) -> __m128 {
+    value::value_1d::<Sse41>(F32x4(x), interpolation, seed).0
+}
+
 /// Get a single value of 1d fractal brownian motion.
 #[target_feature(enable = "sse4.1")]
 pub unsafe fn fbm_1d(
@@ -145,6 +219,22 @@ pub unsafe fn turbulence_1d(
     simplex::turbulence_1d::<Sse41>(F32x4(x), F32x4(lacunarity), F32x4(gain), octaves, seed).0
 }
 
+/// Get a single value of 1d billow noise.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn billow_1d(
+    x: __m128,
+    lacunarity: __m128,
+    gain: __m128,
+    octaves: u8,
+    seed: i32,
+) -> __m128 {
+    simplex::billow_1d::<Sse41>(F32x4(x), F32x4(lacunarity), F32x4(gain), octaves, seed).0
+}
+
 /// Get a single value of 1d simplex noise, results
 /// are not scaled.
 #[target_feature(enable = "sse4.1")]
@@ -188,6 +278,22 @@ pub unsafe fn turbulence_1d_f64(
     simplex_64::turbulence_1d::<Sse41>(F64x2(x), F64x2(lacunarity), F64x2(gain), octaves, seed).0
 }
 
+/// Get a single value of 1d billow noise.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn billow_1d_f64(
+    x: __m128d,
+    lacunarity: __m128d,
+    gain: __m128d,
+    octaves: u8,
+    seed: i64,
+) -> __m128d {
+    simplex_64::billow_1d::<Sse41>(F64x2(x), F64x2(lacunarity), F64x2(gain), octaves, seed).0
+}
+
 /// Gets a width sized block of 1d noise, unscaled.
 /// `start_x` can be used to provide an offset in the
 /// coordinates. Results are unscaled, 'min' and 'max' noise values
@@ -210,6 +316,30 @@ pub unsafe fn get_1d_scaled_noise(noise_type: &NoiseType) -> Vec<f32> {
     noise
 }
 
+/// Evaluates 1d noise at the points `xs`, rather than on a
+/// grid. Coordinates are scaled by the frequency of the settings, results are
+/// unscaled, with the min and max value.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn get_1d_noise_at(noise_type: &NoiseType, xs: &[f32]) -> (Vec<f32>, f32, f32) {
+    noise_helpers::get_1d_noise_at::<Sse41>(noise_type, xs)
+}
+
+/// Fills `out`, which must hold exactly one value per sample, with a block of
+/// 1d noise instead of allocating. Results are unscaled, the min and max
+/// value are returned.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn fill_1d_noise(noise_type: &NoiseType, out: &mut [f32]) -> (f32, f32) {
+    noise_helpers::fill_1d_noise::<Sse41>(noise_type, out)
+}
+
 /// Get a single value of 2d simplex noise, results
 /// are not scaled.
 #[target_feature(enable = "sse4.1")]
@@ -217,6 +347,61 @@ pub unsafe fn simplex_2d(x: __m128, y: __m128, seed: i32) -> __m128 {
     simplex::simplex_2d::<Sse41>(F32x4(x), F32x4(y), seed).0
 }
 
+/// Get a single value of 2d simplex noise along with its partial
+/// derivatives `[d/dx, d/dy]`, results are not scaled.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn simplex_2d_deriv(x: __m128, y: __m128, seed: i32) -> (__m128, [__m128; 2]) {
+    let (value, d) = simplex::simplex_2d_deriv::<Sse41>(F32x4(x), F32x4(y), seed);
+    (value.0, [d[0].0, d[1].0])
+}
+
+/// Get a single value of 2d perlin noise, scaled to roughly
+/// -1 to 1.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn perlin_2d(x: __m128, y: __m128, seed: i32) -> __m128 {
+    perlin::perlin_2d::<Sse41>(F32x4(x), F32x4(y), seed).0
+}
+
+/// Get a single value of 2d value noise, from -1 to 1.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn value_2d(x: __m128, y: __m128, interpolation: Interpolation, seed: i32) -> __m128 {
+    value::value_2d::<Sse41>(F32x4(x), F32x4(y), interpolation, seed).0
+}
+
+/// Get a single value of 2d OpenSimplex2 noise, results
+/// are not scaled.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn opensimplex2_2d(x: __m128, y: __m128, seed: i32) -> __m128 {
+    opensimplex::opensimplex2_2d::<Sse41>(F32x4(x), F32x4(y), seed).0
+}
+
+/// Get a single value of 2d OpenSimplex2S noise, results
+/// are not scaled.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn opensimplex2s_2d(x: __m128, y: __m128, seed: i32) -> __m128 {
+    opensimplex::opensimplex2s_2d::<Sse41>(F32x4(x), F32x4(y), seed).0
+}
+
 /// Get a single value of 2d fractal brownian motion.
 #[target_feature(enable = "sse4.1")]
 pub unsafe fn fbm_2d(
@@ -230,6 +415,46 @@ pub unsafe fn fbm_2d(
     simplex::fbm_2d::<Sse41>(F32x4(x), F32x4(y), F32x4(lac), F32x4(gain), octaves, seed).0
 }
 
+/// Get a single value of 2d fractal brownian motion along with its partial
+/// derivatives `[d/dx, d/dy]`.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn fbm_2d_deriv(
+    x: __m128,
+    y: __m128,
+    lac: __m128,
+    gain: __m128,
+    octaves: u8,
+    seed: i32,
+) -> (__m128, [__m128; 2]) {
+    let (value, d) =
+        simplex::fbm_2d_deriv::<Sse41>(F32x4(x), F32x4(y), F32x4(lac), F32x4(gain), octaves, seed);
+    (value.0, [d[0].0, d[1].0])
+}
+
+/// Get a single 2d curl noise velocity, the curl of a simplex fbm
+/// potential. Results are unscaled.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn curl_2d(
+    x: __m128,
+    y: __m128,
+    lac: __m128,
+    gain: __m128,
+    octaves: u8,
+    seed: i32,
+) -> (__m128, __m128) {
+    let (vx, vy) =
+        curl::curl_2d::<Sse41>(F32x4(x), F32x4(y), F32x4(lac), F32x4(gain), octaves, seed);
+    (vx.0, vy.0)
+}
+
 /// Get a single value of 2d ridge noise.
 #[target_feature(enable = "sse4.1")]
 pub unsafe fn ridge_2d(
@@ -255,6 +480,23 @@ pub unsafe fn turbulence_2d(
     simplex::turbulence_2d::<Sse41>(F32x4(x), F32x4(y), F32x4(lac), F32x4(gain), octaves, seed).0
 }
 
+/// Get a single value of 2d billow noise.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn billow_2d(
+    x: __m128,
+    y: __m128,
+    lac: __m128,
+    gain: __m128,
+    octaves: u8,
+    seed: i32,
+) -> __m128 {
+    simplex::billow_2d::<Sse41>(F32x4(x), F32x4(y), F32x4(lac), F32x4(gain), octaves, seed).0
+}
+
 /// Get a single value of 2d simplex noise, results
 /// are not scaled.
 #[target_feature(enable = "sse4.1")]
@@ -300,6 +542,23 @@ pub unsafe fn turbulence_2d_f64(
     simplex_64::turbulence_2d::<Sse41>(F64x2(x), F64x2(y), F64x2(lac), F64x2(gain), octaves, seed).0
 }
 
+/// Get a single value of 2d billow noise.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn billow_2d_f64(
+    x: __m128d,
+    y: __m128d,
+    lac: __m128d,
+    gain: __m128d,
+    octaves: u8,
+    seed: i64,
+) -> __m128d {
+    simplex_64::billow_2d::<Sse41>(F64x2(x), F64x2(y), F64x2(lac), F64x2(gain), octaves, seed).0
+}
+
 /// Gets a width X height sized block of 2d noise, unscaled.
 /// `start_x` and `start_y` can be used to provide an offset in the
 /// coordinates. Results are unscaled, 'min' and 'max' noise values
@@ -307,7 +566,7 @@ pub unsafe fn turbulence_2d_f64(
 /// in a single pass.
 #[target_feature(enable = "sse4.1")]
 pub unsafe fn get_2d_noise(noise_type: &NoiseType) -> (Vec<f32>, f32, f32) {
-    noise_helpers::get_2d_noise::<Sse41>(noise_type)
+    noise_helpers::get_2d_noise(noise_type, fill_2d_noise_rows)
 }
 
 /// Gets a width X height sized block of scaled 2d noise
@@ -322,6 +581,68 @@ pub unsafe fn get_2d_scaled_noise(noise_type: &NoiseType) -> Vec<f32> {
     noise
 }
 
+/// Evaluates 2d noise at the points `xs` and `ys`, rather than on a
+/// grid. Coordinates are scaled by the frequency of the settings, results are
+/// unscaled, with the min and max value.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn get_2d_noise_at(
+    noise_type: &NoiseType,
+    xs: &[f32],
+    ys: &[f32],
+) -> (Vec<f32>, f32, f32) {
+    noise_helpers::get_2d_noise_at::<Sse41>(noise_type, xs, ys)
+}
+
+/// Fills `out`, which must hold exactly one value per sample, with a block of
+/// 2d noise instead of allocating. Results are unscaled, the min and max
+/// value are returned.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn fill_2d_noise(noise_type: &NoiseType, out: &mut [f32]) -> (f32, f32) {
+    noise_helpers::fill_2d_noise(noise_type, out, fill_2d_noise_rows)
+}
+
+/// Like `fill_2d_noise`, but rows start `pitch` values apart, to write into
+/// part of a larger image. Values between rows are left alone.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn fill_2d_noise_pitched(
+    noise_type: &NoiseType,
+    out: &mut [f32],
+    pitch: usize,
+) -> (f32, f32) {
+    noise_helpers::fill_2d_noise_pitched(noise_type, out, pitch, fill_2d_noise_rows)
+}
+
+/// Fills the rows `rows` of a block of 2d noise, counted across the whole
+/// block, into `out`, which starts at the first of them, with rows `pitch`
+/// values apart. Rows come out exactly as they do in a whole block, so a
+/// block can be split between threads. Results are unscaled, the min and max
+/// value are returned.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn fill_2d_noise_rows(
+    noise_type: &NoiseType,
+    out: &mut [f32],
+    pitch: usize,
+    rows: Range<usize>,
+) -> (f32, f32) {
+    noise_helpers::fill_2d_noise_rows::<Sse41>(noise_type, out, pitch, rows)
+}
+
 /// Get a single value of 3d simplex noise, results
 /// are not scaled.
 #[target_feature(enable = "sse4.1")]
@@ -329,6 +650,72 @@ pub unsafe fn simplex_3d(x: __m128, y: __m128, z: __m128, seed: i32) -> __m128 {
     simplex::simplex_3d::<Sse41>(F32x4(x), F32x4(y), F32x4(z), seed).0
 }
 
+/// Get a single value of 3d simplex noise along with its partial
+/// derivatives `[d/dx, d/dy, d/dz]`, results are not scaled.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn simplex_3d_deriv(
+    x: __m128,
+    y: __m128,
+    z: __m128,
+    seed: i32,
+) -> (__m128, [__m128; 3]) {
+    let (value, d) = simplex::simplex_3d_deriv::<Sse41>(F32x4(x), F32x4(y), F32x4(z), seed);
+    (value.0, [d[0].0, d[1].0, d[2].0])
+}
+
+/// Get a single value of 3d perlin noise, scaled to roughly
+/// -1 to 1.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn perlin_3d(x: __m128, y: __m128, z: __m128, seed: i32) -> __m128 {
+    perlin::perlin_3d::<Sse41>(F32x4(x), F32x4(y), F32x4(z), seed).0
+}
+
+/// Get a single value of 3d value noise, from -1 to 1.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn value_3d(
+    x: __m128,
+    y: __m128,
+    z: __m128,
+    interpolation: Interpolation,
+    seed: i32,
+) -> __m128 {
+    value::value_3d::<Sse41>(F32x4(x), F32x4(y), F32x4(z), interpolation, seed).0
+}
+
+/// Get a single value of 3d OpenSimplex2 noise, results
+/// are not scaled.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn opensimplex2_3d(x: __m128, y: __m128, z: __m128, seed: i32) -> __m128 {
+    opensimplex::opensimplex2_3d::<Sse41>(F32x4(x), F32x4(y), F32x4(z), seed).0
+}
+
+/// Get a single value of 3d OpenSimplex2S noise, results
+/// are not scaled.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn opensimplex2s_3d(x: __m128, y: __m128, z: __m128, seed: i32) -> __m128 {
+    opensimplex::opensimplex2s_3d::<Sse41>(F32x4(x), F32x4(y), F32x4(z), seed).0
+}
+
 /// Get a single value of 3d fractal brownian motion.
 #[target_feature(enable = "sse4.1")]
 pub unsafe fn fbm_3d(
@@ -352,6 +739,62 @@ pub unsafe fn fbm_3d(
     .0
 }
 
+/// Get a single value of 3d fractal brownian motion along with its partial
+/// derivatives `[d/dx, d/dy, d/dz]`.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn fbm_3d_deriv(
+    x: __m128,
+    y: __m128,
+    z: __m128,
+    lac: __m128,
+    gain: __m128,
+    octaves: u8,
+    seed: i32,
+) -> (__m128, [__m128; 3]) {
+    let (value, d) = simplex::fbm_3d_deriv::<Sse41>(
+        F32x4(x),
+        F32x4(y),
+        F32x4(z),
+        F32x4(lac),
+        F32x4(gain),
+        octaves,
+        seed,
+    );
+    (value.0, [d[0].0, d[1].0, d[2].0])
+}
+
+/// Get a single 3d curl noise velocity, the curl of a vector of three simplex
+/// fbm potentials. Results are unscaled.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn curl_3d(
+    x: __m128,
+    y: __m128,
+    z: __m128,
+    lac: __m128,
+    gain: __m128,
+    octaves: u8,
+    seed: i32,
+) -> (__m128, __m128, __m128) {
+    let (vx, vy, vz) = curl::curl_3d::<Sse41>(
+        F32x4(x),
+        F32x4(y),
+        F32x4(z),
+        F32x4(lac),
+        F32x4(gain),
+        octaves,
+        seed,
+    );
+    (vx.0, vy.0, vz.0)
+}
+
 /// Get a single value of 3d ridge noise.
 #[target_feature(enable = "sse4.1")]
 pub unsafe fn ridge_3d(
@@ -398,6 +841,33 @@ pub unsafe fn turbulence_3d(
     .0
 }
 
+/// Get a single value of 3d billow noise.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn billow_3d(
+    x: __m128,
+    y: __m128,
+    z: __m128,
+    lac: __m128,
+    gain: __m128,
+    octaves: u8,
+    seed: i32,
+) -> __m128 {
+    simplex::billow_3d::<Sse41>(
+        F32x4(x),
+        F32x4(y),
+        F32x4(z),
+        F32x4(lac),
+        F32x4(gain),
+        octaves,
+        seed,
+    )
+    .0
+}
+
 /// Get a single value of 3d simplex_64 noise, results
 /// are not scaled.
 #[target_feature(enable = "sse4.1")]
@@ -474,6 +944,33 @@ pub unsafe fn turbulence_3d_f64(
     .0
 }
 
+/// Get a single value of 3d billow noise.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn billow_3d_f64(
+    x: __m128d,
+    y: __m128d,
+    z: __m128d,
+    lac: __m128d,
+    gain: __m128d,
+    octaves: u8,
+    seed: i64,
+) -> __m128d {
+    simplex_64::billow_3d::<Sse41>(
+        F64x2(x),
+        F64x2(y),
+        F64x2(z),
+        F64x2(lac),
+        F64x2(gain),
+        octaves,
+        seed,
+    )
+    .0
+}
+
 /// Gets a width X height X depth sized block of 3d noise, unscaled,
 /// `start_x`,`start_y` and `start_z` can be used to provide an offset in the
 /// coordinates. Results are unscaled, 'min' and 'max' noise values
@@ -481,7 +978,7 @@ pub unsafe fn turbulence_3d_f64(
 /// in a single pass.
 #[target_feature(enable = "sse4.1")]
 pub unsafe fn get_3d_noise(noise_type: &NoiseType) -> (Vec<f32>, f32, f32) {
-    noise_helpers::get_3d_noise::<Sse41>(noise_type)
+    noise_helpers::get_3d_noise(noise_type, fill_3d_noise_rows)
 }
 
 /// Gets a width X height X depth sized block of scaled 3d noise
@@ -496,12 +993,145 @@ pub unsafe fn get_3d_scaled_noise(noise_type: &NoiseType) -> Vec<f32> {
     noise
 }
 
+/// Evaluates 3d noise at the points `xs`, `ys` and `zs`, rather than on a
+/// grid. Coordinates are scaled by the frequency of the settings, results are
+/// unscaled, with the min and max value.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn get_3d_noise_at(
+    noise_type: &NoiseType,
+    xs: &[f32],
+    ys: &[f32],
+    zs: &[f32],
+) -> (Vec<f32>, f32, f32) {
+    noise_helpers::get_3d_noise_at::<Sse41>(noise_type, xs, ys, zs)
+}
+
+/// Fills `out`, which must hold exactly one value per sample, with a block of
+/// 3d noise instead of allocating. Results are unscaled, the min and max
+/// value are returned.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn fill_3d_noise(noise_type: &NoiseType, out: &mut [f32]) -> (f32, f32) {
+    noise_helpers::fill_3d_noise(noise_type, out, fill_3d_noise_rows)
+}
+
+/// Like `fill_3d_noise`, but rows start `pitch` values apart, to write into
+/// part of a larger image. Values between rows are left alone.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn fill_3d_noise_pitched(
+    noise_type: &NoiseType,
+    out: &mut [f32],
+    pitch: usize,
+) -> (f32, f32) {
+    noise_helpers::fill_3d_noise_pitched(noise_type, out, pitch, fill_3d_noise_rows)
+}
+
+/// Fills the rows `rows` of a block of 3d noise, counted across the whole
+/// block, into `out`, which starts at the first of them, with rows `pitch`
+/// values apart. Rows come out exactly as they do in a whole block, so a
+/// block can be split between threads. Results are unscaled, the min and max
+/// value are returned.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn fill_3d_noise_rows(
+    noise_type: &NoiseType,
+    out: &mut [f32],
+    pitch: usize,
+    rows: Range<usize>,
+) -> (f32, f32) {
+    noise_helpers::fill_3d_noise_rows::<Sse41>(noise_type, out, pitch, rows)
+}
+
 /// Get a single value of 4d simplex noise, results
 /// are not scaled.
 #[target_feature(enable = "sse4.1")]
 pub unsafe fn simplex_4d(x: __m128, y: __m128, z: __m128, w: __m128, seed: i32) -> __m128 {
     simplex::simplex_4d::<Sse41>(F32x4(x), F32x4(y), F32x4(z), F32x4(w), seed).0
 }
+
+/// Get a single value of 4d simplex noise along with its partial
+/// derivatives `[d/dx, d/dy, d/dz, d/dw]`, results are not scaled.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn simplex_4d_deriv(
+    x: __m128,
+    y: __m128,
+    z: __m128,
+    w: __m128,
+    seed: i32,
+) -> (__m128, [__m128; 4]) {
+    let (value, d) =
+        simplex::simplex_4d_deriv::<Sse41>(F32x4(x), F32x4(y), F32x4(z), F32x4(w), seed);
+    (value.0, [d[0].0, d[1].0, d[2].0, d[3].0])
+}
+
+/// Get a single value of 4d perlin noise, scaled to roughly
+/// -1 to 1.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn perlin_4d(x: __m128, y: __m128, z: __m128, w: __m128, seed: i32) -> __m128 {
+    perlin::perlin_4d::<Sse41>(F32x4(x), F32x4(y), F32x4(z), F32x4(w), seed).0
+}
+
+/// Get a single value of 4d value noise, from -1 to 1.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn value_4d(
+    x: __m128,
+    y: __m128,
+    z: __m128,
+    w: __m128,
+    interpolation: Interpolation,
+    seed: i32,
+) -> __m128 {
+    value::value_4d::<Sse41>(F32x4(x), F32x4(y), F32x4(z), F32x4(w), interpolation, seed).0
+}
+
+/// Get a single value of 4d OpenSimplex2 noise, results
+/// are not scaled.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn opensimplex2_4d(x: __m128, y: __m128, z: __m128, w: __m128, seed: i32) -> __m128 {
+    opensimplex::opensimplex2_4d::<Sse41>(F32x4(x), F32x4(y), F32x4(z), F32x4(w), seed).0
+}
+
+/// Get a single value of 4d OpenSimplex2S noise, results
+/// are not scaled.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn opensimplex2s_4d(x: __m128, y: __m128, z: __m128, w: __m128, seed: i32) -> __m128 {
+    opensimplex::opensimplex2s_4d::<Sse41>(F32x4(x), F32x4(y), F32x4(z), F32x4(w), seed).0
+}
+
 /// Get a single value of 4d fractal brownian motion.
 #[target_feature(enable = "sse4.1")]
 pub unsafe fn fbm_4d(
@@ -527,6 +1157,36 @@ pub unsafe fn fbm_4d(
     .0
 }
 
+/// Get a single value of 4d fractal brownian motion along with its partial
+/// derivatives `[d/dx, d/dy, d/dz, d/dw]`.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn fbm_4d_deriv(
+    x: __m128,
+    y: __m128,
+    z: __m128,
+    w: __m128,
+    lac: __m128,
+    gain: __m128,
+    octaves: u8,
+    seed: i32,
+) -> (__m128, [__m128; 4]) {
+    let (value, d) = simplex::fbm_4d_deriv::<Sse41>(
+        F32x4(x),
+        F32x4(y),
+        F32x4(z),
+        F32x4(w),
+        F32x4(lac),
+        F32x4(gain),
+        octaves,
+        seed,
+    );
+    (value.0, [d[0].0, d[1].0, d[2].0, d[3].0])
+}
+
 /// Get a single value of 4d ridge noise.
 #[target_feature(enable = "sse4.1")]
 pub unsafe fn ridge_4d(
@@ -577,6 +1237,35 @@ pub unsafe fn turbulence_4d(
     .0
 }
 
+/// Get a single value of 4d billow noise.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn billow_4d(
+    x: __m128,
+    y: __m128,
+    z: __m128,
+    w: __m128,
+    lac: __m128,
+    gain: __m128,
+    octaves: u8,
+    seed: i32,
+) -> __m128 {
+    simplex::billow_4d::<Sse41>(
+        F32x4(x),
+        F32x4(y),
+        F32x4(z),
+        F32x4(w),
+        F32x4(lac),
+        F32x4(gain),
+        octaves,
+        seed,
+    )
+    .0
+}
+
 /// Get a single value of 4d simplex noise, results
 /// are not scaled.
 #[target_feature(enable = "sse4.1")]
@@ -658,6 +1347,35 @@ pub unsafe fn turbulence_4d_f64(
     .0
 }
 
+/// Get a single value of 4d billow noise.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn billow_4d_f64(
+    x: __m128d,
+    y: __m128d,
+    z: __m128d,
+    w: __m128d,
+    lac: __m128d,
+    gain: __m128d,
+    octaves: u8,
+    seed: i64,
+) -> __m128d {
+    simplex_64::billow_4d::<Sse41>(
+        F64x2(x),
+        F64x2(y),
+        F64x2(z),
+        F64x2(w),
+        F64x2(lac),
+        F64x2(gain),
+        octaves,
+        seed,
+    )
+    .0
+}
+
 /// Gets a width X height X depth x time sized block of 4d noise, unscaled,
 /// `start_*` can be used to provide an offset in the
 /// coordinates. Results are unscaled, 'min' and 'max' noise values
@@ -665,7 +1383,7 @@ pub unsafe fn turbulence_4d_f64(
 /// in a single pass.
 #[target_feature(enable = "sse4.1")]
 pub unsafe fn get_4d_noise(noise_type: &NoiseType) -> (Vec<f32>, f32, f32) {
-    noise_helpers::get_4d_noise::<Sse41>(noise_type)
+    noise_helpers::get_4d_noise(noise_type, fill_4d_noise_rows)
 }
 
 /// Gets a width X height X depth X time sized block of scaled 4d noise
@@ -679,3 +1397,89 @@ pub unsafe fn get_4d_scaled_noise(noise_type: &NoiseType) -> Vec<f32> {
     scale_noise::<Sse41>(dim.min, dim.max, min, max, &mut noise);
     noise
 }
+
+/// Evaluates 4d noise at the points `xs`, `ys`, `zs` and `ws`, rather than on a
+/// grid. Coordinates are scaled by the frequency of the settings, results are
+/// unscaled, with the min and max value.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn get_4d_noise_at(
+    noise_type: &NoiseType,
+    xs: &[f32],
+    ys: &[f32],
+    zs: &[f32],
+    ws: &[f32],
+) -> (Vec<f32>, f32, f32) {
+    noise_helpers::get_4d_noise_at::<Sse41>(noise_type, xs, ys, zs, ws)
+}
+
+/// Fills `out`, which must hold exactly one value per sample, with a block of
+/// 4d noise instead of allocating. Results are unscaled, the min and max
+/// value are returned.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn fill_4d_noise(noise_type: &NoiseType, out: &mut [f32]) -> (f32, f32) {
+    noise_helpers::fill_4d_noise(noise_type, out, fill_4d_noise_rows)
+}
+
+/// Like `fill_4d_noise`, but rows start `pitch` values apart, to write into
+/// part of a larger image. Values between rows are left alone.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn fill_4d_noise_pitched(
+    noise_type: &NoiseType,
+    out: &mut [f32],
+    pitch: usize,
+) -> (f32, f32) {
+    noise_helpers::fill_4d_noise_pitched(noise_type, out, pitch, fill_4d_noise_rows)
+}
+
+/// Fills the rows `rows` of a block of 4d noise, counted across the whole
+/// block, into `out`, which starts at the first of them, with rows `pitch`
+/// values apart. Rows come out exactly as they do in a whole block, so a
+/// block can be split between threads. Results are unscaled, the min and max
+/// value are returned.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn fill_4d_noise_rows(
+    noise_type: &NoiseType,
+    out: &mut [f32],
+    pitch: usize,
+    rows: Range<usize>,
+) -> (f32, f32) {
+    noise_helpers::fill_4d_noise_rows::<Sse41>(noise_type, out, pitch, rows)
+}
+
+/// Gets all four sorted distances, F1 to F4, of a block of cellular2 noise.
+/// Results are unscaled.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn get_cellular2_distances(settings: &Cellular2Settings) -> [Vec<f32>; 4] {
+    noise_helpers::get_cellular2_distances::<Sse41>(settings)
+}
+
+/// Gets a block of 2d or 3d curl noise, see `CurlSettings::generate`.
+/// Results are unscaled.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.1.
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn get_curl(settings: &CurlSettings) -> Vec<f32> {
+    noise_helpers::get_curl::<Sse41>(settings)
+}
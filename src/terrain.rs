@@ -0,0 +1,331 @@
+//! Terrain fractals driven by the analytic derivatives of simplex noise.
+//!
+//! `iq_fbm` is the derivative damped fbm of Inigo Quilez, where octaves are
+//! suppressed on steep slopes, giving smooth valleys and eroded looking
+//! ridges. `swiss` and `jordan` are the Swiss and Jordan turbulence of Giliam
+//! de Carpentier, which warp each octave's coordinates and damp its amplitude
+//! using the derivatives summed over the previous octaves.
+//!
+//! These fractals are not linear in the noise value, so each octave uses
+//! simplex noise scaled to roughly -1 to 1, and the warp and damping strengths
+//! are relative to that range.
+use self::simdeez::*;
+use super::*;
+use crate::simplex::{simplex_2d_deriv, simplex_3d_deriv, SIMPLEX_2D_SCALE, SIMPLEX_3D_SCALE};
+
+#[inline(always)]
+unsafe fn noise_2d<S: Simd>(x: S::Vf32, y: S::Vf32, seed: i32) -> (S::Vf32, [S::Vf32; 2]) {
+    let (value, d) = simplex_2d_deriv::<S>(x, y, seed);
+    let scale = S::set1_ps(SIMPLEX_2D_SCALE);
+    (
+        S::mul_ps(value, scale),
+        [S::mul_ps(d[0], scale), S::mul_ps(d[1], scale)],
+    )
+}
+
+#[inline(always)]
+unsafe fn noise_3d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    seed: i32,
+) -> (S::Vf32, [S::Vf32; 3]) {
+    let (value, d) = simplex_3d_deriv::<S>(x, y, z, seed);
+    let scale = S::set1_ps(SIMPLEX_3D_SCALE);
+    (
+        S::mul_ps(value, scale),
+        [
+            S::mul_ps(d[0], scale),
+            S::mul_ps(d[1], scale),
+            S::mul_ps(d[2], scale),
+        ],
+    )
+}
+
+/// `1 / (1 + damping * |d|^2)`
+#[inline(always)]
+unsafe fn slope_damping<S: Simd>(damping: S::Vf32, slope: S::Vf32) -> S::Vf32 {
+    S::div_ps(
+        S::set1_ps(1.0),
+        S::fmadd_ps(damping, slope, S::set1_ps(1.0)),
+    )
+}
+
+/// 2d derivative damped fbm, each octave damped by the slope of the ones
+/// before it
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub unsafe fn iq_fbm_2d<S: Simd>(mut x: S::Vf32, mut y: S::Vf32, s: &IqFbmSettings) -> S::Vf32 {
+    let lac = S::set1_ps(s.lacunarity);
+    let gain = S::set1_ps(s.gain);
+    let damping = S::set1_ps(s.damping);
+    let seed = s.dim.seed;
+    let mut result = S::setzero_ps();
+    let mut amp = S::set1_ps(1.0);
+    let mut dx = S::setzero_ps();
+    let mut dy = S::setzero_ps();
+
+    for _ in 0..s.octaves {
+        let (n, d) = noise_2d::<S>(x, y, seed);
+        dx = S::add_ps(dx, d[0]);
+        dy = S::add_ps(dy, d[1]);
+        let slope = S::fmadd_ps(dx, dx, S::mul_ps(dy, dy));
+        result = S::fmadd_ps(
+            S::mul_ps(amp, n),
+            slope_damping::<S>(damping, slope),
+            result,
+        );
+        x = S::mul_ps(x, lac);
+        y = S::mul_ps(y, lac);
+        amp = S::mul_ps(amp, gain);
+    }
+
+    result
+}
+
+/// 3d derivative damped fbm, each octave damped by the slope of the ones
+/// before it
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub unsafe fn iq_fbm_3d<S: Simd>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    mut z: S::Vf32,
+    s: &IqFbmSettings,
+) -> S::Vf32 {
+    let lac = S::set1_ps(s.lacunarity);
+    let gain = S::set1_ps(s.gain);
+    let damping = S::set1_ps(s.damping);
+    let seed = s.dim.seed;
+    let mut result = S::setzero_ps();
+    let mut amp = S::set1_ps(1.0);
+    let mut dx = S::setzero_ps();
+    let mut dy = S::setzero_ps();
+    let mut dz = S::setzero_ps();
+
+    for _ in 0..s.octaves {
+        let (n, d) = noise_3d::<S>(x, y, z, seed);
+        dx = S::add_ps(dx, d[0]);
+        dy = S::add_ps(dy, d[1]);
+        dz = S::add_ps(dz, d[2]);
+        let slope = S::fmadd_ps(dx, dx, S::fmadd_ps(dy, dy, S::mul_ps(dz, dz)));
+        result = S::fmadd_ps(
+            S::mul_ps(amp, n),
+            slope_damping::<S>(damping, slope),
+            result,
+        );
+        x = S::mul_ps(x, lac);
+        y = S::mul_ps(y, lac);
+        z = S::mul_ps(z, lac);
+        amp = S::mul_ps(amp, gain);
+    }
+
+    result
+}
+
+/// 2d Swiss turbulence, ridged octaves warped and damped by the
+/// derivatives of the ones before them
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub unsafe fn swiss_2d<S: Simd>(x: S::Vf32, y: S::Vf32, s: &SwissSettings) -> S::Vf32 {
+    let lac = S::set1_ps(s.lacunarity);
+    let gain = S::set1_ps(s.gain);
+    let warp = S::set1_ps(s.warp);
+    let seed = s.dim.seed;
+    let one = S::set1_ps(1.0);
+    let mut result = S::setzero_ps();
+    let mut amp = one;
+    let mut freq = one;
+    let mut dx = S::setzero_ps();
+    let mut dy = S::setzero_ps();
+
+    for _ in 0..s.octaves {
+        let (n, d) = noise_2d::<S>(
+            S::mul_ps(S::fmadd_ps(warp, dx, x), freq),
+            S::mul_ps(S::fmadd_ps(warp, dy, y), freq),
+            seed,
+        );
+        result = S::fmadd_ps(amp, S::sub_ps(one, S::abs_ps(n)), result);
+        // Ridges pull the next octaves towards them, weighted by -n
+        let weight = S::mul_ps(S::sub_ps(S::setzero_ps(), amp), n);
+        dx = S::fmadd_ps(weight, d[0], dx);
+        dy = S::fmadd_ps(weight, d[1], dy);
+        freq = S::mul_ps(freq, lac);
+        let saturated = S::min_ps(S::max_ps(result, S::setzero_ps()), one);
+        amp = S::mul_ps(amp, S::mul_ps(gain, saturated));
+    }
+
+    result
+}
+
+/// 3d Swiss turbulence, ridged octaves warped and damped by the
+/// derivatives of the ones before them
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub unsafe fn swiss_3d<S: Simd>(x: S::Vf32, y: S::Vf32, z: S::Vf32, s: &SwissSettings) -> S::Vf32 {
+    let lac = S::set1_ps(s.lacunarity);
+    let gain = S::set1_ps(s.gain);
+    let warp = S::set1_ps(s.warp);
+    let seed = s.dim.seed;
+    let one = S::set1_ps(1.0);
+    let mut result = S::setzero_ps();
+    let mut amp = one;
+    let mut freq = one;
+    let mut dx = S::setzero_ps();
+    let mut dy = S::setzero_ps();
+    let mut dz = S::setzero_ps();
+
+    for _ in 0..s.octaves {
+        let (n, d) = noise_3d::<S>(
+            S::mul_ps(S::fmadd_ps(warp, dx, x), freq),
+            S::mul_ps(S::fmadd_ps(warp, dy, y), freq),
+            S::mul_ps(S::fmadd_ps(warp, dz, z), freq),
+            seed,
+        );
+        result = S::fmadd_ps(amp, S::sub_ps(one, S::abs_ps(n)), result);
+        let weight = S::mul_ps(S::sub_ps(S::setzero_ps(), amp), n);
+        dx = S::fmadd_ps(weight, d[0], dx);
+        dy = S::fmadd_ps(weight, d[1], dy);
+        dz = S::fmadd_ps(weight, d[2], dz);
+        freq = S::mul_ps(freq, lac);
+        let saturated = S::min_ps(S::max_ps(result, S::setzero_ps()), one);
+        amp = S::mul_ps(amp, S::mul_ps(gain, saturated));
+    }
+
+    result
+}
+
+/// 2d Jordan turbulence, squared ridged octaves warped and damped by the
+/// derivatives of the ones before them
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub unsafe fn jordan_2d<S: Simd>(x: S::Vf32, y: S::Vf32, s: &JordanSettings) -> S::Vf32 {
+    let lac = S::set1_ps(s.lacunarity);
+    let gain = S::set1_ps(s.gain);
+    let first_gain = S::set1_ps(s.first_gain);
+    let first_warp = S::set1_ps(s.first_warp);
+    let warp = S::set1_ps(s.warp);
+    let first_damping = S::set1_ps(s.first_damping);
+    let damping = S::set1_ps(s.damping);
+    let damping_scale = S::set1_ps(s.damping_scale);
+    let seed = s.dim.seed;
+    let one = S::set1_ps(1.0);
+    // The first octave is squared noise, its derivatives scaled by the noise
+    let (n, d) = noise_2d::<S>(x, y, seed);
+    let mut result = S::mul_ps(n, n);
+    let (d2x, d2y) = (S::mul_ps(d[0], n), S::mul_ps(d[1], n));
+    let mut warp_x = S::mul_ps(first_warp, d2x);
+    let mut warp_y = S::mul_ps(first_warp, d2y);
+    let mut damp_x = S::mul_ps(first_damping, d2x);
+    let mut damp_y = S::mul_ps(first_damping, d2y);
+
+    let mut amp = first_gain;
+    let mut freq = lac;
+    let mut damped_amp = S::mul_ps(amp, gain);
+
+    for _ in 1..s.octaves {
+        let (n, d) = noise_2d::<S>(
+            S::fmadd_ps(x, freq, warp_x),
+            S::fmadd_ps(y, freq, warp_y),
+            seed,
+        );
+        result = S::fmadd_ps(damped_amp, S::mul_ps(n, n), result);
+        let (d2x, d2y) = (S::mul_ps(d[0], n), S::mul_ps(d[1], n));
+        warp_x = S::fmadd_ps(warp, d2x, warp_x);
+        warp_y = S::fmadd_ps(warp, d2y, warp_y);
+        damp_x = S::fmadd_ps(damping, d2x, damp_x);
+        damp_y = S::fmadd_ps(damping, d2y, damp_y);
+        freq = S::mul_ps(freq, lac);
+        amp = S::mul_ps(amp, gain);
+        let slope = S::fmadd_ps(damp_x, damp_x, S::mul_ps(damp_y, damp_y));
+        damped_amp = S::mul_ps(
+            amp,
+            S::sub_ps(one, S::div_ps(damping_scale, S::add_ps(one, slope))),
+        );
+    }
+
+    result
+}
+
+/// 3d Jordan turbulence, squared ridged octaves warped and damped by the
+/// derivatives of the ones before them
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub unsafe fn jordan_3d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    s: &JordanSettings,
+) -> S::Vf32 {
+    let lac = S::set1_ps(s.lacunarity);
+    let gain = S::set1_ps(s.gain);
+    let first_gain = S::set1_ps(s.first_gain);
+    let first_warp = S::set1_ps(s.first_warp);
+    let warp = S::set1_ps(s.warp);
+    let first_damping = S::set1_ps(s.first_damping);
+    let damping = S::set1_ps(s.damping);
+    let damping_scale = S::set1_ps(s.damping_scale);
+    let seed = s.dim.seed;
+    let one = S::set1_ps(1.0);
+    let (n, d) = noise_3d::<S>(x, y, z, seed);
+    let mut result = S::mul_ps(n, n);
+    let (d2x, d2y, d2z) = (S::mul_ps(d[0], n), S::mul_ps(d[1], n), S::mul_ps(d[2], n));
+    let mut warp_x = S::mul_ps(first_warp, d2x);
+    let mut warp_y = S::mul_ps(first_warp, d2y);
+    let mut warp_z = S::mul_ps(first_warp, d2z);
+    let mut damp_x = S::mul_ps(first_damping, d2x);
+    let mut damp_y = S::mul_ps(first_damping, d2y);
+    let mut damp_z = S::mul_ps(first_damping, d2z);
+
+    let mut amp = first_gain;
+    let mut freq = lac;
+    let mut damped_amp = S::mul_ps(amp, gain);
+
+    for _ in 1..s.octaves {
+        let (n, d) = noise_3d::<S>(
+            S::fmadd_ps(x, freq, warp_x),
+            S::fmadd_ps(y, freq, warp_y),
+            S::fmadd_ps(z, freq, warp_z),
+            seed,
+        );
+        result = S::fmadd_ps(damped_amp, S::mul_ps(n, n), result);
+        let (d2x, d2y, d2z) = (S::mul_ps(d[0], n), S::mul_ps(d[1], n), S::mul_ps(d[2], n));
+        warp_x = S::fmadd_ps(warp, d2x, warp_x);
+        warp_y = S::fmadd_ps(warp, d2y, warp_y);
+        warp_z = S::fmadd_ps(warp, d2z, warp_z);
+        damp_x = S::fmadd_ps(damping, d2x, damp_x);
+        damp_y = S::fmadd_ps(damping, d2y, damp_y);
+        damp_z = S::fmadd_ps(damping, d2z, damp_z);
+        freq = S::mul_ps(freq, lac);
+        amp = S::mul_ps(amp, gain);
+        let slope = S::fmadd_ps(
+            damp_x,
+            damp_x,
+            S::fmadd_ps(damp_y, damp_y, S::mul_ps(damp_z, damp_z)),
+        );
+        damped_amp = S::mul_ps(
+            amp,
+            S::sub_ps(one, S::div_ps(damping_scale, S::add_ps(one, slope))),
+        );
+    }
+
+    result
+}
@@ -0,0 +1,190 @@
+use self::simdeez::*;
+use super::*;
+use crate::perlin::{fade, lerp};
+use std::f32;
+
+const X_PRIME: i32 = 1619;
+const Y_PRIME: i32 = 31337;
+const Z_PRIME: i32 = 6971;
+const W_PRIME: i32 = 1013;
+const HASH_2_FLOAT: f32 = 1.0 / 2147483648.0;
+
+/// Remaps the fractional lattice position with the chosen interpolation curve
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn interpolate<S: Simd>(t: S::Vf32, interpolation: Interpolation) -> S::Vf32 {
+    match interpolation {
+        Interpolation::Linear => t,
+        Interpolation::Cubic => S::mul_ps(
+            S::mul_ps(t, t),
+            S::fnmadd_ps(S::set1_ps(2.0), t, S::set1_ps(3.0)),
+        ),
+        Interpolation::Quintic => fade::<S>(t),
+    }
+}
+
+/// Hashes the premultiplied lattice coordinates to a value in [-1, 1]
+#[inline(always)]
+unsafe fn val_coord<S: Simd>(seed: i32, hash: S::Vi32) -> S::Vf32 {
+    let n = S::xor_epi32(hash, S::set1_epi32(seed));
+    let n = S::mullo_epi32(
+        S::mullo_epi32(S::mullo_epi32(n, n), n),
+        S::set1_epi32(60493),
+    );
+    S::mul_ps(S::cvtepi32_ps(n), S::set1_ps(HASH_2_FLOAT))
+}
+
+/// 1d value noise, random lattice values blended with `interpolation`, from
+/// -1 to 1
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn value_1d<S: Simd>(x: S::Vf32, interpolation: Interpolation, seed: i32) -> S::Vf32 {
+    let x0 = S::fast_floor_ps(x);
+    let i0 = S::mullo_epi32(S::cvtps_epi32(x0), S::set1_epi32(X_PRIME));
+    let i1 = S::add_epi32(i0, S::set1_epi32(X_PRIME));
+
+    let xs = interpolate::<S>(S::sub_ps(x, x0), interpolation);
+
+    lerp::<S>(xs, val_coord::<S>(seed, i0), val_coord::<S>(seed, i1))
+}
+
+/// 2d value noise, random lattice values blended with `interpolation`, from
+/// -1 to 1
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn value_2d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    interpolation: Interpolation,
+    seed: i32,
+) -> S::Vf32 {
+    let x0 = S::fast_floor_ps(x);
+    let y0 = S::fast_floor_ps(y);
+
+    let i0 = S::mullo_epi32(S::cvtps_epi32(x0), S::set1_epi32(X_PRIME));
+    let j0 = S::mullo_epi32(S::cvtps_epi32(y0), S::set1_epi32(Y_PRIME));
+    let i1 = S::add_epi32(i0, S::set1_epi32(X_PRIME));
+    let j1 = S::add_epi32(j0, S::set1_epi32(Y_PRIME));
+
+    let xs = interpolate::<S>(S::sub_ps(x, x0), interpolation);
+    let ys = interpolate::<S>(S::sub_ps(y, y0), interpolation);
+
+    let v00 = val_coord::<S>(seed, S::xor_epi32(i0, j0));
+    let v10 = val_coord::<S>(seed, S::xor_epi32(i1, j0));
+    let v01 = val_coord::<S>(seed, S::xor_epi32(i0, j1));
+    let v11 = val_coord::<S>(seed, S::xor_epi32(i1, j1));
+
+    lerp::<S>(ys, lerp::<S>(xs, v00, v10), lerp::<S>(xs, v01, v11))
+}
+
+/// 3d value noise, random lattice values blended with `interpolation`, from
+/// -1 to 1
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn value_3d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    interpolation: Interpolation,
+    seed: i32,
+) -> S::Vf32 {
+    let x0 = S::fast_floor_ps(x);
+    let y0 = S::fast_floor_ps(y);
+    let z0 = S::fast_floor_ps(z);
+
+    let i0 = S::mullo_epi32(S::cvtps_epi32(x0), S::set1_epi32(X_PRIME));
+    let j0 = S::mullo_epi32(S::cvtps_epi32(y0), S::set1_epi32(Y_PRIME));
+    let k0 = S::mullo_epi32(S::cvtps_epi32(z0), S::set1_epi32(Z_PRIME));
+    let i1 = S::add_epi32(i0, S::set1_epi32(X_PRIME));
+    let j1 = S::add_epi32(j0, S::set1_epi32(Y_PRIME));
+    let k1 = S::add_epi32(k0, S::set1_epi32(Z_PRIME));
+
+    let xs = interpolate::<S>(S::sub_ps(x, x0), interpolation);
+    let ys = interpolate::<S>(S::sub_ps(y, y0), interpolation);
+    let zs = interpolate::<S>(S::sub_ps(z, z0), interpolation);
+
+    let ij00 = S::xor_epi32(i0, j0);
+    let ij10 = S::xor_epi32(i1, j0);
+    let ij01 = S::xor_epi32(i0, j1);
+    let ij11 = S::xor_epi32(i1, j1);
+
+    let v000 = val_coord::<S>(seed, S::xor_epi32(ij00, k0));
+    let v100 = val_coord::<S>(seed, S::xor_epi32(ij10, k0));
+    let v010 = val_coord::<S>(seed, S::xor_epi32(ij01, k0));
+    let v110 = val_coord::<S>(seed, S::xor_epi32(ij11, k0));
+    let v001 = val_coord::<S>(seed, S::xor_epi32(ij00, k1));
+    let v101 = val_coord::<S>(seed, S::xor_epi32(ij10, k1));
+    let v011 = val_coord::<S>(seed, S::xor_epi32(ij01, k1));
+    let v111 = val_coord::<S>(seed, S::xor_epi32(ij11, k1));
+
+    let vz0 = lerp::<S>(ys, lerp::<S>(xs, v000, v100), lerp::<S>(xs, v010, v110));
+    let vz1 = lerp::<S>(ys, lerp::<S>(xs, v001, v101), lerp::<S>(xs, v011, v111));
+
+    lerp::<S>(zs, vz0, vz1)
+}
+
+/// 4d value noise, random lattice values blended with `interpolation`, from
+/// -1 to 1
+///
+/// # Safety
+///
+/// The CPU must support the instruction set of `S`.
+#[inline(always)]
+pub unsafe fn value_4d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    w: S::Vf32,
+    interpolation: Interpolation,
+    seed: i32,
+) -> S::Vf32 {
+    let x0 = S::fast_floor_ps(x);
+    let y0 = S::fast_floor_ps(y);
+    let z0 = S::fast_floor_ps(z);
+    let w0 = S::fast_floor_ps(w);
+
+    let i0 = S::mullo_epi32(S::cvtps_epi32(x0), S::set1_epi32(X_PRIME));
+    let j0 = S::mullo_epi32(S::cvtps_epi32(y0), S::set1_epi32(Y_PRIME));
+    let k0 = S::mullo_epi32(S::cvtps_epi32(z0), S::set1_epi32(Z_PRIME));
+    let l0 = S::mullo_epi32(S::cvtps_epi32(w0), S::set1_epi32(W_PRIME));
+    let i = [i0, S::add_epi32(i0, S::set1_epi32(X_PRIME))];
+    let j = [j0, S::add_epi32(j0, S::set1_epi32(Y_PRIME))];
+    let k = [k0, S::add_epi32(k0, S::set1_epi32(Z_PRIME))];
+    let l = [l0, S::add_epi32(l0, S::set1_epi32(W_PRIME))];
+
+    let xs = interpolate::<S>(S::sub_ps(x, x0), interpolation);
+    let ys = interpolate::<S>(S::sub_ps(y, y0), interpolation);
+    let zs = interpolate::<S>(S::sub_ps(z, z0), interpolation);
+    let ws = interpolate::<S>(S::sub_ps(w, w0), interpolation);
+
+    // Interpolate along x first, then collapse y, z and w in turn
+    let mut vw = [S::setzero_ps(); 2];
+    for d in 0..2 {
+        let mut vz = [S::setzero_ps(); 2];
+        for c in 0..2 {
+            let mut vy = [S::setzero_ps(); 2];
+            for b in 0..2 {
+                let jkl = S::xor_epi32(S::xor_epi32(j[b], k[c]), l[d]);
+                let v0 = val_coord::<S>(seed, S::xor_epi32(i[0], jkl));
+                let v1 = val_coord::<S>(seed, S::xor_epi32(i[1], jkl));
+                vy[b] = lerp::<S>(xs, v0, v1);
+            }
+            vz[c] = lerp::<S>(ys, vy[0], vy[1]);
+        }
+        vw[d] = lerp::<S>(zs, vz[0], vz[1]);
+    }
+
+    lerp::<S>(ws, vw[0], vw[1])
+}